@@ -4,5 +4,17 @@
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
 
-    let _channel = rustc_version::version_meta().unwrap().channel;
+    let version_meta = rustc_version::version_meta().unwrap();
+    let _channel = version_meta.channel;
+
+    // Recorded in the metadata of the libraries that export root modules,
+    // since the compiler that compiles abi_stable is the one that compiles the library.
+    println!(
+        "cargo:rustc-env=ABI_STABLE_RUSTC_VERSION={}",
+        version_meta.short_version_string
+    );
+    println!(
+        "cargo:rustc-env=ABI_STABLE_TARGET={}",
+        std::env::var("TARGET").unwrap()
+    );
 }
@@ -5,14 +5,21 @@ pub mod abi_checking;
 mod const_generics;
 pub mod extra_checks;
 pub mod get_static_equivalent;
+pub mod layout_diff;
 pub mod stable_abi_trait;
 
 pub use self::{
-    abi_checking::exported_check_layout_compatibility as check_layout_compatibility,
+    abi_checking::{
+        exported_check_layout_compatibility as check_layout_compatibility,
+        AbiInstabilityReport, InstabilityReport, TypeInstabilityReport,
+    },
     const_generics::ConstGeneric,
     get_static_equivalent::{GetStaticEquivalent, GetStaticEquivalent_},
+    layout_diff::LayoutDiff,
     stable_abi_trait::{AbiConsts, PrefixStableAbi, StableAbi},
 };
 
 #[doc(no_inline)]
 pub use self::extra_checks::{ExtraChecks, TypeChecker};
+
+pub(crate) use self::const_generics::ConstGenericSnapshot;
@@ -32,10 +32,14 @@ use crate::{
 };
 
 mod errors;
+mod report;
 
-pub use self::errors::{
-    AbiInstability, AbiInstability as AI, AbiInstabilityError, AbiInstabilityErrors,
-    ExtraCheckError,
+pub use self::{
+    errors::{
+        AbiInstability, AbiInstability as AI, AbiInstabilityError, AbiInstabilityErrors,
+        ExtraCheckError,
+    },
+    report::{AbiInstabilityReport, InstabilityReport, TypeInstabilityReport},
 };
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -109,95 +109,15 @@ impl fmt::Display for AbiInstabilityError {
         writeln!(f)?;
 
         for err in &self.errs {
-            let pair = match err {
-                AI::ReentrantLayoutCheckingCall => ("reentrant layout checking call", None),
-                AI::CyclicTypeChecking { interface, .. } => {
-                    extra_err = Some(format!("The type:\n{}", interface));
-
-                    (
-                        "Attempted to check the layout of a type while checking the layout \
-                         of one of it's const parameters/extra_checks\
-                         (not necessarily a direct one).",
-                        None,
-                    )
-                }
-                AI::NonZeroness(v) => ("mismatched non-zeroness", v.display_str()),
-                AI::Name(v) => ("mismatched type", v.display_str()),
-                AI::Package(v) => ("mismatched package", v.display_str()),
-                AI::PackageVersionParseError(v) => {
-                    let expected = "a valid version string".to_string();
-                    let found = format!("{:#?}", v);
-
-                    (
-                        "could not parse version string",
-                        Some(ExpectedFound { expected, found }),
-                    )
-                }
-                AI::PackageVersion(v) => ("incompatible package versions", v.display_str()),
-                AI::MismatchedPrefixSize(v) => {
-                    ("prefix-types have a different prefix", v.display_str())
-                }
-                AI::Size(v) => ("incompatible type size", v.display_str()),
-                AI::Alignment(v) => ("incompatible type alignment", v.display_str()),
-                AI::GenericParamCount(v) => {
-                    ("incompatible amount of generic parameters", v.display_str())
-                }
-
-                AI::TLDataDiscriminant(v) => ("incompatible data ", v.debug_str()),
-                AI::MismatchedPrimitive(v) => ("incompatible primitive", v.debug_str()),
-                AI::FieldCountMismatch(v) => ("too many fields", v.display_str()),
-                AI::FnLifetimeMismatch(v) => (
-                    "function pointers reference different lifetimes",
-                    v.display_str(),
-                ),
-                AI::FnQualifierMismatch(v) => (
-                    "function pointers have different qualifiers (`unsafe`, etc.)",
-                    v.display_str(),
-                ),
-                AI::FieldLifetimeMismatch(v) => {
-                    ("field references different lifetimes", v.display_str())
-                }
-                AI::UnexpectedField(v) => ("unexpected field", v.display_str()),
-                AI::TooManyVariants(v) => ("too many variants", v.display_str()),
-                AI::MismatchedPrefixConditionality(v) => (
-                    "prefix fields differ in whether they are conditional",
-                    v.debug_str(),
-                ),
-                AI::MismatchedExhaustiveness(v) => {
-                    ("enums differ in whether they are exhaustive", v.debug_str())
-                }
-                AI::MismatchedConstParam(v) => {
-                    ("The cconst parameters are different", v.debug_str())
-                }
-                AI::UnexpectedVariant(v) => ("unexpected variant", v.debug_str()),
-                AI::ReprAttr(v) => ("incompatible repr attributes", v.debug_str()),
-                AI::EnumDiscriminant(v) => ("different discriminants", v.debug_str()),
-                AI::IncompatibleWithNonExhaustive(e) => {
-                    extra_err = Some(e.to_string());
-
-                    ("", None)
-                }
-                AI::NoneExtraChecks => {
-                    let msg = "\
-                        Interface contains a value in `extra_checks` \
-                        while the implementation does not.\
-                    ";
-                    (msg, None)
-                }
-                AI::ExtraCheckError(ec_error) => {
-                    let ExtraCheckError { err, expected_err } = &**ec_error;
-                    extra_err = Some((**err).to_string());
-
-                    ("", expected_err.display_str())
-                }
-                AI::TagError { err } => {
-                    extra_err = Some(err.to_string());
-
-                    ("", None)
-                }
-            };
-
-            let (error_msg, expected_err): (&'static str, Option<ExpectedFound<String>>) = pair;
+            let ErrorDescription {
+                message: error_msg,
+                expected_found: expected_err,
+                extra,
+            } = err.description();
+
+            if extra.is_some() {
+                extra_err = extra;
+            }
 
             if let Some(expected_err) = expected_err {
                 writeln!(
@@ -216,6 +136,148 @@ impl fmt::Display for AbiInstabilityError {
     }
 }
 
+/// A description of an `AbiInstability`, used to print it and to build reports.
+pub(super) struct ErrorDescription {
+    pub(super) message: &'static str,
+    pub(super) expected_found: Option<ExpectedFound<String>>,
+    pub(super) extra: Option<String>,
+}
+
+impl AbiInstability {
+    /// The name of this variant, eg: `"Size"` for `AbiInstability::Size`.
+    pub const fn variant_name(&self) -> &'static str {
+        match self {
+            AI::ReentrantLayoutCheckingCall => "ReentrantLayoutCheckingCall",
+            AI::CyclicTypeChecking { .. } => "CyclicTypeChecking",
+            AI::NonZeroness { .. } => "NonZeroness",
+            AI::Name { .. } => "Name",
+            AI::Package { .. } => "Package",
+            AI::PackageVersionParseError { .. } => "PackageVersionParseError",
+            AI::PackageVersion { .. } => "PackageVersion",
+            AI::MismatchedPrefixSize { .. } => "MismatchedPrefixSize",
+            AI::Size { .. } => "Size",
+            AI::Alignment { .. } => "Alignment",
+            AI::GenericParamCount { .. } => "GenericParamCount",
+            AI::TLDataDiscriminant { .. } => "TLDataDiscriminant",
+            AI::MismatchedPrimitive { .. } => "MismatchedPrimitive",
+            AI::FieldCountMismatch { .. } => "FieldCountMismatch",
+            AI::FieldLifetimeMismatch { .. } => "FieldLifetimeMismatch",
+            AI::FnLifetimeMismatch { .. } => "FnLifetimeMismatch",
+            AI::FnQualifierMismatch { .. } => "FnQualifierMismatch",
+            AI::UnexpectedField { .. } => "UnexpectedField",
+            AI::TooManyVariants { .. } => "TooManyVariants",
+            AI::MismatchedPrefixConditionality { .. } => "MismatchedPrefixConditionality",
+            AI::MismatchedExhaustiveness { .. } => "MismatchedExhaustiveness",
+            AI::MismatchedConstParam { .. } => "MismatchedConstParam",
+            AI::UnexpectedVariant { .. } => "UnexpectedVariant",
+            AI::ReprAttr { .. } => "ReprAttr",
+            AI::EnumDiscriminant { .. } => "EnumDiscriminant",
+            AI::IncompatibleWithNonExhaustive { .. } => "IncompatibleWithNonExhaustive",
+            AI::NoneExtraChecks => "NoneExtraChecks",
+            AI::ExtraCheckError { .. } => "ExtraCheckError",
+            AI::TagError { .. } => "TagError",
+        }
+    }
+
+    pub(super) fn description(&self) -> ErrorDescription {
+        let mut extra_err = None::<String>;
+
+        let pair = match self {
+            AI::ReentrantLayoutCheckingCall => ("reentrant layout checking call", None),
+            AI::CyclicTypeChecking { interface, .. } => {
+                extra_err = Some(format!("The type:\n{}", interface));
+
+                (
+                    "Attempted to check the layout of a type while checking the layout \
+                     of one of it's const parameters/extra_checks\
+                     (not necessarily a direct one).",
+                    None,
+                )
+            }
+            AI::NonZeroness(v) => ("mismatched non-zeroness", v.display_str()),
+            AI::Name(v) => ("mismatched type", v.display_str()),
+            AI::Package(v) => ("mismatched package", v.display_str()),
+            AI::PackageVersionParseError(v) => {
+                let expected = "a valid version string".to_string();
+                let found = format!("{:#?}", v);
+
+                (
+                    "could not parse version string",
+                    Some(ExpectedFound { expected, found }),
+                )
+            }
+            AI::PackageVersion(v) => ("incompatible package versions", v.display_str()),
+            AI::MismatchedPrefixSize(v) => {
+                ("prefix-types have a different prefix", v.display_str())
+            }
+            AI::Size(v) => ("incompatible type size", v.display_str()),
+            AI::Alignment(v) => ("incompatible type alignment", v.display_str()),
+            AI::GenericParamCount(v) => {
+                ("incompatible amount of generic parameters", v.display_str())
+            }
+
+            AI::TLDataDiscriminant(v) => ("incompatible data ", v.debug_str()),
+            AI::MismatchedPrimitive(v) => ("incompatible primitive", v.debug_str()),
+            AI::FieldCountMismatch(v) => ("too many fields", v.display_str()),
+            AI::FnLifetimeMismatch(v) => (
+                "function pointers reference different lifetimes",
+                v.display_str(),
+            ),
+            AI::FnQualifierMismatch(v) => (
+                "function pointers have different qualifiers (`unsafe`, etc.)",
+                v.display_str(),
+            ),
+            AI::FieldLifetimeMismatch(v) => {
+                ("field references different lifetimes", v.display_str())
+            }
+            AI::UnexpectedField(v) => ("unexpected field", v.display_str()),
+            AI::TooManyVariants(v) => ("too many variants", v.display_str()),
+            AI::MismatchedPrefixConditionality(v) => (
+                "prefix fields differ in whether they are conditional",
+                v.debug_str(),
+            ),
+            AI::MismatchedExhaustiveness(v) => {
+                ("enums differ in whether they are exhaustive", v.debug_str())
+            }
+            AI::MismatchedConstParam(v) => ("The cconst parameters are different", v.debug_str()),
+            AI::UnexpectedVariant(v) => ("unexpected variant", v.debug_str()),
+            AI::ReprAttr(v) => ("incompatible repr attributes", v.debug_str()),
+            AI::EnumDiscriminant(v) => ("different discriminants", v.debug_str()),
+            AI::IncompatibleWithNonExhaustive(e) => {
+                extra_err = Some(e.to_string());
+
+                ("", None)
+            }
+            AI::NoneExtraChecks => {
+                let msg = "\
+                    Interface contains a value in `extra_checks` \
+                    while the implementation does not.\
+                ";
+                (msg, None)
+            }
+            AI::ExtraCheckError(ec_error) => {
+                let ExtraCheckError { err, expected_err } = &**ec_error;
+                extra_err = Some((**err).to_string());
+
+                ("", expected_err.display_str())
+            }
+            AI::TagError { err } => {
+                extra_err = Some(err.to_string());
+
+                ("", None)
+            }
+        };
+
+        let (message, expected_found): (&'static str, Option<ExpectedFound<String>>) = pair;
+
+        ErrorDescription {
+            message,
+            expected_found,
+            extra: extra_err,
+        }
+    }
+}
+
 /// All the errors from checking the layout of every nested type in TypeLayout.
 #[derive(Clone, PartialEq)]
 #[repr(C)]
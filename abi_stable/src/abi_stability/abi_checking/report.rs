@@ -0,0 +1,232 @@
+//! Structured, owned versions of the errors from checking the layout of types.
+
+use super::{errors::ErrorDescription, *};
+
+use crate::std_types::{ROption, RString};
+
+/// A structured and owned version of [`AbiInstabilityErrors`],
+/// which can be passed across the ffi boundary and serialized (eg: to json).
+///
+/// Unlike `AbiInstabilityErrors`,
+/// this doesn't contain references into the libraries whose types were checked.
+///
+/// When a library fails to load because of incompatible layouts,
+/// this is the type of the error in [`LibraryError::AbiInstability`],
+/// which can be accessed with `RBoxError::downcast_ref`.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::{
+///     abi_stability::{abi_checking::check_layout_compatibility, AbiInstabilityReport},
+///     std_types::{RString, RVec},
+///     StableAbi,
+/// };
+///
+/// let errors = check_layout_compatibility(
+///     <RVec<u8> as StableAbi>::LAYOUT,
+///     <RString as StableAbi>::LAYOUT,
+/// )
+/// .unwrap_err();
+///
+/// let report: AbiInstabilityReport = errors.to_report();
+///
+/// assert!(!report.errors.is_empty());
+/// assert_eq!(report.to_string(), errors.to_string());
+///
+/// let json = serde_json::to_string(&report).unwrap();
+/// let deserialized: AbiInstabilityReport = serde_json::from_str(&json).unwrap();
+/// assert_eq!(report, deserialized);
+///
+/// ```
+///
+/// [`AbiInstabilityErrors`]: ./abi_checking/struct.AbiInstabilityErrors.html
+/// [`LibraryError::AbiInstability`]:
+/// ../library/enum.LibraryError.html#variant.AbiInstability
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, Eq, StableAbi, Deserialize, Serialize)]
+pub struct AbiInstabilityReport {
+    /// The full path of the expected type, eg: `my_crate::foo::Module_Prefix`.
+    pub interface: RString,
+    /// The full path of the found type, eg: `my_crate::foo::Module_Prefix`.
+    pub implementation: RString,
+    /// The errors found in each type that's (directly or indirectly)
+    /// referenced by the checked types.
+    pub errors: RVec<TypeInstabilityReport>,
+    /// The error formatted as text, the same as the `Display` impl of
+    /// `AbiInstabilityErrors`.
+    pub message: RString,
+}
+
+/// The errors found while checking a single type,
+/// which is part of an [`AbiInstabilityReport`].
+///
+/// [`AbiInstabilityReport`]: ./struct.AbiInstabilityReport.html
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, Eq, StableAbi, Deserialize, Serialize)]
+pub struct TypeInstabilityReport {
+    /// The full path of the (found) type that has errors,
+    /// eg: `abi_stable::std_types::vec::RVec<u8>`.
+    pub type_path: RString,
+    /// The names of the fields (and function pointers) from the root type
+    /// down to the type with errors.
+    ///
+    /// This is empty if the errors are in the root type.
+    pub field_path: RVec<RString>,
+    /// The individual errors found in the type.
+    pub instabilities: RVec<InstabilityReport>,
+}
+
+/// An individual incompatibility, which is part of a [`TypeInstabilityReport`].
+///
+/// [`TypeInstabilityReport`]: ./struct.TypeInstabilityReport.html
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, Eq, StableAbi, Deserialize, Serialize)]
+pub struct InstabilityReport {
+    /// The name of the `AbiInstability` variant of this error, eg: `"Size"`.
+    pub kind: RString,
+    /// The description of the error, eg: `"incompatible type size"`.
+    pub message: RString,
+    /// The expected value, formatted as text.
+    pub expected: ROption<RString>,
+    /// The found value, formatted as text.
+    pub found: ROption<RString>,
+    /// Additional information about the error.
+    pub extra: ROption<RString>,
+}
+
+impl AbiInstabilityErrors {
+    /// Converts these errors into an owned and serializable [`AbiInstabilityReport`].
+    ///
+    /// [`AbiInstabilityReport`]: ../struct.AbiInstabilityReport.html
+    pub fn to_report(&self) -> AbiInstabilityReport {
+        AbiInstabilityReport {
+            interface: type_path(self.interface).into(),
+            implementation: type_path(self.implementation).into(),
+            errors: self
+                .errors
+                .iter()
+                .map(|e| e.to_report(self.implementation))
+                .collect(),
+            message: self.to_string().into(),
+        }
+    }
+}
+
+impl AbiInstabilityError {
+    fn to_report(&self, root: &'static TypeLayout) -> TypeInstabilityReport {
+        let type_path = match self.stack_trace.last() {
+            Some(ExpectedFound {
+                found: TLFieldOrFunction::Field(field),
+                ..
+            }) => type_path(field.layout()),
+            Some(ExpectedFound {
+                found: TLFieldOrFunction::Function(func),
+                ..
+            }) => func.to_string(),
+            None => type_path(root),
+        };
+
+        TypeInstabilityReport {
+            type_path: type_path.into(),
+            field_path: self
+                .stack_trace
+                .iter()
+                .map(|x| RString::from(field_or_function_name(&x.found)))
+                .collect(),
+            instabilities: self.errs.iter().map(AbiInstability::to_report).collect(),
+        }
+    }
+}
+
+impl AbiInstability {
+    /// Converts this error into an owned and serializable [`InstabilityReport`].
+    ///
+    /// [`InstabilityReport`]: ../struct.InstabilityReport.html
+    pub fn to_report(&self) -> InstabilityReport {
+        let ErrorDescription {
+            message,
+            expected_found,
+            extra,
+        } = self.description();
+
+        let (expected, found) = match expected_found {
+            Some(ExpectedFound { expected, found }) => {
+                (RSome(expected.into()), RSome(found.into()))
+            }
+            None => (RNone, RNone),
+        };
+
+        InstabilityReport {
+            kind: self.variant_name().into(),
+            message: message.into(),
+            expected,
+            found,
+            extra: extra.map(RString::from).into_c(),
+        }
+    }
+}
+
+impl AbiInstabilityReport {
+    /// Constructs a report for errors that are only available as text.
+    pub fn from_message(
+        interface: &'static TypeLayout,
+        implementation: &'static TypeLayout,
+        message: String,
+    ) -> Self {
+        Self {
+            interface: type_path(interface).into(),
+            implementation: type_path(implementation).into(),
+            errors: RVec::new(),
+            message: message.into(),
+        }
+    }
+
+    /// Converts an error returned by
+    /// [`check_layout_compatibility`](../fn.check_layout_compatibility.html)
+    /// into a report.
+    ///
+    /// If the error can't be downcasted to `AbiInstabilityErrors`
+    /// (because the layout checker is from another copy of abi_stable),
+    /// this checks the layouts once more without the global layout-checking state,
+    /// using the text of `error` as the message.
+    pub fn from_check_error(
+        interface: &'static TypeLayout,
+        implementation: &'static TypeLayout,
+        error: &RBoxError,
+    ) -> Self {
+        if let Some(errors) = error.downcast_ref::<AbiInstabilityErrors>() {
+            return errors.to_report();
+        }
+
+        let message = error.to_string();
+
+        let globals = CheckingGlobals::new();
+        match check_layout_compatibility_with_globals(interface, implementation, &globals) {
+            Err(errors) => Self {
+                message: message.into(),
+                ..errors.to_report()
+            },
+            Ok(()) => Self::from_message(interface, implementation, message),
+        }
+    }
+}
+
+impl fmt::Display for AbiInstabilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.message, f)
+    }
+}
+
+impl std::error::Error for AbiInstabilityReport {}
+
+fn type_path(layout: &'static TypeLayout) -> String {
+    format!("{}::{}", layout.mod_path(), layout.full_type())
+}
+
+fn field_or_function_name(x: &TLFieldOrFunction) -> &'static str {
+    match x {
+        TLFieldOrFunction::Field(x) => x.name(),
+        TLFieldOrFunction::Function(x) => x.name.as_str(),
+    }
+}
@@ -12,7 +12,7 @@ use crate::{
     marker_type::ErasedObject,
     prefix_type::WithMetadata,
     sabi_types::RRef,
-    std_types::{RErr, ROk, RResult, RString},
+    std_types::{RErr, ROk, RResult, RString, UTypeId},
     type_layout::TypeLayout,
     StableAbi,
 };
@@ -55,10 +55,77 @@ impl ConstGeneric {
         mut checker: TypeCheckerMut<'_>,
     ) -> Result<bool, ExtraChecksError> {
         match checker.check_compatibility(self.vtable.layout(), other.vtable.layout()) {
-            ROk(_) => unsafe { Ok(self.vtable.partial_eq()(self.ptr, other.ptr)) },
+            ROk(_) => unsafe { Ok(self.value_eq(other)) },
             RErr(e) => Err(e),
         }
     }
+
+    /// Constructs a ConstGeneric for a constant reconstructed from a layout snapshot.
+    ///
+    /// # Safety
+    ///
+    /// The returned `ConstGeneric` must not be used after `snapshot` is deallocated.
+    pub(crate) unsafe fn from_snapshot(snapshot: &'static ConstGenericSnapshot) -> Self {
+        Self {
+            ptr: unsafe {
+                RRef::from_raw(&snapshot.value as *const SnapshotValue as *const ErasedObject)
+            },
+            vtable: ConstGenericVTable_Ref(snapshot.vtable.static_as_prefix()),
+        }
+    }
+
+    /// Gets the layout of the type of the constant.
+    pub(crate) fn layout(&self) -> &'static TypeLayout {
+        self.vtable.layout()
+    }
+
+    fn is_snapshot(&self) -> bool {
+        UTypeId::is_from_snapshot(&self.vtable.layout().get_utypeid())
+    }
+
+    /// Compares the values of the constants,
+    /// comparing their `Debug` representation if either one comes from a layout snapshot.
+    ///
+    /// # Safety
+    ///
+    /// The types of both constants must have compatible layouts.
+    unsafe fn value_eq(&self, other: &Self) -> bool {
+        if self.is_snapshot() || other.is_snapshot() {
+            format!("{:?}", self) == format!("{:?}", other)
+        } else {
+            unsafe { self.vtable.partial_eq()(self.ptr, other.ptr) }
+        }
+    }
+}
+
+/// The value of a constant in a layout snapshot,stored as its `Debug` representation.
+#[derive(PartialEq, Eq)]
+struct SnapshotValue(RString);
+
+/// The value and vtable of a constant reconstructed from a layout snapshot,
+/// which `ConstGeneric::from_snapshot` borrows.
+pub(crate) struct ConstGenericSnapshot {
+    value: SnapshotValue,
+    vtable: WithMetadata<ConstGenericVTable>,
+}
+
+impl ConstGenericSnapshot {
+    pub(crate) fn new(layout: &'static TypeLayout, value: &str) -> Self {
+        Self {
+            value: SnapshotValue(value.into()),
+            vtable: WithMetadata::new(ConstGenericVTable {
+                layout,
+                partial_eq: partial_eq_impl::<SnapshotValue>,
+                debug: debug_impl::<SnapshotValue>,
+            }),
+        }
+    }
+}
+
+impl Debug for SnapshotValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
 }
 
 impl Debug for ConstGeneric {
@@ -74,7 +141,7 @@ impl PartialEq for ConstGeneric {
         if check_layout_compatibility(self.vtable.layout(), other.vtable.layout()).is_err() {
             false
         } else {
-            unsafe { self.vtable.partial_eq()(self.ptr, other.ptr) }
+            unsafe { self.value_eq(other) }
         }
     }
 }
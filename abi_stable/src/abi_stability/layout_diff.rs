@@ -0,0 +1,1092 @@
+//! A semantic diff between two `TypeLayout`s,
+//! which explains what changed between two versions of a type.
+
+use crate::{
+    abi_stability::abi_checking::{
+        check_layout_compatibility_with_globals, AbiInstability, AbiInstabilityError,
+        AbiInstabilityErrors, CheckingGlobals,
+    },
+    std_types::RSome,
+    type_layout::{
+        TLData, TLEnum, TLField, TLFieldOrFunction, TLFunction, TLPrefixType, TypeLayout,
+    },
+};
+
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+};
+
+/// A semantic diff between the layout of an interface type,
+/// and the layout of an implementation of it
+/// (eg: the layout of a type in an old version of a library,and in a new version).
+///
+/// Every difference is classified as either a compatible extension,
+/// or a breaking change,depending on whether [`check_layout_compatibility`]
+/// reports an error for it.
+/// Errors reported by `check_layout_compatibility` that don't correspond to
+/// any of the differences (eg: failed extra checks) are included as
+/// [`ChangeKind::Other`] breaking changes,
+/// so the diff is compatible exactly when `check_layout_compatibility` succeeds.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::{
+///     abi_stability::layout_diff::{ChangeKind, Compatibility, LayoutDiff},
+///     StableAbi,
+/// };
+///
+/// mod v1 {
+///     #[repr(C)]
+///     #[derive(abi_stable::StableAbi)]
+///     #[sabi(kind(Prefix))]
+///     pub struct Module {
+///         #[sabi(last_prefix_field)]
+///         pub get: extern "C" fn() -> u32,
+///     }
+/// }
+///
+/// mod v2 {
+///     #[repr(C)]
+///     #[derive(abi_stable::StableAbi)]
+///     #[sabi(kind(Prefix))]
+///     pub struct Module {
+///         #[sabi(last_prefix_field)]
+///         pub get: extern "C" fn() -> u32,
+///         pub set: extern "C" fn(u32),
+///     }
+/// }
+///
+/// let diff = LayoutDiff::new(
+///     <v1::Module_Ref as StableAbi>::LAYOUT,
+///     <v2::Module_Ref as StableAbi>::LAYOUT,
+/// );
+///
+/// assert!(diff.is_compatible());
+/// assert_eq!(diff.changes.len(), 1);
+///
+/// let change = &diff.changes[0];
+/// assert_eq!(change.compatibility, Compatibility::Compatible);
+/// assert!(matches!(&change.kind, ChangeKind::PrefixFieldAdded{field, ..} if field == "set"));
+/// assert_eq!(
+///     change.to_string(),
+///     "field `Module::set` of type `fn(u32)` \
+///      added at the end of a prefix type (compatible)",
+/// );
+///
+/// ```
+///
+/// [`check_layout_compatibility`]: ../fn.check_layout_compatibility.html
+/// [`ChangeKind::Other`]: ./enum.ChangeKind.html#variant.Other
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LayoutDiff {
+    /// The type of the interface,eg: `Module_Ref`.
+    pub interface: String,
+    /// The type of the implementation,eg: `Module_Ref`.
+    pub implementation: String,
+    /// Every difference between the layouts,
+    /// in the order that they were found by walking both layouts.
+    pub changes: Vec<LayoutChange>,
+}
+
+/// A difference between two layouts,which is part of a [`LayoutDiff`].
+///
+/// [`LayoutDiff`]: ./struct.LayoutDiff.html
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LayoutChange {
+    /// Where the change is,as the name of the type,
+    /// optionally followed by the field or variant, eg: `Module::foo`.
+    pub path: String,
+    /// The fields from the root type down to the type that contains the change,
+    /// eg: `["Bytes::data"]` for a change in the `RVec<u8>` of this struct:
+    /// `struct Bytes{ data: RVec<u8> }`.
+    ///
+    /// This is empty if the change is in the root type.
+    pub field_path: Vec<String>,
+    /// What changed.
+    pub kind: ChangeKind,
+    /// Whether the change is compatible.
+    pub compatibility: Compatibility,
+}
+
+/// Whether a [`LayoutChange`] is compatible.
+///
+/// [`LayoutChange`]: ./struct.LayoutChange.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Compatibility {
+    /// The implementation is an extension of the interface,
+    /// eg: a field was added at the end of a prefix type.
+    Compatible,
+    /// The implementation is incompatible with the interface.
+    Breaking,
+}
+
+/// What changed between two layouts,in a [`LayoutChange`].
+///
+/// Types are written with their name and generic arguments,eg: `RVec<u8>`.
+///
+/// [`LayoutChange`]: ./struct.LayoutChange.html
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ChangeKind {
+    /// The type was replaced with another type.
+    TypeChanged {
+        /// The type in the interface.
+        from: String,
+        /// The type in the implementation.
+        to: String,
+    },
+    /// The type is declared in another package.
+    PackageChanged {
+        /// The package of the type in the interface.
+        from: String,
+        /// The package of the type in the implementation.
+        to: String,
+    },
+    /// The version of the package that declares the type changed.
+    PackageVersionChanged {
+        /// The package version of the interface.
+        from: String,
+        /// The package version of the implementation.
+        to: String,
+    },
+    /// Whether the type is non-zero (eg: a non-null pointer) changed.
+    NonZeronessChanged {
+        /// Whether the type is non-zero in the interface.
+        from: bool,
+        /// Whether the type is non-zero in the implementation.
+        to: bool,
+    },
+    /// The `#[repr(..)]` attribute of the type changed.
+    ReprAttrChanged {
+        /// The representation attribute of the interface.
+        from: String,
+        /// The representation attribute of the implementation.
+        to: String,
+    },
+    /// The type changed between being a struct/union/enum/prefix type/etc.
+    KindChanged {
+        /// The kind of type of the interface.
+        from: String,
+        /// The kind of type of the implementation.
+        to: String,
+    },
+    /// The size of the type changed.
+    SizeChanged {
+        /// The size of the interface,in bytes.
+        from: usize,
+        /// The size of the implementation,in bytes.
+        to: usize,
+    },
+    /// The alignment of the type changed.
+    AlignmentChanged {
+        /// The alignment of the interface,in bytes.
+        from: usize,
+        /// The alignment of the implementation,in bytes.
+        to: usize,
+    },
+    /// The tag of the type (from the `#[sabi(tag = ...)]` attribute) changed.
+    TagChanged {
+        /// The tag of the interface.
+        from: String,
+        /// The tag of the implementation.
+        to: String,
+    },
+    /// A field was added after the existing ones.
+    FieldAdded {
+        /// The name of the field.
+        field: String,
+        /// The type of the field.
+        ty: String,
+    },
+    /// A field was added after the existing fields of a prefix type.
+    PrefixFieldAdded {
+        /// The name of the field.
+        field: String,
+        /// The type of the field.
+        ty: String,
+    },
+    /// A trailing field was removed.
+    FieldRemoved {
+        /// The name of the field.
+        field: String,
+        /// The type of the field.
+        ty: String,
+    },
+    /// A field was renamed,or replaced with a differently named field.
+    FieldRenamed {
+        /// The name of the field in the interface.
+        from: String,
+        /// The name of the field in the implementation.
+        to: String,
+    },
+    /// The signature of a function pointer field changed.
+    FunctionChanged {
+        /// The name of the field.
+        field: String,
+        /// The signature in the interface.
+        from: String,
+        /// The signature in the implementation.
+        to: String,
+    },
+    /// The field that's marked with `#[sabi(last_prefix_field)]` changed.
+    PrefixFieldCountChanged {
+        /// The amount of fields in the prefix of the interface.
+        from: usize,
+        /// The amount of fields in the prefix of the implementation.
+        to: usize,
+    },
+    /// Which prefix fields are conditional
+    /// (with the `#[sabi(accessible_if = ...)]` attribute) changed.
+    PrefixConditionalityChanged {
+        /// Which prefix fields are conditional in the interface.
+        from: String,
+        /// Which prefix fields are conditional in the implementation.
+        to: String,
+    },
+    /// A variant was added after the existing ones.
+    VariantAdded {
+        /// The name of the variant.
+        variant: String,
+        /// Whether the enum is nonexhaustive.
+        nonexhaustive: bool,
+    },
+    /// A trailing variant was removed.
+    VariantRemoved {
+        /// The name of the variant.
+        variant: String,
+    },
+    /// A variant was renamed,or replaced with a differently named variant.
+    VariantRenamed {
+        /// The name of the variant in the interface.
+        from: String,
+        /// The name of the variant in the implementation.
+        to: String,
+    },
+    /// The discriminants of the variants changed.
+    DiscriminantsChanged {
+        /// The discriminants in the interface.
+        from: String,
+        /// The discriminants in the implementation.
+        to: String,
+    },
+    /// The enum changed between exhaustive and nonexhaustive.
+    ExhaustivenessChanged {
+        /// Whether the interface is nonexhaustive.
+        from_nonexhaustive: bool,
+        /// Whether the implementation is nonexhaustive.
+        to_nonexhaustive: bool,
+    },
+    /// A difference that isn't described by the other variants,
+    /// eg: a failed `#[sabi(extra_checks = ...)]` check,
+    /// or an error reported by the layout checker that no other change accounts for.
+    Other {
+        /// A description of the difference.
+        description: String,
+    },
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl LayoutDiff {
+    /// Walks the `interface` and `implementation` layouts,
+    /// collecting every difference between them.
+    ///
+    /// # Warning
+    ///
+    /// Like [`check_layout_compatibility`], this function is not symmetric,
+    /// the first parameter must be the expected layout,
+    /// and the second must be actual layout.
+    ///
+    /// [`check_layout_compatibility`]: ../fn.check_layout_compatibility.html
+    pub fn new(interface: &'static TypeLayout, implementation: &'static TypeLayout) -> Self {
+        let mut differ = Differ {
+            changes: Vec::new(),
+            visited: HashSet::new(),
+            field_path: Vec::new(),
+        };
+        differ.diff_layout(None, interface, implementation);
+
+        // The compatibility of every change comes from the layout checker,
+        // so that the diff agrees with what loading a library would do.
+        let mut errors = match check_compatibility(interface, implementation) {
+            Ok(()) => Vec::new(),
+            Err(e) => e
+                .errors
+                .iter()
+                .map(|error| CheckerError::new(interface, implementation, error))
+                .collect(),
+        };
+
+        let mut changes = Vec::with_capacity(differ.changes.len());
+        for change in differ.changes {
+            let mut is_breaking = false;
+            for error in &mut errors {
+                for (instability, is_explained) in &mut error.instabilities {
+                    if change.is_reported_as(&error.positions, instability) {
+                        *is_explained = true;
+                        is_breaking = true;
+                    }
+                }
+            }
+            changes.push(LayoutChange {
+                path: change.path,
+                field_path: change.field_path,
+                kind: change.kind,
+                compatibility: compatibility_of_bool(!is_breaking),
+            });
+        }
+
+        // Errors that the diff doesn't know how to describe,
+        // eg: extra checks.
+        for error in &errors {
+            for (instability, _) in error.instabilities.iter().filter(|(_, x)| !x) {
+                changes.push(LayoutChange {
+                    path: error.type_name.clone(),
+                    field_path: error.field_path.clone(),
+                    kind: ChangeKind::Other {
+                        description: describe_instability(instability),
+                    },
+                    compatibility: Compatibility::Breaking,
+                });
+            }
+        }
+
+        Self {
+            interface: interface.full_type().to_string(),
+            implementation: implementation.full_type().to_string(),
+            changes,
+        }
+    }
+
+    /// Whether all the changes are compatible.
+    pub fn is_compatible(&self) -> bool {
+        self.breaking_changes().next().is_none()
+    }
+
+    /// Whether there are no differences between the layouts.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Iterates over the breaking changes.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &LayoutChange> + '_ {
+        self.changes
+            .iter()
+            .filter(|x| x.compatibility == Compatibility::Breaking)
+    }
+}
+
+impl Display for LayoutDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return write!(
+                f,
+                "No changes between `{}` and `{}`.",
+                self.interface, self.implementation
+            );
+        }
+
+        let breaking = self.breaking_changes().count();
+        writeln!(
+            f,
+            "{} change(s) between `{}` and `{}`, {} breaking:",
+            self.changes.len(),
+            self.interface,
+            self.implementation,
+            breaking,
+        )?;
+        for change in &self.changes {
+            writeln!(f, "- {}", change)?;
+            if !change.field_path.is_empty() {
+                writeln!(f, "    inside: {}", change.field_path.join(" -> "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Compatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Compatibility::Compatible => "compatible",
+            Compatibility::Breaking => "breaking",
+        })
+    }
+}
+
+impl Display for LayoutChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = &self.path;
+        match &self.kind {
+            ChangeKind::TypeChanged { from, to } => {
+                write!(f, "type of `{}` changed from `{}` to `{}`", path, from, to)
+            }
+            ChangeKind::PackageChanged { from, to } => write!(
+                f,
+                "package of `{}` changed from `{}` to `{}`",
+                path, from, to
+            ),
+            ChangeKind::PackageVersionChanged { from, to } => write!(
+                f,
+                "package version of `{}` changed from `{}` to `{}`",
+                path, from, to
+            ),
+            ChangeKind::NonZeronessChanged { from, to } => write!(
+                f,
+                "non-zeroness of `{}` changed from {} to {}",
+                path, from, to
+            ),
+            ChangeKind::ReprAttrChanged { from, to } => write!(
+                f,
+                "representation attribute of `{}` changed from `{}` to `{}`",
+                path, from, to
+            ),
+            ChangeKind::KindChanged { from, to } => {
+                write!(f, "`{}` changed from a {} to a {}", path, from, to)
+            }
+            ChangeKind::SizeChanged { from, to } => write!(
+                f,
+                "size of `{}` changed from {} to {} bytes",
+                path, from, to
+            ),
+            ChangeKind::AlignmentChanged { from, to } => write!(
+                f,
+                "alignment of `{}` changed from {} to {} bytes",
+                path, from, to
+            ),
+            ChangeKind::TagChanged { from, to } => {
+                write!(f, "tag of `{}` changed from `{}` to `{}`", path, from, to)
+            }
+            ChangeKind::FieldAdded { field, ty } => {
+                write!(f, "field `{}::{}` of type `{}` added", path, field, ty)
+            }
+            ChangeKind::PrefixFieldAdded { field, ty } => write!(
+                f,
+                "field `{}::{}` of type `{}` added at the end of a prefix type",
+                path, field, ty
+            ),
+            ChangeKind::FieldRemoved { field, ty } => {
+                write!(f, "field `{}::{}` of type `{}` removed", path, field, ty)
+            }
+            ChangeKind::FieldRenamed { from, to } => {
+                write!(f, "field `{}::{}` renamed to `{}`", path, from, to)
+            }
+            ChangeKind::FunctionChanged { field, from, to } => write!(
+                f,
+                "signature of `{}::{}` changed from `{}` to `{}`",
+                path, field, from, to
+            ),
+            ChangeKind::PrefixFieldCountChanged { from, to } => write!(
+                f,
+                "the amount of prefix fields of `{}` changed from {} to {}",
+                path, from, to
+            ),
+            ChangeKind::PrefixConditionalityChanged { from, to } => write!(
+                f,
+                "the conditional prefix fields of `{}` changed from {} to {}",
+                path, from, to
+            ),
+            ChangeKind::VariantAdded {
+                variant,
+                nonexhaustive,
+            } => write!(
+                f,
+                "variant `{}::{}` added to {} enum",
+                path,
+                variant,
+                if *nonexhaustive {
+                    "nonexhaustive"
+                } else {
+                    "exhaustive"
+                },
+            ),
+            ChangeKind::VariantRemoved { variant } => {
+                write!(f, "variant `{}::{}` removed", path, variant)
+            }
+            ChangeKind::VariantRenamed { from, to } => {
+                write!(f, "variant `{}::{}` renamed to `{}`", path, from, to)
+            }
+            ChangeKind::DiscriminantsChanged { from, to } => write!(
+                f,
+                "discriminants of `{}` changed from {} to {}",
+                path, from, to
+            ),
+            ChangeKind::ExhaustivenessChanged {
+                from_nonexhaustive,
+                to_nonexhaustive,
+            } => {
+                let exhaustiveness = |nonexhaustive: bool| {
+                    if nonexhaustive {
+                        "nonexhaustive"
+                    } else {
+                        "exhaustive"
+                    }
+                };
+                write!(
+                    f,
+                    "`{}` changed from {} to {}",
+                    path,
+                    exhaustiveness(*from_nonexhaustive),
+                    exhaustiveness(*to_nonexhaustive),
+                )
+            }
+            ChangeKind::Other { description } => {
+                write!(f, "`{}`: {}", path, description.trim_end())
+            }
+        }?;
+        write!(f, " ({})", self.compatibility)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+struct Differ {
+    changes: Vec<FoundChange>,
+    visited: HashSet<(*const TypeLayout, *const TypeLayout)>,
+    field_path: Vec<String>,
+}
+
+/// A change found by walking the layouts,before its compatibility is known.
+struct FoundChange {
+    path: String,
+    field_path: Vec<String>,
+    kind: ChangeKind,
+    location: Location,
+}
+
+/// Where the layout checker reports the errors caused by a change.
+#[derive(Clone)]
+enum Location {
+    /// In the check of this pair of types.
+    Types(Position),
+    /// In the check of this pair of types/functions,
+    /// or of any type referenced by them.
+    Nested(Position),
+}
+
+/// A pair of types,or a pair of function pointers,
+/// being compared by the layout checker.
+#[derive(Clone, PartialEq)]
+enum Position {
+    Types(*const TypeLayout, *const TypeLayout),
+    Functions(Box<(TLFunction, TLFunction)>),
+}
+
+impl Position {
+    fn types(this: &'static TypeLayout, other: &'static TypeLayout) -> Self {
+        Position::Types(this, other)
+    }
+}
+
+impl FoundChange {
+    /// Whether `instability`,which the layout checker reported at `positions`,
+    /// is caused by this change.
+    ///
+    /// `positions` are the pairs the checker was comparing,
+    /// from the root types to the types with the error.
+    fn is_reported_as(&self, positions: &[Position], instability: &AbiInstability) -> bool {
+        use self::AbiInstability as AI;
+
+        let position = match &self.location {
+            Location::Types(position) => position,
+            Location::Nested(position) => return positions.contains(position),
+        };
+        if positions.last() != Some(position) {
+            return false;
+        }
+
+        match &self.kind {
+            ChangeKind::TypeChanged { .. } | ChangeKind::FunctionChanged { .. } => true,
+            ChangeKind::PackageChanged { .. } => matches!(instability, AI::Package { .. }),
+            ChangeKind::PackageVersionChanged { .. } => matches!(
+                instability,
+                AI::PackageVersion { .. } | AI::PackageVersionParseError { .. }
+            ),
+            ChangeKind::NonZeronessChanged { .. } => matches!(instability, AI::NonZeroness { .. }),
+            ChangeKind::ReprAttrChanged { .. } => matches!(instability, AI::ReprAttr { .. }),
+            ChangeKind::KindChanged { .. } => {
+                matches!(instability, AI::TLDataDiscriminant { .. })
+            }
+            ChangeKind::SizeChanged { .. } => matches!(instability, AI::Size { .. }),
+            ChangeKind::AlignmentChanged { .. } => matches!(instability, AI::Alignment { .. }),
+            ChangeKind::TagChanged { .. } => matches!(instability, AI::TagError { .. }),
+            ChangeKind::FieldAdded { .. }
+            | ChangeKind::PrefixFieldAdded { .. }
+            | ChangeKind::FieldRemoved { .. } => {
+                matches!(instability, AI::FieldCountMismatch { .. })
+            }
+            ChangeKind::FieldRenamed { .. } => matches!(instability, AI::UnexpectedField { .. }),
+            ChangeKind::PrefixFieldCountChanged { .. } => {
+                matches!(instability, AI::MismatchedPrefixSize { .. })
+            }
+            ChangeKind::PrefixConditionalityChanged { .. } => {
+                matches!(instability, AI::MismatchedPrefixConditionality { .. })
+            }
+            ChangeKind::VariantAdded { .. } | ChangeKind::VariantRemoved { .. } => {
+                matches!(instability, AI::TooManyVariants { .. })
+            }
+            ChangeKind::VariantRenamed { .. } => {
+                matches!(instability, AI::UnexpectedVariant { .. })
+            }
+            ChangeKind::DiscriminantsChanged { .. } => {
+                matches!(instability, AI::EnumDiscriminant { .. })
+            }
+            ChangeKind::ExhaustivenessChanged { .. } => {
+                matches!(instability, AI::MismatchedExhaustiveness { .. })
+            }
+            ChangeKind::Other { .. } => {
+                matches!(instability, AI::IncompatibleWithNonExhaustive { .. })
+            }
+        }
+    }
+}
+
+/// An error reported by the layout checker.
+struct CheckerError {
+    /// The pairs the checker was comparing,
+    /// from the root types to the types with the error.
+    positions: Vec<Position>,
+    type_name: String,
+    field_path: Vec<String>,
+    /// The individual errors,and whether any change explains them.
+    instabilities: Vec<(AbiInstability, bool)>,
+}
+
+impl CheckerError {
+    fn new(
+        interface: &'static TypeLayout,
+        implementation: &'static TypeLayout,
+        error: &AbiInstabilityError,
+    ) -> Self {
+        let mut positions = vec![Position::types(interface, implementation)];
+        let mut field_path = Vec::new();
+        let mut type_name = implementation.name();
+        for pair in &error.stack_trace {
+            match (pair.expected, pair.found) {
+                (TLFieldOrFunction::Field(t_field), TLFieldOrFunction::Field(o_field)) => {
+                    positions.push(Position::types(t_field.layout(), o_field.layout()));
+                    field_path.push(format!("{}::{}", type_name, o_field.name()));
+                    type_name = o_field.layout().name();
+                }
+                (TLFieldOrFunction::Function(t_func), TLFieldOrFunction::Function(o_func)) => {
+                    positions.push(Position::Functions(Box::new((t_func, o_func))));
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            positions,
+            type_name: type_name.into(),
+            field_path,
+            instabilities: error.errs.iter().map(|x| (x.clone(), false)).collect(),
+        }
+    }
+}
+
+impl Differ {
+    fn push(&mut self, path: &str, location: &Location, kind: ChangeKind) {
+        self.changes.push(FoundChange {
+            path: path.into(),
+            field_path: self.field_path.clone(),
+            kind,
+            location: location.clone(),
+        });
+    }
+
+    /// Diffs two layouts,
+    /// where `field` is the field that contains the layouts (`None` for the root layout).
+    fn diff_layout(
+        &mut self,
+        field: Option<&str>,
+        this: &'static TypeLayout,
+        other: &'static TypeLayout,
+    ) {
+        if !self.visited.insert((this, other)) {
+            return;
+        }
+
+        let t_type = this.full_type().to_string();
+        let o_type = other.full_type().to_string();
+        if t_type != o_type {
+            let kind = ChangeKind::TypeChanged {
+                from: t_type,
+                to: o_type,
+            };
+            let location = Location::Nested(Position::types(this, other));
+            self.push(field.unwrap_or_else(|| this.name()), &location, kind);
+            return;
+        }
+
+        if let Some(field) = field {
+            self.field_path.push(field.into());
+        }
+        self.diff_same_type(this, other);
+        if field.is_some() {
+            self.field_path.pop();
+        }
+    }
+
+    /// Diffs two layouts of types with the same name.
+    fn diff_same_type(&mut self, this: &'static TypeLayout, other: &'static TypeLayout) {
+        let type_name = this.name();
+        let here = Location::Types(Position::types(this, other));
+
+        let (t_package, t_version) = this.package_and_version();
+        let (o_package, o_version) = other.package_and_version();
+        if t_package != o_package {
+            let kind = ChangeKind::PackageChanged {
+                from: t_package.into(),
+                to: o_package.into(),
+            };
+            self.push(type_name, &here, kind);
+            return;
+        }
+        if t_version != o_version {
+            let kind = ChangeKind::PackageVersionChanged {
+                from: t_version.to_string(),
+                to: o_version.to_string(),
+            };
+            self.push(type_name, &here, kind);
+        }
+
+        if this.is_nonzero() != other.is_nonzero() {
+            let kind = ChangeKind::NonZeronessChanged {
+                from: this.is_nonzero(),
+                to: other.is_nonzero(),
+            };
+            self.push(type_name, &here, kind);
+        }
+
+        if this.repr_attr() != other.repr_attr() {
+            let kind = ChangeKind::ReprAttrChanged {
+                from: format!("{:?}", this.repr_attr()),
+                to: format!("{:?}", other.repr_attr()),
+            };
+            self.push(type_name, &here, kind);
+        }
+
+        if this.tag() != other.tag() {
+            let kind = ChangeKind::TagChanged {
+                from: this.tag().to_string(),
+                to: other.tag().to_string(),
+            };
+            self.push(type_name, &here, kind);
+        }
+
+        if this.size() != other.size() {
+            let kind = ChangeKind::SizeChanged {
+                from: this.size(),
+                to: other.size(),
+            };
+            self.push(type_name, &here, kind);
+        }
+        if this.alignment() != other.alignment() {
+            let kind = ChangeKind::AlignmentChanged {
+                from: this.alignment(),
+                to: other.alignment(),
+            };
+            self.push(type_name, &here, kind);
+        }
+
+        self.diff_fields(
+            type_name,
+            &here,
+            FieldsKind::Exact,
+            &this.phantom_fields().iter().collect::<Vec<_>>(),
+            &other.phantom_fields().iter().collect::<Vec<_>>(),
+        );
+
+        match (this.data(), other.data()) {
+            (TLData::Primitive(_), TLData::Primitive(_)) | (TLData::Opaque, TLData::Opaque) => {}
+            (TLData::Struct { fields: t_fields }, TLData::Struct { fields: o_fields })
+            | (TLData::Union { fields: t_fields }, TLData::Union { fields: o_fields }) => {
+                self.diff_fields(
+                    type_name,
+                    &here,
+                    FieldsKind::Exact,
+                    &t_fields.iter().collect::<Vec<_>>(),
+                    &o_fields.iter().collect::<Vec<_>>(),
+                );
+            }
+            (TLData::Enum(t_enum), TLData::Enum(o_enum)) => {
+                self.diff_enum(type_name, &here, this, other, &t_enum, &o_enum);
+            }
+            (TLData::PrefixType(t_prefix), TLData::PrefixType(o_prefix)) => {
+                self.diff_prefix_type(type_name, &here, &t_prefix, &o_prefix);
+            }
+            (t_data, o_data) => {
+                let kind = ChangeKind::KindChanged {
+                    from: data_kind(&t_data).into(),
+                    to: data_kind(&o_data).into(),
+                };
+                self.push(type_name, &here, kind);
+            }
+        }
+    }
+
+    fn diff_fields(
+        &mut self,
+        path: &str,
+        here: &Location,
+        fields_kind: FieldsKind,
+        t_fields: &[TLField],
+        o_fields: &[TLField],
+    ) {
+        for (t_field, o_field) in t_fields.iter().zip(o_fields) {
+            let field_name = t_field.name();
+            if field_name != o_field.name() {
+                let kind = ChangeKind::FieldRenamed {
+                    from: field_name.into(),
+                    to: o_field.name().into(),
+                };
+                self.push(path, here, kind);
+                continue;
+            }
+
+            let field_path = format!("{}::{}", path, field_name);
+
+            let functions = t_field
+                .function_range()
+                .iter()
+                .zip(o_field.function_range());
+            for (t_func, o_func) in functions {
+                if t_func.to_string() != o_func.to_string() {
+                    let (mut t_sig, mut o_sig) = (signature(&t_func), signature(&o_func));
+                    // The types are the same,so parameter names or lifetimes changed.
+                    if t_sig == o_sig {
+                        t_sig = t_func.to_string();
+                        o_sig = o_func.to_string();
+                    }
+                    let kind = ChangeKind::FunctionChanged {
+                        field: field_name.into(),
+                        from: t_sig,
+                        to: o_sig,
+                    };
+                    let location =
+                        Location::Nested(Position::Functions(Box::new((t_func, o_func))));
+                    self.push(path, &location, kind);
+                    continue;
+                }
+
+                let params = t_func
+                    .get_params_ret_iter()
+                    .zip(o_func.get_params_ret_iter());
+                for (t_param, o_param) in params {
+                    self.diff_layout(Some(&field_path), t_param.layout(), o_param.layout());
+                }
+            }
+
+            self.diff_layout(Some(&field_path), t_field.layout(), o_field.layout());
+        }
+
+        for added in o_fields.iter().skip(t_fields.len()) {
+            let field = added.name().into();
+            let ty = field_type(added);
+            let kind = match fields_kind {
+                FieldsKind::Exact => ChangeKind::FieldAdded { field, ty },
+                FieldsKind::Prefix => ChangeKind::PrefixFieldAdded { field, ty },
+            };
+            self.push(path, here, kind);
+        }
+
+        for removed in t_fields.iter().skip(o_fields.len()) {
+            let kind = ChangeKind::FieldRemoved {
+                field: removed.name().into(),
+                ty: field_type(removed),
+            };
+            self.push(path, here, kind);
+        }
+    }
+
+    fn diff_enum(
+        &mut self,
+        path: &str,
+        here: &Location,
+        this: &'static TypeLayout,
+        other: &'static TypeLayout,
+        t_enum: &TLEnum,
+        o_enum: &TLEnum,
+    ) {
+        let t_nonexhaustive = t_enum.exhaustiveness.as_nonexhaustive();
+        let o_nonexhaustive = o_enum.exhaustiveness.as_nonexhaustive();
+        if t_nonexhaustive.is_some() != o_nonexhaustive.is_some() {
+            let kind = ChangeKind::ExhaustivenessChanged {
+                from_nonexhaustive: t_nonexhaustive.is_some(),
+                to_nonexhaustive: o_nonexhaustive.is_some(),
+            };
+            self.push(path, here, kind);
+        }
+        for (nonexhaustive, layout) in [(t_nonexhaustive, this), (o_nonexhaustive, other)] {
+            if let Some(Err(e)) = nonexhaustive.map(|x| x.check_compatible(layout)) {
+                let kind = ChangeKind::Other {
+                    description: e.to_string(),
+                };
+                self.push(path, here, kind);
+            }
+        }
+
+        if t_enum.discriminants.compare(&o_enum.discriminants).is_err() {
+            let kind = ChangeKind::DiscriminantsChanged {
+                from: format!("{:?}", t_enum.discriminants),
+                to: format!("{:?}", o_enum.discriminants),
+            };
+            self.push(path, here, kind);
+        }
+
+        let t_variants = variants(t_enum);
+        let o_variants = variants(o_enum);
+
+        for ((t_name, t_fields), (o_name, o_fields)) in t_variants.iter().zip(&o_variants) {
+            if t_name != o_name {
+                let kind = ChangeKind::VariantRenamed {
+                    from: (*t_name).into(),
+                    to: (*o_name).into(),
+                };
+                self.push(path, here, kind);
+                continue;
+            }
+            let variant_path = format!("{}::{}", path, t_name);
+            self.diff_fields(&variant_path, here, FieldsKind::Exact, t_fields, o_fields);
+        }
+
+        for (variant, _) in o_variants.iter().skip(t_variants.len()) {
+            let nonexhaustive = t_nonexhaustive.is_some();
+            let kind = ChangeKind::VariantAdded {
+                variant: (*variant).into(),
+                nonexhaustive,
+            };
+            self.push(path, here, kind);
+        }
+
+        for (variant, _) in t_variants.iter().skip(o_variants.len()) {
+            let kind = ChangeKind::VariantRemoved {
+                variant: (*variant).into(),
+            };
+            self.push(path, here, kind);
+        }
+    }
+
+    fn diff_prefix_type(
+        &mut self,
+        path: &str,
+        here: &Location,
+        t_prefix: &TLPrefixType,
+        o_prefix: &TLPrefixType,
+    ) {
+        if t_prefix.first_suffix_field != o_prefix.first_suffix_field {
+            let kind = ChangeKind::PrefixFieldCountChanged {
+                from: t_prefix.first_suffix_field as usize,
+                to: o_prefix.first_suffix_field as usize,
+            };
+            self.push(path, here, kind);
+        }
+
+        if t_prefix.conditional_prefix_fields != o_prefix.conditional_prefix_fields {
+            let kind = ChangeKind::PrefixConditionalityChanged {
+                from: format!("{:?}", t_prefix.conditional_prefix_fields),
+                to: format!("{:?}", o_prefix.conditional_prefix_fields),
+            };
+            self.push(path, here, kind);
+        }
+
+        self.diff_fields(
+            path,
+            here,
+            FieldsKind::Prefix,
+            &t_prefix.fields.iter().collect::<Vec<_>>(),
+            &o_prefix.fields.iter().collect::<Vec<_>>(),
+        );
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum FieldsKind {
+    /// The fields must be the same in the interface and implementation.
+    Exact,
+    /// The implementation can have more fields than the interface.
+    Prefix,
+}
+
+/// The name and fields of each variant of an enum.
+fn variants(enum_: &TLEnum) -> Vec<(&'static str, Vec<TLField>)> {
+    let mut fields = enum_.fields.iter();
+    enum_
+        .variant_names_iter()
+        .zip(enum_.field_count.iter())
+        .map(|(name, &count)| (name, fields.by_ref().take(count as usize).collect()))
+        .collect()
+}
+
+/// The type of a field,using the signature for function pointers.
+fn field_type(field: &TLField) -> String {
+    match field.function_range().get(0) {
+        Some(function) => signature(&function),
+        None => field.full_type().to_string(),
+    }
+}
+
+/// The signature of a function without parameter names,eg: `fn(u32, RStr<'_>) -> RString`.
+fn signature(function: &TLFunction) -> String {
+    let mut out = String::new();
+    if function.fn_qualifs.is_unsafe() {
+        out.push_str("unsafe ");
+    }
+    out.push_str("fn(");
+    for (i, param) in function.get_params().enumerate() {
+        if i != 0 {
+            out.push_str(", ");
+        }
+        out.push_str(&param.full_type().to_string());
+    }
+    out.push(')');
+
+    let ret = function.get_return().full_type().to_string();
+    if ret != "()" {
+        out.push_str(" -> ");
+        out.push_str(&ret);
+    }
+    out
+}
+
+fn data_kind(data: &TLData) -> &'static str {
+    match data {
+        TLData::Primitive(_) => "primitive",
+        TLData::Opaque => "opaque type",
+        TLData::Struct { .. } => "struct",
+        TLData::Union { .. } => "union",
+        TLData::Enum(_) => "enum",
+        TLData::PrefixType(_) => "prefix type",
+    }
+}
+
+fn check_compatibility(
+    interface: &'static TypeLayout,
+    implementation: &'static TypeLayout,
+) -> Result<(), AbiInstabilityErrors> {
+    // Using separate globals so that diffing doesn't affect
+    // the layout checking of loaded libraries.
+    let globals = CheckingGlobals::new();
+    check_layout_compatibility_with_globals(interface, implementation, &globals)
+}
+
+/// Describes an error of the layout checker in a single line.
+fn describe_instability(instability: &AbiInstability) -> String {
+    let report = instability.to_report();
+    match (report.expected, report.found) {
+        (RSome(expected), RSome(found)) => format!(
+            "{}, expected `{}`, found `{}`",
+            report.message, expected, found
+        ),
+        _ => report.message.into(),
+    }
+}
+
+fn compatibility_of_bool(is_compatible: bool) -> Compatibility {
+    if is_compatible {
+        Compatibility::Compatible
+    } else {
+        Compatibility::Breaking
+    }
+}
@@ -5,7 +5,7 @@ use std::{
     cell::{Cell, UnsafeCell},
     marker::{PhantomData, PhantomPinned},
     mem::ManuallyDrop,
-    num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping},
+    num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping},
     pin::Pin,
     ptr::NonNull,
     sync::atomic::{AtomicBool, AtomicIsize, AtomicPtr, AtomicUsize},
@@ -218,13 +218,18 @@ macro_rules! phantomdata_tuples {
     (
         $(($tuple_param:ident,$name_ident:ident=$name_str:literal))*
     )=>{
+        // Tuples don't have a stable layout, so they only implement `GetStaticEquivalent_`,
+        // this allows using them as `#[sabi(not_stableabi(..))]` type parameters.
+        //
+        // `PhantomData<(..)>` gets its `GetStaticEquivalent_` impl
+        // from the blanket impl for `PhantomData<T>`.
         unsafe impl<$($tuple_param,)*>
             GetStaticEquivalent_
-        for PhantomData<($($tuple_param,)*)>
+        for ($($tuple_param,)*)
         where
             $($tuple_param:GetStaticEquivalent_,)*
         {
-            type StaticEquivalent=PhantomData<($($tuple_param::StaticEquivalent,)*)>;
+            type StaticEquivalent=($($tuple_param::StaticEquivalent,)*);
         }
 
         unsafe impl<$($tuple_param,)*>
@@ -918,6 +923,19 @@ where
 macro_rules! impl_for_primitive_ints {
     (
         $( ($type:ty,$type_name:literal,$tl_primitive:expr) ,)*
+    ) => (
+        impl_for_primitive_ints!{
+            @impl
+            $((
+                $type,
+                $type_name,
+                MonoTLData::Primitive($tl_primitive),
+                GenericTLData::Primitive,
+            ))*
+        }
+    );
+    (@impl
+        $( ($type:ty,$type_name:literal,$mono_data:expr,$generic_data:expr $(,)*) )*
     ) => (
         $(
             unsafe impl GetStaticEquivalent_ for $type {
@@ -931,7 +949,7 @@ macro_rules! impl_for_primitive_ints {
                         *mono_shared_vars,
                         rstr!($type_name),
                         ItemInfo::primitive(),
-                        MonoTLData::Primitive($tl_primitive),
+                        $mono_data,
                         tl_genparams!(;;),
                         ReprAttr::Primitive,
                         ModReflMode::Module,
@@ -950,7 +968,7 @@ macro_rules! impl_for_primitive_ints {
                         shared_vars,
                         MONO_TYPE_LAYOUT,
                         Self::ABI_CONSTS,
-                        GenericTLData::Primitive,
+                        $generic_data,
                     )
                 };
             }
@@ -974,6 +992,30 @@ impl_for_primitive_ints! {
     (f64 ,"f64" ,TLPrimitive::F64),
 }
 
+// These types don't have a `TLPrimitive` variant,
+// and are described as opaque types named after the primitive instead,
+// which layout checkers from older versions of `abi_stable` can read.
+//
+// `u128` and `i128` changed alignment from 8 to 16 on some targets
+// (eg: x86 and x86_64) in Rust 1.77,
+// which the layout checker reports as an alignment error.
+macro_rules! impl_for_opaque_primitives {
+    (
+        $( ($type:ty,$type_name:literal) ,)*
+    ) => (
+        impl_for_primitive_ints!{
+            @impl
+            $(($type, $type_name, MonoTLData::Opaque, GenericTLData::Opaque))*
+        }
+    )
+}
+
+impl_for_opaque_primitives! {
+    (u128 ,"u128"),
+    (i128 ,"i128"),
+    (char ,"char"),
+}
+
 macro_rules! impl_for_concrete {
     (
         type IsNonZeroType=$zeroness:ty;
@@ -1043,6 +1085,7 @@ impl_for_concrete! {
         (NonZeroU32  ,"NonZeroU32"  ,u32,"std::num"),
         (NonZeroU64  ,"NonZeroU64"  ,u64,"std::num"),
         (NonZeroUsize,"NonZeroUsize",usize,"std::num"),
+        (NonZeroU128 ,"NonZeroU128" ,u128,"std::num"),
     ]
 }
 /////////////
@@ -1074,6 +1117,7 @@ mod rust_1_34_impls {
             (NonZeroI32  ,"NonZeroI32"  ,i32,"core::num"),
             (NonZeroI64  ,"NonZeroI64"  ,i64,"core::num"),
             (NonZeroIsize,"NonZeroIsize",isize,"core::num"),
+            (NonZeroI128 ,"NonZeroI128" ,i128,"core::num"),
         ]
     }
 }
@@ -1466,3 +1510,6 @@ where
 }
 
 /////////////
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod tests;
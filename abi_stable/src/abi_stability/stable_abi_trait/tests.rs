@@ -0,0 +1,95 @@
+use super::*;
+
+use std::num::NonZeroI128;
+
+use crate::{
+    abi_stability::abi_checking::{check_layout_compatibility, AbiInstability},
+    type_layout::{TLData, TLDataDiscriminant},
+};
+
+/// Emulates how `u128` is laid out by Rust versions before 1.77 on x86/x86_64,
+/// where it was 8-aligned.
+#[repr(C, align(8))]
+struct OldU128(u64, u64);
+
+impl_for_opaque_primitives! {
+    (OldU128, "u128"),
+}
+
+fn errors_of(
+    interface: &'static TypeLayout,
+    implementation: &'static TypeLayout,
+) -> Vec<AbiInstability> {
+    check_layout_compatibility(interface, implementation)
+        .unwrap_err()
+        .flatten_errors()
+        .into_iter()
+        .collect()
+}
+
+#[test]
+fn int128_and_char_layouts() {
+    let layouts = [
+        u128::LAYOUT,
+        i128::LAYOUT,
+        char::LAYOUT,
+        NonZeroU128::LAYOUT,
+        NonZeroI128::LAYOUT,
+        <Option<NonZeroU128>>::LAYOUT,
+    ];
+
+    for (i, this) in layouts.iter().enumerate() {
+        for (j, other) in layouts.iter().enumerate() {
+            let res = check_layout_compatibility(this, other);
+            if i == j {
+                res.unwrap();
+            } else {
+                assert!(res.is_err(), "{} {}", this, other);
+            }
+        }
+    }
+
+    assert_eq!(u128::LAYOUT.size(), 16);
+    assert_eq!(u128::LAYOUT.alignment(), std::mem::align_of::<u128>());
+    assert_eq!(char::LAYOUT.size(), 4);
+    assert_eq!(<Option<NonZeroI128>>::LAYOUT.size(), 16);
+
+    assert!(check_layout_compatibility(char::LAYOUT, u32::LAYOUT).is_err());
+}
+
+#[test]
+fn int128_and_char_are_readable_by_older_checkers() {
+    // Older layout checkers only know the `TLPrimitive` variants that existed
+    // before these types were supported, so these are described as opaque types.
+    for layout in [u128::LAYOUT, i128::LAYOUT, char::LAYOUT] {
+        assert_eq!(layout.data(), TLData::Opaque, "{}", layout);
+        assert_eq!(layout.data_discriminant(), TLDataDiscriminant::Opaque);
+    }
+}
+
+#[test]
+fn int128_alignment_mismatch() {
+    if std::mem::align_of::<u128>() == 8 {
+        // Nothing to compare against when compiling with the older alignment.
+        return;
+    }
+
+    let errs = errors_of(u128::LAYOUT, OldU128::LAYOUT);
+
+    assert!(
+        errs.iter()
+            .any(|e| matches!(e, AbiInstability::Alignment { .. })),
+        "{:#?}",
+        errs,
+    );
+}
+
+#[test]
+fn atomic64_alignment() {
+    use std::sync::atomic::{AtomicI64, AtomicU64};
+
+    assert_eq!(AtomicI64::LAYOUT.alignment(), 8);
+    assert_eq!(AtomicU64::LAYOUT.alignment(), 8);
+    check_layout_compatibility(AtomicU64::LAYOUT, AtomicU64::LAYOUT).unwrap();
+    assert!(check_layout_compatibility(AtomicU64::LAYOUT, AtomicI64::LAYOUT).is_err());
+}
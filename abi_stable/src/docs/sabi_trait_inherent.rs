@@ -40,6 +40,10 @@ generated by the [`sabi_trait`] attribute.
 
 -[`sabi_reborrow`](#sabi_reborrow-method)
 
+-[`sabi_type_vtable`](#sabi_type_vtable-method)
+
+-[`sabi_cross_cast_into`](#sabi_cross_cast_into-method)
+
 
 
 
@@ -57,7 +61,7 @@ Constructs `<trait>_TO` from a pointer to a type that implements `<trait>`
 
 The `can_it_downcast` parameter describes whether the trait object can be
 converted back into the original type or not.<br>
-Its possible values are [`TD_CanDowncast`] and [`TD_Opaque`].
+Its possible values are [`TD_CanDowncast`], [`TD_Opaque`], and [`TD_StableDowncast`].
 
 [Method docs for `Action_TO::from_ptr`
 ](../../sabi_trait/doc_examples/struct.Action_TO.html#method.from_ptr)
@@ -140,7 +144,7 @@ wrapping that value in an [`RBox`].
 
 The `can_it_downcast` parameter describes whether the trait object can be
 converted back into the original type or not.<br>
-Its possible values are [`TD_CanDowncast`] and [`TD_Opaque`].
+Its possible values are [`TD_CanDowncast`], [`TD_Opaque`], and [`TD_StableDowncast`].
 
 [Method docs for `Action_TO::from_value`
 ](../../sabi_trait/doc_examples/struct.Action_TO.html#method.from_value)
@@ -182,7 +186,7 @@ implements `<trait>`,
 
 The `can_it_downcast` parameter describes whether the trait object can be
 converted back into the original type or not.<br>
-Its possible values are [`TD_CanDowncast`] and [`TD_Opaque`].
+Its possible values are [`TD_CanDowncast`], [`TD_Opaque`], and [`TD_StableDowncast`].
 
 [Method docs for `Action_TO::from_const`
 ](../../sabi_trait/doc_examples/struct.Action_TO.html#method.from_const)
@@ -356,19 +360,100 @@ where
 
 ```
 
+## `sabi_type_vtable` method
+
+```text
+impl<'lt, ErasedPtr, …> Trait_TO<'lt, ErasedPtr, …> {
+    pub fn sabi_type_vtable(&self) -> Trait_TypeVTable<'lt, …> {
+```
+
+Gets the static methods of the type that this trait object was constructed from.
+
+This method is only generated for traits with static methods,
+which are methods with no `self` parameter and a `where Self: Sized` bound.
+
+The `Trait_TO` returned by static methods that return `Self` is constructed
+with [`TD_Opaque`], so it can't be downcast.
+
+**Example**:
+```rust
+use abi_stable::{
+    sabi_trait,
+    std_types::{RBox, RString},
+    type_level::downcasting::TD_Opaque,
+};
 
+#[sabi_trait]
+pub trait Plugin {
+    fn create(name: RString) -> Self
+    where
+        Self: Sized;
 
+    fn name(&self) -> RString;
+}
 
+struct Greeter(RString);
 
+impl Plugin for Greeter {
+    fn create(name: RString) -> Self {
+        Greeter(name)
+    }
 
+    fn name(&self) -> RString {
+        self.0.clone()
+    }
+}
 
+# fn main(){
+let object: Plugin_TO<'static, RBox<()>> =
+    Plugin_TO::from_value(Greeter("hello".into()), TD_Opaque);
 
+let other = object.sabi_type_vtable().create("world".into());
 
+assert_eq!(other.name(), "world");
+# }
 
+```
 
+## `sabi_cross_cast_into` method
 
+```text
+impl<'lt, ErasedPtr, …> Trait_TO<'lt, ErasedPtr, …> {
+    pub fn sabi_cross_cast_into<To>(self) -> Result<To, CrossCastError<Self>>
+    where
+        To: CrossCastTarget<'lt, ErasedPtr = ErasedPtr>,
+```
 
+Converts this trait object into the trait object of another `#[sabi_trait]` trait,
+if the erased type was registered as implementing that trait with [`register_cross_cast`]
+(or [`register_stable_cross_cast`] for trait objects constructed with [`TD_StableDowncast`]).
 
+This returns an error if the trait object was constructed with [`TD_Opaque`],
+or if the erased type wasn't registered for `To`
+in the dynamic library/executable that constructed the trait object.
+
+This method is only generated for traits that don't use the `#[sabi(use_dyntrait)]` attribute.
+
+**Example**:
+```rust
+use abi_stable::{
+    sabi_trait::{
+        doc_examples::{Action_TO, Doer_TO},
+        register_cross_cast,
+    },
+    std_types::RBox,
+    type_level::downcasting::TD_CanDowncast,
+};
+
+register_cross_cast::<usize, Action_TO<'static, RBox<()>>>();
+
+let object: Doer_TO<'static, RBox<()>> = Doer_TO::from_value(3_usize, TD_CanDowncast);
+
+let object: Action_TO<'static, RBox<()>> = object.sabi_cross_cast_into().unwrap();
+
+assert_eq!(object.get(), 3);
+
+```
 
 [`sabi_trait`]: ../../attr.sabi_trait.html
 
@@ -384,4 +469,10 @@ where
 
 [`TD_Opaque`]: ../../type_level/downcasting/struct.TD_Opaque.html
 
+[`TD_StableDowncast`]: ../../type_level/downcasting/struct.TD_StableDowncast.html
+
+[`register_cross_cast`]: ../../sabi_trait/fn.register_cross_cast.html
+
+[`register_stable_cross_cast`]: ../../sabi_trait/fn.register_stable_cross_cast.html
+
 */
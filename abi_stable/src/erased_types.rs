@@ -42,8 +42,9 @@ pub use crate::DynTrait;
 pub use self::{
     dyn_trait::UneraseError,
     traits::{
-        DeserializeDyn, InterfaceType, IteratorItem, IteratorItemOrDefault, SerializeProxyType,
-        SerializeType,
+        DerefTarget, DerefTargetOrDefault, DeserializeDyn, FnSignature, FnSignatureOrDefault,
+        FutureOutput, FutureOutputOrDefault, InterfaceQueryableFrom, InterfaceSubsetOf,
+        InterfaceType, IteratorItem, IteratorItemOrDefault, SerializeProxyType, SerializeType,
     },
     type_info::TypeInfo,
     vtable::{MakeRequiredTraits, RequiredTraits},
@@ -53,7 +54,7 @@ pub use self::vtable::MakeVTable;
 pub use self::vtable::VTable_Ref;
 
 #[doc(no_inline)]
-pub use crate::type_level::downcasting::{TD_CanDowncast, TD_Opaque};
+pub use crate::type_level::downcasting::{TD_CanDowncast, TD_Opaque, TD_StableDowncast};
 
 /// The formatting mode for all std::fmt formatters.
 ///
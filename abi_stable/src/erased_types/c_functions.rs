@@ -1,9 +1,14 @@
 #![allow(non_snake_case)]
 
 use std::{
+    borrow::Borrow,
     fmt,
+    future::Future,
     io::{self, BufRead, Read, Write as IoWrite},
-    mem, ptr,
+    mem,
+    ops::Deref,
+    pin::Pin,
+    ptr,
 };
 
 use super::*;
@@ -11,8 +16,11 @@ use super::*;
 use crate::{
     marker_type::ErasedObject,
     pointer_trait::{GetPointerKind, PK_MutReference, PK_Reference, PK_SmartPointer},
-    sabi_types::{RMut, RRef},
-    std_types::{RIoError, RSeekFrom},
+    sabi_types::{MovePtr, RMut, RRef},
+    std_types::{
+        closures::{FnArgs, TupledFn, TupledFnMut, TupledFnOnce},
+        RContext, RIoError, RPoll, RSeekFrom,
+    },
 };
 
 use core_extensions::utils::transmute_ignore_size;
@@ -510,3 +518,119 @@ where
         convert_io_result(this.seek(seek_from.into()))
     }
 }
+
+///////////////////////////
+
+pub(super) unsafe extern "C" fn poll_future<F>(
+    this: RMut<'_, ErasedObject>,
+    cx: RContext<'_>,
+) -> RPoll<F::Output>
+where
+    F: Future + Unpin,
+{
+    extern_fn_panic_handling! {no_early_return;
+        let this = unsafe { this.transmute_into_mut::<F>() };
+
+        cx.with_context(|cx| Pin::new(this).poll(cx)).into()
+    }
+}
+
+///////////////////////////
+
+// The functions below transmute the lifetime of the returned reference
+// to the same lifetime as the input reference,
+// to avoid having to write a `T: 'a` bound.
+
+pub(super) unsafe extern "C" fn as_ref_str<T>(this: RRef<'_, ErasedObject>) -> RStr<'_>
+where
+    T: AsRef<str>,
+{
+    extern_fn_panic_handling! {no_early_return; unsafe {
+        let this = this.transmute_into_ref::<T>();
+
+        mem::transmute::<RStr<'_>, RStr<'_>>(this.as_ref().into())
+    }}
+}
+
+pub(super) unsafe extern "C" fn as_ref_bytes<T>(this: RRef<'_, ErasedObject>) -> RSlice<'_, u8>
+where
+    T: AsRef<[u8]>,
+{
+    extern_fn_panic_handling! {no_early_return; unsafe {
+        let this = this.transmute_into_ref::<T>();
+
+        mem::transmute::<RSlice<'_, u8>, RSlice<'_, u8>>(this.as_ref().into())
+    }}
+}
+
+pub(super) unsafe extern "C" fn borrow_str<T>(this: RRef<'_, ErasedObject>) -> RStr<'_>
+where
+    T: Borrow<str>,
+{
+    extern_fn_panic_handling! {no_early_return; unsafe {
+        let this = this.transmute_into_ref::<T>();
+
+        mem::transmute::<RStr<'_>, RStr<'_>>(this.borrow().into())
+    }}
+}
+
+pub(super) unsafe extern "C" fn deref_impl<T>(
+    this: RRef<'_, ErasedObject>,
+) -> RRef<'_, <T as Deref>::Target>
+where
+    T: Deref,
+    T::Target: Sized,
+{
+    extern_fn_panic_handling! {no_early_return; unsafe {
+        let this = this.transmute_into_ref::<T>();
+
+        mem::transmute::<RRef<'_, T::Target>, RRef<'_, T::Target>>(RRef::new(&**this))
+    }}
+}
+
+///////////////////////////
+
+pub(super) unsafe extern "C" fn call_once_impl<F, Args, R>(
+    this: MovePtr<'_, ErasedObject>,
+    args: Args::CArgs,
+) -> R
+where
+    F: TupledFnOnce<Args, R>,
+    Args: FnArgs,
+{
+    extern_fn_panic_handling! {no_early_return;
+        let this = unsafe { MovePtr::into_inner(MovePtr::transmute::<F>(this)) };
+
+        this.call_tupled_once(Args::from_c_args(args))
+    }
+}
+
+pub(super) unsafe extern "C" fn call_mut_impl<F, Args, R>(
+    this: RMut<'_, ErasedObject>,
+    args: Args::CArgs,
+) -> R
+where
+    F: TupledFnMut<Args, R>,
+    Args: FnArgs,
+{
+    extern_fn_panic_handling! {no_early_return;
+        let this = unsafe { this.transmute_into_mut::<F>() };
+
+        this.call_tupled_mut(Args::from_c_args(args))
+    }
+}
+
+pub(super) unsafe extern "C" fn call_impl<F, Args, R>(
+    this: RRef<'_, ErasedObject>,
+    args: Args::CArgs,
+) -> R
+where
+    F: TupledFn<Args, R>,
+    Args: FnArgs,
+{
+    extern_fn_panic_handling! {no_early_return;
+        let this = unsafe { this.transmute_into_ref::<F>() };
+
+        this.call_tupled(Args::from_c_args(args))
+    }
+}
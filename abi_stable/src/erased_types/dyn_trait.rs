@@ -1,11 +1,15 @@
 //! Contains the `DynTrait` type, and related traits/type aliases.
 
 use std::{
+    borrow::Borrow,
     fmt::{self, Write as fmtWrite},
+    future::Future,
     io,
     mem::ManuallyDrop,
+    pin::Pin,
     ptr,
     rc::Rc,
+    task::{Context, Poll},
 };
 
 use serde::{de, ser, Deserialize, Deserializer};
@@ -19,7 +23,8 @@ use crate::{
     },
     prefix_type::PrefixRef,
     sabi_types::{MovePtr, RMut, RRef},
-    std_types::{RBox, RIoError, RStr, RVec},
+    std_types::{closures::FnArgs, RBox, RContext, RIoError, RStr, RVec},
+    type_layout::stable_type_id::is_same_stable_type,
     type_level::{
         downcasting::{TD_CanDowncast, TD_Opaque},
         impl_enum::{Implemented, Unimplemented},
@@ -33,7 +38,10 @@ use crate::std_types::Tuple2;
 use super::{
     c_functions::adapt_std_fmt,
     trait_objects::*,
-    traits::{DeserializeDyn, GetSerializeProxyType},
+    traits::{
+        DerefTargetOrDefault, DeserializeDyn, FnSignatureOrDefault, FutureOutputOrDefault,
+        GetSerializeProxyType,
+    },
     type_info::TypeInfoFor,
     vtable::{MakeVTable, VTable_Ref},
     IteratorItemOrDefault, *,
@@ -732,6 +740,10 @@ mod priv_ {
         /// - [`TD_Opaque`]:
         ///     Which does not allow the trait object to be downcasted.
         ///
+        /// - [`TD_StableDowncast`]:
+        ///     Which allows the trait object to be downcasted in other dynamic libraries,
+        ///     requires that the value implements `StableAbi`.
+        ///
         /// <br>
         ///
         /// `extra_value`:
@@ -763,6 +775,7 @@ mod priv_ {
         ///
         /// [`TD_CanDowncast`]: ./type_level/downcasting/struct.TD_CanDowncast.html
         /// [`TD_Opaque`]: ./type_level/downcasting/struct.TD_Opaque.html
+        /// [`TD_StableDowncast`]: ./type_level/downcasting/struct.TD_StableDowncast.html
         pub const fn from_const<T, Downcasting>(
             ptr: &'a T,
             can_it_downcast: Downcasting,
@@ -1061,6 +1074,24 @@ mod priv_ {
             }
         }
 
+        /// Like `sabi_check_same_destructor`,
+        /// but also accepts a `T` from another library if the vtable stores its layout
+        /// (because the `DynTrait` was constructed with `TD_StableDowncast`),
+        /// and it's the same type with a compatible layout.
+        pub(super) fn sabi_check_same_stable_type<T>(&self) -> Result<(), UneraseError<()>>
+        where
+            T: StableAbi + 'static,
+            I: InterfaceType,
+        {
+            match (
+                self.sabi_check_same_destructor::<T>(),
+                self.sabi_vtable().stable_layout(),
+            ) {
+                (Err(_), Some(found)) if is_same_stable_type(T::LAYOUT, found) => Ok(()),
+                (res, _) => res,
+            }
+        }
+
         /// Unwraps the `DynTrait<_>` into a pointer of
         /// the concrete type that it was constructed with.
         ///
@@ -1219,6 +1250,150 @@ mod priv_ {
             unsafe { Ok(self.sabi_object_as_mut()) }
         }
 
+        /// Unwraps the `DynTrait<_>` into a pointer of
+        /// the concrete type that it was constructed with,
+        /// even if it was constructed in a different dynamic library.
+        ///
+        /// This requires the `DynTrait` to be constructed with [`TD_StableDowncast`],
+        /// to unwrap it outside of the dynamic library/binary that constructed it.
+        /// In that case,
+        /// the type is compared using the [`StableTypeId`] of `T`
+        /// and its layout in both dynamic libraries is checked to be compatible.
+        ///
+        /// # Errors
+        ///
+        /// This will return an error in any of these conditions:
+        ///
+        /// - It is called in a dynamic library/binary outside
+        /// the one from which this `DynTrait<_>` was constructed,
+        /// and it wasn't constructed with `TD_StableDowncast`.
+        ///
+        /// - The DynTrait was constructed using a `from_borrowing_*` method
+        ///
+        /// - `T` is not the concrete type this `DynTrait<_>` was constructed with,
+        /// or its layout is incompatible with the one in the dynamic library that constructed it.
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        /// use abi_stable::{
+        ///     erased_types::TD_StableDowncast,
+        ///     std_types::{RBox, RString},
+        ///     DynTrait,
+        /// };
+        ///
+        /// fn to() -> DynTrait<'static, RBox<()>, ()> {
+        ///     DynTrait::with_extra_value::<_, TD_StableDowncast>(RBox::new(RString::from("hi")), ())
+        /// }
+        ///
+        /// assert_eq!(
+        ///     to().stable_downcast_into::<RString>().ok(),
+        ///     Some(RBox::new(RString::from("hi")))
+        /// );
+        /// assert_eq!(to().stable_downcast_into::<u16>().ok(), None);
+        ///
+        /// ```
+        ///
+        /// [`TD_StableDowncast`]: crate::type_level::downcasting::TD_StableDowncast
+        /// [`StableTypeId`]: crate::type_layout::StableTypeId
+        pub fn stable_downcast_into<T>(self) -> Result<P::TransmutedPtr, UneraseError<Self>>
+        where
+            T: StableAbi + 'static,
+            P: CanTransmuteElement<T>,
+            I: InterfaceType,
+        {
+            check_unerased!(self, self.sabi_check_same_stable_type::<T>());
+            unsafe {
+                let this = ManuallyDrop::new(self);
+                Ok(ptr::read(&*this.object).transmute_element::<T>())
+            }
+        }
+
+        /// Unwraps the `DynTrait<_>` into a reference of
+        /// the concrete type that it was constructed with,
+        /// even if it was constructed in a different dynamic library.
+        ///
+        /// For details on how this differs from `downcast_as`,
+        /// look at [`stable_downcast_into`](#method.stable_downcast_into).
+        ///
+        /// # Errors
+        ///
+        /// This will return an error in any of these conditions:
+        ///
+        /// - It is called in a dynamic library/binary outside
+        /// the one from which this `DynTrait<_>` was constructed,
+        /// and it wasn't constructed with `TD_StableDowncast`.
+        ///
+        /// - The DynTrait was constructed using a `from_borrowing_*` method
+        ///
+        /// - `T` is not the concrete type this `DynTrait<_>` was constructed with,
+        /// or its layout is incompatible with the one in the dynamic library that constructed it.
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        /// use abi_stable::{erased_types::TD_StableDowncast, std_types::RArc, DynTrait};
+        ///
+        /// let to: DynTrait<'static, RArc<()>, ()> =
+        ///     DynTrait::with_extra_value::<_, TD_StableDowncast>(RArc::new(8u32), ());
+        ///
+        /// assert_eq!(to.stable_downcast_as::<u32>().ok(), Some(&8));
+        /// assert_eq!(to.stable_downcast_as::<i32>().ok(), None);
+        ///
+        /// ```
+        pub fn stable_downcast_as<T>(&self) -> Result<&T, UneraseError<&Self>>
+        where
+            T: StableAbi + 'static,
+            P: AsPtr,
+            I: InterfaceType,
+        {
+            check_unerased!(self, self.sabi_check_same_stable_type::<T>());
+            unsafe { Ok(self.sabi_object_as()) }
+        }
+
+        /// Unwraps the `DynTrait<_>` into a mutable reference of
+        /// the concrete type that it was constructed with,
+        /// even if it was constructed in a different dynamic library.
+        ///
+        /// For details on how this differs from `downcast_as_mut`,
+        /// look at [`stable_downcast_into`](#method.stable_downcast_into).
+        ///
+        /// # Errors
+        ///
+        /// This will return an error in any of these conditions:
+        ///
+        /// - It is called in a dynamic library/binary outside
+        /// the one from which this `DynTrait<_>` was constructed,
+        /// and it wasn't constructed with `TD_StableDowncast`.
+        ///
+        /// - The DynTrait was constructed using a `from_borrowing_*` method
+        ///
+        /// - `T` is not the concrete type this `DynTrait<_>` was constructed with,
+        /// or its layout is incompatible with the one in the dynamic library that constructed it.
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        /// use abi_stable::{erased_types::TD_StableDowncast, std_types::RBox, DynTrait};
+        ///
+        /// let mut to: DynTrait<'static, RBox<()>, ()> =
+        ///     DynTrait::with_extra_value::<_, TD_StableDowncast>(RBox::new(3u8), ());
+        ///
+        /// *to.stable_downcast_as_mut::<u8>().unwrap() += 5;
+        /// assert_eq!(to.stable_downcast_as_mut::<u8>().ok(), Some(&mut 8));
+        /// assert_eq!(to.stable_downcast_as_mut::<i8>().ok(), None);
+        ///
+        /// ```
+        pub fn stable_downcast_as_mut<T>(&mut self) -> Result<&mut T, UneraseError<&mut Self>>
+        where
+            T: StableAbi + 'static,
+            P: AsMutPtr,
+            I: InterfaceType,
+        {
+            check_unerased!(self, self.sabi_check_same_stable_type::<T>());
+            unsafe { Ok(self.sabi_object_as_mut()) }
+        }
+
         /// Unwraps the `DynTrait<_>` into a pointer to T,
         /// without checking whether `T` is the type that the DynTrait was constructed with.
         ///
@@ -1462,6 +1637,147 @@ mod priv_ {
         }
     }
 
+    impl<'borr, P, I, EV> DynTrait<'borr, P, I, EV>
+    where
+        P: GetPointerKind,
+        I: InterfaceType,
+    {
+        /// Converts this `DynTrait` into one whose `InterfaceType` requires
+        /// a subset of the traits that `I` requires,reusing the same vtable.
+        ///
+        /// The traits that both `I` and `I2` require must have the same
+        /// associated types (eg: the same `Iterator::Item`).
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        /// use abi_stable::{
+        ///     erased_types::interfaces::{CloneInterface, DebugDisplayInterface},
+        ///     std_types::{RArc, RBox},
+        ///     DynTrait, StableAbi,
+        /// };
+        ///
+        /// #[repr(C)]
+        /// #[derive(StableAbi)]
+        /// #[sabi(impl_InterfaceType(Send, Sync, Debug))]
+        /// pub struct DebugInterface;
+        ///
+        /// let to: DynTrait<'static, RBox<()>, DebugDisplayInterface> =
+        ///     DynTrait::from_value(3u8);
+        ///
+        /// let to: DynTrait<'static, RBox<()>, DebugInterface> = to.upcast();
+        /// assert_eq!(format!("{:?}", to), "3");
+        ///
+        /// // `()` only requires `Send + Sync`,dropping the `Clone` requirement.
+        /// let to: DynTrait<'static, RArc<()>, CloneInterface> =
+        ///     DynTrait::from_ptr(RArc::new(5u8));
+        /// let to: DynTrait<'static, RArc<()>, ()> = to.clone().upcast();
+        /// assert_eq!(to.downcast_as::<u8>().ok(), Some(&5));
+        ///
+        /// ```
+        pub fn upcast<I2>(self) -> DynTrait<'borr, P, I2, EV>
+        where
+            I2: InterfaceSubsetOf<'borr, I>,
+        {
+            let this = ManuallyDrop::new(self);
+            unsafe {
+                DynTrait {
+                    object: ptr::read(&this.object),
+                    // The fields accessible in the vtable are a superset of the ones that
+                    // `I2` requires,so only the interface type parameter is changed here.
+                    vtable: VTable_Ref(this.vtable.0.cast()),
+                    extra_value: ptr::read(&this.extra_value),
+                    _marker: NonOwningPhantom::NEW,
+                    _marker2: UnsafeIgnoredType::DEFAULT,
+                }
+            }
+        }
+
+        /// Whether the vtable of this `DynTrait` has the functions for
+        /// all the traits that `I2` requires.
+        ///
+        /// This is useful when the `DynTrait` was constructed in a dynamic library
+        /// compiled against a version of the interface that required fewer traits,
+        /// checking at runtime which traits the vtable provides.
+        ///
+        /// For an example, look at [`query_interface`](#method.query_interface).
+        pub fn supports_interface<I2>(&self) -> bool
+        where
+            I2: InterfaceQueryableFrom<'borr, I>,
+        {
+            self.vtable.has_fields_for::<I2>()
+        }
+
+        /// Converts this `DynTrait` into one whose `InterfaceType` is `I2`,
+        /// returning `None` if the vtable doesn't have the functions for
+        /// all the traits that `I2` requires.
+        ///
+        /// `I2` can require traits that `I` doesn't,
+        /// [`InterfaceQueryableFrom`] describes which traits those can be.
+        ///
+        /// To query the traits without consuming a `DynTrait` that owns its value,
+        /// you can call this on the `DynTrait` returned by
+        /// [`reborrow`](#method.reborrow) or [`reborrow_mut`](#method.reborrow_mut).
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        /// use abi_stable::{
+        ///     erased_types::interfaces::DebugDisplayInterface, std_types::RBox, DynTrait,
+        ///     StableAbi,
+        /// };
+        ///
+        /// #[repr(C)]
+        /// #[derive(StableAbi)]
+        /// #[sabi(impl_InterfaceType(Send, Sync))]
+        /// pub struct PlainInterface;
+        ///
+        /// #[repr(C)]
+        /// #[derive(StableAbi)]
+        /// #[sabi(impl_InterfaceType(Send, Sync, Hash))]
+        /// pub struct HashInterface;
+        ///
+        /// // Pretending that these were returned by a plugin,
+        /// // requiring only the traits in `PlainInterface` to be implemented.
+        /// let number: DynTrait<'static, RBox<()>, PlainInterface> =
+        ///     DynTrait::from_value(3u8)
+        ///         .interface(DebugDisplayInterface)
+        ///         .upcast();
+        ///
+        /// assert!(number.supports_interface::<DebugDisplayInterface>());
+        /// assert!(!number.supports_interface::<HashInterface>());
+        ///
+        /// assert!(number.reborrow().query_interface::<HashInterface>().is_none());
+        ///
+        /// let number = number
+        ///     .query_interface::<DebugDisplayInterface>()
+        ///     .unwrap();
+        /// assert_eq!(format!("{:?} {}", number, number), "3 3");
+        ///
+        /// ```
+        pub fn query_interface<I2>(self) -> Option<DynTrait<'borr, P, I2, EV>>
+        where
+            I2: InterfaceQueryableFrom<'borr, I>,
+        {
+            if !self.supports_interface::<I2>() {
+                return None;
+            }
+
+            let this = ManuallyDrop::new(self);
+            unsafe {
+                Some(DynTrait {
+                    object: ptr::read(&this.object),
+                    // The vtable has all the fields that `I2` requires,
+                    // which was checked at runtime above.
+                    vtable: VTable_Ref(this.vtable.0.cast()),
+                    extra_value: ptr::read(&this.extra_value),
+                    _marker: NonOwningPhantom::NEW,
+                    _marker2: UnsafeIgnoredType::DEFAULT,
+                })
+            }
+        }
+    }
+
     impl<'borr, P, I, EV> DynTrait<'borr, P, I, EV>
     where
         P: AsPtr,
@@ -2159,6 +2475,219 @@ where
 
 //////////////////////////////////////////////////////////////////
 
+impl<'borr, P, I, Output, EV> Future for DynTrait<'borr, P, I, EV>
+where
+    P: AsMutPtr,
+    I: FutureOutputOrDefault<'borr, Output = Output>,
+    I: InterfaceType<Future = Implemented<trait_marker::Future>>,
+{
+    type Output = Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Output> {
+        // safety: the erased future is required to be `Unpin` when the vtable is constructed,
+        // and nothing is moved out of `self`.
+        let this = unsafe { self.get_unchecked_mut() };
+        let poll = this.sabi_vtable().poll();
+
+        RContext::scoped(cx, |cx| unsafe { poll(this.sabi_erased_mut(), cx) }).into()
+    }
+}
+
+//////////////////////////////////////////////////////////////////
+
+impl<'borr, P, I, EV> AsRef<str> for DynTrait<'borr, P, I, EV>
+where
+    P: AsPtr,
+    I: InterfaceType<AsRefStr = Implemented<trait_marker::AsRefStr>>,
+{
+    fn as_ref(&self) -> &str {
+        unsafe { self.sabi_vtable().as_ref_str()(self.sabi_erased_ref()).as_str() }
+    }
+}
+
+impl<'borr, P, I, EV> AsRef<[u8]> for DynTrait<'borr, P, I, EV>
+where
+    P: AsPtr,
+    I: InterfaceType<AsRefBytes = Implemented<trait_marker::AsRefBytes>>,
+{
+    fn as_ref(&self) -> &[u8] {
+        unsafe { self.sabi_vtable().as_ref_bytes()(self.sabi_erased_ref()).as_slice() }
+    }
+}
+
+impl<'borr, P, I, EV> Borrow<str> for DynTrait<'borr, P, I, EV>
+where
+    P: AsPtr,
+    I: InterfaceType<BorrowStr = Implemented<trait_marker::BorrowStr>>,
+{
+    fn borrow(&self) -> &str {
+        unsafe { self.sabi_vtable().borrow_str()(self.sabi_erased_ref()).as_str() }
+    }
+}
+
+impl<'borr, P, I, Target, EV> DynTrait<'borr, P, I, EV>
+where
+    P: AsPtr,
+    I: DerefTargetOrDefault<'borr, Target = Target>,
+    I: InterfaceType<Deref = Implemented<trait_marker::Deref>>,
+{
+    /// Dereferences the erased object,
+    /// equivalent to calling [`Deref::deref`](std::ops::Deref::deref) on it.
+    ///
+    /// `DynTrait` doesn't implement `Deref` itself,
+    /// because that would prevent inferring the `I` type parameter
+    /// from methods called on `DynTrait`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use abi_stable::{
+    ///     erased_types::DerefTarget,
+    ///     std_types::{RBox, RVec},
+    ///     DynTrait, StableAbi,
+    /// };
+    ///
+    /// #[repr(C)]
+    /// #[derive(StableAbi)]
+    /// #[sabi(impl_InterfaceType(Deref))]
+    /// struct Interface;
+    ///
+    /// impl<'a> DerefTarget<'a> for Interface {
+    ///     type Target = RVec<u32>;
+    /// }
+    ///
+    /// let wrapped: DynTrait<'static, RBox<()>, Interface> =
+    ///     DynTrait::from_value(Box::new(RVec::from(vec![3, 5, 8])));
+    ///
+    /// assert_eq!(wrapped.deref_target()[..], [3, 5, 8]);
+    /// ```
+    pub fn deref_target(&self) -> &Target {
+        unsafe { self.sabi_vtable().deref()(self.sabi_erased_ref()).get() }
+    }
+}
+
+//////////////////////////////////////////////////////////////////
+
+impl<'borr, P, I, Args, Output, EV> DynTrait<'borr, P, I, EV>
+where
+    P: GetPointerKind,
+    I: FnSignatureOrDefault<'borr, Args = Args, Output = Output>,
+    Args: FnArgs,
+{
+    /// Calls the erased closure by value,
+    /// passing the arguments as a tuple.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use abi_stable::{
+    ///     erased_types::FnSignature,
+    ///     std_types::{RBox, RString},
+    ///     DynTrait, StableAbi,
+    /// };
+    ///
+    /// #[repr(C)]
+    /// #[derive(StableAbi)]
+    /// #[sabi(impl_InterfaceType(FnOnce))]
+    /// struct Interface;
+    ///
+    /// impl<'a> FnSignature<'a> for Interface {
+    ///     type Args = (u32,);
+    ///     type Output = RString;
+    /// }
+    ///
+    /// let greeting = RString::from("hello");
+    /// let func: DynTrait<'static, RBox<()>, Interface> =
+    ///     DynTrait::from_value(move |n: u32| {
+    ///         let mut greeting = greeting;
+    ///         greeting.push_str(&"!".repeat(n as usize));
+    ///         greeting
+    ///     });
+    ///
+    /// assert_eq!(func.call_once((3,)), "hello!!!");
+    /// ```
+    pub fn call_once(self, args: Args) -> Output
+    where
+        P: OwnedPointer<PtrTarget = ()>,
+        I: InterfaceType<FnOnce = Implemented<trait_marker::FnOnce>>,
+    {
+        let call_once = self.sabi_vtable().call_once();
+        self.sabi_with_value(move |this| unsafe {
+            call_once(MovePtr::transmute(this), args.into_c_args())
+        })
+    }
+
+    /// Calls the erased closure by mutable reference,
+    /// passing the arguments as a tuple.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use abi_stable::{erased_types::FnSignature, std_types::RBox, DynTrait, StableAbi};
+    ///
+    /// #[repr(C)]
+    /// #[derive(StableAbi)]
+    /// #[sabi(impl_InterfaceType(FnMut))]
+    /// struct Interface;
+    ///
+    /// impl<'a> FnSignature<'a> for Interface {
+    ///     type Args = (u32,);
+    ///     type Output = u32;
+    /// }
+    ///
+    /// let mut total = 0;
+    /// let mut func: DynTrait<'static, RBox<()>, Interface> =
+    ///     DynTrait::from_value(move |n: u32| {
+    ///         total += n;
+    ///         total
+    ///     });
+    ///
+    /// assert_eq!(func.call_mut((3,)), 3);
+    /// assert_eq!(func.call_mut((5,)), 8);
+    /// ```
+    pub fn call_mut(&mut self, args: Args) -> Output
+    where
+        P: AsMutPtr,
+        I: InterfaceType<FnMut = Implemented<trait_marker::FnMut>>,
+    {
+        unsafe { self.sabi_vtable().call_mut()(self.sabi_erased_mut(), args.into_c_args()) }
+    }
+
+    /// Calls the erased closure by reference,
+    /// passing the arguments as a tuple.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use abi_stable::{erased_types::FnSignature, std_types::RBox, DynTrait, StableAbi};
+    ///
+    /// #[repr(C)]
+    /// #[derive(StableAbi)]
+    /// #[sabi(impl_InterfaceType(Fn))]
+    /// struct Interface;
+    ///
+    /// impl<'a> FnSignature<'a> for Interface {
+    ///     type Args = (u32, u32);
+    ///     type Output = u32;
+    /// }
+    ///
+    /// let func: DynTrait<'static, RBox<()>, Interface> =
+    ///     DynTrait::from_value(|l: u32, r: u32| l * r);
+    ///
+    /// assert_eq!(func.call((3, 5)), 15);
+    /// assert_eq!(func.call((8, 13)), 104);
+    /// ```
+    pub fn call(&self, args: Args) -> Output
+    where
+        P: AsPtr,
+        I: InterfaceType<Fn = Implemented<trait_marker::Fn>>,
+    {
+        unsafe { self.sabi_vtable().call()(self.sabi_erased_ref(), args.into_c_args()) }
+    }
+}
+
+//////////////////////////////////////////////////////////////////
+
 unsafe impl<'borr, P, I, EV> Send for DynTrait<'borr, P, I, EV>
 where
     P: Send + GetPointerKind,
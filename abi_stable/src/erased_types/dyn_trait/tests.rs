@@ -1083,3 +1083,546 @@ mod borrowing {
         }
     }
 }
+
+//////////////////////////////////////////////////////////////////
+
+mod future_and_closures {
+    use super::*;
+
+    use std::{
+        cell::Cell,
+        collections::HashSet,
+        future::Future,
+        pin::Pin,
+        rc::Rc,
+        sync::{
+            atomic::{AtomicUsize, Ordering as AtomicOrdering},
+            Arc,
+        },
+        task::{Context, Poll, Wake, Waker},
+    };
+
+    use crate::{
+        erased_types::{DerefTarget, FnSignature, FutureOutput, MakeRequiredTraits},
+        sabi_types::RRef,
+        std_types::RVec,
+    };
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, AtomicOrdering::SeqCst);
+        }
+    }
+
+    /// Polls `fut` until it's ready, returning its output and the amount of times it was woken up.
+    fn block_on<F: Future + Unpin>(mut fut: F) -> (F::Output, usize) {
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(x) = Pin::new(&mut fut).poll(&mut cx) {
+                return (x, counter.0.load(AtomicOrdering::SeqCst));
+            }
+        }
+    }
+
+    /// A future that is pending `count` times before outputting `value`.
+    struct Countdown {
+        count: u32,
+        value: u32,
+    }
+
+    impl Future for Countdown {
+        type Output = RString;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<RString> {
+            if self.count == 0 {
+                Poll::Ready(self.value.to_string().into())
+            } else {
+                self.count -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(Send, Sync, Future))]
+    struct FutureInterface;
+
+    impl<'a> FutureOutput<'a> for FutureInterface {
+        type Output = RString;
+    }
+
+    #[test]
+    fn future_interface_test() {
+        type GI = GetImpls<DynTraitBox<FutureInterface>>;
+        assert!(GI::IMPLS_SEND);
+        assert!(GI::IMPLS_SYNC);
+        assert!(GI::IMPLS_UNPIN);
+        assert!(GI::IMPLS_FUTURE);
+        assert!(!GI::IMPLS_ITERATOR);
+        assert!(!GI::IMPLS_AS_REF_STR);
+        assert!(!GI::IMPLS_AS_REF_BYTES);
+        assert!(!GI::IMPLS_BORROW_STR);
+
+        let required = <FutureInterface as MakeRequiredTraits>::MAKE;
+        assert!(required.contains_future());
+        assert!(!required.contains_fn_once());
+    }
+
+    #[test]
+    fn future_poll() {
+        let wrapped: DynTraitBox<FutureInterface> = DynTrait::from_value(Countdown {
+            count: 3,
+            value: 21,
+        });
+        assert_eq!(block_on(wrapped), (RString::from("21"), 3));
+
+        let wrapped: DynTraitBox<FutureInterface> =
+            DynTrait::from_value(Box::pin(async { RString::from("hello") }));
+        assert_eq!(block_on(wrapped), (RString::from("hello"), 0));
+
+        let mut countdown = Countdown { count: 1, value: 8 };
+        let wrapped = DynTrait::from_ptr(&mut countdown).interface(FutureInterface);
+        assert_eq!(block_on(wrapped), (RString::from("8"), 1));
+    }
+
+    ////////////////
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(Debug, Hash, Eq, AsRefStr, AsRefBytes, BorrowStr))]
+    struct StrInterface;
+
+    #[test]
+    fn str_interface_test() {
+        type GI = GetImpls<DynTraitBox<StrInterface>>;
+        assert!(GI::IMPLS_DEBUG);
+        assert!(GI::IMPLS_HASH);
+        assert!(GI::IMPLS_EQ);
+        assert!(GI::IMPLS_AS_REF_STR);
+        assert!(GI::IMPLS_AS_REF_BYTES);
+        assert!(GI::IMPLS_BORROW_STR);
+        assert!(!GI::IMPLS_FUTURE);
+    }
+
+    #[test]
+    fn as_ref_and_borrow() {
+        use std::borrow::Borrow;
+
+        let wrapped: DynTraitBox<StrInterface> = DynTrait::from_value(String::from("hello"));
+
+        assert_eq!(AsRef::<str>::as_ref(&wrapped), "hello");
+        assert_eq!(AsRef::<[u8]>::as_ref(&wrapped), b"hello");
+        assert_eq!(Borrow::<str>::borrow(&wrapped), "hello");
+
+        let set = ["foo", "bar", "baz"]
+            .iter()
+            .map(|s| DynTrait::from_value(s.to_string()).interface(StrInterface))
+            .collect::<HashSet<DynTraitBox<StrInterface>>>();
+
+        assert!(set.contains("foo"));
+        assert!(set.contains("baz"));
+        assert!(!set.contains("qux"));
+    }
+
+    ////////////////
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(Deref))]
+    struct DerefInterface;
+
+    impl<'a> DerefTarget<'a> for DerefInterface {
+        type Target = RVec<u32>;
+    }
+
+    #[test]
+    fn deref_target() {
+        let wrapped: DynTraitBox<DerefInterface> =
+            DynTrait::from_value(Rc::new(RVec::from(vec![3, 5, 8])));
+        assert_eq!(wrapped.deref_target()[..], [3, 5, 8]);
+
+        let boxed = Box::new(RVec::from(vec![13, 21]));
+        let wrapped = DynTrait::from_ptr(&boxed).interface(DerefInterface);
+        assert_eq!(wrapped.deref_target()[..], [13, 21]);
+    }
+
+    ////////////////
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(FnOnce))]
+    struct FnOnceInterface;
+
+    impl<'a> FnSignature<'a> for FnOnceInterface {
+        type Args = (u32, RString);
+        type Output = RString;
+    }
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(FnMut))]
+    struct FnMutInterface;
+
+    impl<'a> FnSignature<'a> for FnMutInterface {
+        type Args = (u32,);
+        type Output = usize;
+    }
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(Send, Sync, Fn))]
+    struct FnInterface;
+
+    impl<'a> FnSignature<'a> for FnInterface {
+        type Args = (RStr<'a>,);
+        type Output = RStr<'a>;
+    }
+
+    #[test]
+    fn required_closure_traits() {
+        let once = <FnOnceInterface as MakeRequiredTraits>::MAKE;
+        assert!(once.contains_fn_once());
+        assert!(!once.contains_fn_mut());
+        assert!(!once.contains_fn());
+
+        let mut_ = <FnMutInterface as MakeRequiredTraits>::MAKE;
+        assert!(mut_.contains_fn_once());
+        assert!(mut_.contains_fn_mut());
+        assert!(!mut_.contains_fn());
+
+        let fn_ = <FnInterface as MakeRequiredTraits>::MAKE;
+        assert!(fn_.contains_fn_once());
+        assert!(fn_.contains_fn_mut());
+        assert!(fn_.contains_fn());
+    }
+
+    #[test]
+    fn call_once() {
+        let rc = Rc::new(());
+        let captured = rc.clone();
+        let func: DynTraitBox<FnOnceInterface> =
+            DynTrait::from_value(move |n: u32, mut acc: RString| {
+                acc.push_str(&(n * Rc::strong_count(&captured) as u32).to_string());
+                acc
+            });
+        assert_eq!(func.call_once((3, RString::from("n="))), "n=6");
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let captured = rc.clone();
+        let func: DynTraitBox<FnOnceInterface> =
+            DynTrait::from_value(move |_: u32, acc: RString| {
+                drop(captured);
+                acc
+            });
+        assert_eq!(Rc::strong_count(&rc), 2);
+        drop(func);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn call_mut() {
+        let mut list = Vec::new();
+        {
+            let mut push = |x: u32| {
+                list.push(x);
+                list.len()
+            };
+            let mut func = DynTrait::from_borrowing_ptr(&mut push).interface(FnMutInterface);
+            assert_eq!(func.call_mut((3,)), 1);
+            assert_eq!(func.call_mut((5,)), 2);
+        }
+        assert_eq!(list, [3, 5]);
+
+        let calls = Cell::new(0);
+        let func: DynTrait<'_, RBox<()>, FnMutInterface> =
+            DynTrait::from_borrowing_value(|x: u32| {
+                calls.set(calls.get() + 1);
+                x as usize * 2
+            });
+        assert_eq!(func.call_once((8,)), 16);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn call() {
+        fn trim(s: RStr<'_>) -> RStr<'_> {
+            s.as_str().trim().into()
+        }
+
+        let owned = String::from("  hello  ");
+
+        let func: DynTraitBox<FnInterface> = DynTrait::from_value(trim);
+        assert_eq!(func.call((RStr::from(" world "),)), "world");
+
+        let func = DynTrait::from_borrowing_value(trim).interface(FnInterface);
+        assert_eq!(func.call((owned.as_str().into(),)), "hello");
+
+        let func = DynTrait::from_ptr(RRef::new(&trim)).interface(FnInterface);
+        assert_eq!(func.call((" foo".into(),)), "foo");
+    }
+}
+
+mod upcasting {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::erased_types::{
+        interfaces::{DEIteratorInterface, DebugDisplayInterface, IteratorInterface},
+        InterfaceSubsetOf,
+    };
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(Debug))]
+    struct DebugInterface;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(Send, Sync, Iterator))]
+    struct StrIteratorInterface;
+
+    impl<'a> IteratorItem<'a> for StrIteratorInterface {
+        type Item = &'a str;
+    }
+
+    fn is_subset_of<'borr, I2, I>() -> bool
+    where
+        I: InterfaceType,
+        I2: InterfaceSubsetOf<'borr, I>,
+    {
+        true
+    }
+
+    #[test]
+    fn subset_relations() {
+        assert!(is_subset_of::<DebugInterface, DebugDisplayInterface>());
+        assert!(is_subset_of::<DebugDisplayInterface, DebugDisplayInterface>());
+        assert!(is_subset_of::<IteratorInterface<u8>, DEIteratorInterface<u8>>());
+        assert!(is_subset_of::<(), DEIteratorInterface<u8>>());
+    }
+
+    #[test]
+    fn upcast_formatting() {
+        let wide: DynTraitBox<FooInterface> = DynTrait::from_value(Foo {
+            l: 3,
+            r: 5,
+            name: RString::from("hello"),
+        });
+        let expected_debug = format!("{:?}", wide);
+
+        let narrow: DynTraitBox<DebugInterface> = wide.upcast();
+        assert_eq!(format!("{:?}", narrow), expected_debug);
+        assert_eq!(
+            narrow.downcast_as::<Foo<RString>>().unwrap().name.as_str(),
+            "hello"
+        );
+    }
+
+    #[test]
+    fn upcast_iterator() {
+        let wide = DynTrait::from_value(0..10u8).interface(DEIteratorInterface::NEW);
+        let mut narrow: DynTraitBox<IteratorInterface<u8>> = wide.upcast();
+        assert_eq!(narrow.next(), Some(0));
+        assert_eq!(narrow.by_ref().skip(8).next(), Some(9));
+
+        let text = String::from("foo bar baz");
+        let wide = DynTrait::from_borrowing_value(text.split(' ')).interface(StrIteratorInterface);
+        let narrow: DynTrait<'_, RBox<()>, IteratorInterface<&str>> = wide.upcast();
+        assert_eq!(narrow.collect::<Vec<_>>(), ["foo", "bar", "baz"]);
+    }
+
+    #[test]
+    fn upcast_reborrow_and_drop() {
+        let rc = Arc::new(());
+        let mut wide: DynTraitBox<DebugDisplayInterface> =
+            DynTrait::from_value(Displayable(rc.clone()));
+
+        {
+            let narrow: DynTrait<'_, RMut<'_, ()>, DebugInterface> = wide.reborrow_mut().upcast();
+            assert_eq!(format!("{:?}", narrow), "Displayable(())");
+        }
+        assert_eq!(Arc::strong_count(&rc), 2);
+
+        let narrow: DynTraitBox<()> = wide.upcast();
+        assert_eq!(Arc::strong_count(&rc), 2);
+        drop(narrow);
+        assert_eq!(Arc::strong_count(&rc), 1);
+    }
+
+    #[derive(Debug)]
+    struct Displayable(Arc<()>);
+
+    impl Display for Displayable {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("Displayable")
+        }
+    }
+}
+
+mod interface_queries {
+    use super::*;
+
+    use crate::erased_types::{
+        interfaces::{DEIteratorInterface, IteratorInterface},
+        InterfaceQueryableFrom,
+    };
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(Debug))]
+    struct DebugInterface;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(Clone, Debug, Display))]
+    struct CloneDisplayInterface;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(Debug, FmtWrite))]
+    struct FmtWriteInterface;
+
+    fn is_queryable_from<'borr, I2, I>() -> bool
+    where
+        I: InterfaceType,
+        I2: InterfaceQueryableFrom<'borr, I>,
+    {
+        true
+    }
+
+    #[test]
+    fn queryable_relations() {
+        assert!(is_queryable_from::<CloneDisplayInterface, DebugInterface>());
+        assert!(is_queryable_from::<FmtWriteInterface, ()>());
+        assert!(is_queryable_from::<
+            DEIteratorInterface<u8>,
+            IteratorInterface<u8>,
+        >());
+    }
+
+    fn foo() -> DynTraitBox<FooInterface> {
+        DynTrait::from_value(Foo {
+            l: 3,
+            r: 5,
+            name: RString::from("hello"),
+        })
+    }
+
+    #[test]
+    fn query_formatting() {
+        let narrow: DynTraitBox<DebugInterface> = foo().upcast();
+
+        assert!(narrow.supports_interface::<CloneDisplayInterface>());
+        assert!(!narrow.supports_interface::<FmtWriteInterface>());
+        assert!(narrow
+            .reborrow()
+            .query_interface::<FmtWriteInterface>()
+            .is_none());
+
+        let queried = narrow.query_interface::<CloneDisplayInterface>().unwrap();
+        let cloned = queried.clone();
+        assert_eq!(format!("{}", cloned), format!("{}", foo()));
+        assert_eq!(format!("{:?}", queried), format!("{:?}", foo()));
+    }
+
+    #[test]
+    fn query_missing_fields() {
+        // The vtable was constructed with an interface that only requires `Debug`.
+        let narrow = DynTrait::from_value(Foo {
+            l: 3,
+            r: 5,
+            name: RString::from("hello"),
+        })
+        .interface(DebugInterface);
+
+        assert!(narrow.supports_interface::<DebugInterface>());
+        assert!(!narrow.supports_interface::<CloneDisplayInterface>());
+        assert!(narrow.query_interface::<CloneDisplayInterface>().is_none());
+    }
+
+    #[test]
+    fn query_iterator() {
+        let wide = DynTrait::from_value(0..10u8).interface(DEIteratorInterface::NEW);
+        let narrow: DynTraitBox<IteratorInterface<u8>> = wide.upcast();
+        let mut queried = narrow.query_interface::<DEIteratorInterface<u8>>().unwrap();
+        assert_eq!(queried.next_back(), Some(9));
+        assert_eq!(queried.next(), Some(0));
+
+        let narrow = DynTrait::from_value(0..10u8).interface(IteratorInterface::NEW);
+        assert!(!narrow.supports_interface::<DEIteratorInterface<u8>>());
+        assert!(narrow
+            .query_interface::<DEIteratorInterface<u8>>()
+            .is_none());
+    }
+}
+
+mod stable_downcasting {
+    use super::*;
+
+    use crate::{
+        erased_types::TD_StableDowncast,
+        std_types::RVec,
+        test_utils::{TD_ForeignCanDowncast, TD_ForeignStable},
+    };
+
+    #[test]
+    fn local_stable_downcast() {
+        let mut to: DynTraitBox<()> =
+            DynTrait::with_extra_value::<_, TD_StableDowncast>(RBox::new(RString::from("hi")), ());
+
+        assert_eq!(to.downcast_as::<RString>().unwrap(), "hi");
+        assert_eq!(to.stable_downcast_as::<RString>().unwrap(), "hi");
+        assert!(to.stable_downcast_as::<RVec<u8>>().is_err());
+
+        to.stable_downcast_as_mut::<RString>()
+            .unwrap()
+            .push_str(" there");
+        assert_eq!(
+            *to.stable_downcast_into::<RString>().unwrap(),
+            RString::from("hi there")
+        );
+    }
+
+    #[test]
+    fn foreign_stable_downcast() {
+        let mut to: DynTraitBox<()> =
+            DynTrait::with_extra_value::<_, TD_ForeignStable>(RBox::new(RString::from("hi")), ());
+
+        // The regular downcasting methods only work in the library that constructed the object.
+        assert!(to.downcast_as::<RString>().is_err());
+        assert!(to.downcast_as_mut::<RString>().is_err());
+
+        assert_eq!(to.stable_downcast_as::<RString>().unwrap(), "hi");
+        assert!(to.stable_downcast_as::<RVec<u8>>().is_err());
+        assert!(to.stable_downcast_as::<RStr<'static>>().is_err());
+
+        to.stable_downcast_as_mut::<RString>().unwrap().push('!');
+
+        let to = to.stable_downcast_into::<u32>().unwrap_err().into_inner();
+        assert_eq!(
+            *to.stable_downcast_into::<RString>().unwrap(),
+            RString::from("hi!")
+        );
+    }
+
+    #[test]
+    fn foreign_without_stable_layout() {
+        let mut to: DynTraitBox<()> =
+            DynTrait::with_extra_value::<_, TD_ForeignCanDowncast>(RBox::new(3u32), ());
+
+        assert!(to.downcast_as::<u32>().is_err());
+        assert!(to.stable_downcast_as::<u32>().is_err());
+        assert!(to.stable_downcast_as_mut::<u32>().is_err());
+        assert!(to.stable_downcast_into::<u32>().is_err());
+
+        let opaque: DynTraitBox<()> = DynTrait::from_borrowing_value(3u32);
+        assert!(opaque.stable_downcast_as::<u32>().is_err());
+    }
+}
@@ -1,11 +1,11 @@
 //! Traits for types wrapped in `DynTrait<_>`
 
-use crate::std_types::RBoxError;
+use crate::std_types::{closures::FnArgs, RBoxError};
 
 #[allow(unused_imports)]
 use crate::type_level::{
     bools::{False, True},
-    impl_enum::{Implementability, Implemented, Unimplemented},
+    impl_enum::{Implementability, Implemented, IsSubsetOf, Unimplemented},
     trait_marker,
 };
 
@@ -32,6 +32,20 @@ macro_rules! declare_InterfaceType {
             type define_this_in_the_impl_InterfaceType_macro;
         }
 
+        /// Implemented for [`InterfaceType`]s that require a subset of the traits that `I` does,
+        /// with the same associated types
+        /// (eg: [`IteratorItem`], [`FutureOutput`]) for the traits they both require.
+        ///
+        /// This allows [`DynTrait::upcast`](crate::DynTrait::upcast)
+        /// to convert a `DynTrait<'borr, P, I>` into a `DynTrait<'borr, P, Self>`.
+        pub trait InterfaceSubsetOf<'borr, I: InterfaceType>: InterfaceType {}
+
+        impl<'borr, I, This> InterfaceSubsetOf<'borr, I> for This
+        where
+            I: InterfaceType,
+            This: InterfaceType + SameAssocTypesAs<'borr, I>,
+            $( This::$trait_: IsSubsetOf<I::$trait_>, )*
+        {}
 
     )
 }
@@ -121,6 +135,22 @@ declare_InterfaceType! {
     ///     // type IoBufRead = Unimplemented<trait_marker::IoBufRead>;
     ///
     ///     // type Error = Unimplemented<trait_marker::Error>;
+    ///
+    ///     // type Future = Unimplemented<trait_marker::Future>;
+    ///
+    ///     // type AsRefStr = Unimplemented<trait_marker::AsRefStr>;
+    ///
+    ///     // type AsRefBytes = Unimplemented<trait_marker::AsRefBytes>;
+    ///
+    ///     // type BorrowStr = Unimplemented<trait_marker::BorrowStr>;
+    ///
+    ///     // type Deref = Unimplemented<trait_marker::Deref>;
+    ///
+    ///     // type FnOnce = Unimplemented<trait_marker::FnOnce>;
+    ///
+    ///     // type FnMut = Unimplemented<trait_marker::FnMut>;
+    ///
+    ///     // type Fn = Unimplemented<trait_marker::Fn>;
     /// }
     /// */
     ///
@@ -198,6 +228,35 @@ declare_InterfaceType! {
 
         /// For the `std::error::Error` trait
         type Error;
+
+        /// For the `std::future::Future` trait,
+        /// the output type is specified with [`FutureOutput`].
+        type Future;
+
+        /// For the `AsRef<str>` trait
+        type AsRefStr;
+
+        /// For the `AsRef<[u8]>` trait
+        type AsRefBytes;
+
+        /// For the `Borrow<str>` trait
+        type BorrowStr;
+
+        /// For the `std::ops::Deref` trait,
+        /// the target type is specified with [`DerefTarget`].
+        type Deref;
+
+        /// For the `std::ops::FnOnce` trait,
+        /// the signature is specified with [`FnSignature`].
+        type FnOnce;
+
+        /// For the `std::ops::FnMut` trait,
+        /// the signature is specified with [`FnSignature`].
+        type FnMut;
+
+        /// For the `std::ops::Fn` trait,
+        /// the signature is specified with [`FnSignature`].
+        type Fn;
     ]
 
 
@@ -426,6 +485,248 @@ impl<'borr, I> IteratorItemOrDefaultHelper<'borr, Unimplemented<trait_marker::It
     type Item = ();
 }
 
+///////////////////////////////////////
+
+/// The way to specify the expected `Future::Output` type for an `InterfaceType`.
+///
+/// This is a separate trait to allow futures that output borrowed values.
+pub trait FutureOutput<'a>: InterfaceType {
+    /// The future output type.
+    type Output;
+}
+
+/// Gets the expected `Future::Output` type for an `InterfaceType`,
+/// defaulting to `()` if it doesn't require `Future` to be implemented.
+///
+/// Used by `DynTrait`'s vtable to give its `poll` function a defaulted return type.
+pub trait FutureOutputOrDefault<'borr>: InterfaceType {
+    /// The future output type.
+    type Output;
+}
+
+impl<'borr, I, Output> FutureOutputOrDefault<'borr> for I
+where
+    I: InterfaceType,
+    I: FutureOutputOrDefaultHelper<'borr, <I as InterfaceType>::Future, Output = Output>,
+{
+    type Output = Output;
+}
+
+#[doc(hidden)]
+pub trait FutureOutputOrDefaultHelper<'borr, ImplIsRequired> {
+    type Output;
+}
+
+impl<'borr, I, Output> FutureOutputOrDefaultHelper<'borr, Implemented<trait_marker::Future>> for I
+where
+    I: FutureOutput<'borr, Output = Output>,
+{
+    type Output = Output;
+}
+
+impl<'borr, I> FutureOutputOrDefaultHelper<'borr, Unimplemented<trait_marker::Future>> for I {
+    type Output = ();
+}
+
+///////////////////////////////////////
+
+/// The way to specify the expected `Deref::Target` type for an `InterfaceType`.
+///
+/// The target must be a sized type,
+/// `AsRef<str>`/`AsRef<[u8]>`/`Borrow<str>` can be used for string and byte slices.
+pub trait DerefTarget<'a>: InterfaceType {
+    /// The type that the erased type dereferences to.
+    type Target;
+}
+
+/// Gets the expected `Deref::Target` type for an `InterfaceType`,
+/// defaulting to `()` if it doesn't require `Deref` to be implemented.
+///
+/// Used by `DynTrait`'s vtable to give its `deref` function a defaulted return type.
+pub trait DerefTargetOrDefault<'borr>: InterfaceType {
+    /// The type that the erased type dereferences to.
+    type Target;
+}
+
+impl<'borr, I, Target> DerefTargetOrDefault<'borr> for I
+where
+    I: InterfaceType,
+    I: DerefTargetOrDefaultHelper<'borr, <I as InterfaceType>::Deref, Target = Target>,
+{
+    type Target = Target;
+}
+
+#[doc(hidden)]
+pub trait DerefTargetOrDefaultHelper<'borr, ImplIsRequired> {
+    type Target;
+}
+
+impl<'borr, I, Target> DerefTargetOrDefaultHelper<'borr, Implemented<trait_marker::Deref>> for I
+where
+    I: DerefTarget<'borr, Target = Target>,
+{
+    type Target = Target;
+}
+
+impl<'borr, I> DerefTargetOrDefaultHelper<'borr, Unimplemented<trait_marker::Deref>> for I {
+    type Target = ();
+}
+
+///////////////////////////////////////
+
+/// The way to specify the expected signature of the
+/// `FnOnce`/`FnMut`/`Fn` traits for an `InterfaceType`.
+///
+/// The arguments are passed as a tuple,
+/// `Args = (u32, RString)` corresponds to `Fn(u32, RString) -> Self::Output`.
+pub trait FnSignature<'a>: InterfaceType {
+    /// The tuple of arguments that the closure is called with.
+    type Args: FnArgs;
+    /// The return type of the closure.
+    type Output;
+}
+
+/// Gets the expected closure signature for an `InterfaceType`,
+/// defaulting to `() -> ()` if it doesn't require `FnOnce` to be implemented.
+///
+/// Used by `DynTrait`'s vtable to give its closure functions defaulted types.
+pub trait FnSignatureOrDefault<'borr>: InterfaceType {
+    /// The tuple of arguments that the closure is called with.
+    type Args: FnArgs;
+    /// The return type of the closure.
+    type Output;
+}
+
+impl<'borr, I, Args, Output> FnSignatureOrDefault<'borr> for I
+where
+    I: InterfaceType,
+    I: FnSignatureOrDefaultHelper<
+        'borr,
+        <I as InterfaceType>::FnOnce,
+        Args = Args,
+        Output = Output,
+    >,
+    Args: FnArgs,
+{
+    type Args = Args;
+    type Output = Output;
+}
+
+#[doc(hidden)]
+pub trait FnSignatureOrDefaultHelper<'borr, ImplIsRequired> {
+    type Args;
+    type Output;
+}
+
+impl<'borr, I, Args, Output> FnSignatureOrDefaultHelper<'borr, Implemented<trait_marker::FnOnce>>
+    for I
+where
+    I: FnSignature<'borr, Args = Args, Output = Output>,
+{
+    type Args = Args;
+    type Output = Output;
+}
+
+impl<'borr, I> FnSignatureOrDefaultHelper<'borr, Unimplemented<trait_marker::FnOnce>> for I {
+    type Args = ();
+    type Output = ();
+}
+
+///////////////////////////////////////
+
+/// Implemented for [`InterfaceType`]s whose required traits can be queried at runtime
+/// from a `DynTrait<'borr, P, I>`,
+/// with [`DynTrait::query_interface`](crate::DynTrait::query_interface).
+///
+/// `Self` can require traits that `I` doesn't,
+/// so long as they are stored in the vtable and don't have associated types.
+///
+/// These traits must be required by `I` for `Self` to require them:
+///
+/// - The auto traits: `Send`, `Sync`, and `Unpin`.
+///
+/// - The traits that aren't stored in the vtable:
+/// `Eq`, `Error`, and `Deserialize`.
+///
+/// - The traits with associated types:
+/// `Iterator`, `Future`, `Deref`, `FnOnce`, and `Serialize`.
+/// `DoubleEndedIterator`, `FnMut`, and `Fn` use the associated types of
+/// `Iterator` and `FnOnce`, so they can be queried when `I` requires those.
+///
+pub trait InterfaceQueryableFrom<'borr, I: InterfaceType>: InterfaceType {}
+
+impl<'borr, I, This> InterfaceQueryableFrom<'borr, I> for This
+where
+    I: InterfaceType,
+    This: InterfaceType + SameAssocTypesAs<'borr, I>,
+    This::Send: IsSubsetOf<I::Send>,
+    This::Sync: IsSubsetOf<I::Sync>,
+    This::Unpin: IsSubsetOf<I::Unpin>,
+    This::Eq: IsSubsetOf<I::Eq>,
+    This::Error: IsSubsetOf<I::Error>,
+    This::Deserialize: IsSubsetOf<I::Deserialize>,
+    This::Iterator: IsSubsetOf<I::Iterator>,
+    This::Future: IsSubsetOf<I::Future>,
+    This::Deref: IsSubsetOf<I::Deref>,
+    This::FnOnce: IsSubsetOf<I::FnOnce>,
+    This::Serialize: IsSubsetOf<I::Serialize>,
+{
+}
+
+/// Requires that the associated types of `Self` and `I` be the same
+/// for the traits that `Self` requires.
+#[doc(hidden)]
+pub trait SameAssocTypesAs<'borr, I> {}
+
+impl<'borr, I, This> SameAssocTypesAs<'borr, I> for This
+where
+    This: IteratorItemOrDefault<'borr>
+        + FutureOutputOrDefault<'borr>
+        + DerefTargetOrDefault<'borr>
+        + FnSignatureOrDefault<'borr>
+        + GetSerializeProxyType<'borr>,
+    I: IteratorItemOrDefault<'borr>
+        + FutureOutputOrDefault<'borr>
+        + DerefTargetOrDefault<'borr>
+        + FnSignatureOrDefault<'borr>
+        + GetSerializeProxyType<'borr>,
+    This::Iterator: SameIfImplemented<
+        <This as IteratorItemOrDefault<'borr>>::Item,
+        <I as IteratorItemOrDefault<'borr>>::Item,
+    >,
+    This::Future: SameIfImplemented<
+        <This as FutureOutputOrDefault<'borr>>::Output,
+        <I as FutureOutputOrDefault<'borr>>::Output,
+    >,
+    This::Deref: SameIfImplemented<
+        <This as DerefTargetOrDefault<'borr>>::Target,
+        <I as DerefTargetOrDefault<'borr>>::Target,
+    >,
+    This::FnOnce: SameIfImplemented<
+        (
+            <This as FnSignatureOrDefault<'borr>>::Args,
+            <This as FnSignatureOrDefault<'borr>>::Output,
+        ),
+        (
+            <I as FnSignatureOrDefault<'borr>>::Args,
+            <I as FnSignatureOrDefault<'borr>>::Output,
+        ),
+    >,
+    This::Serialize: SameIfImplemented<
+        <This as GetSerializeProxyType<'borr>>::ProxyType,
+        <I as GetSerializeProxyType<'borr>>::ProxyType,
+    >,
+{
+}
+
+/// Requires `A` and `B` to be the same type if `Self` is `Implemented<_>`.
+#[doc(hidden)]
+pub trait SameIfImplemented<A, B> {}
+
+impl<T, A> SameIfImplemented<A, A> for Implemented<T> {}
+
+impl<T, A, B> SameIfImplemented<A, B> for Unimplemented<T> {}
+
 /////////////////////////////////////////////////////////////////////
 
 crate::impl_InterfaceType! {
@@ -2,15 +2,21 @@
 #![allow(missing_docs)]
 
 use std::{
+    borrow::Borrow,
     fmt::{self, Debug, Write as FmtWrite},
+    future::Future,
     io,
     marker::PhantomData,
+    ops::Deref,
 };
 
 use super::{
     c_functions::*,
     iterator::{DoubleEndedIteratorFns, IteratorFns, MakeDoubleEndedIteratorFns, MakeIteratorFns},
-    traits::{GetSerializeProxyType, IteratorItemOrDefault, SerializeType},
+    traits::{
+        DerefTarget, DerefTargetOrDefault, FnSignature, FnSignatureOrDefault, FutureOutput,
+        FutureOutputOrDefault, GetSerializeProxyType, IteratorItemOrDefault, SerializeType,
+    },
     type_info::TypeInfoFor,
     *,
 };
@@ -19,8 +25,11 @@ use crate::{
     marker_type::{ErasedObject, NonOwningPhantom},
     pointer_trait::GetPointerKind,
     prefix_type::{panic_on_missing_fieldname, WithMetadata},
-    sabi_types::{RMut, RRef, StaticRef},
-    std_types::{RIoError, RNone, RSeekFrom, RSome},
+    sabi_types::{MovePtr, RMut, RRef, StaticRef},
+    std_types::{
+        closures::{FnArgs, TupledFn, TupledFnMut, TupledFnOnce},
+        RContext, RIoError, RNone, RPoll, RSeekFrom, RSome,
+    },
     type_level::{
         downcasting::GetUTID,
         impl_enum::{Implemented, Unimplemented},
@@ -105,6 +114,12 @@ macro_rules! declare_meta_vtable {
             bound(<I as IteratorItemOrDefault<'borr>>::Item: StableAbi),
             bound(I: GetSerializeProxyType<'borr>),
             bound(<I as GetSerializeProxyType<'borr>>::ProxyType: StableAbi),
+            bound(I: FutureOutputOrDefault<'borr>),
+            bound(<I as FutureOutputOrDefault<'borr>>::Output: StableAbi),
+            bound(I: DerefTargetOrDefault<'borr>),
+            bound(<I as DerefTargetOrDefault<'borr>>::Target: StableAbi),
+            bound(I: FnSignatureOrDefault<'borr>),
+            bound(<I as FnSignatureOrDefault<'borr>>::Output: StableAbi),
             $($(bound=$struct_bound,)*)*
         )]
         pub struct VTable<'borr,$erased_ptr,$interf>{
@@ -115,6 +130,10 @@ macro_rules! declare_meta_vtable {
                 $( #[$field_attr] )*
                 $priv_field:$option_ty<$field_ty>,
             )*
+            /// The layout of the erased type,
+            /// only stored if the `DynTrait` was constructed with `TD_StableDowncast`.
+            #[sabi(missing_field(default))]
+            pub stable_layout: Option<&'static TypeLayout>,
         }
 
 
@@ -135,6 +154,25 @@ macro_rules! declare_meta_vtable {
                     }
                 }
             )*
+
+            /// Whether this vtable has the fields for all the traits that `I2` requires,
+            /// checking which fields are accessible at runtime.
+            ///
+            /// This only checks the traits that are stored as fields in the vtable.
+            pub fn has_fields_for<I2>(&self) -> bool
+            where
+                I2: MakeRequiredTraits,
+            {
+                let required = <I2 as MakeRequiredTraits>::MAKE;
+                let accessible = self.0.field_accessibility();
+                $(
+                    (
+                        !required.$trait_query() ||
+                        accessible.at(Self::$field_index as usize).is_accessible()
+                    )
+                )&&*
+            }
+
             pub fn iter(
                 &self
             )->IteratorFns< <I as IteratorItemOrDefault<'borr>>::Item >
@@ -182,6 +220,108 @@ macro_rules! declare_meta_vtable {
         }
 
 
+        impl<'borr,$erased_ptr,$interf> VTable_Ref<'borr,$erased_ptr,$interf>{
+            pub fn poll(&self)->UnerasedPollFn<'borr,I>
+            where
+                $interf:InterfaceType<Future=Implemented<trait_marker::Future>>,
+                $interf:FutureOutputOrDefault<'borr>,
+            {
+                unsafe{
+                    std::mem::transmute::<
+                        unsafe extern "C" fn(RMut<'_, ErasedObject>,RContext<'_>)->RPoll<()>,
+                        UnerasedPollFn<'borr,I>,
+                    >( self.erased_poll() )
+                }
+            }
+
+            pub fn deref(&self)->UnerasedDerefFn<'borr,I>
+            where
+                $interf:InterfaceType<Deref=Implemented<trait_marker::Deref>>,
+                $interf:DerefTargetOrDefault<'borr>,
+            {
+                unsafe{
+                    std::mem::transmute::<
+                        unsafe extern "C" fn(RRef<'_, ErasedObject>)->RRef<'_, ()>,
+                        UnerasedDerefFn<'borr,I>,
+                    >( self.erased_deref() )
+                }
+            }
+
+            pub fn call_once(&self)->UnerasedCallOnceFn<'borr,I>
+            where
+                $interf:InterfaceType<FnOnce=Implemented<trait_marker::FnOnce>>,
+                $interf:FnSignatureOrDefault<'borr>,
+            {
+                unsafe{
+                    std::mem::transmute::<
+                        unsafe extern "C" fn(MovePtr<'_, ErasedObject>,())->(),
+                        UnerasedCallOnceFn<'borr,I>,
+                    >( self.erased_call_once() )
+                }
+            }
+
+            pub fn call_mut(&self)->UnerasedCallMutFn<'borr,I>
+            where
+                $interf:InterfaceType<FnMut=Implemented<trait_marker::FnMut>>,
+                $interf:FnSignatureOrDefault<'borr>,
+            {
+                unsafe{
+                    std::mem::transmute::<
+                        unsafe extern "C" fn(RMut<'_, ErasedObject>,())->(),
+                        UnerasedCallMutFn<'borr,I>,
+                    >( self.erased_call_mut() )
+                }
+            }
+
+            pub fn call(&self)->UnerasedCallFn<'borr,I>
+            where
+                $interf:InterfaceType<Fn=Implemented<trait_marker::Fn>>,
+                $interf:FnSignatureOrDefault<'borr>,
+            {
+                unsafe{
+                    std::mem::transmute::<
+                        unsafe extern "C" fn(RRef<'_, ErasedObject>,())->(),
+                        UnerasedCallFn<'borr,I>,
+                    >( self.erased_call() )
+                }
+            }
+        }
+
+
+        pub type UnerasedPollFn<'borr,I>=
+            unsafe extern "C" fn(
+                RMut<'_, ErasedObject>,
+                RContext<'_>,
+            )->RPoll<<I as FutureOutputOrDefault<'borr>>::Output>;
+
+        pub type UnerasedDerefFn<'borr,I>=
+            unsafe extern "C" fn(
+                RRef<'_, ErasedObject>,
+            )->RRef<'_, <I as DerefTargetOrDefault<'borr>>::Target>;
+
+        pub type UnerasedCallOnceFn<'borr,I>=
+            unsafe extern "C" fn(
+                MovePtr<'_, ErasedObject>,
+                FnCArgs<'borr,I>,
+            )-><I as FnSignatureOrDefault<'borr>>::Output;
+
+        pub type UnerasedCallMutFn<'borr,I>=
+            unsafe extern "C" fn(
+                RMut<'_, ErasedObject>,
+                FnCArgs<'borr,I>,
+            )-><I as FnSignatureOrDefault<'borr>>::Output;
+
+        pub type UnerasedCallFn<'borr,I>=
+            unsafe extern "C" fn(
+                RRef<'_, ErasedObject>,
+                FnCArgs<'borr,I>,
+            )-><I as FnSignatureOrDefault<'borr>>::Output;
+
+        /// The ffi-safe tuple of arguments that a `DynTrait` closure is called with.
+        pub type FnCArgs<'borr,I>=
+            <<I as FnSignatureOrDefault<'borr>>::Args as FnArgs>::CArgs;
+
+
         pub type UnerasedSerializeFn<'s,I>=
             unsafe extern "C" fn(
                 RRef<'s, ErasedObject>
@@ -326,6 +466,7 @@ macro_rules! declare_meta_vtable {
                             >
                         >::FIELD,
                 )*
+                stable_layout: <$can_downcast as GetUTID<$value>>::STABLE_LAYOUT,
                 _marker:NonOwningPhantom::NEW,
             });
 
@@ -679,6 +820,234 @@ declare_meta_vtable! {
             io_Seek_seek::<T>
         }
     ]
+    [
+        #[sabi(
+            unsafe_change_type=
+            unsafe extern "C" fn(
+                RMut<'_, ErasedObject>,
+                RContext<'_>,
+            )->RPoll<<I as FutureOutputOrDefault<'borr>>::Output>
+        )]
+        #[sabi(accessible_if= <I as MakeRequiredTraits>::MAKE.contains_future())]
+        erased_poll:unsafe extern "C" fn(RMut<'_, ErasedObject>,RContext<'_>)->RPoll<()>;
+        priv _poll;
+        option=Option,Some,None;
+        field_index=field_index_for__poll;
+        query_fn = contains_future;
+
+        impl[] VtableFieldValue<Future(std::future::Future)>
+        where [
+            T:Future + Unpin,
+            I:FutureOutput<'borr,Output=<T as Future>::Output>,
+        ]{
+            unsafe{
+                Transmuter::<
+                    unsafe extern "C" fn(
+                        RMut<'_, ErasedObject>,
+                        RContext<'_>,
+                    )->RPoll<<T as Future>::Output>,
+                    unsafe extern "C" fn(RMut<'_, ErasedObject>,RContext<'_>)->RPoll<()>
+                >{
+                    from:poll_future::<T>
+                }.to
+            }
+        }
+    ]
+    [
+        #[sabi(accessible_if= <I as MakeRequiredTraits>::MAKE.contains_as_ref_str())]
+        as_ref_str:unsafe extern "C" fn(RRef<'_, ErasedObject>)->RStr<'_>;
+        priv _as_ref_str;
+        option=Option,Some,None;
+        field_index=field_index_for__as_ref_str;
+        query_fn = contains_as_ref_str;
+
+        impl[] VtableFieldValue<AsRefStr(std::convert::AsRef<str>)>
+        where [ T:AsRef<str> ]
+        {
+            as_ref_str::<T>
+        }
+    ]
+    [
+        #[sabi(accessible_if= <I as MakeRequiredTraits>::MAKE.contains_as_ref_bytes())]
+        as_ref_bytes:unsafe extern "C" fn(RRef<'_, ErasedObject>)->RSlice<'_, u8>;
+        priv _as_ref_bytes;
+        option=Option,Some,None;
+        field_index=field_index_for__as_ref_bytes;
+        query_fn = contains_as_ref_bytes;
+
+        impl[] VtableFieldValue<AsRefBytes(std::convert::AsRef<[u8]>)>
+        where [ T:AsRef<[u8]> ]
+        {
+            as_ref_bytes::<T>
+        }
+    ]
+    [
+        #[sabi(accessible_if= <I as MakeRequiredTraits>::MAKE.contains_borrow_str())]
+        borrow_str:unsafe extern "C" fn(RRef<'_, ErasedObject>)->RStr<'_>;
+        priv _borrow_str;
+        option=Option,Some,None;
+        field_index=field_index_for__borrow_str;
+        query_fn = contains_borrow_str;
+
+        impl[] VtableFieldValue<BorrowStr(std::borrow::Borrow<str>)>
+        where [ T:Borrow<str> ]
+        {
+            borrow_str::<T>
+        }
+    ]
+    [
+        #[sabi(
+            unsafe_change_type=
+            for<'s>
+            unsafe extern "C" fn(
+                RRef<'s, ErasedObject>
+            )->RRef<'s, <I as DerefTargetOrDefault<'borr>>::Target>
+        )]
+        #[sabi(accessible_if= <I as MakeRequiredTraits>::MAKE.contains_deref())]
+        erased_deref:unsafe extern "C" fn(RRef<'_, ErasedObject>)->RRef<'_, ()>;
+        priv _deref;
+        option=Option,Some,None;
+        field_index=field_index_for__deref;
+        query_fn = contains_deref;
+
+        impl[] VtableFieldValue<Deref(std::ops::Deref)>
+        where [
+            T:Deref,
+            <T as Deref>::Target:Sized,
+            I:DerefTarget<'borr,Target=<T as Deref>::Target>,
+        ]{
+            unsafe{
+                Transmuter::<
+                    unsafe extern "C" fn(
+                        RRef<'_, ErasedObject>
+                    )->RRef<'_, <T as Deref>::Target>,
+                    unsafe extern "C" fn(RRef<'_, ErasedObject>)->RRef<'_, ()>
+                >{
+                    from:deref_impl::<T>
+                }.to
+            }
+        }
+    ]
+    [
+        #[sabi(
+            unsafe_change_type=
+            unsafe extern "C" fn(
+                MovePtr<'_, ErasedObject>,
+                FnCArgs<'borr,I>,
+            )-><I as FnSignatureOrDefault<'borr>>::Output
+        )]
+        #[sabi(accessible_if= <I as MakeRequiredTraits>::MAKE.contains_fn_once())]
+        erased_call_once:unsafe extern "C" fn(MovePtr<'_, ErasedObject>,())->();
+        priv _call_once;
+        option=Option,Some,None;
+        field_index=field_index_for__call_once;
+        query_fn = contains_fn_once;
+
+        impl[] VtableFieldValue<FnOnce(std::ops::FnOnce)>
+        where [
+            I:FnSignature<'borr>,
+            T:TupledFnOnce<
+                <I as FnSignature<'borr>>::Args,
+                <I as FnSignature<'borr>>::Output,
+            >,
+        ]{
+            unsafe{
+                Transmuter::<
+                    unsafe extern "C" fn(
+                        MovePtr<'_, ErasedObject>,
+                        <<I as FnSignature<'borr>>::Args as FnArgs>::CArgs,
+                    )-><I as FnSignature<'borr>>::Output,
+                    unsafe extern "C" fn(MovePtr<'_, ErasedObject>,())->()
+                >{
+                    from:call_once_impl::<
+                        T,
+                        <I as FnSignature<'borr>>::Args,
+                        <I as FnSignature<'borr>>::Output,
+                    >
+                }.to
+            }
+        }
+    ]
+    [
+        #[sabi(
+            unsafe_change_type=
+            unsafe extern "C" fn(
+                RMut<'_, ErasedObject>,
+                FnCArgs<'borr,I>,
+            )-><I as FnSignatureOrDefault<'borr>>::Output
+        )]
+        #[sabi(accessible_if= <I as MakeRequiredTraits>::MAKE.contains_fn_mut())]
+        erased_call_mut:unsafe extern "C" fn(RMut<'_, ErasedObject>,())->();
+        priv _call_mut;
+        option=Option,Some,None;
+        field_index=field_index_for__call_mut;
+        query_fn = contains_fn_mut;
+
+        impl[] VtableFieldValue<FnMut(std::ops::FnMut)>
+        where [
+            I:FnSignature<'borr>,
+            T:TupledFnMut<
+                <I as FnSignature<'borr>>::Args,
+                <I as FnSignature<'borr>>::Output,
+            >,
+        ]{
+            unsafe{
+                Transmuter::<
+                    unsafe extern "C" fn(
+                        RMut<'_, ErasedObject>,
+                        <<I as FnSignature<'borr>>::Args as FnArgs>::CArgs,
+                    )-><I as FnSignature<'borr>>::Output,
+                    unsafe extern "C" fn(RMut<'_, ErasedObject>,())->()
+                >{
+                    from:call_mut_impl::<
+                        T,
+                        <I as FnSignature<'borr>>::Args,
+                        <I as FnSignature<'borr>>::Output,
+                    >
+                }.to
+            }
+        }
+    ]
+    [
+        #[sabi(
+            unsafe_change_type=
+            unsafe extern "C" fn(
+                RRef<'_, ErasedObject>,
+                FnCArgs<'borr,I>,
+            )-><I as FnSignatureOrDefault<'borr>>::Output
+        )]
+        #[sabi(accessible_if= <I as MakeRequiredTraits>::MAKE.contains_fn())]
+        erased_call:unsafe extern "C" fn(RRef<'_, ErasedObject>,())->();
+        priv _call;
+        option=Option,Some,None;
+        field_index=field_index_for__call;
+        query_fn = contains_fn;
+
+        impl[] VtableFieldValue<Fn(std::ops::Fn)>
+        where [
+            I:FnSignature<'borr>,
+            T:TupledFn<
+                <I as FnSignature<'borr>>::Args,
+                <I as FnSignature<'borr>>::Output,
+            >,
+        ]{
+            unsafe{
+                Transmuter::<
+                    unsafe extern "C" fn(
+                        RRef<'_, ErasedObject>,
+                        <<I as FnSignature<'borr>>::Args as FnArgs>::CArgs,
+                    )-><I as FnSignature<'borr>>::Output,
+                    unsafe extern "C" fn(RRef<'_, ErasedObject>,())->()
+                >{
+                    from:call_impl::<
+                        T,
+                        <I as FnSignature<'borr>>::Args,
+                        <I as FnSignature<'borr>>::Output,
+                    >
+                }.to
+            }
+        }
+    ]
 }
 
 //////////////
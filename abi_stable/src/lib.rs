@@ -83,6 +83,14 @@ features = [  ]
 ```
 enabling the features you need in the `features` array.
 
+This is a non-default cargo feature that enables an optional crate:
+
+- "futures":
+    Depends on `futures-core`,
+    implementing `futures_core::Stream` for `abi_stable::std_types::RStream_`,
+    and allowing `futures_core::Stream`s to be wrapped in it
+    with `abi_stable::std_types::future::FuturesStream`.
+
 ### Manually enabled
 
 These are crate features to manually enable support for newer language features:
@@ -219,6 +227,7 @@ extern crate self as abi_stable;
 
 include! {"./proc_macro_reexports/get_static_equivalent.rs"}
 include! {"./proc_macro_reexports/export_root_module.rs"}
+include! {"./proc_macro_reexports/export_root_modules.rs"}
 include! {"./proc_macro_reexports/sabi_extern_fn.rs"}
 include! {"./proc_macro_reexports/sabi_trait_attribute.rs"}
 include! {"./proc_macro_reexports/stable_abi_derive.rs"}
@@ -229,7 +238,7 @@ pub use abi_stable::sabi_types::{RMut, RRef};
 use abi_stable_derive::impl_InterfaceType;
 
 #[doc(hidden)]
-pub use abi_stable_derive::get_root_module_static;
+pub use abi_stable_derive::{get_root_module_index_static, get_root_module_static};
 
 #[macro_use]
 mod impls;
@@ -20,9 +20,37 @@
 //!
 //! All steps can return errors.
 //!
+//! # Multiple root modules
+//!
+//! A library can export multiple independent root modules with the
+//! [`export_root_modules`] macro,
+//! each one having its own [`LibHeader`], so that they're versioned separately.
+//! These are listed in the [`RootModuleIndex`] of the library,
+//! and are loaded with the [`RootModule`]`::load_named*` associated functions.
+//!
+//! # Dependencies between root modules
+//!
+//! Root modules can declare the root modules that they depend on with
+//! [`RootModule::DEPENDENCIES`],
+//! a [`ModuleGraph`] loads all of them in an order where
+//! dependencies are loaded before the root modules that depend on them.
+//!
+//! # Unloading libraries
+//!
+//! Libraries loaded with the [`RootModule`]`::load_from*` associated functions
+//! are never unloaded.
+//! To load a library that can be unloaded (or replaced with a newer version),
+//! you can use [`LoadedLibrary`] instead,
+//! which requires manually tracking the values that come from the library.
+//!
 //! [`RawLibrary`]: ./struct.RawLibrary.html
+//! [`RootModule::DEPENDENCIES`]: ./trait.RootModule.html#associatedconstant.DEPENDENCIES
+//! [`ModuleGraph`]: ./module_graph/struct.ModuleGraph.html
+//! [`export_root_modules`]: ../macro.export_root_modules.html
+//! [`RootModuleIndex`]: ./struct.RootModuleIndex.html
 //! [`AbiHeaderRef`]: ./struct.AbiHeaderRef.html
 //! [`RootModule`]: ./trait.RootModule.html
+//! [`LoadedLibrary`]: ./struct.LoadedLibrary.html
 //! [`RootModule::initialize`]: ./trait.RootModule.html#method.initialization
 //! [`&'static LibHeader`]: ./struct.LibHeader.html
 
@@ -50,26 +78,46 @@ use crate::{
 
 pub mod c_abi_testing;
 pub mod development_utils;
+pub mod discover;
 mod errors;
 mod lib_header;
+mod lib_metadata;
+mod loaded_library;
+pub mod module_graph;
 
 #[cfg(test)]
 mod library_tests;
 
 mod raw_library;
 mod root_mod_trait;
+mod root_module_index;
+mod target_info;
 
 #[doc(no_inline)]
 pub use self::c_abi_testing::{CAbiTestingFns, C_ABI_TESTING_FNS};
 
+#[doc(hidden)]
+pub use self::lib_metadata::{__count_enabled_features, __enabled_features};
+
+#[doc(hidden)]
+pub use self::root_module_index::{__assert_unique_root_module_keys, __root_module_key};
+
 pub use self::{
     errors::{IntoRootModuleResult, LibraryError, RootModuleError},
     lib_header::{AbiHeader, AbiHeaderRef, LibHeader},
+    lib_metadata::{LibMetadata, LibMetadata_Prefix, LibMetadata_Ref},
+    loaded_library::{LibraryInUse, LibraryKeepAlive, LoadedLibrary, ReloadError, Tracked},
+    module_graph::{RootModuleDependencies, RootModuleDependency},
     raw_library::RawLibrary,
     root_mod_trait::{
         abi_header_from_path, abi_header_from_raw_library, lib_header_from_path,
         lib_header_from_raw_library, RootModule, RootModuleConsts,
     },
+    root_module_index::{
+        root_module_index_from_path, root_module_index_from_raw_library, NamedRootModule,
+        RootModuleIndex,
+    },
+    target_info::{Endianness, PanicStrategy, TargetInfo, TARGET_TRIPLE_CAPACITY},
 };
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -288,6 +336,22 @@ pub const ROOT_MODULE_LOADER_NAME_WITH_NUL: &str = PRIV_MANGLED_ROOT_MODULE_LOAD
 pub const ROOT_MODULE_LOADER_NAME_NULSTR: NulStr<'_> =
     NulStr::from_str(PRIV_MANGLED_ROOT_MODULE_LOADER_NAME_NUL);
 
+/// The name of the `static` that contains the [`RootModuleIndex`] of an abi_stable library,
+/// declared with the [`export_root_modules`] macro.
+///
+/// There's also [`ROOT_MODULE_INDEX_NAME_WITH_NUL`],
+/// which is this constant concatenated with `"\0"`.
+///
+/// [`RootModuleIndex`]: ./struct.RootModuleIndex.html
+/// [`export_root_modules`]: ../macro.export_root_modules.html
+/// [`ROOT_MODULE_INDEX_NAME_WITH_NUL`]: ./constant.ROOT_MODULE_INDEX_NAME_WITH_NUL.html
+pub const ROOT_MODULE_INDEX_NAME: &str = PRIV_MANGLED_ROOT_MODULE_INDEX_NAME;
+
+/// A nul-terminated equivalent of [`ROOT_MODULE_INDEX_NAME`].
+///
+/// [`ROOT_MODULE_INDEX_NAME`]: ./constant.ROOT_MODULE_INDEX_NAME.html
+pub const ROOT_MODULE_INDEX_NAME_WITH_NUL: &str = PRIV_MANGLED_ROOT_MODULE_INDEX_NAME_NUL;
+
 //////////////////////////////////////////////////////////////////////
 
 #[doc(hidden)]
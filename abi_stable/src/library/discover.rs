@@ -0,0 +1,524 @@
+//! Discovering and loading all the libraries in a directory that export a [`RootModule`].
+//!
+//! The entry point of this module is the [`discover`] function.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use abi_stable::{
+//!     for_examples::Module_Ref,
+//!     library::{discover::discover, LibrarySuffix},
+//! };
+//!
+//! let report = discover::<Module_Ref>("./plugins/".as_ref(), LibrarySuffix::NoSuffix)
+//!     .expect("could not read the plugin directory");
+//!
+//! for lib in report.libraries() {
+//!     if let Some(module) = lib.status().module() {
+//!         println!("loaded {}: {}", lib.path().display(), module.second());
+//!     } else {
+//!         println!("skipped {}: {:?}", lib.path().display(), lib.status().kind());
+//!     }
+//! }
+//!
+//! ```
+//!
+//! [`RootModule`]: ../trait.RootModule.html
+//! [`discover`]: ./fn.discover.html
+
+use super::{
+    raw_library::pointer_width_suffix, root_mod_trait::load_cached_instance,
+    root_module_index::named_root_module_from_index, *,
+};
+
+use std::{
+    env::consts::{DLL_PREFIX, DLL_SUFFIX},
+    fmt::{self, Display},
+    fs, io,
+};
+
+/// Scans `directory` for dynamic libraries,
+/// and attempts to load the `M` root module from every one of them.
+///
+/// Only files that have the platform's dynamic library prefix and extension
+/// (eg: `lib*.so` on Linux, `*.dll` on Windows) are probed,
+/// and if `suffix` is [`LibrarySuffix::Suffix`],
+/// only the ones whose name ends with the pointer width of the loader
+/// (eg: `libfoo-64.so`) are probed.
+///
+/// The returned [`DiscoveryReport`] lists every probed library sorted by path,
+/// with the root module for the compatible ones.
+///
+/// Root modules are loaded in the same way as
+/// [`RootModule::load_instance_from`],
+/// leaking the libraries that are abi_stable libraries.
+/// Calling this multiple times (or calling `load_instance_from` on a discovered library)
+/// returns the same root modules, without initializing them again.
+/// Files that don't export a compatible `abi_stable` header are unloaded
+/// without calling any function that they export.
+///
+/// Libraries that don't export a root module with the [`export_root_module`] attribute
+/// are probed for the root module with the [`RootModule::NAME`] key,
+/// exported with the [`export_root_modules`] macro,
+/// loading it in the same way as [`RootModule::load_named_instance_from`].
+///
+/// Every probed file is opened as a dynamic library,
+/// which runs its static initializers (if it has any),
+/// so this must only be called on directories with trusted libraries.
+///
+/// # Errors
+///
+/// This returns an `io::Error` if the contents of the directory could not be read.
+///
+/// [`LibrarySuffix::Suffix`]: ../enum.LibrarySuffix.html#variant.Suffix
+/// [`DiscoveryReport`]: ./struct.DiscoveryReport.html
+/// [`RootModule::load_instance_from`]: ../trait.RootModule.html#method.load_instance_from
+/// [`RootModule::load_named_instance_from`]:
+/// ../trait.RootModule.html#method.load_named_instance_from
+/// [`RootModule::NAME`]: ../trait.RootModule.html#associatedconstant.NAME
+/// [`export_root_module`]: ../../attr.export_root_module.html
+/// [`export_root_modules`]: ../../macro.export_root_modules.html
+pub fn discover<M>(directory: &Path, suffix: LibrarySuffix) -> io::Result<DiscoveryReport<M>>
+where
+    M: RootModule,
+{
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_file() && is_library_file(&path, suffix) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let libraries = paths
+        .into_iter()
+        .map(|path| {
+            let status = probe_library::<M>(&path);
+            DiscoveredLibrary { path, status }
+        })
+        .collect();
+
+    Ok(DiscoveryReport { libraries })
+}
+
+/// Whether the name of the file at `path` is that of a dynamic library
+/// using the `suffix` naming convention.
+fn is_library_file(path: &Path, suffix: LibrarySuffix) -> bool {
+    let file_name = match path.file_name().and_then(|x| x.to_str()) {
+        Some(x) => x,
+        None => return false,
+    };
+
+    let name = match file_name
+        .strip_prefix(DLL_PREFIX)
+        .and_then(|x| x.strip_suffix(DLL_SUFFIX))
+    {
+        Some(x) if !x.is_empty() => x,
+        _ => return false,
+    };
+
+    match suffix {
+        LibrarySuffix::NoSuffix => true,
+        LibrarySuffix::Suffix => name
+            .strip_suffix(pointer_width_suffix())
+            .map_or(false, |x| x.ends_with('-')),
+    }
+}
+
+fn probe_library<M>(path: &Path) -> LibraryStatus<M>
+where
+    M: RootModule,
+{
+    let raw_library = match RawLibrary::load_at(path) {
+        Ok(x) => x,
+        Err(e) => return LibraryStatus::from_error(e),
+    };
+
+    let (library_addr, header) = match unsafe { find_root_module::<M>(&raw_library) } {
+        Ok(x) => x,
+        Err(e) => return LibraryStatus::from_error(e),
+    };
+
+    // The library is only leaked if it's compatible,
+    // since functions from it are called to load the root module.
+    match unsafe { load_cached_instance::<M>(raw_library, library_addr, header) } {
+        Ok((module, _)) => LibraryStatus::Compatible(module),
+        Err(e) => LibraryStatus::from_error(e),
+    }
+}
+
+/// Finds the header of the `M` root module in `raw_library`,
+/// along with the address of the symbol that identifies the library.
+///
+/// This looks for the root module exported with `export_root_module` first,
+/// falling back to the one with the `M::NAME` key exported with `export_root_modules`.
+///
+/// # Safety
+///
+/// The returned header is implicitly tied to the lifetime of the library.
+unsafe fn find_root_module<M>(
+    raw_library: &RawLibrary,
+) -> Result<(usize, AbiHeaderRef), LibraryError>
+where
+    M: RootModule,
+{
+    let err = match unsafe { abi_header_from_raw_library(raw_library) } {
+        Ok(header) => return Ok((header.address(), header)),
+        Err(e @ LibraryError::GetSymbolError { .. }) => e,
+        Err(e) => return Err(e),
+    };
+
+    let index = match unsafe { root_module_index_from_raw_library(raw_library) } {
+        Ok(index) => index,
+        // Reporting the missing `export_root_module` symbol,
+        // since that's the one that `RootModule::load_from` requires.
+        Err(LibraryError::GetSymbolError { .. }) => return Err(err),
+        Err(e) => return Err(e),
+    };
+
+    let header = named_root_module_from_index(index, raw_library, M::NAME)?.abi_header();
+
+    Ok((index.address(), header))
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// The result of calling [`discover`] on a directory.
+///
+/// [`discover`]: ./fn.discover.html
+#[derive(Debug)]
+pub struct DiscoveryReport<M> {
+    libraries: Vec<DiscoveredLibrary<M>>,
+}
+
+impl<M> DiscoveryReport<M> {
+    /// All the libraries that were probed, sorted by path.
+    pub fn libraries(&self) -> &[DiscoveredLibrary<M>] {
+        &self.libraries
+    }
+
+    /// Converts this into a list of all the probed libraries, sorted by path.
+    pub fn into_libraries(self) -> Vec<DiscoveredLibrary<M>> {
+        self.libraries
+    }
+
+    /// Iterates over the root modules that were loaded,
+    /// along with the path to the library they were loaded from.
+    pub fn modules(&self) -> impl Iterator<Item = (&Path, &M)> + '_ {
+        self.libraries
+            .iter()
+            .filter_map(|lib| Some((&*lib.path, lib.status.module()?)))
+    }
+
+    /// Iterates over the libraries that could not be loaded,
+    /// along with the reason why.
+    pub fn errors(&self) -> impl Iterator<Item = (&Path, &LibraryError)> + '_ {
+        self.libraries
+            .iter()
+            .filter_map(|lib| Some((&*lib.path, lib.status.error()?)))
+    }
+}
+
+impl<M> Display for DiscoveryReport<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for lib in &self.libraries {
+            writeln!(f, "{}: {}", lib.path.display(), lib.status.kind())?;
+        }
+        Ok(())
+    }
+}
+
+/// A library found by [`discover`], and whether it could be loaded.
+///
+/// [`discover`]: ./fn.discover.html
+#[derive(Debug)]
+pub struct DiscoveredLibrary<M> {
+    path: PathBuf,
+    status: LibraryStatus<M>,
+}
+
+impl<M> DiscoveredLibrary<M> {
+    /// The path to the library.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the library could be loaded.
+    pub const fn status(&self) -> &LibraryStatus<M> {
+        &self.status
+    }
+
+    /// Unwraps this into the path to the library and its status.
+    pub fn into_parts(self) -> (PathBuf, LibraryStatus<M>) {
+        (self.path, self.status)
+    }
+}
+
+/// Whether a library found by [`discover`] could be loaded,
+/// containing the root module if it could.
+///
+/// [`discover`]: ./fn.discover.html
+#[derive(Debug)]
+pub enum LibraryStatus<M> {
+    /// The library is compatible, and its root module was loaded.
+    Compatible(M),
+    /// The library could not be opened.
+    ///
+    /// The error is a `LibraryError::OpenError`.
+    OpenError(LibraryError),
+    /// The library doesn't export an abi_stable root module.
+    ///
+    /// The error is a `LibraryError::GetSymbolError`
+    /// or a `LibraryError::RootModuleNotExported`.
+    NotAbiStableLibrary(LibraryError),
+    /// The library uses an incompatible version of abi_stable.
+    ///
+    /// The error is a `LibraryError::InvalidAbiHeader`.
+    IncompatibleAbiStableVersion(LibraryError),
+    /// The library uses a different C abi from the loader's.
+    ///
+    /// The error is a `LibraryError::InvalidCAbi`.
+    IncompatibleCAbi(LibraryError),
+    /// The library was compiled for a different target.
+    ///
+    /// The error is one of
+    /// `LibraryError::IncompatibleEndianness`,
+    /// `LibraryError::IncompatiblePointerWidth`,
+    /// `LibraryError::IncompatiblePanicStrategy`,
+    /// or `LibraryError::IncompatibleTarget`.
+    IncompatibleTarget(LibraryError),
+    /// The version number of the library is incompatible with the loader's,
+    /// or it could not be parsed.
+    ///
+    /// The error is a `LibraryError::IncompatibleVersionNumber`
+    /// or a `LibraryError::ParseVersionError`.
+    IncompatibleVersion(LibraryError),
+    /// The layout of the root module is incompatible with the loader's.
+    ///
+    /// The error is a `LibraryError::AbiInstability` or a `LibraryError::Many`.
+    IncompatibleLayout(LibraryError),
+    /// The dependencies of the root module could not be resolved.
+    ///
+    /// The error is one of
+    /// `LibraryError::MissingDependency`,
+    /// `LibraryError::IncompatibleDependency`,
+    /// or `LibraryError::DependencyCycle`.
+    UnresolvedDependency(LibraryError),
+    /// The function that exports the root module returned an error or panicked.
+    ///
+    /// The error is a `LibraryError::RootModule`.
+    RootModuleError(LibraryError),
+}
+
+impl<M> LibraryStatus<M> {
+    fn from_error(e: LibraryError) -> Self {
+        match e {
+            LibraryError::OpenError { .. } => LibraryStatus::OpenError(e),
+            LibraryError::GetSymbolError { .. } | LibraryError::RootModuleNotExported { .. } => {
+                LibraryStatus::NotAbiStableLibrary(e)
+            }
+            LibraryError::InvalidAbiHeader { .. } => LibraryStatus::IncompatibleAbiStableVersion(e),
+            LibraryError::InvalidCAbi { .. } => LibraryStatus::IncompatibleCAbi(e),
+            LibraryError::IncompatibleEndianness { .. }
+            | LibraryError::IncompatiblePointerWidth { .. }
+            | LibraryError::IncompatiblePanicStrategy { .. }
+            | LibraryError::IncompatibleTarget { .. } => LibraryStatus::IncompatibleTarget(e),
+            LibraryError::ParseVersionError { .. }
+            | LibraryError::IncompatibleVersionNumber { .. } => {
+                LibraryStatus::IncompatibleVersion(e)
+            }
+            LibraryError::RootModule { .. } => LibraryStatus::RootModuleError(e),
+            LibraryError::MissingDependency { .. }
+            | LibraryError::IncompatibleDependency { .. }
+            | LibraryError::DependencyCycle { .. } => LibraryStatus::UnresolvedDependency(e),
+            LibraryError::AbiInstability { .. } | LibraryError::Many { .. } => {
+                LibraryStatus::IncompatibleLayout(e)
+            }
+        }
+    }
+
+    /// Gets the kind of status this is, without the data.
+    pub const fn kind(&self) -> LibraryStatusKind {
+        match self {
+            LibraryStatus::Compatible { .. } => LibraryStatusKind::Compatible,
+            LibraryStatus::OpenError { .. } => LibraryStatusKind::OpenError,
+            LibraryStatus::NotAbiStableLibrary { .. } => LibraryStatusKind::NotAbiStableLibrary,
+            LibraryStatus::IncompatibleAbiStableVersion { .. } => {
+                LibraryStatusKind::IncompatibleAbiStableVersion
+            }
+            LibraryStatus::IncompatibleCAbi { .. } => LibraryStatusKind::IncompatibleCAbi,
+            LibraryStatus::IncompatibleTarget { .. } => LibraryStatusKind::IncompatibleTarget,
+            LibraryStatus::IncompatibleVersion { .. } => LibraryStatusKind::IncompatibleVersion,
+            LibraryStatus::IncompatibleLayout { .. } => LibraryStatusKind::IncompatibleLayout,
+            LibraryStatus::UnresolvedDependency { .. } => LibraryStatusKind::UnresolvedDependency,
+            LibraryStatus::RootModuleError { .. } => LibraryStatusKind::RootModuleError,
+        }
+    }
+
+    /// Gets the root module if the library was compatible.
+    pub const fn module(&self) -> Option<&M> {
+        match self {
+            LibraryStatus::Compatible(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Gets the error if the library could not be loaded.
+    pub const fn error(&self) -> Option<&LibraryError> {
+        match self {
+            LibraryStatus::Compatible(_) => None,
+            LibraryStatus::OpenError(e)
+            | LibraryStatus::NotAbiStableLibrary(e)
+            | LibraryStatus::IncompatibleAbiStableVersion(e)
+            | LibraryStatus::IncompatibleCAbi(e)
+            | LibraryStatus::IncompatibleTarget(e)
+            | LibraryStatus::IncompatibleVersion(e)
+            | LibraryStatus::IncompatibleLayout(e)
+            | LibraryStatus::UnresolvedDependency(e)
+            | LibraryStatus::RootModuleError(e) => Some(e),
+        }
+    }
+
+    /// Converts this into a `Result`.
+    pub fn into_result(self) -> Result<M, LibraryError> {
+        match self {
+            LibraryStatus::Compatible(x) => Ok(x),
+            LibraryStatus::OpenError(e)
+            | LibraryStatus::NotAbiStableLibrary(e)
+            | LibraryStatus::IncompatibleAbiStableVersion(e)
+            | LibraryStatus::IncompatibleCAbi(e)
+            | LibraryStatus::IncompatibleTarget(e)
+            | LibraryStatus::IncompatibleVersion(e)
+            | LibraryStatus::IncompatibleLayout(e)
+            | LibraryStatus::UnresolvedDependency(e)
+            | LibraryStatus::RootModuleError(e) => Err(e),
+        }
+    }
+}
+
+/// The kind of a [`LibraryStatus`], without the data.
+///
+/// [`LibraryStatus`]: ./enum.LibraryStatus.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub enum LibraryStatusKind {
+    /// Corresponds to `LibraryStatus::Compatible`
+    Compatible,
+    /// Corresponds to `LibraryStatus::OpenError`
+    OpenError,
+    /// Corresponds to `LibraryStatus::NotAbiStableLibrary`
+    NotAbiStableLibrary,
+    /// Corresponds to `LibraryStatus::IncompatibleAbiStableVersion`
+    IncompatibleAbiStableVersion,
+    /// Corresponds to `LibraryStatus::IncompatibleCAbi`
+    IncompatibleCAbi,
+    /// Corresponds to `LibraryStatus::IncompatibleTarget`
+    IncompatibleTarget,
+    /// Corresponds to `LibraryStatus::IncompatibleVersion`
+    IncompatibleVersion,
+    /// Corresponds to `LibraryStatus::IncompatibleLayout`
+    IncompatibleLayout,
+    /// Corresponds to `LibraryStatus::UnresolvedDependency`
+    UnresolvedDependency,
+    /// Corresponds to `LibraryStatus::RootModuleError`
+    RootModuleError,
+}
+
+impl Display for LibraryStatusKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LibraryStatusKind::Compatible => "compatible",
+            LibraryStatusKind::OpenError => "could not be opened",
+            LibraryStatusKind::NotAbiStableLibrary => "not an abi_stable library",
+            LibraryStatusKind::IncompatibleAbiStableVersion => "incompatible abi_stable version",
+            LibraryStatusKind::IncompatibleCAbi => "incompatible C abi",
+            LibraryStatusKind::IncompatibleTarget => "incompatible target",
+            LibraryStatusKind::IncompatibleVersion => "incompatible version",
+            LibraryStatusKind::IncompatibleLayout => "incompatible layout",
+            LibraryStatusKind::UnresolvedDependency => "unresolved dependency",
+            LibraryStatusKind::RootModuleError => "root module error",
+        })
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::for_examples::Module_Ref;
+
+    fn lib_name(name: &str) -> String {
+        format!("{}{}{}", DLL_PREFIX, name, DLL_SUFFIX)
+    }
+
+    #[test]
+    fn is_library_file_test() {
+        let bits = pointer_width_suffix();
+        let other_bits = if bits == "64" { "32" } else { "64" };
+
+        let is_lib = |name: &str, suffix| is_library_file(name.as_ref(), suffix);
+
+        assert!(is_lib(&lib_name("foo"), LibrarySuffix::NoSuffix));
+        assert!(!is_lib(&lib_name("foo"), LibrarySuffix::Suffix));
+        assert!(is_lib(
+            &lib_name(&format!("foo-{}", bits)),
+            LibrarySuffix::Suffix
+        ));
+        assert!(is_lib(
+            &lib_name(&format!("foo-{}", bits)),
+            LibrarySuffix::NoSuffix
+        ));
+        assert!(!is_lib(
+            &lib_name(&format!("foo-{}", other_bits)),
+            LibrarySuffix::Suffix
+        ));
+        assert!(!is_lib(&lib_name(""), LibrarySuffix::NoSuffix));
+        assert!(!is_lib("foo.txt", LibrarySuffix::NoSuffix));
+    }
+
+    #[test]
+    fn discover_non_libraries() {
+        let dir =
+            std::env::temp_dir().join(format!("abi_stable_discover_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(dir.join(lib_name("not_a_library")), b"hello").unwrap();
+        fs::write(dir.join("readme.txt"), b"world").unwrap();
+
+        let report = discover::<Module_Ref>(&dir, LibrarySuffix::NoSuffix);
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        let libraries = report.unwrap().into_libraries();
+        assert_eq!(libraries.len(), 1);
+        assert_eq!(
+            libraries[0].path().file_name().unwrap(),
+            &*lib_name("not_a_library")
+        );
+        assert_eq!(libraries[0].status().kind(), LibraryStatusKind::OpenError);
+        assert!(matches!(
+            libraries[0].status().error(),
+            Some(LibraryError::OpenError { .. })
+        ));
+    }
+
+    #[test]
+    fn dependency_errors_status() {
+        let errors = vec![
+            LibraryError::MissingDependency {
+                module: "feature".into(),
+                dependency: "storage".into(),
+            },
+            LibraryError::DependencyCycle {
+                cycle: vec!["first".into(), "second".into(), "first".into()],
+            },
+        ];
+        for error in errors {
+            let status = LibraryStatus::<Module_Ref>::from_error(error);
+            assert_eq!(status.kind(), LibraryStatusKind::UnresolvedDependency);
+            assert_eq!(status.kind().to_string(), "unresolved dependency");
+        }
+    }
+}
@@ -1,6 +1,10 @@
 #![allow(clippy::missing_const_for_fn)]
 
-use super::{lib_header::AbiHeader, root_mod_trait::RootModule};
+use super::{
+    lib_header::AbiHeader,
+    root_mod_trait::RootModule,
+    target_info::{Endianness, PanicStrategy},
+};
 
 use crate::{
     sabi_types::{ParseVersionError, VersionNumber, VersionStrings},
@@ -56,8 +60,11 @@ pub enum LibraryError {
         version: VersionStrings,
     },
     /// The abi is incompatible.
-    /// The error is opaque,since the error always comes from the main binary
-    /// (dynamic libraries can be loaded from other dynamic libraries).
+    ///
+    /// The error is an [`AbiInstabilityReport`] when it's created by this crate,
+    /// which can be accessed with `RBoxError::downcast_ref`.
+    ///
+    /// [`AbiInstabilityReport`]: ../abi_stability/struct.AbiInstabilityReport.html
     AbiInstability(RBoxError),
     /// The type used to check that this is a compatible abi_stable
     /// is not the same.
@@ -69,8 +76,73 @@ pub enum LibraryError {
         ///
         found: RBoxError,
     },
+    /// When the library was compiled for a target with a different byte order.
+    IncompatibleEndianness {
+        ///
+        expected: Endianness,
+        ///
+        found: Endianness,
+    },
+    /// When the library was compiled for a target with a different pointer size,
+    /// in bits.
+    IncompatiblePointerWidth {
+        ///
+        expected: u32,
+        ///
+        found: u32,
+    },
+    /// When the library was compiled with a different panic strategy
+    /// (`panic = "unwind"` or `panic = "abort"`).
+    IncompatiblePanicStrategy {
+        ///
+        expected: PanicStrategy,
+        ///
+        found: PanicStrategy,
+    },
+    /// When the library was compiled for a different target triple.
+    IncompatibleTarget {
+        ///
+        expected: String,
+        ///
+        found: String,
+    },
+    /// When a root module depends on a root module that isn't in the same
+    /// [`ModuleGraph`](./module_graph/struct.ModuleGraph.html).
+    MissingDependency {
+        /// The name of the root module.
+        module: String,
+        /// The name of the dependency.
+        dependency: String,
+    },
+    /// When the version of a dependency is incompatible with the one
+    /// that the root module requires.
+    IncompatibleDependency {
+        /// The name of the root module.
+        module: String,
+        /// The name of the dependency.
+        dependency: String,
+        /// The version of the dependency that the root module requires.
+        required: VersionNumber,
+        /// The version of the dependency.
+        found: VersionNumber,
+    },
+    /// When root modules (transitively) depend on themselves.
+    DependencyCycle {
+        /// The names of the root modules in the cycle,
+        /// starting and ending with the same root module.
+        cycle: Vec<String>,
+    },
     /// There could have been 0 or more errors in the function.
     Many(RVec<Self>),
+    /// When a library doesn't export a named root module with the requested key.
+    RootModuleNotExported {
+        /// The path to the library
+        library: PathBuf,
+        /// The key of the root module.
+        key: String,
+        /// The keys of the root modules that the library does export.
+        exported: Vec<String>,
+    },
 }
 
 impl From<ParseVersionError> for LibraryError {
@@ -144,12 +216,70 @@ impl Display for LibraryError {
                     expected=expected,
                 }
             }
+            LibraryError::IncompatibleEndianness { expected, found } => writeln!(
+                f,
+                "The library was compiled for a {} target,\n\
+                 when this library expected a {} target.",
+                found, expected,
+            ),
+            LibraryError::IncompatiblePointerWidth { expected, found } => writeln!(
+                f,
+                "The library was compiled for a target with {}-bit pointers,\n\
+                 when this library expected {}-bit pointers.",
+                found, expected,
+            ),
+            LibraryError::IncompatiblePanicStrategy { expected, found } => writeln!(
+                f,
+                "The library was compiled with the '{}' panic strategy,\n\
+                 when this library expected the '{}' panic strategy.",
+                found, expected,
+            ),
+            LibraryError::IncompatibleTarget { expected, found } => writeln!(
+                f,
+                "The library was compiled for the '{}' target,\n\
+                 when this library expected the '{}' target.",
+                found, expected,
+            ),
+            LibraryError::MissingDependency { module, dependency } => writeln!(
+                f,
+                "The '{}' root module depends on the '{}' root module,\n\
+                 which is not being loaded.",
+                module, dependency,
+            ),
+            LibraryError::IncompatibleDependency {
+                module,
+                dependency,
+                required,
+                found,
+            } => writeln!(
+                f,
+                "The '{}' root module requires version {} of the '{}' root module,\n\
+                 found the incompatible version {}.",
+                module, required, dependency, found,
+            ),
+            LibraryError::DependencyCycle { cycle } => writeln!(
+                f,
+                "These root modules depend on each other:\n\t{}",
+                cycle.join(" -> "),
+            ),
             LibraryError::Many(list) => {
                 for e in list {
                     Display::fmt(e, f)?;
                 }
                 Ok(())
             }
+            LibraryError::RootModuleNotExported {
+                library,
+                key,
+                exported,
+            } => writeln!(
+                f,
+                "The library at:\n\t{}\ndoes not export a root module named '{}',\n\
+                 the root modules it exports are:\n\t{:?}",
+                library.display(),
+                key,
+                exported,
+            ),
         }?;
         f.write_str("\n")?;
         Ok(())
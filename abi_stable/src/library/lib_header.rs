@@ -1,8 +1,12 @@
 use super::*;
 
 use crate::{
+    abi_stability::abi_checking::{
+        check_layout_compatibility_with_globals, AbiInstabilityReport, CheckingGlobals,
+    },
     prefix_type::{PrefixRef, PrefixRefTrait},
     sabi_types::RRef,
+    std_types::RBoxError,
 };
 
 /// Used to check the layout of modules returned by module-loading functions
@@ -10,6 +14,9 @@ use crate::{
 ///
 /// Module-loading functions are declared with the [`export_root_module`] attribute.
 ///
+/// This also contains the [metadata](#method.metadata) of the library,
+/// which can be read without loading the root module.
+///
 /// [`export_root_module`]: ../attr.export_root_module.html
 #[repr(C)]
 #[derive(StableAbi)]
@@ -19,6 +26,7 @@ pub struct LibHeader {
     init_globals_with: InitGlobalsWith,
     module: LateStaticRef<PrefixRef<ErasedPrefix>>,
     constructor: extern "C" fn() -> RootModuleResult,
+    metadata: LibMetadata_Ref,
 }
 
 impl LibHeader {
@@ -44,9 +52,41 @@ impl LibHeader {
             init_globals_with: INIT_GLOBALS_WITH,
             module: LateStaticRef::new(),
             constructor,
+            metadata: LibMetadata_Ref::DEFAULT,
         }
     }
 
+    /// Replaces the metadata of the library.
+    ///
+    /// The [`export_root_module`] attribute sets the metadata with this method.
+    ///
+    /// [`export_root_module`]: ../attr.export_root_module.html
+    pub const fn with_metadata(self, metadata: LibMetadata_Ref) -> Self {
+        Self { metadata, ..self }
+    }
+
+    /// Constructs a LibHeader from the root module loader,
+    /// using the return type of `loader` to get the type of the root module.
+    ///
+    /// This is used by the [`export_root_modules`] macro.
+    ///
+    /// # Safety
+    ///
+    /// `constructor` must return the root module returned by `loader`.
+    ///
+    /// [`export_root_modules`]: ../macro.export_root_modules.html
+    #[doc(hidden)]
+    pub const unsafe fn __from_loader_fn<T>(
+        _loader: fn() -> T,
+        constructor: extern "C" fn() -> RootModuleResult,
+        check_layout: CheckTypeLayout,
+    ) -> Self
+    where
+        T: IntoRootModuleResult,
+    {
+        unsafe { Self::from_constructor::<T::Module>(constructor, check_layout) }
+    }
+
     /// Constructs a LibHeader from the module.
     pub fn from_module<M>(value: M) -> Self
     where
@@ -61,14 +101,31 @@ impl LibHeader {
                 LateStaticRef::from_prefixref(erased)
             },
             constructor: GetAbortingConstructor::aborting_constructor,
+            metadata: LibMetadata_Ref::DEFAULT,
         }
     }
 
+    /// The header used to check that the library uses a compatible version of abi_stable.
+    pub(super) const fn abi_header(&self) -> &AbiHeader {
+        &self.header
+    }
+
+    /// The target that the library was compiled for.
+    pub const fn target_info(&self) -> &TargetInfo {
+        &self.header.target_info
+    }
+
     /// All the important constants of a `RootModule` for some erased type.
     pub const fn root_mod_consts(&self) -> &RootModuleConsts {
         &self.root_mod_consts
     }
 
+    /// The metadata of the library,
+    /// which includes its description, authors, license, and build information.
+    pub const fn metadata(&self) -> LibMetadata_Ref {
+        self.metadata
+    }
+
     /// The version string of the library the module is being loaded from.
     pub const fn version_strings(&self) -> VersionStrings {
         self.root_mod_consts.version_strings()
@@ -91,7 +148,34 @@ impl LibHeader {
     where
         M: RootModule,
     {
-        let expected_version = M::VERSION_STRINGS.piped(VersionNumber::new)?;
+        self.check_version_with(M::NAME, M::VERSION_STRINGS)
+    }
+
+    /// Checks that the version number of the library is compatible with
+    /// the `expected` version number,
+    /// using the same rules as the `RootModule::load_*` associated functions.
+    ///
+    /// This is useful for tools that compare two libraries without loading their root modules.
+    ///
+    /// # Errors
+    ///
+    /// This returns these errors:
+    ///
+    /// - `LibraryError::ParseVersionError`:
+    /// If either version string can't be parsed as a version number.
+    ///
+    /// - `LibraryError::IncompatibleVersionNumber`:
+    /// If the version number of the library is incompatible with `expected`.
+    pub fn ensure_version_compatible(&self, expected: VersionStrings) -> Result<(), LibraryError> {
+        self.check_version_with(self.root_mod_consts.name().as_str(), expected)
+    }
+
+    fn check_version_with(
+        &self,
+        library_name: &'static str,
+        expected: VersionStrings,
+    ) -> Result<(), LibraryError> {
+        let expected_version = expected.piped(VersionNumber::new)?;
 
         let actual_version = self.version_strings().piped(VersionNumber::new)?;
 
@@ -99,7 +183,7 @@ impl LibHeader {
             || (expected_version.major == 0) && expected_version.minor > actual_version.minor
         {
             return Err(LibraryError::IncompatibleVersionNumber {
-                library_name: M::NAME,
+                library_name,
                 expected_version,
                 actual_version,
             });
@@ -222,13 +306,12 @@ impl LibHeader {
             (globals::initialized_globals().layout_checking)(<M>::LAYOUT, root_mod_layout)
                 .into_result()
                 .map_err(|e| {
-                    // Fixes the bug where printing the error causes a segfault because it
+                    // Converting the error into an owned report fixes the bug where
+                    // printing the error causes a segfault because it
                     // contains static references and function pointers into the unloaded library.
-                    //
-                    // This isn't strictly required anymore because abi_stable doesn't
-                    // unload libraries right now.
-                    let formatted = e.to_formatted_error();
-                    LibraryError::AbiInstability(formatted)
+                    let report =
+                        AbiInstabilityReport::from_check_error(<M>::LAYOUT, root_mod_layout, &e);
+                    LibraryError::AbiInstability(RBoxError::new(report))
                 })?;
         }
 
@@ -237,6 +320,24 @@ impl LibHeader {
         Ok(())
     }
 
+    /// Like `ensure_layout`,
+    /// except that it doesn't record the checked types in the global layout-checking maps,
+    /// so that no reference into the library outlives it if it's unloaded.
+    pub(super) fn ensure_layout_unshared<M>(&self) -> Result<(), LibraryError>
+    where
+        M: RootModule,
+    {
+        if let IsLayoutChecked::Yes(root_mod_layout) = self.root_mod_consts.layout() {
+            let globals = CheckingGlobals::new();
+            check_layout_compatibility_with_globals(<M>::LAYOUT, root_mod_layout, &globals)
+                .map_err(|e| LibraryError::AbiInstability(RBoxError::new(e.to_report())))?;
+        }
+
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+
+        Ok(())
+    }
+
     /// Gets the root module,first
     /// checking that the layout of the `M` from the dynamic library is
     /// compatible with the expected layout.
@@ -346,20 +447,34 @@ impl std::ops::Deref for AbiHeaderRef {
 }
 
 /// Represents the abi_stable version used by a compiled dynamic library,
-/// which if incompatible would produce a [`LibraryError::InvalidAbiHeader`]
+/// which if incompatible would produce a [`LibraryError::InvalidAbiHeader`],
+/// and the target that the library was compiled for.
 ///
 /// [`LibraryError::InvalidAbiHeader`]: ./enum.LibraryError.html#variant.InvalidAbiHeader
 #[repr(C)]
 #[derive(Debug, StableAbi, Copy, Clone)]
-// This type will never have new fields clippy, that's the point <_<
+// New fields require bumping the revision at the end of `magic_string`,
+// since they can't be read from libraries that use the previous layout.
 #[allow(clippy::manual_non_exhaustive)]
 pub struct AbiHeader {
     /// A magic string used to check that this is actually abi_stable.
+    ///
+    /// This ends with a revision that's changed whenever the layout of this type or
+    /// of [`LibHeader`] (including the [`RootModuleConsts`] it contains)
+    /// changes within an abi version of abi_stable,
+    /// so that libraries using the older layout produce a
+    /// [`LibraryError::InvalidAbiHeader`] error.
+    ///
+    /// [`LibHeader`]: ./struct.LibHeader.html
+    /// [`RootModuleConsts`]: ./struct.RootModuleConsts.html
+    /// [`LibraryError::InvalidAbiHeader`]: ./enum.LibraryError.html#variant.InvalidAbiHeader
     pub magic_string: [u8; 32],
     /// The major abi version of abi_stable
     pub abi_major: u32,
     /// The minor abi version of abi_stable
     pub abi_minor: u32,
+    /// The target that the library was compiled for.
+    pub target_info: TargetInfo,
     _priv: (),
 }
 
@@ -388,9 +503,53 @@ impl AbiHeader {
     pub fn is_valid(&self) -> bool {
         self.is_compatible(&AbiHeader::VALUE)
     }
+
+    /// Checks that the abi_stable version of this AbiHeader is compatible with
+    /// the one from where this function is called,
+    /// and that the library was compiled for the same target.
+    ///
+    /// # Errors
+    ///
+    /// This returns these errors:
+    ///
+    /// - `LibraryError::InvalidAbiHeader`:
+    /// If the abi_stable used by the library is not compatible.
+    ///
+    /// - `LibraryError::IncompatibleEndianness`,
+    /// `LibraryError::IncompatiblePointerWidth`,
+    /// `LibraryError::IncompatiblePanicStrategy`,
+    /// `LibraryError::IncompatibleTarget`:
+    /// If the library was compiled for a different target,
+    /// as described in [`TargetInfo::ensure_compatible`].
+    ///
+    /// [`TargetInfo::ensure_compatible`]: ./struct.TargetInfo.html#method.ensure_compatible
+    pub fn ensure_compatible(&self) -> Result<(), LibraryError> {
+        if self.is_valid() {
+            return self.target_info.ensure_compatible();
+        }
+
+        // Libraries compiled for a target with the opposite byte order
+        // store the abi version byte-swapped,
+        // which is reported as an endianness mismatch instead of an abi_stable version mismatch.
+        let swapped = AbiHeader {
+            abi_major: self.abi_major.swap_bytes(),
+            abi_minor: self.abi_minor.swap_bytes(),
+            ..*self
+        };
+        if swapped.is_valid() {
+            swapped.target_info.ensure_compatible()?;
+        }
+
+        Err(LibraryError::InvalidAbiHeader(*self))
+    }
 }
 
 impl AbiHeaderRef {
+    /// The address of the header, used to identify the root module it belongs to.
+    pub(super) fn address(self) -> usize {
+        self.0.get() as *const AbiHeader as usize
+    }
+
     /// Gets the LibHeader of a library.
     ///
     /// # Errors
@@ -400,12 +559,19 @@ impl AbiHeaderRef {
     /// - `LibraryError::InvalidAbiHeader`:
     /// If the abi_stable used by the library is not compatible.
     ///
+    /// - `LibraryError::IncompatibleEndianness`,
+    /// `LibraryError::IncompatiblePointerWidth`,
+    /// `LibraryError::IncompatiblePanicStrategy`,
+    /// `LibraryError::IncompatibleTarget`:
+    /// If the library was compiled for a different target.
+    ///
     /// - `LibraryError::InvalidCAbi`:
     /// If the C abi used by the library is not compatible.
     pub fn upgrade(self) -> Result<&'static LibHeader, LibraryError> {
-        if !self.is_valid() {
-            return Err(LibraryError::InvalidAbiHeader(*self));
-        }
+        // This is checked before calling any function through the header,
+        // since calling functions compiled for another target could crash.
+        // Note that loading the library already ran its static initializers.
+        self.ensure_compatible()?;
 
         let lib_header: &'static LibHeader = unsafe { self.0.transmute_into_ref() };
 
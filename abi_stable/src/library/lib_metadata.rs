@@ -0,0 +1,175 @@
+use super::*;
+
+use crate::{
+    prefix_type::WithMetadata,
+    std_types::{ROption, RSlice, Tuple2},
+};
+
+use std::fmt::{self, Debug};
+
+/// The version of the compiler that compiled abi_stable,
+/// which is the compiler that compiles the libraries that use it.
+const RUSTC_VERSION: &str = env!("ABI_STABLE_RUSTC_VERSION");
+
+/// The target triple that abi_stable was compiled for.
+const TARGET: &str = env!("ABI_STABLE_TARGET");
+
+/// Metadata about a dynamic library, stored in its [`LibHeader`]
+/// so that it can be read without loading the root module.
+///
+/// This is an extensible prefix type,
+/// fields that are missing in libraries compiled with older versions of abi_stable
+/// are returned as their default value.
+///
+/// The metadata is set with arguments to the [`export_root_module`] attribute,
+/// defaulting to the information in the `Cargo.toml` of the library.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use abi_stable::library::{lib_header_from_path, LibraryError};
+///
+/// use std::path::Path;
+///
+/// fn main() -> Result<(), LibraryError> {
+///     let header = lib_header_from_path(Path::new("plugins/libplugin.so"))?;
+///     let metadata = header.metadata();
+///
+///     println!("{}", header.root_mod_consts().name());
+///     println!("{}", metadata.description());
+///     println!("compiled with {} for {}", metadata.rustc_version(), metadata.target());
+///
+///     for feature in metadata.features() {
+///         println!("with the {} feature", feature);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [`LibHeader`]: ./struct.LibHeader.html
+/// [`export_root_module`]: ../attr.export_root_module.html
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix(prefix_ref = LibMetadata_Ref, prefix_fields = LibMetadata_Prefix)))]
+#[sabi(missing_field(default))]
+pub struct LibMetadata {
+    /// A description of the library.
+    pub description: RStr<'static>,
+    /// The authors of the library, separated by `:`.
+    pub authors: RStr<'static>,
+    /// The license of the library.
+    pub license: RStr<'static>,
+    /// When the library was compiled, in seconds since the UNIX epoch.
+    pub build_timestamp: ROption<u64>,
+    /// The version of the compiler that compiled the library (eg: `rustc 1.61.0`).
+    pub rustc_version: RStr<'static>,
+    /// The target triple that the library was compiled for.
+    pub target: RStr<'static>,
+    /// The cargo features that were enabled when the library was compiled.
+    pub features: RSlice<'static, RStr<'static>>,
+    /// Arbitrary key-value pairs.
+    #[sabi(last_prefix_field)]
+    pub extra: RSlice<'static, Tuple2<RStr<'static>, RStr<'static>>>,
+}
+
+impl LibMetadata {
+    /// Constructs a `LibMetadata` with the version of the compiler and the target triple,
+    /// with every other field empty.
+    pub const fn new() -> Self {
+        Self {
+            description: RStr::from_str(""),
+            authors: RStr::from_str(""),
+            license: RStr::from_str(""),
+            build_timestamp: ROption::RNone,
+            rustc_version: RStr::from_str(RUSTC_VERSION),
+            target: RStr::from_str(TARGET),
+            features: RSlice::EMPTY,
+            extra: RSlice::EMPTY,
+        }
+    }
+}
+
+impl Default for LibMetadata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LibMetadata_Ref {
+    /// The `LibMetadata` of libraries that don't specify any,
+    /// as returned by [`LibMetadata::new`].
+    ///
+    /// [`LibMetadata::new`]: ./struct.LibMetadata.html#method.new
+    pub const DEFAULT: Self = {
+        const S: &WithMetadata<LibMetadata> = &WithMetadata::new(LibMetadata::new());
+
+        LibMetadata_Ref(S.static_as_prefix())
+    };
+
+    /// Gets the value of the `key` key in the arbitrary key-value pairs.
+    pub fn get_extra(self, key: &str) -> Option<RStr<'static>> {
+        self.extra()
+            .iter()
+            .find(|Tuple2(k, _)| k.as_str() == key)
+            .map(|Tuple2(_, v)| *v)
+    }
+}
+
+impl Debug for LibMetadata_Ref {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LibMetadata")
+            .field("description", &self.description())
+            .field("authors", &self.authors())
+            .field("license", &self.license())
+            .field("build_timestamp", &self.build_timestamp())
+            .field("rustc_version", &self.rustc_version())
+            .field("target", &self.target())
+            .field("features", &self.features())
+            .field("extra", &self.extra())
+            .finish()
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// Counts how many of the features in `features` are enabled,
+/// used by the [`export_root_module`] attribute.
+///
+/// [`export_root_module`]: ../attr.export_root_module.html
+#[doc(hidden)]
+pub const fn __count_enabled_features(features: &[(&'static str, bool)]) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < features.len() {
+        if features[i].1 {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+/// Gets the names of the features in `features` that are enabled,
+/// used by the [`export_root_module`] attribute.
+///
+/// `N` must be the value returned by `__count_enabled_features(features)`.
+///
+/// [`export_root_module`]: ../attr.export_root_module.html
+#[doc(hidden)]
+pub const fn __enabled_features<const N: usize>(
+    features: &[(&'static str, bool)],
+) -> [RStr<'static>; N] {
+    let mut out = [RStr::from_str(""); N];
+    let mut i = 0;
+    let mut j = 0;
+    while i < features.len() {
+        let (name, enabled) = features[i];
+        if enabled {
+            out[j] = RStr::from_str(name);
+            j += 1;
+        }
+        i += 1;
+    }
+    out
+}
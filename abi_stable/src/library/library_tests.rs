@@ -1,7 +1,8 @@
 use crate::library::{
-    ROOT_MODULE_LOADER_NAME, ROOT_MODULE_LOADER_NAME_NULSTR, ROOT_MODULE_LOADER_NAME_WITH_NUL,
+    ROOT_MODULE_INDEX_NAME, ROOT_MODULE_INDEX_NAME_WITH_NUL, ROOT_MODULE_LOADER_NAME,
+    ROOT_MODULE_LOADER_NAME_NULSTR, ROOT_MODULE_LOADER_NAME_WITH_NUL,
 };
-use abi_stable_shared::mangled_root_module_loader_name;
+use abi_stable_shared::{mangled_root_module_index_name, mangled_root_module_loader_name};
 
 #[test]
 fn root_module_loader_name_test() {
@@ -13,3 +14,598 @@ fn root_module_loader_name_test() {
     assert_eq!(ROOT_MODULE_LOADER_NAME_NULSTR.to_str(), name);
     assert_eq!(ROOT_MODULE_LOADER_NAME_NULSTR.to_str_with_nul(), with_nul);
 }
+
+#[test]
+fn root_module_index_name_test() {
+    let name = mangled_root_module_index_name();
+
+    assert_eq!(ROOT_MODULE_INDEX_NAME, name);
+    assert_eq!(ROOT_MODULE_INDEX_NAME_WITH_NUL, format!("{}\0", name));
+    assert_ne!(ROOT_MODULE_INDEX_NAME, ROOT_MODULE_LOADER_NAME);
+}
+
+mod named_root_modules {
+    use crate::{
+        library::{LibraryError, RootModule, RootModuleIndex},
+        prefix_type::PrefixTypeTrait,
+        sabi_types::VersionStrings,
+        std_types::RStr,
+        StableAbi,
+    };
+
+    crate::export_root_modules! {
+        get_first,
+        "second_key" => get_second,
+        #[unsafe_no_layout_constant]
+        "unchecked_first" => get_first,
+    }
+
+    fn get_first() -> FirstMod_Ref {
+        FirstMod { number: 100 }.leak_into_prefix()
+    }
+
+    fn get_second() -> SecondMod_Ref {
+        SecondMod {
+            text: RStr::from_str("hello"),
+        }
+        .leak_into_prefix()
+    }
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_ref = FirstMod_Ref)))]
+    pub struct FirstMod {
+        #[sabi(last_prefix_field)]
+        pub number: u32,
+    }
+
+    impl RootModule for FirstMod_Ref {
+        crate::declare_root_module_statics! {FirstMod_Ref}
+        const BASE_NAME: &'static str = "services";
+        const NAME: &'static str = "first";
+        const VERSION_STRINGS: VersionStrings = crate::package_version_strings!();
+    }
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_ref = SecondMod_Ref)))]
+    pub struct SecondMod {
+        #[sabi(last_prefix_field)]
+        pub text: RStr<'static>,
+    }
+
+    impl RootModule for SecondMod_Ref {
+        crate::declare_root_module_statics! {SecondMod_Ref}
+        const BASE_NAME: &'static str = "services";
+        const NAME: &'static str = "second";
+        const VERSION_STRINGS: VersionStrings = crate::package_version_strings!();
+    }
+
+    fn index() -> &'static RootModuleIndex {
+        &crate::get_root_module_index_static!(crate::library::library_tests::named_root_modules)
+    }
+
+    #[test]
+    fn enumerate_modules() {
+        let index = index();
+        assert!(index.modules()[0].abi_header().is_valid());
+
+        assert_eq!(
+            index.keys().collect::<Vec<_>>(),
+            ["first", "second_key", "unchecked_first"]
+        );
+
+        let names = index
+            .modules()
+            .iter()
+            .map(|m| m.lib_header().unwrap().root_mod_consts().name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["first", "second", "first"]);
+
+        assert!(index.get("second").is_none());
+        assert!(index.get("missing").is_none());
+    }
+
+    #[test]
+    fn load_modules_independently() {
+        let index = index();
+
+        let first = index.get("first").unwrap().lib_header().unwrap();
+        assert!(first.layout().is_some());
+        let module = first.init_root_module::<FirstMod_Ref>().unwrap();
+        assert_eq!(module.number(), 100);
+
+        let second = index.get("second_key").unwrap().lib_header().unwrap();
+        let module = second.init_root_module::<SecondMod_Ref>().unwrap();
+        assert_eq!(module.text().as_str(), "hello");
+
+        // each root module is layout checked against its own type
+        match second.init_root_module::<FirstMod_Ref>() {
+            Err(LibraryError::AbiInstability(_)) => {}
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("expected a layout error"),
+        }
+
+        let unchecked = index.get("unchecked_first").unwrap().lib_header().unwrap();
+        assert!(unchecked.layout().is_none());
+        let module = unchecked.init_root_module::<FirstMod_Ref>().unwrap();
+        assert_eq!(module.number(), 100);
+    }
+}
+
+mod lib_metadata {
+    use crate::{
+        library::{LibHeader, LibMetadata_Ref, RootModule},
+        prefix_type::PrefixTypeTrait,
+        sabi_types::VersionStrings,
+        std_types::{RNone, RSome, RStr},
+        StableAbi,
+    };
+
+    #[crate::export_root_module(
+        description = "the test library",
+        features = ["channels", "sabi_trait_examples"],
+        extra("homepage" = "example.com", "channel" = "beta"),
+    )]
+    fn get_library() -> MetaMod_Ref {
+        MetaMod { number: 3 }.leak_into_prefix()
+    }
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_ref = MetaMod_Ref)))]
+    pub struct MetaMod {
+        #[sabi(last_prefix_field)]
+        pub number: u32,
+    }
+
+    impl RootModule for MetaMod_Ref {
+        crate::declare_root_module_statics! {MetaMod_Ref}
+        const BASE_NAME: &'static str = "meta";
+        const NAME: &'static str = "meta";
+        const VERSION_STRINGS: VersionStrings = crate::package_version_strings!();
+    }
+
+    fn header() -> &'static LibHeader {
+        &crate::get_root_module_static!(crate::library::library_tests::lib_metadata)
+    }
+
+    #[test]
+    fn exported_metadata() {
+        let metadata = header().metadata();
+
+        assert_eq!(metadata.description().as_str(), "the test library");
+        assert_eq!(metadata.authors().as_str(), env!("CARGO_PKG_AUTHORS"));
+        assert_eq!(metadata.license().as_str(), env!("CARGO_PKG_LICENSE"));
+        assert!(matches!(metadata.build_timestamp(), RSome(x) if x > 0));
+        assert!(metadata.rustc_version().starts_with("rustc "));
+        assert!(!metadata.target().is_empty());
+
+        let mut expected_features = Vec::new();
+        if cfg!(feature = "channels") {
+            expected_features.push("channels");
+        }
+        if cfg!(feature = "sabi_trait_examples") {
+            expected_features.push("sabi_trait_examples");
+        }
+        let features = metadata
+            .features()
+            .iter()
+            .map(RStr::as_str)
+            .collect::<Vec<_>>();
+        assert_eq!(features, expected_features);
+
+        assert_eq!(metadata.extra().len(), 2);
+        assert_eq!(
+            metadata.get_extra("homepage").unwrap().as_str(),
+            "example.com"
+        );
+        assert_eq!(metadata.get_extra("channel").unwrap().as_str(), "beta");
+        assert!(metadata.get_extra("missing").is_none());
+
+        // reading the metadata doesn't run the root module constructor
+        assert!(MetaMod_Ref::get_module().is_none());
+    }
+
+    #[test]
+    fn default_metadata() {
+        let module = MetaMod { number: 5 }.leak_into_prefix();
+        assert_eq!(module.number(), 5);
+
+        let module_header = LibHeader::from_module(module);
+
+        for metadata in [module_header.metadata(), LibMetadata_Ref::DEFAULT] {
+            assert_eq!(metadata.description().as_str(), "");
+            assert_eq!(metadata.authors().as_str(), "");
+            assert_eq!(metadata.build_timestamp(), RNone);
+            assert_eq!(
+                metadata.rustc_version(),
+                header().metadata().rustc_version()
+            );
+            assert_eq!(metadata.target(), header().metadata().target());
+            assert!(metadata.features().is_empty());
+            assert!(metadata.extra().is_empty());
+        }
+    }
+}
+
+mod target_info {
+    use crate::library::{AbiHeader, Endianness, LibraryError, PanicStrategy, TargetInfo};
+
+    fn header_with_target(target_info: TargetInfo) -> AbiHeader {
+        let mut header = AbiHeader::VALUE;
+        header.target_info = target_info;
+        header
+    }
+
+    fn current_target_with(
+        pointer_width: u32,
+        endianness: Endianness,
+        panic_strategy: PanicStrategy,
+    ) -> TargetInfo {
+        TargetInfo::new(
+            env!("ABI_STABLE_TARGET"),
+            pointer_width,
+            endianness,
+            panic_strategy,
+        )
+    }
+
+    #[test]
+    fn current_target() {
+        let info = TargetInfo::VALUE;
+
+        assert_eq!(info.target(), env!("ABI_STABLE_TARGET"));
+        assert_eq!(
+            info.pointer_width() as usize,
+            std::mem::size_of::<usize>() * 8
+        );
+        assert_eq!(
+            info.endianness() == Endianness::BIG,
+            cfg!(target_endian = "big")
+        );
+        assert_eq!(
+            info.panic_strategy() == PanicStrategy::ABORT,
+            cfg!(panic = "abort")
+        );
+
+        AbiHeader::VALUE.ensure_compatible().unwrap();
+    }
+
+    #[test]
+    fn incompatible_targets() {
+        let native = TargetInfo::VALUE;
+        let other_endianness = if Endianness::NATIVE == Endianness::LITTLE {
+            Endianness::BIG
+        } else {
+            Endianness::LITTLE
+        };
+        let other_panic_strategy = if PanicStrategy::CURRENT == PanicStrategy::UNWIND {
+            PanicStrategy::ABORT
+        } else {
+            PanicStrategy::UNWIND
+        };
+
+        let err = header_with_target(current_target_with(
+            native.pointer_width(),
+            other_endianness,
+            native.panic_strategy(),
+        ))
+        .ensure_compatible()
+        .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                LibraryError::IncompatibleEndianness { expected, found }
+                if expected == Endianness::NATIVE && found == other_endianness
+            ),
+            "{:?}",
+            err
+        );
+
+        let err = header_with_target(current_target_with(
+            16,
+            native.endianness(),
+            native.panic_strategy(),
+        ))
+        .ensure_compatible()
+        .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                LibraryError::IncompatiblePointerWidth { found: 16, .. }
+            ),
+            "{:?}",
+            err
+        );
+
+        let err = header_with_target(current_target_with(
+            native.pointer_width(),
+            native.endianness(),
+            other_panic_strategy,
+        ))
+        .ensure_compatible()
+        .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                LibraryError::IncompatiblePanicStrategy { found, .. }
+                if found == other_panic_strategy
+            ),
+            "{:?}",
+            err
+        );
+
+        let err = header_with_target(TargetInfo::new(
+            "wasm32-os",
+            native.pointer_width(),
+            native.endianness(),
+            native.panic_strategy(),
+        ))
+        .ensure_compatible()
+        .unwrap_err();
+        match err {
+            LibraryError::IncompatibleTarget { expected, found } => {
+                assert_eq!(expected, env!("ABI_STABLE_TARGET"));
+                assert_eq!(found, "wasm32-os");
+            }
+            err => panic!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn byte_swapped_header() {
+        let native = TargetInfo::VALUE;
+        let other_endianness = if Endianness::NATIVE == Endianness::LITTLE {
+            Endianness::BIG
+        } else {
+            Endianness::LITTLE
+        };
+
+        // What a library compiled for a target with the other byte order would contain.
+        let mut header = header_with_target(current_target_with(
+            native.pointer_width(),
+            other_endianness,
+            native.panic_strategy(),
+        ));
+        header.abi_major = header.abi_major.swap_bytes();
+        header.abi_minor = header.abi_minor.swap_bytes();
+        assert!(!header.is_valid());
+
+        let err = header.ensure_compatible().unwrap_err();
+        assert!(
+            matches!(err, LibraryError::IncompatibleEndianness { .. }),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn incompatible_abi_version() {
+        let mut header = AbiHeader::VALUE;
+        header.abi_major += 1;
+
+        let err = header.ensure_compatible().unwrap_err();
+        assert!(
+            matches!(err, LibraryError::InvalidAbiHeader { .. }),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn legacy_header_layout() {
+        // The magic string used before fields were added to `LibHeader` and `AbiHeader`.
+        let mut header = AbiHeader::VALUE;
+        header.magic_string = *b"abi stable library for Rust     ";
+
+        assert!(!header.is_valid());
+
+        let err = header.ensure_compatible().unwrap_err();
+        assert!(
+            matches!(err, LibraryError::InvalidAbiHeader { .. }),
+            "{:?}",
+            err
+        );
+    }
+}
+
+mod module_graph {
+    // The prefix field accessors of the modules that are never loaded are unused.
+    #![allow(dead_code)]
+
+    use crate::{
+        library::{
+            module_graph::{RegisteredModule, ResolvedModule, ResolvedModuleGraph},
+            LibHeader, LibraryError, RootModule, RootModuleDependencies, RootModuleDependency,
+        },
+        prefix_type::PrefixTypeTrait,
+        sabi_types::{VersionNumber, VersionStrings},
+        utils::leak_value,
+        StableAbi,
+    };
+
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    macro_rules! declare_modules {
+        ($(
+            $struct_:ident, $ref_:ident, $name:literal, [$($dep:expr),* $(,)?];
+        )*) => {$(
+            #[repr(C)]
+            #[derive(StableAbi)]
+            #[sabi(kind(Prefix(prefix_ref = $ref_)))]
+            pub struct $struct_ {
+                #[sabi(last_prefix_field)]
+                pub number: u32,
+            }
+
+            impl RootModule for $ref_ {
+                crate::declare_root_module_statics! {$ref_}
+                const BASE_NAME: &'static str = $name;
+                const NAME: &'static str = $name;
+                const VERSION_STRINGS: VersionStrings = VersionStrings::new("1.2.0");
+                const DEPENDENCIES: &'static [RootModuleDependency] = &[$($dep),*];
+            }
+        )*};
+    }
+
+    declare_modules! {
+        Cyclic0, Cyclic0_Ref, "cyclic_0", [RootModuleDependency::of::<Cyclic1_Ref>()];
+        Cyclic1, Cyclic1_Ref, "cyclic_1", [RootModuleDependency::of::<Cyclic2_Ref>()];
+        Cyclic2, Cyclic2_Ref, "cyclic_2", [RootModuleDependency::of::<Cyclic0_Ref>()];
+        Missing, Missing_Ref, "missing", [RootModuleDependency::of::<Storage_Ref>()];
+        TooNew, TooNew_Ref, "too_new", [
+            RootModuleDependency::new("storage", VersionStrings::new("1.3.0")),
+        ];
+    }
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_ref = Storage_Ref)))]
+    pub struct Storage {
+        #[sabi(last_prefix_field)]
+        pub number: u32,
+    }
+
+    impl RootModule for Storage_Ref {
+        crate::declare_root_module_statics! {Storage_Ref}
+        const BASE_NAME: &'static str = "storage";
+        const NAME: &'static str = "storage";
+        const VERSION_STRINGS: VersionStrings = VersionStrings::new("1.2.5");
+    }
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_ref = Feature_Ref)))]
+    pub struct Feature {
+        #[sabi(last_prefix_field)]
+        pub number: u32,
+    }
+
+    // The `number` of the storage module that `Feature_Ref` got in its initialization.
+    static STORAGE_NUMBER: AtomicU32 = AtomicU32::new(0);
+
+    impl RootModule for Feature_Ref {
+        crate::declare_root_module_statics! {Feature_Ref}
+        const BASE_NAME: &'static str = "feature";
+        const NAME: &'static str = "feature";
+        const VERSION_STRINGS: VersionStrings = VersionStrings::new("0.1.0");
+        const DEPENDENCIES: &'static [RootModuleDependency] = &[
+            RootModuleDependency::of::<Storage_Ref>(),
+            RootModuleDependency::new("storage", VersionStrings::new("1.1.0")),
+        ];
+
+        fn initialization_with_dependencies(
+            self,
+            dependencies: RootModuleDependencies<'_>,
+        ) -> Result<Self, LibraryError> {
+            assert_eq!(dependencies.declared().len(), 2);
+            assert!(dependencies.get::<Feature_Ref>().is_none());
+
+            let storage = dependencies.get::<Storage_Ref>().unwrap();
+            STORAGE_NUMBER.store(storage.number(), Ordering::SeqCst);
+            Ok(self)
+        }
+    }
+
+    fn resolved<M>(module: M) -> ResolvedModule
+    where
+        M: RootModule,
+    {
+        ResolvedModule {
+            module: RegisteredModule::of::<M>(),
+            header: leak_value(LibHeader::from_module(module)),
+            raw_library: None,
+        }
+    }
+
+    #[test]
+    fn load_in_dependency_order() {
+        let graph = ResolvedModuleGraph::new(vec![
+            resolved(Feature { number: 5 }.leak_into_prefix()),
+            resolved(Storage { number: 8 }.leak_into_prefix()),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            graph.load_order().collect::<Vec<_>>(),
+            ["storage", "feature"]
+        );
+
+        graph.load().unwrap();
+
+        assert_eq!(Storage_Ref::get_module().unwrap().number(), 8);
+        assert_eq!(Feature_Ref::get_module().unwrap().number(), 5);
+        assert_eq!(STORAGE_NUMBER.load(Ordering::SeqCst), 8);
+    }
+
+    #[test]
+    fn missing_dependency() {
+        let err =
+            ResolvedModuleGraph::new(vec![resolved(Missing { number: 0 }.leak_into_prefix())])
+                .unwrap_err();
+
+        match err {
+            LibraryError::MissingDependency { module, dependency } => {
+                assert_eq!(module, "missing");
+                assert_eq!(dependency, "storage");
+            }
+            err => panic!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn incompatible_dependency() {
+        let err = ResolvedModuleGraph::new(vec![
+            resolved(Storage { number: 0 }.leak_into_prefix()),
+            resolved(TooNew { number: 0 }.leak_into_prefix()),
+        ])
+        .unwrap_err();
+
+        match err {
+            LibraryError::IncompatibleDependency {
+                module,
+                dependency,
+                required,
+                found,
+            } => {
+                assert_eq!(module, "too_new");
+                assert_eq!(dependency, "storage");
+                assert_eq!(
+                    required,
+                    VersionNumber {
+                        major: 1,
+                        minor: 3,
+                        patch: 0
+                    }
+                );
+                assert_eq!(
+                    found,
+                    VersionNumber {
+                        major: 1,
+                        minor: 2,
+                        patch: 5
+                    }
+                );
+            }
+            err => panic!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn dependency_cycle() {
+        let err = ResolvedModuleGraph::new(vec![
+            resolved(Cyclic1 { number: 0 }.leak_into_prefix()),
+            resolved(Cyclic0 { number: 0 }.leak_into_prefix()),
+            resolved(Cyclic2 { number: 0 }.leak_into_prefix()),
+        ])
+        .unwrap_err();
+
+        match err {
+            LibraryError::DependencyCycle { cycle } => {
+                assert_eq!(cycle, ["cyclic_1", "cyclic_2", "cyclic_0", "cyclic_1"]);
+            }
+            err => panic!("{:?}", err),
+        }
+    }
+}
@@ -0,0 +1,468 @@
+//! An unloadable handle to a dynamic library that exports a [`RootModule`].
+//!
+//! [`RootModule`]: ../trait.RootModule.html
+
+use super::{root_mod_trait::load_raw_library, *};
+
+use std::{
+    fmt::{self, Debug, Display},
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+/// A handle to a dynamic library, and the root module loaded from it,
+/// which unlike [`RootModule::load_from`] does not leak the library.
+///
+/// The library is unloaded once this and every [`Tracked`] and [`LibraryKeepAlive`]
+/// created from it are dropped.
+/// [`unload`](#method.unload) unloads the library immediately,
+/// returning an error if any of those are still alive.
+///
+/// Only [`Tracked`]s and [`LibraryKeepAlive`]s are counted,
+/// values that the library returns (including values it allocated,
+/// like `RBox`es, `RArc`s, and trait objects whose vtables are in the library)
+/// are not tracked automatically,
+/// they must be wrapped with [`track`](#method.track)
+/// (or [`Tracked::map`](./struct.Tracked.html#method.map))
+/// for the library to know that they're alive.
+/// This is why [`unload`](#method.unload) is unsafe:
+/// the caller must ensure that no untracked value from the library is alive.
+///
+/// The root module loaded with this type is independent from the one loaded with
+/// the `RootModule::load_*` associated functions,
+/// so this does not interact with [`RootModule::get_module`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use abi_stable::{
+///     for_examples::Module_Ref,
+///     library::{LibraryPath, LoadedLibrary},
+///     std_types::RStr,
+/// };
+///
+/// let path = std::path::Path::new("./target/debug/");
+///
+/// // safety: the library doesn't spawn threads,nor register thread-local destructors,
+/// // and doesn't store references into itself in the loader.
+/// let library =
+///     unsafe { LoadedLibrary::<Module_Ref>::load_from(LibraryPath::Directory(path)) }
+///         .unwrap_or_else(|e| panic!("{}", e));
+///
+/// // Values that come from the library are wrapped in `Tracked`,
+/// // so that the library can't be unloaded while they're alive.
+/// let second: abi_stable::library::Tracked<RStr<'static>> =
+///     library.module().map(|module| module.second());
+///
+/// // The library is still in use, so it's returned in the error.
+/// //
+/// // safety: every value from the library is tracked.
+/// let library = unsafe { library.unload() }.unwrap_err().into_library();
+///
+/// drop(second);
+///
+/// // Loading a new version of the library, checking its layout once more.
+/// //
+/// // safety: the new library upholds the same requirements as the previous one.
+/// let library = unsafe { library.reload(LibraryPath::Directory(path)) }.unwrap();
+///
+/// // safety: nothing from the library is alive.
+/// unsafe { library.unload() }.unwrap();
+///
+/// ```
+///
+/// [`RootModule::load_from`]: ./trait.RootModule.html#method.load_from
+/// [`RootModule::get_module`]: ./trait.RootModule.html#method.get_module
+/// [`Tracked`]: ./struct.Tracked.html
+/// [`LibraryKeepAlive`]: ./struct.LibraryKeepAlive.html
+pub struct LoadedLibrary<M> {
+    module: M,
+    library: Arc<RawLibrary>,
+}
+
+impl<M> LoadedLibrary<M>
+where
+    M: RootModule,
+{
+    /// Loads the dynamic library at `where_`,
+    /// checking that it's compatible in the same way that [`RootModule::load_from`] does,
+    /// then loading its root module.
+    ///
+    /// The layout of the root module is checked without registering any of its types
+    /// in the global (process-wide) layout-checking state,
+    /// since that state would otherwise point into the unloaded library.
+    ///
+    /// # Safety
+    ///
+    /// Because the library can be unloaded, callers must ensure that:
+    ///
+    /// - Nothing obtained from the library
+    ///   (copies of the root module, `'static` references, function pointers,
+    ///   values allocated by the library, trait objects with vtables in the library)
+    ///   outlives this `LoadedLibrary`,
+    ///   unless it's wrapped in a [`Tracked`] or accompanied by a [`LibraryKeepAlive`].
+    ///
+    /// - The library does not do anything that prevents sound unloading,
+    ///   like spawning threads that outlive the call into the library,
+    ///   registering thread-local destructors,
+    ///   or leaking references to itself into the global state of the loader.
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`RootModule::load_from`].
+    ///
+    /// [`RootModule::load_from`]: ./trait.RootModule.html#method.load_from
+    /// [`Tracked`]: ./struct.Tracked.html
+    /// [`LibraryKeepAlive`]: ./struct.LibraryKeepAlive.html
+    pub unsafe fn load_from(where_: LibraryPath<'_>) -> Result<Self, LibraryError> {
+        let library = load_raw_library::<M>(where_)?;
+
+        let module = {
+            let header = unsafe { lib_header_from_raw_library(&library)? };
+
+            header.ensure_layout_unshared::<M>()?;
+
+            // safety: the layout was checked in the code above,
+            unsafe {
+                header
+                    .init_root_module_with_unchecked_layout::<M>()?
+                    .initialization()?
+            }
+        };
+
+        Ok(Self {
+            module,
+            library: Arc::new(library),
+        })
+    }
+
+    /// Loads the dynamic library at the `path_` file.
+    ///
+    /// # Safety
+    ///
+    /// Safety requirements and errors are detailed in [`load_from`](#method.load_from).
+    pub unsafe fn load_from_file(path_: &Path) -> Result<Self, LibraryError> {
+        unsafe { Self::load_from(LibraryPath::FullPath(path_)) }
+    }
+
+    /// Loads the dynamic library from the `where_` directory.
+    ///
+    /// # Safety
+    ///
+    /// Safety requirements and errors are detailed in [`load_from`](#method.load_from).
+    pub unsafe fn load_from_directory(where_: &Path) -> Result<Self, LibraryError> {
+        unsafe { Self::load_from(LibraryPath::Directory(where_)) }
+    }
+
+    /// Unloads this library,then loads the library at `where_`,
+    /// checking that it's compatible in the same way that [`load_from`](#method.load_from)
+    /// does.
+    ///
+    /// The library is unloaded before loading the new one,
+    /// because most platforms return the already loaded library when
+    /// the same file is loaded again.
+    ///
+    /// # Errors
+    ///
+    /// This returns a `ReloadError::InUse` if there are
+    /// any [`Tracked`]s or [`LibraryKeepAlive`]s for this library,
+    /// which contains this `LoadedLibrary`.
+    ///
+    /// This returns a `ReloadError::Load` if the new library could not be loaded,
+    /// in which case the previous library was already unloaded.
+    ///
+    /// # Safety
+    ///
+    /// This has the same safety requirements as [`unload`](#method.unload)
+    /// for the current library.
+    ///
+    /// The library at `where_` must uphold the same requirements as
+    /// the ones described in [`load_from`](#method.load_from),
+    /// the requirements that this `LoadedLibrary` was constructed with
+    /// don't carry over to the new library.
+    ///
+    /// [`Tracked`]: ./struct.Tracked.html
+    /// [`LibraryKeepAlive`]: ./struct.LibraryKeepAlive.html
+    pub unsafe fn reload(self, where_: LibraryPath<'_>) -> Result<Self, ReloadError<M>> {
+        // safety: the caller ensures that no untracked value from the library is alive.
+        unsafe { self.unload() }.map_err(ReloadError::InUse)?;
+
+        // safety: the caller promised that the new library upholds
+        // the safety requirements of `load_from`.
+        unsafe { Self::load_from(where_) }.map_err(ReloadError::Load)
+    }
+}
+
+impl<M> LoadedLibrary<M> {
+    /// Gets the root module of the library,
+    /// wrapped in a [`Tracked`] so that the library can't be unloaded while it's alive.
+    ///
+    /// Copying the module out of the [`Tracked`] is allowed,
+    /// so long as the copy doesn't outlive the [`Tracked`]
+    /// (this is one of the safety requirements of [`load_from`](#method.load_from)).
+    ///
+    /// [`Tracked`]: ./struct.Tracked.html
+    pub fn module(&self) -> Tracked<M>
+    where
+        M: Copy,
+    {
+        self.track(self.module)
+    }
+
+    /// The path the library was loaded from.
+    pub fn path(&self) -> &Path {
+        self.library.path()
+    }
+
+    /// Gets a handle that prevents this library from being unloaded while it's alive.
+    pub fn keep_alive(&self) -> LibraryKeepAlive {
+        LibraryKeepAlive {
+            library: self.library.clone(),
+        }
+    }
+
+    /// Wraps `value` (which came from the library) so that the library
+    /// can't be unloaded while it's alive.
+    ///
+    /// This is the only way that values returned by the library are counted,
+    /// the library can't tell whether an untracked value is still alive.
+    pub fn track<T>(&self, value: T) -> Tracked<T> {
+        Tracked {
+            value,
+            keep_alive: self.keep_alive(),
+        }
+    }
+
+    /// How many [`Tracked`]s and [`LibraryKeepAlive`]s are alive for this library.
+    ///
+    /// This doesn't count values from the library that aren't wrapped in a [`Tracked`].
+    ///
+    /// [`Tracked`]: ./struct.Tracked.html
+    /// [`LibraryKeepAlive`]: ./struct.LibraryKeepAlive.html
+    pub fn outstanding_objects(&self) -> usize {
+        Arc::strong_count(&self.library) - 1
+    }
+
+    /// Unloads the library.
+    ///
+    /// # Safety
+    ///
+    /// Values from the library that aren't wrapped in a [`Tracked`]
+    /// (or accompanied by a [`LibraryKeepAlive`]) aren't counted,
+    /// so the caller must ensure that none of them are alive.
+    ///
+    /// This includes values that were allocated by the library
+    /// (eg: `RBox`, `RArc`, `RString`),
+    /// trait objects (eg: `DynTrait`, `RObject`) constructed in the library,
+    /// function pointers, and `'static` references into the library.
+    /// Using any of them after the library is unloaded is Undefined Behavior.
+    ///
+    /// # Errors
+    ///
+    /// This returns back the `LoadedLibrary` (inside a [`LibraryInUse`]) if there are
+    /// any [`Tracked`]s or [`LibraryKeepAlive`]s for this library.
+    ///
+    /// [`LibraryInUse`]: ./struct.LibraryInUse.html
+    /// [`Tracked`]: ./struct.Tracked.html
+    /// [`LibraryKeepAlive`]: ./struct.LibraryKeepAlive.html
+    pub unsafe fn unload(self) -> Result<(), LibraryInUse<M>> {
+        let Self { module, library } = self;
+        match Arc::try_unwrap(library) {
+            Ok(library) => {
+                drop(library);
+                Ok(())
+            }
+            Err(library) => Err(LibraryInUse {
+                outstanding: Arc::strong_count(&library) - 1,
+                library: LoadedLibrary { module, library },
+            }),
+        }
+    }
+}
+
+impl<M> Debug for LoadedLibrary<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoadedLibrary")
+            .field("path", &self.path())
+            .field("outstanding_objects", &self.outstanding_objects())
+            .finish()
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// Prevents the [`LoadedLibrary`] it was created from from being unloaded while it's alive.
+///
+/// [`LoadedLibrary`]: ./struct.LoadedLibrary.html
+#[derive(Clone)]
+pub struct LibraryKeepAlive {
+    library: Arc<RawLibrary>,
+}
+
+impl LibraryKeepAlive {
+    /// The path the library was loaded from.
+    pub fn path(&self) -> &Path {
+        self.library.path()
+    }
+}
+
+impl Debug for LibraryKeepAlive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LibraryKeepAlive")
+            .field("path", &self.path())
+            .finish()
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// A value that came from a [`LoadedLibrary`],
+/// which prevents the library from being unloaded while it's alive.
+///
+/// The wrapped value is dropped before the library is allowed to be unloaded.
+///
+/// [`LoadedLibrary`]: ./struct.LoadedLibrary.html
+pub struct Tracked<T> {
+    // Declared before `keep_alive` so that it's dropped first.
+    value: T,
+    keep_alive: LibraryKeepAlive,
+}
+
+impl<T> Tracked<T> {
+    /// Gets the handle that keeps the library loaded.
+    pub const fn keep_alive(&self) -> &LibraryKeepAlive {
+        &self.keep_alive
+    }
+
+    /// Transforms the wrapped value, keeping the library loaded.
+    ///
+    /// This is useful for values that are derived from the wrapped value,
+    /// like trait objects returned by a function pointer in the root module.
+    pub fn map<U, F>(self, f: F) -> Tracked<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Tracked {
+            value: f(self.value),
+            keep_alive: self.keep_alive,
+        }
+    }
+
+    /// Unwraps this into the value and the handle that keeps the library loaded.
+    pub fn into_parts(self) -> (T, LibraryKeepAlive) {
+        (self.value, self.keep_alive)
+    }
+}
+
+impl<T: Clone> Clone for Tracked<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            keep_alive: self.keep_alive.clone(),
+        }
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Tracked<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: Debug> Debug for Tracked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.value, f)
+    }
+}
+
+impl<T: Display> Display for Tracked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.value, f)
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// Returned when a [`LoadedLibrary`] can't be unloaded,
+/// because there are values from the library that are still alive.
+///
+/// [`LoadedLibrary`]: ./struct.LoadedLibrary.html
+pub struct LibraryInUse<M> {
+    library: LoadedLibrary<M>,
+    outstanding: usize,
+}
+
+impl<M> LibraryInUse<M> {
+    /// How many [`Tracked`]s and [`LibraryKeepAlive`]s were alive.
+    ///
+    /// [`Tracked`]: ./struct.Tracked.html
+    /// [`LibraryKeepAlive`]: ./struct.LibraryKeepAlive.html
+    pub const fn outstanding(&self) -> usize {
+        self.outstanding
+    }
+
+    /// Gets back the library that couldn't be unloaded.
+    pub fn into_library(self) -> LoadedLibrary<M> {
+        self.library
+    }
+}
+
+impl<M> Debug for LibraryInUse<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LibraryInUse")
+            .field("library", &self.library)
+            .field("outstanding", &self.outstanding)
+            .finish()
+    }
+}
+
+impl<M> Display for LibraryInUse<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Could not unload the library at:\n\t{}\nbecause {} value(s) from it are still alive",
+            self.library.path().display(),
+            self.outstanding,
+        )
+    }
+}
+
+impl<M> ::std::error::Error for LibraryInUse<M> {}
+
+/// The error returned by [`LoadedLibrary::reload`].
+///
+/// [`LoadedLibrary::reload`]: ./struct.LoadedLibrary.html#method.reload
+pub enum ReloadError<M> {
+    /// The previous library could not be unloaded.
+    InUse(LibraryInUse<M>),
+    /// The previous library was unloaded, but the new one could not be loaded.
+    Load(LibraryError),
+}
+
+impl<M> Debug for ReloadError<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReloadError::InUse(x) => f.debug_tuple("InUse").field(x).finish(),
+            ReloadError::Load(x) => f.debug_tuple("Load").field(x).finish(),
+        }
+    }
+}
+
+impl<M> Display for ReloadError<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReloadError::InUse(x) => Display::fmt(x, f),
+            ReloadError::Load(x) => Display::fmt(x, f),
+        }
+    }
+}
+
+impl<M> ::std::error::Error for ReloadError<M> {}
@@ -0,0 +1,504 @@
+//! Loading root modules that depend on other root modules,
+//! in an order where every root module is loaded after its dependencies.
+//!
+//! Root modules declare the root modules that they depend on with
+//! [`RootModule::DEPENDENCIES`],
+//! which is stored in the [`LibHeader`] of the library,
+//! so that the dependencies can be read without loading the root module.
+//!
+//! The entry point of this module is the [`ModuleGraph`] type.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use abi_stable::{
+//!     library::{module_graph::ModuleGraph, RootModule, RootModuleDependency},
+//!     sabi_types::VersionStrings,
+//!     std_types::RStr,
+//!     StableAbi,
+//! };
+//!
+//! use std::path::Path;
+//!
+//! #[repr(C)]
+//! #[derive(StableAbi)]
+//! #[sabi(kind(Prefix(prefix_ref = Storage_Ref)))]
+//! pub struct Storage {
+//!     #[sabi(last_prefix_field)]
+//!     pub get: extern "C" fn(RStr<'_>) -> u64,
+//! }
+//!
+//! impl RootModule for Storage_Ref {
+//!     abi_stable::declare_root_module_statics! {Storage_Ref}
+//!     const BASE_NAME: &'static str = "storage";
+//!     const NAME: &'static str = "storage";
+//!     const VERSION_STRINGS: VersionStrings = abi_stable::package_version_strings!();
+//! }
+//!
+//! #[repr(C)]
+//! #[derive(StableAbi)]
+//! #[sabi(kind(Prefix(prefix_ref = Metrics_Ref)))]
+//! pub struct Metrics {
+//!     #[sabi(last_prefix_field)]
+//!     pub set_storage: extern "C" fn(Storage_Ref),
+//! }
+//!
+//! impl RootModule for Metrics_Ref {
+//!     abi_stable::declare_root_module_statics! {Metrics_Ref}
+//!     const BASE_NAME: &'static str = "metrics";
+//!     const NAME: &'static str = "metrics";
+//!     const VERSION_STRINGS: VersionStrings = abi_stable::package_version_strings!();
+//!     const DEPENDENCIES: &'static [RootModuleDependency] =
+//!         &[RootModuleDependency::of::<Storage_Ref>()];
+//!
+//!     fn initialization_with_dependencies(
+//!         self,
+//!         dependencies: abi_stable::library::RootModuleDependencies<'_>,
+//!     ) -> Result<Self, abi_stable::library::LibraryError> {
+//!         // The storage module is always loaded before this one.
+//!         let storage = dependencies.get::<Storage_Ref>().unwrap();
+//!         self.set_storage()(storage);
+//!         Ok(self)
+//!     }
+//! }
+//!
+//! let mut graph = ModuleGraph::new();
+//! graph.add::<Storage_Ref>().add::<Metrics_Ref>();
+//!
+//! // Loads `storage`, then `metrics`.
+//! graph
+//!     .load_from_directory(Path::new("plugins"))
+//!     .unwrap_or_else(|e| panic!("{}", e));
+//!
+//! let metrics = Metrics_Ref::get_module().unwrap();
+//! ```
+//!
+//! [`RootModule::DEPENDENCIES`]: ../trait.RootModule.html#associatedconstant.DEPENDENCIES
+//! [`LibHeader`]: ../struct.LibHeader.html
+//! [`ModuleGraph`]: ./struct.ModuleGraph.html
+
+use super::*;
+
+use crate::utils::leak_value;
+
+use std::fmt::{self, Debug};
+
+/// A root module that another root module depends on,
+/// declared in [`RootModule::DEPENDENCIES`].
+///
+/// [`RootModule::DEPENDENCIES`]: ../trait.RootModule.html#associatedconstant.DEPENDENCIES
+#[repr(C)]
+#[derive(StableAbi, Copy, Clone)]
+pub struct RootModuleDependency {
+    name: RStr<'static>,
+    version_strings: VersionStrings,
+}
+
+impl RootModuleDependency {
+    /// Constructs a dependency on the root module with the `name` [`RootModule::NAME`],
+    /// requiring a version compatible with `version_strings`.
+    ///
+    /// [`RootModule::NAME`]: ../trait.RootModule.html#associatedconstant.NAME
+    pub const fn new(name: &'static str, version_strings: VersionStrings) -> Self {
+        Self {
+            name: RStr::from_str(name),
+            version_strings,
+        }
+    }
+
+    /// Constructs a dependency on the `M` root module,
+    /// requiring a version compatible with the one that the dependent is compiled with.
+    pub const fn of<M>() -> Self
+    where
+        M: RootModule,
+    {
+        Self::new(M::NAME, M::VERSION_STRINGS)
+    }
+
+    /// The [`RootModule::NAME`] of the dependency.
+    ///
+    /// [`RootModule::NAME`]: ../trait.RootModule.html#associatedconstant.NAME
+    pub fn name(&self) -> &'static str {
+        self.name.as_str()
+    }
+
+    /// The minimum version of the dependency that's required.
+    pub const fn version_strings(&self) -> VersionStrings {
+        self.version_strings
+    }
+}
+
+impl Debug for RootModuleDependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RootModuleDependency")
+            .field("name", &self.name)
+            .field("version_strings", &self.version_strings)
+            .finish()
+    }
+}
+
+/// The dependencies of a root module,
+/// passed to [`RootModule::initialization_with_dependencies`]
+/// once all of them are loaded.
+///
+/// [`RootModule::initialization_with_dependencies`]:
+/// ../trait.RootModule.html#method.initialization_with_dependencies
+#[derive(Debug, Copy, Clone)]
+pub struct RootModuleDependencies<'a> {
+    declared: &'a [RootModuleDependency],
+}
+
+impl<'a> RootModuleDependencies<'a> {
+    pub(super) const fn new(declared: &'a [RootModuleDependency]) -> Self {
+        Self { declared }
+    }
+
+    /// The dependencies declared by the library that the root module was loaded from.
+    pub const fn declared(&self) -> &'a [RootModuleDependency] {
+        self.declared
+    }
+
+    /// Gets the `M` root module,
+    /// returning None if `M` isn't a declared dependency, or if it's not loaded.
+    pub fn get<M>(&self) -> Option<M>
+    where
+        M: RootModule,
+    {
+        if self.declared.iter().any(|dep| dep.name() == M::NAME) {
+            M::get_module()
+        } else {
+            None
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// A set of root module types that are loaded in dependency order.
+///
+/// Every root module is loaded from the library at
+/// [`RootModule::get_library_path`] in a directory,
+/// into its [`root_module_statics`],
+/// like [`RootModule::load_from_directory`] does.
+///
+/// All the root modules that the added root modules depend on must be added too.
+///
+/// For an example, look at the [module-level documentation](./index.html)
+///
+/// [`RootModule::get_library_path`]: ../trait.RootModule.html#method.get_library_path
+/// [`root_module_statics`]: ../trait.RootModule.html#tymethod.root_module_statics
+/// [`RootModule::load_from_directory`]:
+/// ../trait.RootModule.html#method.load_from_directory
+#[derive(Debug, Clone, Default)]
+pub struct ModuleGraph {
+    modules: Vec<RegisteredModule>,
+}
+
+#[derive(Copy, Clone)]
+pub(super) struct RegisteredModule {
+    name: &'static str,
+    library_path: fn(&Path) -> PathBuf,
+    load: fn(&'static LibHeader, Option<&'static RawLibrary>) -> Result<(), LibraryError>,
+}
+
+impl RegisteredModule {
+    pub(super) fn of<M>() -> Self
+    where
+        M: RootModule,
+    {
+        Self {
+            name: M::NAME,
+            library_path: M::get_library_path,
+            load: load_module::<M>,
+        }
+    }
+}
+
+impl Debug for RegisteredModule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegisteredModule")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl ModuleGraph {
+    /// Constructs an empty `ModuleGraph`.
+    pub const fn new() -> Self {
+        Self {
+            modules: Vec::new(),
+        }
+    }
+
+    /// Adds the `M` root module to the graph.
+    ///
+    /// Adding a root module with the same [`RootModule::NAME`] as
+    /// a previously added one replaces it.
+    ///
+    /// [`RootModule::NAME`]: ../trait.RootModule.html#associatedconstant.NAME
+    pub fn add<M>(&mut self) -> &mut Self
+    where
+        M: RootModule,
+    {
+        let module = RegisteredModule::of::<M>();
+        match self.modules.iter_mut().find(|m| m.name == M::NAME) {
+            Some(prev) => *prev = module,
+            None => self.modules.push(module),
+        }
+        self
+    }
+
+    /// Iterates over the [`RootModule::NAME`]s of the added root modules.
+    ///
+    /// [`RootModule::NAME`]: ../trait.RootModule.html#associatedconstant.NAME
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.modules.iter().map(|m| m.name)
+    }
+
+    /// Reads the dependencies of every root module from the libraries in `directory`,
+    /// computing the order that they're loaded in.
+    ///
+    /// This does not call the functions exported with
+    /// [`export_root_module`](../../attr.export_root_module.html),
+    /// but it does check the C abi and initialize the globals of every library,
+    /// the same way that [`lib_header_from_path`](../fn.lib_header_from_path.html) does.
+    ///
+    /// The libraries are leaked, for the same reasons as
+    /// [`RootModule::load_from`](../trait.RootModule.html#method.load_from) does it.
+    ///
+    /// # Errors
+    ///
+    /// This returns the errors that
+    /// [`lib_header_from_path`](../fn.lib_header_from_path.html) returns,
+    /// and these errors:
+    ///
+    /// - `LibraryError::MissingDependency`:
+    /// If a root module depends on one that wasn't added to the graph.
+    ///
+    /// - `LibraryError::IncompatibleDependency`:
+    /// If the version of a dependency is incompatible with the one that's required.
+    ///
+    /// - `LibraryError::DependencyCycle`:
+    /// If root modules (transitively) depend on themselves.
+    ///
+    /// - `LibraryError::ParseVersionError`:
+    /// If the version strings of a dependency can't be parsed as version numbers,
+    /// this can only happen if the version strings are manually constructed.
+    ///
+    pub fn resolve(&self, directory: &Path) -> Result<ResolvedModuleGraph, LibraryError> {
+        let modules = self
+            .modules
+            .iter()
+            .map(|&module| {
+                let path = (module.library_path)(directory);
+                let raw_library = leak_value(RawLibrary::load_at(&path)?);
+                let header = unsafe { lib_header_from_raw_library(raw_library)? };
+                Ok(ResolvedModule {
+                    module,
+                    header,
+                    raw_library: Some(raw_library),
+                })
+            })
+            .collect::<Result<Vec<_>, LibraryError>>()?;
+
+        ResolvedModuleGraph::new(modules)
+    }
+
+    /// Loads every root module from the libraries in `directory`,
+    /// each one after the ones it depends on.
+    ///
+    /// This is equivalent to `self.resolve(directory)?.load()`.
+    ///
+    /// # Errors
+    ///
+    /// This returns the errors that [`resolve`](#method.resolve) and
+    /// [`ResolvedModuleGraph::load`](./struct.ResolvedModuleGraph.html#method.load)
+    /// return.
+    pub fn load_from_directory(&self, directory: &Path) -> Result<(), LibraryError> {
+        self.resolve(directory)?.load()
+    }
+}
+
+/// A [`ModuleGraph`] whose dependencies have been checked,
+/// with the order that the root modules are loaded in.
+///
+/// [`ModuleGraph`]: ./struct.ModuleGraph.html
+pub struct ResolvedModuleGraph {
+    modules: Vec<ResolvedModule>,
+    load_order: Vec<usize>,
+}
+
+#[derive(Copy, Clone)]
+pub(super) struct ResolvedModule {
+    pub(super) module: RegisteredModule,
+    pub(super) header: &'static LibHeader,
+    pub(super) raw_library: Option<&'static RawLibrary>,
+}
+
+impl ResolvedModuleGraph {
+    pub(super) fn new(modules: Vec<ResolvedModule>) -> Result<Self, LibraryError> {
+        let index_of = |name: &str| modules.iter().position(|m| m.module.name == name);
+
+        let mut dependencies = Vec::with_capacity(modules.len());
+        for resolved in &modules {
+            let mut deps = Vec::new();
+            for dep in resolved.header.root_mod_consts().dependencies().iter() {
+                let index =
+                    index_of(dep.name()).ok_or_else(|| LibraryError::MissingDependency {
+                        module: resolved.module.name.to_string(),
+                        dependency: dep.name().to_string(),
+                    })?;
+
+                let required = VersionNumber::new(dep.version_strings())?;
+                let found = VersionNumber::new(modules[index].header.version_strings())?;
+                if !required.is_compatible(found) {
+                    return Err(LibraryError::IncompatibleDependency {
+                        module: resolved.module.name.to_string(),
+                        dependency: dep.name().to_string(),
+                        required,
+                        found,
+                    });
+                }
+
+                deps.push(index);
+            }
+            dependencies.push(deps);
+        }
+
+        let load_order = topological_order(&modules, &dependencies)?;
+
+        Ok(Self {
+            modules,
+            load_order,
+        })
+    }
+
+    /// Iterates over the [`RootModule::NAME`]s of the root modules,
+    /// in the order that they're loaded in.
+    ///
+    /// [`RootModule::NAME`]: ../trait.RootModule.html#associatedconstant.NAME
+    pub fn load_order(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.load_order
+            .iter()
+            .map(move |&i| self.modules[i].module.name)
+    }
+
+    /// Loads every root module, each one after the ones it depends on,
+    /// calling [`RootModule::initialization_with_dependencies`] on each of them.
+    ///
+    /// Root modules that were already loaded are not loaded again.
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`RootModule::load_from`],
+    /// stopping at the first root module that fails to load.
+    ///
+    /// [`RootModule::initialization_with_dependencies`]:
+    /// ../trait.RootModule.html#method.initialization_with_dependencies
+    /// [`RootModule::load_from`]: ../trait.RootModule.html#method.load_from
+    pub fn load(self) -> Result<(), LibraryError> {
+        for &i in &self.load_order {
+            let ResolvedModule {
+                module,
+                header,
+                raw_library,
+            } = self.modules[i];
+            (module.load)(header, raw_library)?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for ResolvedModuleGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolvedModuleGraph")
+            .field("load_order", &self.load_order().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Sorts the modules so that every one comes after its dependencies,
+/// otherwise preserving the order that they were added in.
+fn topological_order(
+    modules: &[ResolvedModule],
+    dependencies: &[Vec<usize>],
+) -> Result<Vec<usize>, LibraryError> {
+    #[derive(Copy, Clone, PartialEq)]
+    enum State {
+        Unvisited,
+        Visiting,
+        Visited,
+    }
+
+    fn visit(
+        index: usize,
+        dependencies: &[Vec<usize>],
+        states: &mut [State],
+        stack: &mut Vec<usize>,
+        order: &mut Vec<usize>,
+    ) -> Result<(), Vec<usize>> {
+        match states[index] {
+            State::Visited => return Ok(()),
+            State::Visiting => {
+                let start = stack.iter().position(|&i| i == index).unwrap_or(0);
+                let mut cycle = stack[start..].to_vec();
+                cycle.push(index);
+                return Err(cycle);
+            }
+            State::Unvisited => {}
+        }
+
+        states[index] = State::Visiting;
+        stack.push(index);
+        for &dep in &dependencies[index] {
+            visit(dep, dependencies, states, stack, order)?;
+        }
+        stack.pop();
+        states[index] = State::Visited;
+        order.push(index);
+        Ok(())
+    }
+
+    let mut states = vec![State::Unvisited; modules.len()];
+    let mut stack = Vec::new();
+    let mut order = Vec::with_capacity(modules.len());
+
+    for index in 0..modules.len() {
+        visit(index, dependencies, &mut states, &mut stack, &mut order).map_err(|cycle| {
+            LibraryError::DependencyCycle {
+                cycle: cycle
+                    .into_iter()
+                    .map(|i| modules[i].module.name.to_string())
+                    .collect(),
+            }
+        })?;
+    }
+
+    Ok(order)
+}
+
+/// Loads the `M` root module from `header` into its statics,
+/// unless it was already loaded.
+fn load_module<M>(
+    header: &'static LibHeader,
+    raw_library: Option<&'static RawLibrary>,
+) -> Result<(), LibraryError>
+where
+    M: RootModule,
+{
+    let statics = M::root_module_statics();
+    statics.root_mod.try_init(|| {
+        if let Some(raw_library) = raw_library {
+            statics
+                .raw_lib
+                .try_init(|| Ok::<_, Infallible>(raw_library))
+                .unwrap_or_else(|e| match e {});
+        }
+
+        let dependencies = header.root_mod_consts().dependencies();
+        header
+            .init_root_module::<M>()?
+            .initialization_with_dependencies(RootModuleDependencies::new(dependencies.as_slice()))
+    })?;
+    Ok(())
+}
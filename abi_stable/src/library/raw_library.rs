@@ -2,6 +2,16 @@ use super::*;
 
 use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
 
+/// The suffix that [`LibrarySuffix::Suffix`] appends to the name of a library.
+pub(super) fn pointer_width_suffix() -> &'static str {
+    let is_64_bits = cfg!(any(x86_64, powerpc64, aarch64)) || ::std::mem::size_of::<usize>() == 8;
+    if is_64_bits {
+        "64"
+    } else {
+        "32"
+    }
+}
+
 /// A handle to any dynamically loaded library,
 /// not necessarily ones that export abi_stable compatible modules.
 pub struct RawLibrary {
@@ -14,13 +24,9 @@ impl RawLibrary {
     pub fn path_in_directory(directory: &Path, base_name: &str, suffix: LibrarySuffix) -> PathBuf {
         let formatted: String;
 
-        let is_64_bits =
-            cfg!(any(x86_64, powerpc64, aarch64)) || ::std::mem::size_of::<usize>() == 8;
-        let bits = if is_64_bits { "64" } else { "32" };
-
         let maybe_suffixed_name = match suffix {
             LibrarySuffix::Suffix => {
-                formatted = format!("{}-{}", base_name, bits);
+                formatted = format!("{}-{}", base_name, pointer_width_suffix());
                 &*formatted
             }
             LibrarySuffix::NoSuffix => base_name,
@@ -45,6 +51,11 @@ impl RawLibrary {
         }
     }
 
+    /// The path this library was loaded from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     /// Gets access to a static/function declared by the library.
     ///
     /// # Safety
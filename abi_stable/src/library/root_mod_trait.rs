@@ -1,6 +1,12 @@
 use super::*;
 
-use crate::{prefix_type::PrefixRefTrait, utils::leak_value};
+use super::root_module_index::{named_root_module_from_index, named_root_module_from_raw_library};
+
+use crate::{
+    external_types::RMutex, prefix_type::PrefixRefTrait, std_types::RSlice, utils::leak_value,
+};
+
+use std::any::TypeId;
 
 /// The root module of a dynamic library,
 /// which may contain other modules,function pointers,and static references.
@@ -53,6 +59,18 @@ pub trait RootModule: Sized + StableAbi + PrefixRefTrait + 'static {
     /// [`package_version_strings!()`](../macro.package_version_strings.html)
     const VERSION_STRINGS: VersionStrings;
 
+    /// The root modules that this root module depends on,
+    /// which are loaded before this one by a [`ModuleGraph`].
+    ///
+    /// This is stored in the [`LibHeader`] of the library that exports this root module,
+    /// so it can be read without loading the root module.
+    ///
+    /// Defaults to no dependencies.
+    ///
+    /// [`ModuleGraph`]: ./module_graph/struct.ModuleGraph.html
+    /// [`LibHeader`]: ./struct.LibHeader.html
+    const DEPENDENCIES: &'static [RootModuleDependency] = &[];
+
     /// All the constants of this trait and supertraits.
     ///
     /// It can safely be used as a proxy for the associated constants of this trait.
@@ -62,6 +80,7 @@ pub trait RootModule: Sized + StableAbi + PrefixRefTrait + 'static {
         version_strings: Self::VERSION_STRINGS,
         layout: IsLayoutChecked::Yes(<Self as StableAbi>::LAYOUT),
         c_abi_testing_fns: crate::library::c_abi_testing::C_ABI_TESTING_FNS,
+        dependencies: RSlice::from_slice(Self::DEPENDENCIES),
         _priv: (),
     };
 
@@ -147,6 +166,9 @@ pub trait RootModule: Sized + StableAbi + PrefixRefTrait + 'static {
     /// - `LibraryError::InvalidAbiHeader`:
     /// If the abi_stable version used by the library is not compatible.
     ///
+    /// - `LibraryError::IncompatibleTarget` and similar errors:
+    /// If the library was compiled for a different target.
+    ///
     /// - `LibraryError::ParseVersionError`:
     /// If the version strings in the library can't be parsed as version numbers,
     /// this can only happen if the version strings are manually constructed.
@@ -174,19 +196,154 @@ pub trait RootModule: Sized + StableAbi + PrefixRefTrait + 'static {
                 // sound library unloading.
                 Ok(leak_value(raw_library))
             })?;
-            let items = unsafe { lib_header_from_raw_library(lib)? };
+            unsafe { load_module_from_raw_library::<Self>(lib) }
+        })
+    }
 
-            items.ensure_layout::<Self>()?;
+    /// Loads an instance of this module from the library at `where_`,
+    /// without going through the statics returned by
+    /// [`root_module_statics`](#tymethod.root_module_statics).
+    ///
+    /// This allows loading the same kind of root module from multiple
+    /// dynamic libraries (eg: two plugins that implement the same interface),
+    /// returning the module along with the library that it was loaded from.
+    ///
+    /// Unlike [`load_from`](#method.load_from),
+    /// this can load the root module from multiple libraries,
+    /// and [`get_module`](#method.get_module) doesn't return modules loaded with this.
+    ///
+    /// Every library is leaked once,
+    /// for the same reasons as [`load_from`](#method.load_from) does it,
+    /// loading an already loaded library returns the same `&'static RawLibrary`
+    /// and root module as the first time,
+    /// without leaking another handle to the library.
+    ///
+    /// [`initialization`](#method.initialization) is only called the first time
+    /// that the root module is loaded from a library,
+    /// unless this is called concurrently for the same library
+    /// (in which case it may be called once per thread).
+    /// Errors from `initialization` are not cached.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use abi_stable::{
+    ///     for_examples::Module_Ref,
+    ///     library::{LibraryPath, RootModule},
+    /// };
+    ///
+    /// use std::path::Path;
+    ///
+    /// let plugins = ["plugins/libplugin_a.so", "plugins/libplugin_b.so"];
+    ///
+    /// for plugin in plugins {
+    ///     let (module, library) =
+    ///         Module_Ref::load_instance_from(LibraryPath::FullPath(Path::new(plugin)))
+    ///             .unwrap_or_else(|e| panic!("{}", e));
+    ///
+    ///     println!("{}: {}", library.path().display(), module.second());
+    /// }
+    ///
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`load_from`](#method.load_from).
+    ///
+    fn load_instance_from(
+        where_: LibraryPath<'_>,
+    ) -> Result<(Self, &'static RawLibrary), LibraryError> {
+        let raw_library = load_raw_library::<Self>(where_)?;
 
-            // safety: the layout was checked in the code above,
-            unsafe {
-                items
-                    .init_root_module_with_unchecked_layout::<Self>()?
-                    .initialization()
-            }
+        let header = unsafe { abi_header_from_raw_library(&raw_library)? };
+
+        unsafe { load_cached_instance(raw_library, header.address(), header) }
+    }
+
+    /// Loads the root module with the `key` key from the named root modules of
+    /// the library at `where_`,
+    /// first loading the dynamic library if it wasn't already loaded.
+    ///
+    /// `key` is [`NAME`](#associatedconstant.NAME) unless the module was exported with
+    /// an explicit key.
+    ///
+    /// This is for libraries that export multiple root modules with the
+    /// [`export_root_modules`](../macro.export_root_modules.html) macro,
+    /// each root module is loaded and layout-checked independently of the others.
+    ///
+    /// Once the root module is loaded,
+    /// this will return the already loaded root module,
+    /// regardless of the `key` passed to later calls.
+    /// To load the same type of root module with different keys,
+    /// use [`load_named_instance_from`](#method.load_named_instance_from).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use abi_stable::{
+    ///     for_examples::Module_Ref,
+    ///     library::{LibraryPath, RootModule},
+    /// };
+    ///
+    /// use std::path::Path;
+    ///
+    /// let path = LibraryPath::Directory(Path::new("plugins"));
+    /// let module = Module_Ref::load_named_from(path, Module_Ref::NAME)
+    ///     .unwrap_or_else(|e| panic!("{}", e));
+    ///
+    /// println!("{}", module.second());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`load_from`](#method.load_from),
+    /// as well as `LibraryError::RootModuleNotExported`
+    /// if the library doesn't export a root module with the `key` key.
+    ///
+    fn load_named_from(where_: LibraryPath<'_>, key: &str) -> Result<Self, LibraryError> {
+        let statics = Self::root_module_statics();
+        statics.root_mod.try_init(|| {
+            let lib = statics.raw_lib.try_init(|| -> Result<_, LibraryError> {
+                let raw_library = load_raw_library::<Self>(where_)?;
+
+                // Leaked for the same reason as in `load_from`
+                Ok(leak_value(raw_library))
+            })?;
+            unsafe { load_named_module_from_raw_library::<Self>(lib, key) }
         })
     }
 
+    /// Loads an instance of the root module with the `key` key from
+    /// the named root modules of the library at `where_`,
+    /// without going through the statics returned by
+    /// [`root_module_statics`](#tymethod.root_module_statics).
+    ///
+    /// This is the equivalent of
+    /// [`load_instance_from`](#method.load_instance_from) for libraries that export
+    /// multiple root modules with the
+    /// [`export_root_modules`](../macro.export_root_modules.html) macro.
+    ///
+    /// Loading the same root module from the same library returns
+    /// the same `&'static RawLibrary` and root module as the first time,
+    /// and [`initialization`](#method.initialization) is only called the first time,
+    /// the same as with [`load_instance_from`](#method.load_instance_from).
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`load_named_from`](#method.load_named_from).
+    ///
+    fn load_named_instance_from(
+        where_: LibraryPath<'_>,
+        key: &str,
+    ) -> Result<(Self, &'static RawLibrary), LibraryError> {
+        let raw_library = load_raw_library::<Self>(where_)?;
+
+        let index = unsafe { root_module_index_from_raw_library(&raw_library)? };
+        let header = named_root_module_from_index(index, &raw_library, key)?.abi_header();
+
+        unsafe { load_cached_instance(raw_library, index.address(), header) }
+    }
+
     /// Loads this module from the directory specified by `where_`,
     /// first loading the dynamic library if it wasn't already loaded.
     ///
@@ -220,10 +377,27 @@ pub trait RootModule: Sized + StableAbi + PrefixRefTrait + 'static {
     fn initialization(self) -> Result<Self, LibraryError> {
         Ok(self)
     }
+
+    /// Defines behavior that happens once the module is loaded by a [`ModuleGraph`],
+    /// which is after all the root modules that it depends on were loaded.
+    ///
+    /// This is called instead of [`initialization`](#method.initialization)
+    /// when loading the root module with a `ModuleGraph`.
+    ///
+    /// The default implementation calls [`initialization`](#method.initialization).
+    ///
+    /// [`ModuleGraph`]: ./module_graph/struct.ModuleGraph.html
+    fn initialization_with_dependencies(
+        self,
+        dependencies: RootModuleDependencies<'_>,
+    ) -> Result<Self, LibraryError> {
+        let _ = dependencies;
+        self.initialization()
+    }
 }
 
 /// Loads the raw library at `where_`
-fn load_raw_library<M>(where_: LibraryPath<'_>) -> Result<RawLibrary, LibraryError>
+pub(super) fn load_raw_library<M>(where_: LibraryPath<'_>) -> Result<RawLibrary, LibraryError>
 where
     M: RootModule,
 {
@@ -234,6 +408,133 @@ where
     RawLibrary::load_at(&path)
 }
 
+/// The libraries and root modules loaded by `RootModule::load_instance_from`
+/// and similar functions.
+struct LoadedInstances {
+    /// The leaked libraries,
+    /// along with the address of the symbol that they were looked up with
+    /// (the `LibHeader` or the `RootModuleIndex`).
+    libraries: Vec<(usize, &'static RawLibrary)>,
+    /// The initialized root modules,
+    /// along with the address of the `LibHeader` they were loaded from,
+    /// and the type of the root module.
+    modules: Vec<((usize, TypeId), PrefixRef<ErasedPrefix>)>,
+}
+
+static LOADED_INSTANCES: RMutex<LoadedInstances> = RMutex::new(LoadedInstances {
+    libraries: Vec::new(),
+    modules: Vec::new(),
+});
+
+/// Loads the `M` root module from `header`,
+/// returning the cached root module and library if it was already loaded.
+///
+/// `library_addr` is the address of the symbol that `header` was looked up with,
+/// used to leak only one handle to each library.
+///
+/// The library is leaked before any function from it is called.
+///
+/// # Safety
+///
+/// `header` must come from `raw_library`.
+pub(super) unsafe fn load_cached_instance<M>(
+    raw_library: RawLibrary,
+    library_addr: usize,
+    header: AbiHeaderRef,
+) -> Result<(M, &'static RawLibrary), LibraryError>
+where
+    M: RootModule,
+{
+    // The error doesn't reference the library, since it's unloaded when this returns.
+    header.ensure_compatible()?;
+
+    let module_key = (header.address(), TypeId::of::<M>());
+
+    let lib = {
+        let mut instances = LOADED_INSTANCES.lock();
+
+        // Dropping `raw_library` when the library was already leaked doesn't unload it,
+        // because the leaked handle keeps the library loaded.
+        let lib = match instances.libraries.iter().find(|x| x.0 == library_addr) {
+            Some(&(_, lib)) => lib,
+            None => {
+                let lib = leak_value(raw_library);
+                instances.libraries.push((library_addr, lib));
+                lib
+            }
+        };
+
+        if let Some(&(_, module)) = instances.modules.iter().find(|x| x.0 == module_key) {
+            // safety: the module was stored below, with the same `M` type.
+            return Ok((M::from_prefix_ref(unsafe { module.cast() }), lib));
+        }
+
+        lib
+    };
+
+    // Not holding the lock while initializing the root module,
+    // because `initialization` can load other libraries.
+    let module = load_module_from_lib_header::<M>(header.upgrade()?)?;
+
+    LOADED_INSTANCES
+        .lock()
+        .modules
+        .push((module_key, unsafe { module.to_prefix_ref().cast() }));
+
+    Ok((module, lib))
+}
+
+/// Checks that the root module in `raw_library` is compatible with `M`,
+/// then loads and initializes it.
+///
+/// # Safety
+///
+/// The returned module is implicitly tied to the lifetime of the library.
+pub(super) unsafe fn load_module_from_raw_library<M>(
+    raw_library: &RawLibrary,
+) -> Result<M, LibraryError>
+where
+    M: RootModule,
+{
+    let items = unsafe { lib_header_from_raw_library(raw_library)? };
+
+    load_module_from_lib_header(items)
+}
+
+/// Checks that the root module with the `key` key in `raw_library` is compatible with `M`,
+/// then loads and initializes it.
+///
+/// # Safety
+///
+/// The returned module is implicitly tied to the lifetime of the library.
+pub(super) unsafe fn load_named_module_from_raw_library<M>(
+    raw_library: &RawLibrary,
+    key: &str,
+) -> Result<M, LibraryError>
+where
+    M: RootModule,
+{
+    let items = unsafe { named_root_module_from_raw_library(raw_library, key)? }.lib_header()?;
+
+    load_module_from_lib_header(items)
+}
+
+/// Checks that the root module of `items` is compatible with `M`,
+/// then loads and initializes it.
+fn load_module_from_lib_header<M>(items: &'static LibHeader) -> Result<M, LibraryError>
+where
+    M: RootModule,
+{
+    items.ensure_layout::<M>()?;
+
+    // safety: the layout was checked in the code above,
+    unsafe {
+        items
+            .init_root_module_with_unchecked_layout::<M>()?
+            .initialization()
+    }
+}
+
 /// Gets the LibHeader of a library.
 ///
 /// # Errors
@@ -246,6 +547,9 @@ where
 /// - `LibraryError::InvalidAbiHeader`:
 /// If the abi_stable used by the library is not compatible.
 ///
+/// - `LibraryError::IncompatibleTarget` and similar errors:
+/// If the library was compiled for a different target.
+///
 /// # Safety
 ///
 /// The LibHeader is implicitly tied to the lifetime of the library,
@@ -301,6 +605,9 @@ pub unsafe fn abi_header_from_raw_library(
 /// - `LibraryError::InvalidAbiHeader`:
 /// If the abi_stable version used by the library is not compatible.
 ///
+/// - `LibraryError::IncompatibleTarget` and similar errors:
+/// If the library was compiled for a different target.
+///
 ///
 pub fn lib_header_from_path(path: &Path) -> Result<&'static LibHeader, LibraryError> {
     let raw_lib = RawLibrary::load_at(path)?;
@@ -397,5 +704,8 @@ declare_root_module_consts! {
          and the loader\
         ",
         c_abi_testing_fns:&'static CAbiTestingFns,
+
+        method_docs="The root modules that the root module depends on.",
+        dependencies: RSlice<'static, RootModuleDependency>,
     ]
 }
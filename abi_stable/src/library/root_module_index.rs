@@ -0,0 +1,286 @@
+use super::*;
+
+use crate::{sabi_types::RRef, std_types::RSlice};
+
+use const_panic::concat_panic;
+
+use std::fmt::{self, Debug};
+
+/// The list of named root modules that a dynamic library exports,
+/// declared with the [`export_root_modules`] macro.
+///
+/// Every root module in the list has its own [`LibHeader`],
+/// so each one is version-checked and layout-checked independently when loaded.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use abi_stable::library::{root_module_index_from_path, LibraryError};
+///
+/// use std::path::Path;
+///
+/// fn main() -> Result<(), LibraryError> {
+///     let index = root_module_index_from_path(Path::new("plugins/libservices.so"))?;
+///
+///     for module in index.modules() {
+///         let consts = module.lib_header()?.root_mod_consts();
+///         println!("{}: {} {}", module.key(), consts.name(), consts.version_strings());
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [`export_root_modules`]: ../macro.export_root_modules.html
+/// [`LibHeader`]: ./struct.LibHeader.html
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RootModuleIndex {
+    header: AbiHeader,
+    modules: RSlice<'static, NamedRootModule>,
+}
+
+impl RootModuleIndex {
+    #[doc(hidden)]
+    pub const fn __new(modules: &'static [NamedRootModule]) -> Self {
+        Self {
+            header: AbiHeader::VALUE,
+            modules: RSlice::from_slice(modules),
+        }
+    }
+
+    /// The address of the index, used to identify the library it belongs to.
+    pub(super) fn address(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    /// The root modules exported by the library.
+    pub fn modules(&self) -> &'static [NamedRootModule] {
+        self.modules.as_slice()
+    }
+
+    /// Iterates over the keys of the root modules exported by the library.
+    pub fn keys(&self) -> impl Iterator<Item = &'static str> + Clone {
+        self.modules().iter().map(NamedRootModule::key)
+    }
+
+    /// Gets the root module with the `key` key,
+    /// returning None if the library doesn't export it.
+    pub fn get(&self, key: &str) -> Option<&'static NamedRootModule> {
+        self.modules().iter().find(|m| m.key() == key)
+    }
+}
+
+/// A root module in a [`RootModuleIndex`],
+/// along with the key that it's exported with.
+///
+/// [`RootModuleIndex`]: ./struct.RootModuleIndex.html
+#[repr(C)]
+#[derive(StableAbi, Copy, Clone)]
+pub struct NamedRootModule {
+    key: RStr<'static>,
+    header: AbiHeaderRef,
+}
+
+impl NamedRootModule {
+    #[doc(hidden)]
+    pub const fn __new<T>(
+        _loader: fn() -> T,
+        key: Option<&'static str>,
+        lib_header: &'static LibHeader,
+    ) -> Self
+    where
+        T: IntoRootModuleResult,
+    {
+        Self {
+            key: RStr::from_str(__root_module_key(_loader, key)),
+            header: AbiHeaderRef(RRef::new(lib_header.abi_header())),
+        }
+    }
+
+    /// The key that the root module is exported with,
+    /// which is [`RootModule::NAME`] unless an explicit key was passed to
+    /// [`export_root_modules`].
+    ///
+    /// [`RootModule::NAME`]: ./trait.RootModule.html#associatedconstant.NAME
+    /// [`export_root_modules`]: ../macro.export_root_modules.html
+    pub fn key(&self) -> &'static str {
+        self.key.as_str()
+    }
+
+    /// Gets the [`AbiHeaderRef`] of the root module.
+    ///
+    /// [`AbiHeaderRef`]: ./struct.AbiHeaderRef.html
+    pub const fn abi_header(&self) -> AbiHeaderRef {
+        self.header
+    }
+
+    /// Gets the [`LibHeader`] of the root module,
+    /// which is used to check the version and layout of the root module when loading it.
+    ///
+    /// # Errors
+    ///
+    /// This returns the same errors as [`AbiHeaderRef::upgrade`].
+    ///
+    /// [`LibHeader`]: ./struct.LibHeader.html
+    /// [`AbiHeaderRef::upgrade`]: ./struct.AbiHeaderRef.html#method.upgrade
+    pub fn lib_header(&self) -> Result<&'static LibHeader, LibraryError> {
+        self.header.upgrade()
+    }
+}
+
+impl Debug for NamedRootModule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NamedRootModule")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// Resolves the key that a root module is exported with,
+/// defaulting to [`RootModule::NAME`] when no explicit key was passed.
+#[doc(hidden)]
+pub const fn __root_module_key<T>(_loader: fn() -> T, key: Option<&'static str>) -> &'static str
+where
+    T: IntoRootModuleResult,
+{
+    match key {
+        Some(key) => key,
+        None => <T::Module as RootModule>::NAME,
+    }
+}
+
+/// Panics at compile-time if any two of the resolved root module keys are equal.
+#[doc(hidden)]
+#[track_caller]
+pub const fn __assert_unique_root_module_keys(keys: &[&str]) {
+    let mut i = 0;
+    while i < keys.len() {
+        let mut j = i + 1;
+        while j < keys.len() {
+            if str_eq(keys[i], keys[j]) {
+                concat_panic!(
+                    "export_root_modules: multiple root modules are exported with the `",
+                    keys[i],
+                    "` key",
+                )
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+const fn str_eq(left: &str, right: &str) -> bool {
+    let (left, right) = (left.as_bytes(), right.as_bytes());
+    if left.len() != right.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < left.len() {
+        if left[i] != right[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// Gets the [`RootModuleIndex`] of a library.
+///
+/// # Errors
+///
+/// This will return these errors:
+///
+/// - `LibraryError::GetSymbolError`:
+/// If the library doesn't export named root modules.
+///
+/// - `LibraryError::InvalidAbiHeader`:
+/// If the abi_stable used by the library is not compatible.
+///
+/// - `LibraryError::IncompatibleTarget` and similar errors:
+/// If the library was compiled for a different target.
+///
+/// # Safety
+///
+/// The RootModuleIndex is implicitly tied to the lifetime of the library,
+/// it will contain dangling `'static` references if the library is dropped before it does.
+///
+/// [`RootModuleIndex`]: ./struct.RootModuleIndex.html
+pub unsafe fn root_module_index_from_raw_library(
+    raw_library: &RawLibrary,
+) -> Result<&'static RootModuleIndex, LibraryError> {
+    let mangled = ROOT_MODULE_INDEX_NAME_WITH_NUL;
+    let header: AbiHeaderRef = unsafe { *raw_library.get::<AbiHeaderRef>(mangled.as_bytes())? };
+
+    header.ensure_compatible()?;
+
+    Ok(unsafe { header.0.transmute_into_ref() })
+}
+
+/// Gets the [`RootModuleIndex`] of the library at the path.
+///
+/// This leaks the underlying dynamic library,
+/// if you need to do this without leaking you'll need to use
+/// `root_module_index_from_raw_library` instead.
+///
+/// # Errors
+///
+/// This will return these errors:
+///
+/// - `LibraryError::OpenError`:
+/// If the dynamic library itself could not be loaded.
+///
+/// - `LibraryError::GetSymbolError`:
+/// If the library doesn't export named root modules.
+///
+/// - `LibraryError::InvalidAbiHeader`:
+/// If the abi_stable used by the library is not compatible.
+///
+/// - `LibraryError::IncompatibleTarget` and similar errors:
+/// If the library was compiled for a different target.
+///
+/// [`RootModuleIndex`]: ./struct.RootModuleIndex.html
+pub fn root_module_index_from_path(path: &Path) -> Result<&'static RootModuleIndex, LibraryError> {
+    let raw_lib = RawLibrary::load_at(path)?;
+
+    let index = unsafe { root_module_index_from_raw_library(&raw_lib)? };
+
+    mem::forget(raw_lib);
+
+    Ok(index)
+}
+
+/// Gets the [`NamedRootModule`] with the `key` key from the library.
+///
+/// # Safety
+///
+/// The NamedRootModule is implicitly tied to the lifetime of the library,
+/// it will contain dangling `'static` references if the library is dropped before it does.
+pub(super) unsafe fn named_root_module_from_raw_library(
+    raw_library: &RawLibrary,
+    key: &str,
+) -> Result<&'static NamedRootModule, LibraryError> {
+    let index = unsafe { root_module_index_from_raw_library(raw_library)? };
+
+    named_root_module_from_index(index, raw_library, key)
+}
+
+/// Gets the [`NamedRootModule`] with the `key` key from the `index` of `raw_library`.
+pub(super) fn named_root_module_from_index(
+    index: &'static RootModuleIndex,
+    raw_library: &RawLibrary,
+    key: &str,
+) -> Result<&'static NamedRootModule, LibraryError> {
+    index
+        .get(key)
+        .ok_or_else(|| LibraryError::RootModuleNotExported {
+            library: raw_library.path().to_owned(),
+            key: key.to_owned(),
+            exported: index.keys().map(String::from).collect(),
+        })
+}
@@ -0,0 +1,248 @@
+use super::*;
+
+use std::fmt::{self, Debug, Display};
+
+/// The maximum length of the target triple stored in a [`TargetInfo`],
+/// longer target triples are truncated.
+///
+/// [`TargetInfo`]: ./struct.TargetInfo.html
+pub const TARGET_TRIPLE_CAPACITY: usize = 64;
+
+/// The target that a dynamic library was compiled for,
+/// stored in its [`AbiHeader`].
+///
+/// This is checked before any function exported by the library is called,
+/// returning one of these errors if it's not the same as the loader's:
+///
+/// - `LibraryError::IncompatibleEndianness`
+///
+/// - `LibraryError::IncompatiblePointerWidth`
+///
+/// - `LibraryError::IncompatiblePanicStrategy`
+///
+/// - `LibraryError::IncompatibleTarget`
+///
+/// This type only contains bytes,
+/// so that it can be read from libraries compiled for any target.
+///
+/// Note that this check can't prevent the static initializers of the library
+/// from running, since those run when the operating system loads the library.
+///
+/// [`AbiHeader`]: ./struct.AbiHeader.html
+#[repr(C)]
+#[derive(StableAbi, Copy, Clone, PartialEq, Eq)]
+pub struct TargetInfo {
+    target: [u8; TARGET_TRIPLE_CAPACITY],
+    pointer_width: u8,
+    endianness: Endianness,
+    panic_strategy: PanicStrategy,
+}
+
+impl TargetInfo {
+    /// The `TargetInfo` of the target that this is compiled for.
+    pub const VALUE: Self = Self::new(
+        env!("ABI_STABLE_TARGET"),
+        mem::size_of::<usize>() as u32 * 8,
+        Endianness::NATIVE,
+        PanicStrategy::CURRENT,
+    );
+
+    /// Constructs a `TargetInfo`,
+    /// truncating the `target` triple to [`TARGET_TRIPLE_CAPACITY`] bytes.
+    ///
+    /// `pointer_width` is the size of pointers in bits.
+    ///
+    /// [`TARGET_TRIPLE_CAPACITY`]: ./constant.TARGET_TRIPLE_CAPACITY.html
+    pub const fn new(
+        target: &str,
+        pointer_width: u32,
+        endianness: Endianness,
+        panic_strategy: PanicStrategy,
+    ) -> Self {
+        Self {
+            target: target_triple_bytes(target),
+            pointer_width: pointer_width as u8,
+            endianness,
+            panic_strategy,
+        }
+    }
+
+    /// The target triple (eg: `x86_64-unknown-linux-gnu`),
+    /// truncated to [`TARGET_TRIPLE_CAPACITY`] bytes.
+    ///
+    /// [`TARGET_TRIPLE_CAPACITY`]: ./constant.TARGET_TRIPLE_CAPACITY.html
+    pub fn target(&self) -> &str {
+        let len = self
+            .target
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(TARGET_TRIPLE_CAPACITY);
+
+        std::str::from_utf8(&self.target[..len]).unwrap_or("<invalid target triple>")
+    }
+
+    /// The size of pointers in bits.
+    pub const fn pointer_width(&self) -> u32 {
+        self.pointer_width as u32
+    }
+
+    /// The byte order of the target.
+    pub const fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// The strategy that the library was compiled to use for panics.
+    pub const fn panic_strategy(&self) -> PanicStrategy {
+        self.panic_strategy
+    }
+
+    /// Checks that a library compiled for the `self` target can be loaded
+    /// from the target that this is compiled for.
+    ///
+    /// # Errors
+    ///
+    /// This returns these errors:
+    ///
+    /// - `LibraryError::IncompatibleEndianness`:
+    /// If the byte order of the targets is different.
+    ///
+    /// - `LibraryError::IncompatiblePointerWidth`:
+    /// If the size of pointers is different.
+    ///
+    /// - `LibraryError::IncompatiblePanicStrategy`:
+    /// If one of them was compiled with `panic = "abort"` and the other with
+    /// `panic = "unwind"`.
+    ///
+    /// - `LibraryError::IncompatibleTarget`:
+    /// If the target triples are different.
+    pub fn ensure_compatible(&self) -> Result<(), LibraryError> {
+        let expected = &Self::VALUE;
+
+        if self.endianness != expected.endianness {
+            Err(LibraryError::IncompatibleEndianness {
+                expected: expected.endianness,
+                found: self.endianness,
+            })
+        } else if self.pointer_width != expected.pointer_width {
+            Err(LibraryError::IncompatiblePointerWidth {
+                expected: expected.pointer_width(),
+                found: self.pointer_width(),
+            })
+        } else if self.panic_strategy != expected.panic_strategy {
+            Err(LibraryError::IncompatiblePanicStrategy {
+                expected: expected.panic_strategy,
+                found: self.panic_strategy,
+            })
+        } else if self.target != expected.target {
+            Err(LibraryError::IncompatibleTarget {
+                expected: expected.target().to_string(),
+                found: self.target().to_string(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Debug for TargetInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TargetInfo")
+            .field("target", &self.target())
+            .field("pointer_width", &self.pointer_width())
+            .field("endianness", &self.endianness)
+            .field("panic_strategy", &self.panic_strategy)
+            .finish()
+    }
+}
+
+const fn target_triple_bytes(target: &str) -> [u8; TARGET_TRIPLE_CAPACITY] {
+    let target = target.as_bytes();
+    let mut out = [0u8; TARGET_TRIPLE_CAPACITY];
+    let mut i = 0;
+    while i < target.len() && i < TARGET_TRIPLE_CAPACITY {
+        out[i] = target[i];
+        i += 1;
+    }
+    out
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// The byte order of a target.
+///
+/// This is a newtype around a `u8` instead of an enum,
+/// so that reading an unknown value from a library is not Undefined Behavior.
+#[repr(transparent)]
+#[derive(StableAbi, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Endianness(u8);
+
+impl Endianness {
+    /// Least significant byte first.
+    pub const LITTLE: Self = Self(0);
+
+    /// Most significant byte first.
+    pub const BIG: Self = Self(1);
+
+    /// The byte order of the target that this is compiled for.
+    pub const NATIVE: Self = if cfg!(target_endian = "big") {
+        Self::BIG
+    } else {
+        Self::LITTLE
+    };
+}
+
+impl Debug for Endianness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for Endianness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::LITTLE => f.write_str("little endian"),
+            Self::BIG => f.write_str("big endian"),
+            Self(x) => write!(f, "<unknown endianness {}>", x),
+        }
+    }
+}
+
+/// How panics are handled in a library,
+/// determined by the `panic` setting of the profile it was compiled with.
+///
+/// This is a newtype around a `u8` instead of an enum,
+/// so that reading an unknown value from a library is not Undefined Behavior.
+#[repr(transparent)]
+#[derive(StableAbi, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PanicStrategy(u8);
+
+impl PanicStrategy {
+    /// Panics unwind the stack.
+    pub const UNWIND: Self = Self(0);
+
+    /// Panics abort the process.
+    pub const ABORT: Self = Self(1);
+
+    /// The panic strategy that this is compiled with.
+    pub const CURRENT: Self = if cfg!(panic = "abort") {
+        Self::ABORT
+    } else {
+        Self::UNWIND
+    };
+}
+
+impl Debug for PanicStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for PanicStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::UNWIND => f.write_str("unwind"),
+            Self::ABORT => f.write_str("abort"),
+            Self(x) => write!(f, "<unknown panic strategy {}>", x),
+        }
+    }
+}
@@ -33,7 +33,15 @@ use core_extensions::type_asserts::AssertEq;
     IoSeek,
     IoRead,
     IoBufRead,
-    Error
+    Error,
+    Future,
+    AsRefStr,
+    AsRefBytes,
+    BorrowStr,
+    Deref,
+    FnOnce,
+    FnMut,
+    Fn
 ))]
 pub struct AllTraitsImpld;
 
@@ -61,6 +69,14 @@ fn assert_all_traits_impld() {
     let _: AssertEq<<AllTraitsImpld as InterfaceType>::IoRead, Implemented<_>>;
     let _: AssertEq<<AllTraitsImpld as InterfaceType>::IoBufRead, Implemented<_>>;
     let _: AssertEq<<AllTraitsImpld as InterfaceType>::Error, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::Future, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::AsRefStr, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::AsRefBytes, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::BorrowStr, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::Deref, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::FnOnce, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::FnMut, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::Fn, Implemented<_>>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -95,6 +111,14 @@ fn assert_all_traits_unimpld() {
     let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::IoRead, Unimplemented<_>>;
     let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::IoBufRead, Unimplemented<_>>;
     let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::Error, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::Future, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::AsRefStr, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::AsRefBytes, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::BorrowStr, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::Deref, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::FnOnce, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::FnMut, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::Fn, Unimplemented<_>>;
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -334,3 +358,48 @@ fn assert_only_de_iter() {
     let _: AssertEq<<OnlyDEIter as InterfaceType>::IoBufRead, Unimplemented<_>>;
     let _: AssertEq<<OnlyDEIter as InterfaceType>::Error, Unimplemented<_>>;
 }
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[repr(C)]
+#[derive(GetStaticEquivalent)]
+#[sabi(impl_InterfaceType(FnMut))]
+pub struct OnlyFnMut;
+
+#[test]
+fn assert_only_fn_mut() {
+    let _: AssertEq<<OnlyFnMut as InterfaceType>::FnOnce, Implemented<_>>;
+    let _: AssertEq<<OnlyFnMut as InterfaceType>::FnMut, Implemented<_>>;
+    let _: AssertEq<<OnlyFnMut as InterfaceType>::Fn, Unimplemented<_>>;
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[repr(C)]
+#[derive(GetStaticEquivalent)]
+#[sabi(impl_InterfaceType(Fn))]
+pub struct OnlyFn;
+
+#[test]
+fn assert_only_fn() {
+    let _: AssertEq<<OnlyFn as InterfaceType>::FnOnce, Implemented<_>>;
+    let _: AssertEq<<OnlyFn as InterfaceType>::FnMut, Implemented<_>>;
+    let _: AssertEq<<OnlyFn as InterfaceType>::Fn, Implemented<_>>;
+    let _: AssertEq<<OnlyFn as InterfaceType>::Future, Unimplemented<_>>;
+    let _: AssertEq<<OnlyFn as InterfaceType>::Deref, Unimplemented<_>>;
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[repr(C)]
+#[derive(GetStaticEquivalent)]
+#[sabi(impl_InterfaceType(Future))]
+pub struct OnlyFuture;
+
+#[test]
+fn assert_only_future() {
+    let _: AssertEq<<OnlyFuture as InterfaceType>::Future, Implemented<_>>;
+    let _: AssertEq<<OnlyFuture as InterfaceType>::Unpin, Implemented<_>>;
+    let _: AssertEq<<OnlyFuture as InterfaceType>::Send, Unimplemented<_>>;
+    let _: AssertEq<<OnlyFuture as InterfaceType>::FnOnce, Unimplemented<_>>;
+}
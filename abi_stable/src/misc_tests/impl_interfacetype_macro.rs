@@ -39,6 +39,14 @@ impl_InterfaceType! {
         type IoRead=True;
         type IoBufRead=True;
         type Error=True;
+        type Future=True;
+        type AsRefStr=True;
+        type AsRefBytes=True;
+        type BorrowStr=True;
+        type Deref=True;
+        type FnOnce=True;
+        type FnMut=True;
+        type Fn=True;
     }
 }
 
@@ -66,6 +74,14 @@ fn assert_all_traits_impld() {
     let _: AssertEq<<AllTraitsImpld as InterfaceType>::IoRead, Implemented<_>>;
     let _: AssertEq<<AllTraitsImpld as InterfaceType>::IoBufRead, Implemented<_>>;
     let _: AssertEq<<AllTraitsImpld as InterfaceType>::Error, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::Future, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::AsRefStr, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::AsRefBytes, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::BorrowStr, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::Deref, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::FnOnce, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::FnMut, Implemented<_>>;
+    let _: AssertEq<<AllTraitsImpld as InterfaceType>::Fn, Implemented<_>>;
 }
 
 #[repr(C)]
@@ -103,6 +119,14 @@ fn assert_all_traits_unimpld() {
     let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::IoRead, Unimplemented<_>>;
     let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::IoBufRead, Unimplemented<_>>;
     let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::Error, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::Future, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::AsRefStr, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::AsRefBytes, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::BorrowStr, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::Deref, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::FnOnce, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::FnMut, Unimplemented<_>>;
+    let _: AssertEq<<NoTraitsImpld<()> as InterfaceType>::Fn, Unimplemented<_>>;
 }
 
 #[repr(C)]
@@ -69,6 +69,8 @@ for checking that the types are compatible with whatever loads that library.
 
 - A [`LateStaticRef`] of the root module.
 
+- The [`LibMetadata`] of the library.
+
 
 The name used for generated static is the value of 
 [`abi_stable::library::ROOT_MODULE_LOADER_NAME`](./library/constant.ROOT_MODULE_LOADER_NAME.html).
@@ -87,6 +89,63 @@ This attribute should not be used unconditionally,
 it should be disabled in Continuous Integration so that the 
 binary compatibility of a dynamic library is checked at some point before releasing it.
 
+# Library metadata
+
+The attribute takes optional arguments to set the [`LibMetadata`] of the library,
+which can be read from the [`LibHeader`] without loading the root module:
+
+- `description = "..."`: the description of the library,
+defaulting to the `description` in its `Cargo.toml`.
+
+- `authors = "..."`: the authors of the library separated by `:`,
+defaulting to the `authors` in its `Cargo.toml`.
+
+- `license = "..."`: the license of the library,
+defaulting to the `license` in its `Cargo.toml`.
+
+- `features = ["foo", "bar"]`: the cargo features to record,
+only the ones that are enabled are stored in the metadata.
+
+- `extra("key" = "value", ...)`: arbitrary key-value pairs.
+
+The version of the compiler, the target triple,
+and the time the library was compiled at are always recorded
+(the `SOURCE_DATE_EPOCH` environment variable overrides the compile time).
+
+```rust
+use abi_stable::prefix_type::PrefixTypeTrait;
+
+#[abi_stable::export_root_module(
+    description = "Reverses strings",
+    features = ["unicode"],
+    extra("homepage" = "https://example.com"),
+)]
+pub fn get_hello_world_mod() -> TextOperationsMod_Ref {
+    TextOperationsMod { reverse_string }.leak_into_prefix()
+}
+
+# #[repr(C)]
+# #[derive(abi_stable::StableAbi)]
+# #[sabi(kind(Prefix(prefix_ref= TextOperationsMod_Ref)))]
+# #[sabi(missing_field(panic))]
+# pub struct TextOperationsMod {
+#     #[sabi(last_prefix_field)]
+#     pub reverse_string: extern "C" fn(),
+# }
+# 
+# extern "C" fn reverse_string() {}
+
+# impl abi_stable::library::RootModule for TextOperationsMod_Ref {
+#     abi_stable::declare_root_module_statics!{TextOperationsMod_Ref}
+#     const BASE_NAME: &'static str = "stuff";
+#     const NAME: &'static str = "stuff";
+#     const VERSION_STRINGS: abi_stable::sabi_types::VersionStrings =
+#           abi_stable::package_version_strings!();
+# }
+
+# fn main(){}
+```
+
 # More examples
 
 For a more detailed example look in the README in the repository for this crate.
@@ -96,6 +155,7 @@ For a more detailed example look in the README in the repository for this crate.
 [`IntoRootModuleResult`]: ./library/trait.IntoRootModuleResult.html
 [`LateStaticRef`]: ./sabi_types/struct.LateStaticRef.html
 [`LibHeader`]: ./library/struct.LibHeader.html
+[`LibMetadata`]: ./library/struct.LibMetadata.html
 
 */
 #[doc(inline)]
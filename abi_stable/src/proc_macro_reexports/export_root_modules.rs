@@ -0,0 +1,157 @@
+/**
+This macro exports multiple independent root modules from an `implementation crate`.
+
+Each root module gets its own [`LibHeader`], so that every module is
+version-checked and layout-checked independently when it's loaded,
+allowing the modules to be versioned separately.
+
+The root modules are listed in a [`RootModuleIndex`],
+which is used to enumerate the root modules that a library exports.
+
+This can be used alongside the [`export_root_module`] attribute,
+which exports the root module loaded by the `RootModule::load_from*` functions.
+
+# Syntax
+
+The macro takes a comma separated list of root module loader functions,
+each of which is one of:
+
+- `function_path`: exports the root module with [`RootModule::NAME`] as its key.
+
+- `"key" => function_path`: exports the root module with `"key"` as its key.
+
+Every key must be unique, including the keys that default to [`RootModule::NAME`],
+exporting two root modules with the same key is a compile-time error:
+
+```compile_fail
+use abi_stable::{
+    library::RootModule,
+    prefix_type::PrefixTypeTrait,
+    sabi_types::VersionStrings,
+    StableAbi,
+};
+
+abi_stable::export_root_modules! {
+    get_old_mod,
+    get_new_mod,
+}
+
+pub fn get_old_mod() -> Module_Ref {
+    Module { value: 0 }.leak_into_prefix()
+}
+
+pub fn get_new_mod() -> Module_Ref {
+    Module { value: 1 }.leak_into_prefix()
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix(prefix_ref = Module_Ref)))]
+pub struct Module {
+    #[sabi(last_prefix_field)]
+    pub value: u64,
+}
+
+impl RootModule for Module_Ref {
+    abi_stable::declare_root_module_statics! {Module_Ref}
+    const BASE_NAME: &'static str = "module";
+    const NAME: &'static str = "module";
+    const VERSION_STRINGS: VersionStrings = abi_stable::package_version_strings!();
+}
+
+# fn main(){}
+```
+
+The functions have the same requirements as the ones annotated with [`export_root_module`],
+including the return types they can have.
+
+Each function can be preceded by these attributes:
+
+- `#[unsafe_no_layout_constant]`:
+which has the same effect as it does in [`export_root_module`].
+
+- `#[metadata(...)]`: which sets the [`LibMetadata`] of the root module,
+taking the same arguments as [`export_root_module`].
+
+# Example
+
+```rust
+use abi_stable::{
+    library::RootModule,
+    prefix_type::PrefixTypeTrait,
+    sabi_types::VersionStrings,
+    std_types::RStr,
+    StableAbi,
+};
+
+abi_stable::export_root_modules! {
+    get_codec_mod,
+    #[metadata(description = "Stores things")]
+    "storage_v2" => get_storage_mod,
+}
+
+pub fn get_codec_mod() -> CodecMod_Ref {
+    CodecMod { name: RStr::from("codec") }.leak_into_prefix()
+}
+
+pub fn get_storage_mod() -> StorageMod_Ref {
+    StorageMod { capacity: 4096 }.leak_into_prefix()
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix(prefix_ref = CodecMod_Ref)))]
+pub struct CodecMod {
+    #[sabi(last_prefix_field)]
+    pub name: RStr<'static>,
+}
+
+impl RootModule for CodecMod_Ref {
+    abi_stable::declare_root_module_statics! {CodecMod_Ref}
+    const BASE_NAME: &'static str = "services";
+    const NAME: &'static str = "codec";
+    const VERSION_STRINGS: VersionStrings = abi_stable::package_version_strings!();
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix(prefix_ref = StorageMod_Ref)))]
+pub struct StorageMod {
+    #[sabi(last_prefix_field)]
+    pub capacity: u64,
+}
+
+impl RootModule for StorageMod_Ref {
+    abi_stable::declare_root_module_statics! {StorageMod_Ref}
+    const BASE_NAME: &'static str = "services";
+    const NAME: &'static str = "storage";
+    const VERSION_STRINGS: VersionStrings = abi_stable::package_version_strings!();
+}
+
+# fn main(){}
+```
+
+The `codec` module can then be loaded with
+`CodecMod_Ref::load_named_from(LibraryPath::Directory(directory), CodecMod_Ref::NAME)`,
+and the `storage_v2` module with
+`StorageMod_Ref::load_named_instance_from(LibraryPath::Directory(directory), "storage_v2")`.
+
+# Generated code
+
+This macro creates a
+`static THE_NAME_USED_FOR_ALL_ROOT_MODULE_INDICES: `[`RootModuleIndex`]` = ... ;`
+which contains the version of `abi_stable` used,
+and a [`LibHeader`] for each root module.
+
+The name used for generated static is the value of
+[`abi_stable::library::ROOT_MODULE_INDEX_NAME`](./library/constant.ROOT_MODULE_INDEX_NAME.html).
+
+[`export_root_module`]: ./attr.export_root_module.html
+[`LibHeader`]: ./library/struct.LibHeader.html
+[`LibMetadata`]: ./library/struct.LibMetadata.html
+[`RootModuleIndex`]: ./library/struct.RootModuleIndex.html
+[`RootModule::NAME`]: ./library/trait.RootModule.html#associatedconstant.NAME
+
+*/
+#[doc(inline)]
+pub use abi_stable_derive::export_root_modules;
@@ -54,7 +54,77 @@ allowing these supertraits:
 
 - `Hash`
 
+### `#[sabi_trait]` supertraits
 
+Supertraits that are `#[sabi_trait]` traits must be listed in the
+`#[sabi(sabi_supertraits(Trait0, Trait1))]` attribute,
+and their `Trait_TO` must be in scope wherever the trait is.
+These can't be used with the `#[sabi(use_dyntrait)]` attribute,
+nor can they have associated types.
+Any other supertrait that isn't in the list above is an error.
+
+The vtables of `#[sabi_trait]` supertraits are stored in a prefix type
+before the methods in the vtable of the trait,
+which allows converting a `Sub_TO` into a `Super_TO` with
+[`CrossCast::sabi_cross_cast`](crate::sabi_trait::CrossCast),
+returning an error if the vtable of `Super` is not available.
+
+The vtable of a supertrait is only unavailable if the trait object was constructed
+in a dynamic library that was compiled with a version of the trait
+that didn't have it as a supertrait.
+Adding the first `#[sabi_trait]` supertrait to a trait is a breaking change,
+while adding the others (at the end of the list) isn't.
+
+To convert a trait object into the trait object of a trait that isn't a supertrait,
+you can use [`register_cross_cast`](crate::sabi_trait::register_cross_cast)
+along with the `sabi_cross_cast_into` method.
+
+`Sub_TO` only implements `Sub` if `Super` is implemented for it,
+which the `#[sabi_trait]` attribute doesn't do.
+
+Example:
+
+```rust
+use abi_stable::{
+    sabi_trait,
+    sabi_trait::CrossCast,
+    std_types::RBox,
+    type_level::downcasting::TD_Opaque,
+};
+
+#[sabi_trait]
+pub trait Shape {
+    fn area(&self) -> u32;
+}
+
+#[sabi_trait]
+#[sabi(sabi_supertraits(Shape))]
+pub trait Square: Shape {
+    fn side(&self) -> u32;
+}
+
+struct Tile(u32);
+
+impl Shape for Tile {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+impl Square for Tile {
+    fn side(&self) -> u32 {
+        self.0
+    }
+}
+
+# fn main(){
+let square: Square_TO<'static, RBox<()>> = Square_TO::from_value(Tile(3), TD_Opaque);
+assert_eq!(square.side(), 3);
+
+let shape: Shape_TO<'static, RBox<()>> = square.sabi_cross_cast().unwrap();
+assert_eq!(shape.area(), 9);
+# }
+```
 
 ### Supertrait Extensibility
 
@@ -102,6 +172,10 @@ These are the items reexported from the module:
 - [`Trait_CTO`](#trait_cto): 
 A type alias for the trait object which is constructible in constants.
 
+- [`Trait_TypeVTable`](#trait_typevtable): 
+The static methods of a type that implements the trait,
+only generated if the trait has [static methods](#static-methods).
+
 
 ### `Trait_TO` 
 
@@ -114,6 +188,9 @@ The ffi-safe trait object.
 only requiring the wrapped pointer to implement the trait in the individual methods
 (instead of putting those bounds in the impl block itself).
 
+`Trait_TO` also has a method for each [associated constant](#associated-constants)
+of the trait, with the same name as the constant.
+
 <br>
 
 This only implements `Trait` if all the methods are callable,
@@ -188,6 +265,20 @@ then it doesn't have this lifetime parameter.
 
 Example: `Trait_CTO<'lt, 'r, u8, u64, 10, AssocFoo>`
 
+###  Trait_TypeVTable
+
+The ffi-safe vtable of the [static methods](#static-methods) of a type,
+only generated if the trait has static methods.
+
+It has inherent method equivalents of the static methods of the trait,
+static methods that return `Self` return a `Trait_TO<'lt, RBox<()>, …>` instead.
+
+It can be constructed with `Trait_TypeVTable::from_type::<Type>()`,
+or gotten from a trait object with its `sabi_type_vtable` method.
+
+Trait_TypeVTable has the same generic parameters as `Trait_TO`,
+except for the `Pointer` type parameter.
+
 ###  Trait 
 
 The trait is defined similarly to how it is before being transformed by the 
@@ -249,12 +340,164 @@ The only valid way to refer to associated types in the trait declaration is with
 Associated types in the trait object are transformed into type parameters 
 that come after those of the trait.
 
+# Associated constants
+
+Associated constants are stored in the vtable of the trait object,
+and are read with the method of the same name on `Trait_TO`,
+eg: `codec.NAME()` for a `const NAME: RStr<'static>;` constant.
+
+The type of associated constants must implement `StableAbi`,
+and can refer to associated types with the `Self::AssocType` syntax.
+
+`<Trait_TO<…> as Trait>::CONSTANT` causes a compile-time error,
+because the value of the constant is only known at runtime.
+This is a post-monomorphization error,
+which `cargo check` doesn't report,
+and is only reported by `cargo build` if the code that uses the constant is compiled
+(it's not reported for generic functions that are never called with `Trait_TO`).
+
+The constants are stored in a [prefix type](./docs/prefix_types/index.html),
+so that constants can be added at the end in minor versions,
+reading a constant that doesn't exist in the vtable (because it comes from a 
+previous version of the library) panics.
+The constants are stored after the methods and the [`Trait_TypeVTable`](#trait_typevtable)
+in the vtable of the trait object.
+Adding the first associated constant to a trait is a breaking change.
+
+Example:
+
+```rust
+use abi_stable::{sabi_trait, std_types::RStr};
+
+#[sabi_trait]
+pub trait Codec {
+    const NAME: RStr<'static>;
+
+    const VERSION: u32 = 1;
+
+    fn encode(&self, x: u32) -> u32;
+}
+
+struct Double;
+
+impl Codec for Double {
+    const NAME: RStr<'static> = RStr::from_str("double");
+
+    fn encode(&self, x: u32) -> u32 {
+        x * 2
+    }
+}
+
+# fn main(){
+use abi_stable::{std_types::RBox, type_level::downcasting::TD_Opaque};
+
+let codec: Codec_TO<'static, RBox<()>> = Codec_TO::from_value(Double, TD_Opaque);
+
+assert_eq!(codec.NAME(), "double");
+assert_eq!(codec.VERSION(), 1);
+assert_eq!(codec.encode(3), 6);
+# }
+```
+
+# Static methods
+
+Methods without a `self` parameter must have a `where Self: Sized` bound,
+they are stored in the [`Trait_TypeVTable`](#trait_typevtable) of the type,
+which is accessible from the trait object with its `sabi_type_vtable` method.
+
+Static methods can return `Self`,
+which is converted into a `Trait_TO<'lt, RBox<()>, …>` in the `Trait_TypeVTable`.
+
+Static methods can't have type parameters.
+
+`Trait_TO` doesn't implement `Trait` if any static method lacks a default implementation,
+because static methods can't be called on the trait object,
+the inherent methods of `Trait_TO` can be used instead.
+
+The `Trait_TypeVTable` is stored after the methods in the vtable of the trait object,
+so adding the first static method doesn't move the methods.
+
+Example:
+
+```compile_fail
+use abi_stable::{sabi_trait, std_types::{RBox, RString}, type_level::downcasting::TD_Opaque};
+
+#[sabi_trait]
+pub trait Plugin {
+    fn create(name: RString) -> Self
+    where
+        Self: Sized;
+
+    fn name(&self) -> RString;
+}
+
+impl Plugin for RString {
+    fn create(name: RString) -> Self {
+        name
+    }
+
+    fn name(&self) -> RString {
+        self.clone()
+    }
+}
+
+type PluginObject = Plugin_TO<'static, RBox<()>>;
+
+# fn main(){
+let object: PluginObject = Plugin_TO::from_value(RString::from("hello"), TD_Opaque);
+assert_eq!(object.name(), "hello");
+assert_eq!(object.sabi_type_vtable().create("world".into()).name(), "world");
+
+// `Plugin_TO` doesn't implement `Plugin`, because `create` can't be called on it.
+let _ = <PluginObject as Plugin>::create("world".into());
+# }
+```
+
+# Generic methods
+
+Methods can have a single type parameter if they're annotated with
+`#[sabi(instantiate(T = Type0, T = Type1))]`,
+which lists the types that the method can be called with through the trait object.
+
+The trait object stores one function pointer for each listed type,
+and only allows calling the method with the listed types.
+
+Adding, removing, or reordering the listed types is a breaking change.
+
+Example:
+
+```rust
+use abi_stable::{sabi_trait, std_types::RString};
+
+#[sabi_trait]
+pub trait Getter {
+    #[sabi(instantiate(T = u32, T = RString))]
+    fn get<T: Default>(&self) -> T;
+}
+
+impl Getter for () {
+    fn get<T: Default>(&self) -> T {
+        T::default()
+    }
+}
+
+# fn main(){
+use abi_stable::{std_types::RBox, type_level::downcasting::TD_Opaque};
+
+let object: Getter_TO<'static, RBox<()>> = Getter_TO::from_value((), TD_Opaque);
+
+assert_eq!(object.get::<u32>(), 0);
+assert_eq!(object.get::<RString>(), "");
+# }
+```
+
 # Object safety
 
 Trait objects generated using this attribute have similar restrictions to built-in trait objects: 
 
 - `Self` can only be used to access associated types 
-    (using the `Self::AssocType` syntax).
+    (using the `Self::AssocType` syntax),
+    and as the return type of [static methods](#static-methods).
 
 - `self` is a valid method receiver,
     this requires that the pointer that the generated trait object wraps 
@@ -222,6 +222,25 @@ and won't be usable afterwards.
 
 - `Error`: corresponds to `std::error::Error` .
 
+- `Future`: corresponds to `std::future::Future`, implies `Unpin`,
+    this type will also have to implement [`abi_stable::erased_types::FutureOutput`].
+
+- `AsRefStr`: corresponds to `AsRef<str>` .
+
+- `AsRefBytes`: corresponds to `AsRef<[u8]>` .
+
+- `BorrowStr`: corresponds to `std::borrow::Borrow<str>` .
+
+- `Deref`: corresponds to `std::ops::Deref`,
+    this type will also have to implement [`abi_stable::erased_types::DerefTarget`].
+
+- `FnOnce`: corresponds to `std::ops::FnOnce`,
+    this type will also have to implement [`abi_stable::erased_types::FnSignature`].
+
+- `FnMut`: corresponds to `std::ops::FnMut`, implies `FnOnce`.
+
+- `Fn`: corresponds to `std::ops::Fn`, implies `FnMut` and `FnOnce`.
+
 <br>
 Examples:
 
@@ -15,7 +15,7 @@ pub mod export_module;
 ///
 /// Module reflection only allows accessing public fields.
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi, Deserialize, Serialize)]
 pub enum ModReflMode {
     /// For modules that are reflected on at runtime.
     ///
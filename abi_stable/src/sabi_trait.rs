@@ -17,18 +17,25 @@ pub mod reexports {
             },
             extern_fn_panic_handling,
             marker_type::{
-                NonOwningPhantom, SyncSend, SyncUnsend, UnsafeIgnoredType, UnsyncSend, UnsyncUnsend,
+                ErasedPrefix, NonOwningPhantom, SyncSend, SyncUnsend, UnsafeIgnoredType,
+                UnsyncSend, UnsyncUnsend,
             },
             pointer_trait::{AsMutPtr, AsPtr, CanTransmuteElement, OwnedPointer, TransmuteElement},
             prefix_type::{PrefixRef, PrefixTypeTrait, WithMetadata},
             sabi_trait::{
+                cross_cast::{CrossCast, CrossCastError, CrossCastTarget},
                 robject::RObject,
-                vtable::{GetRObjectVTable, RObjectVtable, RObjectVtable_Ref},
+                vtable::{
+                    GetRObjectVTable, MakeTraitObjectVTable, RObjectVtable, RObjectVtable_Ref,
+                    TraitObjectVTable,
+                },
             },
             sabi_types::{MovePtr, RMut, RRef},
             std_types::RBox,
             traits::IntoInner,
+            type_level::downcasting::TD_Opaque,
             utils::take_manuallydrop,
+            StableAbi,
         };
 
         pub use core_extensions::{utils::transmute_ignore_size, TypeIdentity};
@@ -44,16 +51,18 @@ pub mod reexports {
 
 /// A prelude for modules using `#[sabi_trait]` generated traits/trait objects.
 pub mod prelude {
-    pub use crate::type_level::downcasting::{TD_CanDowncast, TD_Opaque};
+    pub use crate::type_level::downcasting::{TD_CanDowncast, TD_Opaque, TD_StableDowncast};
 }
 
-pub use crate::type_level::downcasting::{TD_CanDowncast, TD_Opaque};
+pub use crate::type_level::downcasting::{TD_CanDowncast, TD_Opaque, TD_StableDowncast};
 
 #[cfg(any(test, feature = "sabi_trait_examples"))]
 pub mod examples;
 
 pub mod doc_examples;
 
+mod cross_cast;
+
 mod robject;
 
 #[doc(hidden)]
@@ -72,6 +81,12 @@ use std::{
 
 use self::reexports::__sabi_re::*;
 
-pub use self::robject::{RObject, ReborrowBounds, UneraseError};
+pub use self::{
+    cross_cast::{
+        register_cross_cast, register_stable_cross_cast, CrossCast, CrossCastError,
+        CrossCastTarget,
+    },
+    robject::{RObject, ReborrowBounds, UneraseError},
+};
 
 use crate::{erased_types::c_functions, marker_type::ErasedObject, sabi_types::MaybeCmp};
@@ -0,0 +1,425 @@
+use std::fmt::{self, Debug, Display};
+
+use crate::{
+    external_types::RRwLock,
+    globals,
+    marker_type::ErasedPrefix,
+    pointer_trait::{AsPtr, CanTransmuteElement},
+    prefix_type::PrefixRef,
+    sabi_trait::{robject::RObject, vtable::MakeTraitObjectVTable},
+    sabi_types::MaybeCmp,
+    std_types::{ROption, UTypeId},
+    type_layout::TypeLayout,
+    type_level::downcasting::{TD_CanDowncast, TD_StableDowncast},
+    StableAbi,
+};
+
+/// For fallibly converting a `#[sabi_trait]` generated trait object into
+/// the trait object of one of its `#[sabi_trait]` supertraits.
+///
+/// This is implemented by the `#[sabi_trait]` attribute,
+/// for converting `Trait_TO` into the `Trait_TO` of every `#[sabi_trait]` supertrait.
+///
+/// The conversion checks at runtime that the vtable of the trait object contains the vtable
+/// for the other trait,
+/// which it doesn't if the trait object was constructed in a dynamic library
+/// compiled with a version of the trait that didn't have it as a supertrait.
+///
+/// To convert a trait object into the trait object of a trait that isn't a supertrait,
+/// you can use [`register_cross_cast`] and the `sabi_cross_cast_into` method
+/// of `#[sabi_trait]` generated trait objects.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::{
+///     sabi_trait,
+///     sabi_trait::{prelude::TD_Opaque, CrossCast},
+///     std_types::RBox,
+/// };
+///
+/// #[sabi_trait]
+/// pub trait Named {
+///     fn name(&self) -> u32;
+/// }
+///
+/// #[sabi_trait]
+/// #[sabi(sabi_supertraits(Named))]
+/// pub trait Counter: Named {
+///     fn count(&self) -> u32;
+/// }
+///
+/// struct Hello;
+///
+/// impl Named for Hello {
+///     fn name(&self) -> u32 {
+///         3
+///     }
+/// }
+///
+/// impl Counter for Hello {
+///     fn count(&self) -> u32 {
+///         5
+///     }
+/// }
+///
+/// # fn main(){
+/// let counter: Counter_TO<'_, RBox<()>> = Counter_TO::from_value(Hello, TD_Opaque);
+/// assert_eq!(counter.count(), 5);
+///
+/// let named: Named_TO<'_, RBox<()>> = counter.sabi_cross_cast().unwrap();
+/// assert_eq!(named.name(), 3);
+/// # }
+/// ```
+pub trait CrossCast<To>: Sized {
+    /// Converts this trait object into `To`,
+    /// returning an error with `self` if the erased type
+    /// isn't known to implement the trait of `To`.
+    fn sabi_cross_cast(self) -> Result<To, CrossCastError<Self>>;
+}
+
+/// Error for a `#[sabi_trait]` generated trait object that couldn't be converted
+/// into the trait object of another trait,
+/// with [`CrossCast::sabi_cross_cast`] or the `sabi_cross_cast_into` method.
+#[derive(Copy, Clone)]
+pub struct CrossCastError<T> {
+    object: T,
+    to_trait: &'static str,
+    kind: CrossCastErrorKind,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum CrossCastErrorKind {
+    MissingSupertrait,
+    Unregistered,
+}
+
+impl<T> CrossCastError<T> {
+    #[doc(hidden)]
+    pub const fn new(object: T, to_trait: &'static str) -> Self {
+        Self {
+            object,
+            to_trait,
+            kind: CrossCastErrorKind::MissingSupertrait,
+        }
+    }
+
+    pub(crate) const fn unregistered(object: T, to_trait: &'static str) -> Self {
+        Self {
+            object,
+            to_trait,
+            kind: CrossCastErrorKind::Unregistered,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn map_object<U, F>(self, f: F) -> CrossCastError<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        CrossCastError {
+            object: f(self.object),
+            to_trait: self.to_trait,
+            kind: self.kind,
+        }
+    }
+
+    /// The name of the trait that the trait object couldn't be converted into.
+    pub const fn to_trait(&self) -> &'static str {
+        self.to_trait
+    }
+
+    /// Extracts the trait object, to handle the failure to convert it.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.object
+    }
+}
+
+impl<T> Debug for CrossCastError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CrossCastError")
+            .field("object", &"<not shown>")
+            .field("to_trait", &self.to_trait)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+
+impl<T> Display for CrossCastError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            CrossCastErrorKind::MissingSupertrait => write!(
+                f,
+                "The trait object doesn't have the vtable for the `{}` trait,\n\
+                 it was probably constructed in a dynamic library compiled with \
+                 a version of the trait that didn't have it as a supertrait.",
+                self.to_trait,
+            ),
+            CrossCastErrorKind::Unregistered => write!(
+                f,
+                "The erased type of the trait object wasn't registered as implementing \
+                 the `{}` trait,\n\
+                 either the trait object wasn't constructed with `TD_CanDowncast` or \
+                 `TD_StableDowncast`,\
+                 or the `register_cross_cast` (for `TD_CanDowncast`) or \
+                 `register_stable_cross_cast` (for `TD_StableDowncast`) function \
+                 wasn't called for the type,\
+                 in the dynamic library/executable that constructed the trait object.",
+                self.to_trait,
+            ),
+        }
+    }
+}
+
+impl<T> ::std::error::Error for CrossCastError<T> {}
+
+//////////////////////////////////////////////////////////////////////////////
+
+/// A `#[sabi_trait]` generated trait object that other trait objects can be converted into
+/// with [`register_cross_cast`] and the `sabi_cross_cast_into` method.
+///
+/// This is implemented by the `#[sabi_trait]` attribute for the `Trait_TO` of traits
+/// that don't have the `#[sabi(use_dyntrait)]` attribute nor associated types.
+///
+/// # Safety
+///
+/// This trait must only be implemented by the `#[sabi_trait]` attribute.
+pub unsafe trait CrossCastTarget<'lt>: StableAbi + Sized {
+    /// The pointer type of the trait object,with its pointee type erased.
+    type ErasedPtr: AsPtr<PtrTarget = ()>;
+
+    /// The name of the trait.
+    const TRAIT_NAME: &'static str;
+
+    /// Constructs this trait object from an `RObject` and a vtable for this trait object.
+    ///
+    /// # Safety
+    ///
+    /// `vtable` must be the vtable of this trait object,
+    /// constructed for the same type, pointer, and downcasting behavior as
+    /// the vtable of `obj`.
+    #[doc(hidden)]
+    unsafe fn from_erased_vtable<I, V>(
+        obj: RObject<'lt, Self::ErasedPtr, I, V>,
+        vtable: PrefixRef<ErasedPrefix>,
+    ) -> Self;
+}
+
+/// Registers `T` as implementing the trait of the `To` trait object,
+/// allowing trait objects of other `#[sabi_trait]` traits that wrap a `T`
+/// to be converted into `To` with their `sabi_cross_cast_into` method.
+///
+/// The conversion only succeeds if the trait object being converted
+/// was constructed with `TD_CanDowncast`,
+/// in the same dynamic library/executable that this function was called in.
+/// Trait objects constructed with `TD_StableDowncast` require
+/// [`register_stable_cross_cast`] instead,
+/// so that the converted trait object can also be downcasted with
+/// the `stable_downcast_*` methods.
+///
+/// The registration is specific to the pointer type of `To`,
+/// so converting an `RBox` based trait object and
+/// an `RRef` based one requires registering both.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::{
+///     sabi_trait,
+///     sabi_trait::{prelude::TD_CanDowncast, register_cross_cast},
+///     std_types::RBox,
+/// };
+///
+/// #[sabi_trait]
+/// pub trait Named {
+///     fn name(&self) -> u32;
+/// }
+///
+/// #[sabi_trait]
+/// pub trait Counter {
+///     fn count(&self) -> u32;
+/// }
+///
+/// struct Hello;
+///
+/// impl Named for Hello {
+///     fn name(&self) -> u32 {
+///         3
+///     }
+/// }
+///
+/// impl Counter for Hello {
+///     fn count(&self) -> u32 {
+///         5
+///     }
+/// }
+///
+/// # fn main(){
+/// register_cross_cast::<Hello, Named_TO<'static, RBox<()>>>();
+///
+/// let counter: Counter_TO<'static, RBox<()>> = Counter_TO::from_value(Hello, TD_CanDowncast);
+///
+/// let named: Named_TO<'static, RBox<()>> = counter.sabi_cross_cast_into().unwrap();
+/// assert_eq!(named.name(), 3);
+///
+/// // The erased type of this trait object isn't registered as implementing `Named`.
+/// let counter: Counter_TO<'static, RBox<()>> = Counter_TO::from_value(8u32, TD_CanDowncast);
+/// assert!(counter.sabi_cross_cast_into::<Named_TO<'static, RBox<()>>>().is_err());
+/// # }
+///
+/// # impl Counter for u32 {
+/// #     fn count(&self) -> u32 {
+/// #         *self
+/// #     }
+/// # }
+/// ```
+pub fn register_cross_cast<'lt, T, To>()
+where
+    T: 'static,
+    To: CrossCastTarget<'lt>,
+    To::ErasedPtr: CanTransmuteElement<T>,
+    To: MakeTraitObjectVTable<
+        TD_CanDowncast,
+        T,
+        To::ErasedPtr,
+        <To::ErasedPtr as CanTransmuteElement<T>>::TransmutedPtr,
+    >,
+{
+    register_vtable(CrossCastEntry {
+        type_id: UTypeId::new::<T>(),
+        stable: false,
+        to_layout: To::LAYOUT,
+        vtable: <To as MakeTraitObjectVTable<TD_CanDowncast, T, _, _>>::VTABLE,
+    });
+}
+
+/// Registers `T` as implementing the trait of the `To` trait object,
+/// for trait objects constructed with `TD_StableDowncast`.
+///
+/// This is the equivalent of [`register_cross_cast`] for trait objects that
+/// were constructed with `TD_StableDowncast`,
+/// the converted trait object is constructed with `TD_StableDowncast` as well,
+/// so that it can still be downcasted with the `stable_downcast_*` methods.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::{
+///     sabi_trait,
+///     sabi_trait::{prelude::TD_StableDowncast, register_stable_cross_cast},
+///     std_types::RBox,
+/// };
+///
+/// #[sabi_trait]
+/// pub trait Named {
+///     fn name(&self) -> u32;
+/// }
+///
+/// #[sabi_trait]
+/// pub trait Counter {
+///     fn count(&self) -> u32;
+/// }
+///
+/// impl Named for u32 {
+///     fn name(&self) -> u32 {
+///         *self + 1
+///     }
+/// }
+///
+/// impl Counter for u32 {
+///     fn count(&self) -> u32 {
+///         *self
+///     }
+/// }
+///
+/// # fn main(){
+/// register_stable_cross_cast::<u32, Named_TO<'static, RBox<()>>>();
+///
+/// let counter: Counter_TO<'static, RBox<()>> = Counter_TO::from_value(3, TD_StableDowncast);
+///
+/// let named: Named_TO<'static, RBox<()>> = counter.sabi_cross_cast_into().unwrap();
+/// assert_eq!(named.name(), 4);
+/// assert_eq!(named.obj.stable_downcast_as::<u32>().unwrap(), &3);
+/// # }
+/// ```
+pub fn register_stable_cross_cast<'lt, T, To>()
+where
+    T: StableAbi + 'static,
+    To: CrossCastTarget<'lt>,
+    To::ErasedPtr: CanTransmuteElement<T>,
+    To: MakeTraitObjectVTable<
+        TD_StableDowncast,
+        T,
+        To::ErasedPtr,
+        <To::ErasedPtr as CanTransmuteElement<T>>::TransmutedPtr,
+    >,
+{
+    register_vtable(CrossCastEntry {
+        type_id: UTypeId::new::<T>(),
+        stable: true,
+        to_layout: To::LAYOUT,
+        vtable: <To as MakeTraitObjectVTable<TD_StableDowncast, T, _, _>>::VTABLE,
+    });
+}
+
+fn register_vtable(new_entry: CrossCastEntry) {
+    let mut registry = CROSS_CAST_REGISTRY.write();
+
+    let already_registered = registry.iter().any(|entry| {
+        entry.type_id == new_entry.type_id
+            && entry.stable == new_entry.stable
+            && std::ptr::eq(entry.to_layout, new_entry.to_layout)
+    });
+
+    if !already_registered {
+        registry.push(new_entry);
+    }
+}
+
+struct CrossCastEntry {
+    type_id: UTypeId,
+    /// Whether the vtable was constructed with `TD_StableDowncast`.
+    stable: bool,
+    to_layout: &'static TypeLayout,
+    vtable: PrefixRef<ErasedPrefix>,
+}
+
+static CROSS_CAST_REGISTRY: RRwLock<Vec<CrossCastEntry>> = RRwLock::new(Vec::new());
+
+/// Finds the vtable registered with [`register_cross_cast`] or [`register_stable_cross_cast`]
+/// (depending on whether `stable` is true)
+/// in this dynamic library/executable,
+/// for the type with the `type_id` id, and the trait object with the `to_layout` layout.
+///
+/// This is stored in the vtable of `#[sabi_trait]` generated trait objects,
+/// so that the lookup happens in the dynamic library/executable that
+/// constructed the trait object.
+pub(crate) extern "C" fn find_cross_cast_vtable(
+    type_id: MaybeCmp<UTypeId>,
+    stable: bool,
+    to_layout: &'static TypeLayout,
+) -> ROption<PrefixRef<ErasedPrefix>> {
+    extern_fn_panic_handling! {
+        let type_id = match type_id {
+            MaybeCmp::Just(x) => x,
+            _ => return ROption::RNone,
+        };
+
+        CROSS_CAST_REGISTRY
+            .read()
+            .iter()
+            .find(|entry| {
+                entry.type_id == type_id
+                    && entry.stable == stable
+                    && (std::ptr::eq(entry.to_layout, to_layout)
+                        || (globals::initialized_globals().layout_checking)(
+                            to_layout,
+                            entry.to_layout,
+                        )
+                        .is_ok())
+            })
+            .map(|entry| entry.vtable)
+            .into()
+    }
+}
@@ -7,14 +7,20 @@ use core_extensions::SelfOps;
 
 use crate::{
     abi_stability::PrefixStableAbi,
-    erased_types::{c_functions::adapt_std_fmt, InterfaceType, MakeRequiredTraits},
+    erased_types::{
+        c_functions::adapt_std_fmt, InterfaceQueryableFrom, InterfaceType, MakeRequiredTraits,
+    },
     pointer_trait::{
         AsMutPtr, AsPtr, CanTransmuteElement, GetPointerKind, PK_Reference, PK_SmartPointer,
         PointerKind, TransmuteElement,
     },
-    sabi_trait::vtable::{BaseVtable_Prefix, BaseVtable_Ref},
+    sabi_trait::{
+        cross_cast::{CrossCastError, CrossCastTarget},
+        vtable::{BaseVtable_Prefix, BaseVtable_Ref},
+    },
     sabi_types::{MaybeCmp, RMut, RRef},
     std_types::UTypeId,
+    type_layout::stable_type_id::is_same_stable_type,
     type_level::{
         impl_enum::{Implemented, Unimplemented},
         trait_marker,
@@ -336,6 +342,23 @@ where
         }
     }
 
+    /// Like `sabi_check_same_utypeid`,
+    /// but also accepts a `T` from another library if the vtable stores its layout
+    /// (because the trait object was constructed with `TD_StableDowncast`),
+    /// and it's the same type with a compatible layout.
+    fn sabi_check_same_stable_type<T>(&self) -> Result<(), UneraseError<()>>
+    where
+        T: StableAbi + 'static,
+    {
+        match (
+            self.sabi_check_same_utypeid::<T>(),
+            self.sabi_robject_vtable()._sabi_stable_layout(),
+        ) {
+            (Err(_), Some(found)) if is_same_stable_type(T::LAYOUT, found) => Ok(()),
+            (res, _) => res,
+        }
+    }
+
     /// Attempts to unerase this trait object into the pointer it was constructed with.
     ///
     /// # Errors
@@ -494,6 +517,152 @@ where
         unsafe { Ok(&mut *(self.ptr.as_mut_ptr() as *mut T)) }
     }
 
+    /// Attempts to unerase this trait object into the pointer it was constructed with,
+    /// even if it was constructed in a different dynamic library.
+    ///
+    /// This requires the trait object to be constructed with [`TD_StableDowncast`],
+    /// to unerase it outside of the dynamic library/binary that constructed it.
+    /// In that case,
+    /// the type is compared using the [`StableTypeId`] of `T`
+    /// and its layout in both dynamic libraries is checked to be compatible.
+    ///
+    /// # Errors
+    ///
+    /// This will return an error in any of these conditions:
+    ///
+    /// - It is called in a dynamic library/binary outside
+    /// the one from which this RObject was constructed,
+    /// and the trait object wasn't constructed with `TD_StableDowncast`.
+    ///
+    /// - The trait object wrapping this `RObject` was constructed with a
+    /// `TD_Opaque` argument.
+    ///
+    /// - `T` is not the concrete type this `RObject<_>` was constructed with,
+    /// or its layout is incompatible with the one in the dynamic library that constructed it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use abi_stable::{
+    ///     sabi_trait::doc_examples::Doer_TO, std_types::RBox,
+    ///     type_level::downcasting::TD_StableDowncast,
+    /// };
+    ///
+    /// let to = || Doer_TO::from_value(5usize, TD_StableDowncast);
+    ///
+    /// // `to.obj` is an RObject
+    /// assert_eq!(
+    ///     to().obj.stable_downcast_into::<usize>().ok(),
+    ///     Some(RBox::new(5usize))
+    /// );
+    /// assert_eq!(to().obj.stable_downcast_into::<u8>().ok(), None);
+    ///
+    /// ```
+    ///
+    /// [`TD_StableDowncast`]: crate::type_level::downcasting::TD_StableDowncast
+    /// [`StableTypeId`]: crate::type_layout::StableTypeId
+    pub fn stable_downcast_into<T>(self) -> Result<P::TransmutedPtr, UneraseError<Self>>
+    where
+        T: StableAbi + 'static,
+        P: AsPtr<PtrTarget = ()> + CanTransmuteElement<T>,
+    {
+        check_unerased!(self, self.sabi_check_same_stable_type::<T>());
+        unsafe {
+            let this = ManuallyDrop::new(self);
+            Ok(ptr::read(&*this.ptr).transmute_element::<T>())
+        }
+    }
+
+    /// Attempts to unerase this trait object into a reference of
+    /// the value was constructed with,
+    /// even if it was constructed in a different dynamic library.
+    ///
+    /// For details on how this differs from `downcast_as`,
+    /// look at [`stable_downcast_into`](#method.stable_downcast_into).
+    ///
+    /// # Errors
+    ///
+    /// This will return an error in any of these conditions:
+    ///
+    /// - It is called in a dynamic library/binary outside
+    /// the one from which this RObject was constructed,
+    /// and the trait object wasn't constructed with `TD_StableDowncast`.
+    ///
+    /// - The trait object wrapping this `RObject` was constructed with a
+    /// `TD_Opaque` argument.
+    ///
+    /// - `T` is not the concrete type this `RObject<_>` was constructed with,
+    /// or its layout is incompatible with the one in the dynamic library that constructed it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use abi_stable::{
+    ///     sabi_trait::doc_examples::Doer_TO, std_types::RArc,
+    ///     type_level::downcasting::TD_StableDowncast,
+    /// };
+    ///
+    /// let to: Doer_TO<'_, RArc<()>> = Doer_TO::from_ptr(RArc::new(8usize), TD_StableDowncast);
+    ///
+    /// // `to.obj` is an RObject
+    /// assert_eq!(to.obj.stable_downcast_as::<usize>().ok(), Some(&8usize));
+    /// assert_eq!(to.obj.stable_downcast_as::<u8>().ok(), None);
+    ///
+    /// ```
+    pub fn stable_downcast_as<T>(&self) -> Result<&T, UneraseError<&Self>>
+    where
+        T: StableAbi + 'static,
+        P: AsPtr<PtrTarget = ()> + CanTransmuteElement<T>,
+    {
+        check_unerased!(self, self.sabi_check_same_stable_type::<T>());
+        unsafe { Ok(&*(self.ptr.as_ptr() as *const T)) }
+    }
+
+    /// Attempts to unerase this trait object into a mutable reference of
+    /// the value was constructed with,
+    /// even if it was constructed in a different dynamic library.
+    ///
+    /// For details on how this differs from `downcast_as_mut`,
+    /// look at [`stable_downcast_into`](#method.stable_downcast_into).
+    ///
+    /// # Errors
+    ///
+    /// This will return an error in any of these conditions:
+    ///
+    /// - It is called in a dynamic library/binary outside
+    /// the one from which this RObject was constructed,
+    /// and the trait object wasn't constructed with `TD_StableDowncast`.
+    ///
+    /// - The trait object wrapping this `RObject` was constructed with a
+    /// `TD_Opaque` argument.
+    ///
+    /// - `T` is not the concrete type this `RObject<_>` was constructed with,
+    /// or its layout is incompatible with the one in the dynamic library that constructed it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use abi_stable::{
+    ///     sabi_trait::doc_examples::Doer_TO, std_types::RBox,
+    ///     type_level::downcasting::TD_StableDowncast,
+    /// };
+    ///
+    /// let mut to: Doer_TO<'_, RBox<()>> = Doer_TO::from_value(34usize, TD_StableDowncast);
+    ///
+    /// // `to.obj` is an RObject
+    /// assert_eq!(to.obj.stable_downcast_as_mut::<usize>().ok(), Some(&mut 34usize));
+    /// assert_eq!(to.obj.stable_downcast_as_mut::<u8>().ok(), None);
+    ///
+    /// ```
+    pub fn stable_downcast_as_mut<T>(&mut self) -> Result<&mut T, UneraseError<&mut Self>>
+    where
+        T: StableAbi + 'static,
+        P: AsMutPtr<PtrTarget = ()> + CanTransmuteElement<T>,
+    {
+        check_unerased!(self, self.sabi_check_same_stable_type::<T>());
+        unsafe { Ok(&mut *(self.ptr.as_mut_ptr() as *mut T)) }
+    }
+
     /// Unwraps the `RObject<_>` into a pointer to T,
     /// without checking whether `T` is the type that the RObject was constructed with.
     ///
@@ -744,6 +913,76 @@ where
             _marker: PhantomData,
         }
     }
+
+    /// Whether the vtable of this `RObject` has the functions for
+    /// all the traits that `I2` requires.
+    ///
+    /// Of the traits that `RObject` can implement,
+    /// only `Clone`, `Debug`, and `Display` are checked at runtime,
+    /// the rest must be required by `I` to be required by `I2`.
+    ///
+    /// For an example, look at [`query_interface`](#method.query_interface).
+    pub fn supports_interface<I2>(&self) -> bool
+    where
+        I2: InterfaceQueryableFrom<'lt, I>,
+    {
+        self.sabi_robject_vtable().has_fields_for::<I2>()
+    }
+
+    /// Converts this `RObject` into one whose `InterfaceType` is `I2`,
+    /// returning `None` if the vtable doesn't have the functions for
+    /// all the traits that `I2` requires.
+    ///
+    /// This is useful when the trait object was constructed in a dynamic library
+    /// compiled against a version of the `#[sabi_trait]` trait with fewer supertraits.
+    ///
+    /// To query the traits without consuming an `RObject` that owns its value,
+    /// you can call this on the `RObject` returned by
+    /// [`reborrow`](#method.reborrow) or [`reborrow_mut`](#method.reborrow_mut).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use abi_stable::{
+    ///     sabi_trait::{doc_examples::Doer_TO, TD_Opaque},
+    ///     std_types::RBox,
+    ///     StableAbi,
+    /// };
+    ///
+    /// #[repr(C)]
+    /// #[derive(StableAbi)]
+    /// #[sabi(impl_InterfaceType(Debug))]
+    /// pub struct DebugInterface;
+    ///
+    /// #[repr(C)]
+    /// #[derive(StableAbi)]
+    /// #[sabi(impl_InterfaceType(Debug, Display))]
+    /// pub struct DebugDisplayInterface;
+    ///
+    /// let to: Doer_TO<'_, RBox<()>> = Doer_TO::from_value(13usize, TD_Opaque);
+    ///
+    /// // `to.obj` is an RObject,
+    /// // whose vtable doesn't have `Display` because `Doer` doesn't have it as a supertrait.
+    /// assert!(!to.obj.supports_interface::<DebugDisplayInterface>());
+    /// assert!(to.obj.reborrow().query_interface::<DebugDisplayInterface>().is_none());
+    ///
+    /// let obj = to.obj.query_interface::<DebugInterface>().unwrap();
+    /// assert_eq!(format!("{:?}", obj), "13");
+    ///
+    /// ```
+    pub fn query_interface<I2>(self) -> Option<RObject<'lt, P, I2, V>>
+    where
+        I2: InterfaceQueryableFrom<'lt, I>,
+    {
+        if self.supports_interface::<I2>() {
+            let vtable = self.vtable;
+            // The vtable has all the functions that `I2` requires,
+            // which was checked at runtime above.
+            unsafe { Some(self.sabi_replace_vtable(vtable)) }
+        } else {
+            None
+        }
+    }
 }
 
 impl<'lt, P, I, V> RObject<'lt, P, I, V>
@@ -762,12 +1001,98 @@ where
         unsafe { BaseVtable_Ref(self.vtable.cast::<BaseVtable_Prefix<(), P, I>>())._sabi_vtable() }
     }
 
+    /// Converts this `RObject` into the `#[sabi_trait]` generated trait object `To`,
+    /// if the erased type was registered as implementing the trait of `To`
+    /// with [`register_cross_cast`](crate::sabi_trait::register_cross_cast)
+    /// (or [`register_stable_cross_cast`](crate::sabi_trait::register_stable_cross_cast)
+    /// if the trait object was constructed with `TD_StableDowncast`).
+    ///
+    /// `#[sabi_trait]` generated trait objects have a `sabi_cross_cast_into` method
+    /// that calls this.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error in any of these conditions:
+    ///
+    /// - The trait object wrapping this `RObject` was constructed with a
+    ///   `TD_Opaque` argument.
+    ///
+    /// - The erased type wasn't registered as implementing the trait of `To`,
+    ///   for the pointer type and downcasting behavior of this `RObject`,
+    ///   in the dynamic library/executable that constructed the trait object.
+    ///
+    /// - The trait object was constructed in a dynamic library that uses a version of
+    ///   `abi_stable` from before cross-casting was supported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use abi_stable::{
+    ///     sabi_trait::{
+    ///         doc_examples::{Action_TO, Doer_TO},
+    ///         register_cross_cast, TD_CanDowncast,
+    ///     },
+    ///     std_types::RBox,
+    /// };
+    ///
+    /// register_cross_cast::<usize, Action_TO<'static, RBox<()>>>();
+    ///
+    /// let to: Doer_TO<'static, RBox<()>> = Doer_TO::from_value(13usize, TD_CanDowncast);
+    ///
+    /// let action: Action_TO<'static, RBox<()>> = to.obj.sabi_cross_cast_into().unwrap();
+    /// assert_eq!(action.get(), 13);
+    ///
+    /// ```
+    pub fn sabi_cross_cast_into<To>(self) -> Result<To, CrossCastError<Self>>
+    where
+        To: CrossCastTarget<'lt, ErasedPtr = P>,
+    {
+        let vtable = self.sabi_robject_vtable();
+        let found = match vtable._sabi_cross_cast() {
+            Some(find_vtable) => {
+                // Trait objects constructed with `TD_StableDowncast` are converted with
+                // vtables that were also constructed with it.
+                let stable = vtable._sabi_stable_layout().is_some();
+                find_vtable(vtable._sabi_type_id()(), stable, To::LAYOUT).into_option()
+            }
+            None => None,
+        };
+        match found {
+            // The vtable was constructed for the same type and pointer as this RObject,
+            // which was checked by `find_vtable`.
+            Some(to_vtable) => unsafe { Ok(To::from_erased_vtable(self, to_vtable)) },
+            None => Err(CrossCastError::unregistered(self, To::TRAIT_NAME)),
+        }
+    }
+
     #[inline]
     fn sabi_into_erased_ptr(self) -> ManuallyDrop<P> {
         let __this = ManuallyDrop::new(self);
         unsafe { ptr::read(&__this.ptr) }
     }
 
+    /// Replaces the vtable of this RObject,
+    /// changing the interface and vtable type parameters.
+    ///
+    /// This is mostly intended to be called by `#[sabi_trait]` generated trait objects,
+    /// to convert a trait object into the trait object of one of its supertraits.
+    ///
+    /// # Safety
+    ///
+    /// `vtable` must have the same requirements as the one passed to `RObject::with_vtable`,
+    /// and it must have been constructed for the same type,
+    /// pointer,and downcasting behavior as the current vtable.
+    pub unsafe fn sabi_replace_vtable<I2, V2>(
+        self,
+        vtable: PrefixRef<V2>,
+    ) -> RObject<'lt, P, I2, V2> {
+        RObject {
+            vtable,
+            ptr: self.sabi_into_erased_ptr(),
+            _marker: PhantomData,
+        }
+    }
+
     /// Gets an `RRef` pointing to the erased object.
     pub fn sabi_erased_ref(&self) -> RRef<'_, ErasedObject<()>>
     where
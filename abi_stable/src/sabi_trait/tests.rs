@@ -1,8 +1,10 @@
 use std::mem;
 
+use std::fmt::Debug;
+
 use crate::{
-    sabi_trait::prelude::*,
-    std_types::{RBox, RStr},
+    sabi_trait::{prelude::*, register_cross_cast, register_stable_cross_cast, CrossCast},
+    std_types::{RBox, RSlice, RStr, RString},
     type_level::bools::*,
     *,
 };
@@ -294,8 +296,643 @@ fn borrow_kinds() {
     assert_eq!(obj.not_borrow(), 89);
 }
 
+/*////////////////////////////////////////////////////////////////////////////////
+Test static methods and generic methods.
+*/////////////////////////////////////////////////////////////////////////////////
+
+mod static_and_generic {
+    use super::*;
+
+    #[sabi_trait]
+    pub trait Plugin: Clone {
+        fn create(name: RString) -> Self
+        where
+            Self: Sized;
+
+        fn version() -> u32
+        where
+            Self: Sized;
+
+        #[sabi(instantiate(T = u32, T = u64))]
+        fn get<T: From<u32>>(&self) -> T;
+
+        #[sabi(instantiate(T = u32))]
+        fn describe<T: ToString>(&self, value: T) -> RString {
+            format!("default: {}", value.to_string()).into()
+        }
+
+        fn name(&self) -> RString;
+    }
+
+    #[derive(Clone)]
+    pub struct Named(pub RString);
+
+    impl Plugin for Named {
+        fn create(name: RString) -> Self {
+            Named(name)
+        }
+
+        fn version() -> u32 {
+            3
+        }
+
+        fn get<T: From<u32>>(&self) -> T {
+            T::from(self.0.len() as u32)
+        }
+
+        fn name(&self) -> RString {
+            self.0.clone()
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct Counted(pub u32);
+
+    impl Plugin for Counted {
+        fn create(name: RString) -> Self {
+            Counted(name.len() as u32 * 10)
+        }
+
+        fn version() -> u32 {
+            5
+        }
+
+        fn get<T: From<u32>>(&self) -> T {
+            T::from(self.0)
+        }
+
+        fn describe<T: ToString>(&self, value: T) -> RString {
+            format!("counted: {}", value.to_string()).into()
+        }
+
+        fn name(&self) -> RString {
+            "counted".into()
+        }
+    }
+
+    pub mod other_instances {
+        use super::*;
+
+        #[sabi_trait]
+        pub trait Plugin: Clone {
+            fn create(name: RString) -> Self
+            where
+                Self: Sized;
+
+            fn version() -> u32
+            where
+                Self: Sized;
+
+            #[sabi(instantiate(T = u64, T = u32))]
+            fn get<T: From<u32>>(&self) -> T;
+
+            #[sabi(instantiate(T = u32))]
+            fn describe<T: ToString>(&self, value: T) -> RString {
+                format!("default: {}", value.to_string()).into()
+            }
+
+            fn name(&self) -> RString;
+        }
+    }
+}
+
+#[test]
+fn static_methods() {
+    use self::static_and_generic::{Counted, Named, Plugin_TypeVTable};
+
+    let named = Plugin_TypeVTable::from_type::<Named>();
+    let counted = Plugin_TypeVTable::from_type::<Counted>();
+
+    assert_eq!(named.version(), 3);
+    assert_eq!(counted.version(), 5);
+
+    let obj = named.create("hello".into());
+    assert_eq!(obj.name(), "hello");
+    assert_eq!(obj.sabi_type_vtable().version(), 3);
+
+    let obj = counted.create("hello".into());
+    assert_eq!(obj.name(), "counted");
+    assert_eq!(obj.get::<u32>(), 50);
+
+    let cloned = obj.clone();
+    assert_eq!(cloned.sabi_type_vtable().version(), 5);
+    let recreated = cloned.sabi_type_vtable().create("hi".into());
+    assert_eq!(recreated.get::<u32>(), 20);
+}
+
+#[test]
+fn generic_methods() {
+    use self::static_and_generic::{Counted, Named, Plugin_TO};
+
+    let named = Plugin_TO::from_value(Named("hello".into()), TD_Opaque);
+    assert_eq!(named.get::<u32>(), 5);
+    assert_eq!(named.get::<u64>(), 5);
+    assert_eq!(named.describe(8u32), "default: 8");
+
+    let counted = Plugin_TO::from_value(Counted(13), TD_Opaque);
+    assert_eq!(counted.get::<u32>(), 13);
+    assert_eq!(counted.get::<u64>(), 13);
+    assert_eq!(counted.describe(21u32), "counted: 21");
+}
+
+#[test]
+fn generic_method_instances_are_checked() {
+    use self::static_and_generic::{other_instances, Plugin_TO};
+
+    use crate::abi_stability::abi_checking::check_layout_compatibility;
+
+    let layout = <Plugin_TO<'static, RBox<()>> as StableAbi>::LAYOUT;
+    let other = <other_instances::Plugin_TO<'static, RBox<()>> as StableAbi>::LAYOUT;
+
+    check_layout_compatibility(layout, layout).unwrap();
+    assert!(check_layout_compatibility(layout, other).is_err());
+}
+
+/*////////////////////////////////////////////////////////////////////////////////
+Test associated constants.
+*/////////////////////////////////////////////////////////////////////////////////
+
+mod assoc_consts {
+    use super::*;
+
+    #[sabi_trait]
+    pub trait Codec {
+        type Unit;
+
+        const NAME: RStr<'static>;
+
+        const VERSION: u32 = 1;
+
+        const UNIT: Self::Unit;
+
+        const GREETING: RString = RString::new();
+
+        fn encode(&self, x: u32) -> u32;
+    }
+
+    pub struct Double;
+
+    impl Codec for Double {
+        type Unit = u8;
+
+        const NAME: RStr<'static> = rstr!("double");
+
+        const UNIT: u8 = 2;
+
+        fn encode(&self, x: u32) -> u32 {
+            x * 2
+        }
+    }
+
+    pub struct Square;
+
+    impl Codec for Square {
+        type Unit = u8;
+
+        const NAME: RStr<'static> = rstr!("square");
+
+        const VERSION: u32 = 3;
+
+        const UNIT: u8 = 4;
+
+        fn encode(&self, x: u32) -> u32 {
+            x * x
+        }
+    }
+
+    #[sabi_trait]
+    pub trait Table<'a, T: 'a> {
+        type Key;
+
+        const KEY: Self::Key;
+
+        const ITEMS: RSlice<'a, T>;
+
+        fn describe(&self) -> RString;
+    }
+
+    pub struct Primes;
+
+    impl<'a> Table<'a, u32> for Primes {
+        type Key = RStr<'static>;
+
+        const KEY: RStr<'static> = rstr!("primes");
+
+        const ITEMS: RSlice<'a, u32> = rslice![2, 3, 5, 7];
+
+        fn describe(&self) -> RString {
+            "the first primes".into()
+        }
+    }
+
+    pub mod more_consts {
+        use super::*;
+
+        #[sabi_trait]
+        pub trait Codec {
+            type Unit;
+
+            const NAME: RStr<'static>;
+
+            const VERSION: u32 = 1;
+
+            const UNIT: Self::Unit;
+
+            const GREETING: RString = RString::new();
+
+            const EXTRA: u64 = 0;
+
+            fn encode(&self, x: u32) -> u32;
+        }
+    }
+
+    pub mod reordered_consts {
+        use super::*;
+
+        #[sabi_trait]
+        pub trait Codec {
+            type Unit;
+
+            const VERSION: u32 = 1;
+
+            const NAME: RStr<'static>;
+
+            const UNIT: Self::Unit;
+
+            const GREETING: RString = RString::new();
+
+            fn encode(&self, x: u32) -> u32;
+        }
+    }
+}
+
+#[test]
+fn assoc_consts_are_readable() {
+    use self::assoc_consts::{Codec_TO, Double, Square};
+
+    let objects: [Codec_TO<'static, RBox<()>, u8>; 2] = [
+        Codec_TO::from_value(Double, TD_Opaque),
+        Codec_TO::from_value(Square, TD_Opaque),
+    ];
+
+    assert_eq!(objects[0].NAME(), "double");
+    assert_eq!(objects[0].VERSION(), 1);
+    assert_eq!(objects[0].UNIT(), 2);
+    assert_eq!(objects[0].GREETING(), "");
+    assert_eq!(objects[0].encode(5), 10);
+
+    assert_eq!(objects[1].NAME(), "square");
+    assert_eq!(objects[1].VERSION(), 3);
+    assert_eq!(objects[1].UNIT(), 4);
+    assert_eq!(objects[1].GREETING(), "");
+    assert_eq!(objects[1].encode(5), 25);
+}
+
+#[test]
+fn generic_assoc_consts_are_readable() {
+    use self::assoc_consts::{Primes, Table_TO};
+
+    let object: Table_TO<'_, '_, RBox<()>, u32, RStr<'static>> =
+        Table_TO::from_value(Primes, TD_Opaque);
+
+    assert_eq!(object.KEY(), "primes");
+    assert_eq!(object.ITEMS(), &[2, 3, 5, 7][..]);
+    assert_eq!(object.describe(), "the first primes");
+
+    // The constant outlives the trait object,
+    // since it borrows for the lifetime parameter of the trait.
+    let items: RSlice<'static, u32> = {
+        let object: Table_TO<'_, 'static, RBox<()>, u32, RStr<'static>> =
+            Table_TO::from_value(Primes, TD_Opaque);
+        object.ITEMS()
+    };
+    assert_eq!(items.len(), 4);
+}
+
+#[test]
+fn assoc_consts_are_prefix_fields() {
+    use self::assoc_consts::{more_consts, reordered_consts, Codec_TO};
+
+    use crate::abi_stability::abi_checking::check_layout_compatibility;
+
+    let layout = <Codec_TO<'static, RBox<()>, u8> as StableAbi>::LAYOUT;
+    let more = <more_consts::Codec_TO<'static, RBox<()>, u8> as StableAbi>::LAYOUT;
+    let reordered = <reordered_consts::Codec_TO<'static, RBox<()>, u8> as StableAbi>::LAYOUT;
+
+    check_layout_compatibility(layout, more).unwrap();
+    assert!(check_layout_compatibility(layout, reordered).is_err());
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
+mod sabi_supertraits {
+    use super::*;
+
+    #[sabi_trait]
+    pub trait Named {
+        fn name(&self) -> RString;
+    }
+
+    #[sabi_trait]
+    pub trait Stepped {
+        fn step(&self) -> u32;
+    }
+
+    #[sabi_trait]
+    #[sabi(sabi_supertraits(Named, Stepped))]
+    pub trait Counter<T>: Named + Stepped + Debug {
+        fn count(&mut self) -> T;
+    }
+
+    #[sabi_trait]
+    #[sabi(sabi_supertraits(Counter))]
+    pub trait Resettable: Counter<u32> + Send + Sync {
+        fn reset(&mut self);
+    }
+
+    #[repr(C)]
+    #[derive(Debug, Clone, PartialEq, StableAbi)]
+    pub struct Tally(pub u32);
+
+    impl Named for Tally {
+        fn name(&self) -> RString {
+            format!("tally {}", self.0).into()
+        }
+    }
+
+    impl Stepped for Tally {
+        fn step(&self) -> u32 {
+            1
+        }
+    }
+
+    impl Counter<u32> for Tally {
+        fn count(&mut self) -> u32 {
+            self.0 += 1;
+            self.0
+        }
+    }
+
+    impl Resettable for Tally {
+        fn reset(&mut self) {
+            self.0 = 0;
+        }
+    }
+
+    impl old_counter::Counter<u32> for Tally {
+        fn count(&mut self) -> u32 {
+            self.0 += 1;
+            self.0
+        }
+    }
+
+    /// A version of `Counter` from before `Stepped` was added as a supertrait.
+    pub mod old_counter {
+        use super::*;
+
+        #[sabi_trait]
+        #[sabi(sabi_supertraits(Named))]
+        pub trait Counter<T>: Named + Debug {
+            fn count(&mut self) -> T;
+        }
+    }
+}
+
+#[test]
+fn sabi_supertrait_upcasting() {
+    use self::sabi_supertraits::{Counter_TO, Named_TO, Resettable_TO, Tally};
+
+    let mut resettable: Resettable_TO<'static, RBox<()>> =
+        Resettable_TO::from_value(Tally(10), TD_CanDowncast);
+    resettable.reset();
+
+    let mut counter: Counter_TO<'static, RBox<()>, u32> = resettable.sabi_cross_cast().unwrap();
+    assert_eq!(counter.count(), 1);
+    assert_eq!(counter.count(), 2);
+    assert_eq!(format!("{:?}", counter), "Tally(2)");
+
+    let named: Named_TO<'static, RBox<()>> = counter.sabi_cross_cast().unwrap();
+    assert_eq!(named.name(), "tally 2");
+
+    // The upcast trait object can still be downcast to the original type.
+    assert_eq!(named.obj.downcast_as::<Tally>().unwrap(), &Tally(2));
+    assert_eq!(*named.obj.downcast_into::<Tally>().unwrap(), Tally(2));
+
+    // Upcasting a reborrowed trait object
+    let mut counter: Counter_TO<'static, RBox<()>, u32> =
+        Counter_TO::from_value(Tally(5), TD_Opaque);
+    {
+        let mut reborrowed = counter.sabi_reborrow_mut();
+        assert_eq!(reborrowed.count(), 6);
+        let named: Named_TO<'_, RMut<'_, ()>> = reborrowed.sabi_cross_cast().unwrap();
+        assert_eq!(named.name(), "tally 6");
+    }
+    assert_eq!(counter.count(), 7);
+}
+
+#[test]
+fn sabi_supertrait_cross_casting() {
+    use self::sabi_supertraits::{old_counter, Counter_TO, Named_TO, Stepped_TO, Tally};
+
+    let counter: Counter_TO<'static, RBox<()>, u32> = Counter_TO::from_value(Tally(3), TD_Opaque);
+    let named: Named_TO<'static, RBox<()>> = counter.sabi_cross_cast().unwrap();
+    assert_eq!(named.name(), "tally 3");
+
+    // Simulating a trait object constructed in a dynamic library compiled with
+    // a version of `Counter` that didn't have `Stepped` as a supertrait.
+    let old: old_counter::Counter_TO<'static, RBox<()>, u32> =
+        old_counter::Counter_TO::from_value(Tally(8), TD_Opaque);
+    let mut counter: Counter_TO<'static, RBox<()>, u32> = unsafe { mem::transmute(old) };
+
+    let err = CrossCast::<Stepped_TO<'static, RBox<()>>>::sabi_cross_cast(counter)
+        .err()
+        .unwrap();
+    assert_eq!(err.to_trait(), "Stepped");
+
+    counter = err.into_inner();
+    assert_eq!(counter.count(), 9);
+
+    let named: Named_TO<'static, RBox<()>> = counter.sabi_cross_cast().unwrap();
+    assert_eq!(named.name(), "tally 9");
+}
+
+#[test]
+fn sabi_trait_sideways_cross_casting() {
+    use self::sabi_supertraits::{Named_TO, Stepped_TO, Tally};
+
+    register_cross_cast::<Tally, Stepped_TO<'static, RBox<()>>>();
+    // Registering twice is fine
+    register_cross_cast::<Tally, Stepped_TO<'static, RBox<()>>>();
+
+    let named: Named_TO<'static, RBox<()>> = Named_TO::from_value(Tally(4), TD_CanDowncast);
+    let stepped: Stepped_TO<'static, RBox<()>> = named.sabi_cross_cast_into().unwrap();
+    assert_eq!(stepped.step(), 1);
+    assert_eq!(stepped.obj.downcast_as::<Tally>().unwrap(), &Tally(4));
+
+    // Trait objects constructed with `TD_Opaque` don't store the type id of the erased type.
+    let named: Named_TO<'static, RBox<()>> = Named_TO::from_value(Tally(5), TD_Opaque);
+    let err = named
+        .sabi_cross_cast_into::<Stepped_TO<'static, RBox<()>>>()
+        .err()
+        .unwrap();
+    assert_eq!(err.to_trait(), "Stepped");
+    assert_eq!(err.into_inner().name(), "tally 5");
+
+    // The registration is specific to the pointer type.
+    let named: Named_TO<'static, RBox<()>> = Named_TO::from_value(Tally(6), TD_CanDowncast);
+    let borrowed = named.sabi_reborrow();
+    assert!(borrowed
+        .sabi_cross_cast_into::<Stepped_TO<'_, RRef<'_, ()>>>()
+        .is_err());
+
+    register_cross_cast::<Tally, Stepped_TO<'static, RRef<'static, ()>>>();
+    let borrowed = named.sabi_reborrow();
+    let stepped = borrowed
+        .sabi_cross_cast_into::<Stepped_TO<'_, RRef<'_, ()>>>()
+        .unwrap();
+    assert_eq!(stepped.step(), 1);
+}
+
+#[test]
+fn sabi_trait_sideways_cross_casting_stable_downcast() {
+    use self::sabi_supertraits::{Named_TO, Stepped_TO, Tally};
+
+    register_cross_cast::<Tally, Stepped_TO<'static, RBox<()>>>();
+
+    // The vtable registered with `register_cross_cast` is not used for trait objects
+    // constructed with `TD_StableDowncast`, since it would lose the layout of the type.
+    let named: Named_TO<'static, RBox<()>> = Named_TO::from_value(Tally(7), TD_StableDowncast);
+    let named = named
+        .sabi_cross_cast_into::<Stepped_TO<'static, RBox<()>>>()
+        .err()
+        .unwrap()
+        .into_inner();
+
+    register_stable_cross_cast::<Tally, Stepped_TO<'static, RBox<()>>>();
+
+    let mut stepped: Stepped_TO<'static, RBox<()>> = named.sabi_cross_cast_into().unwrap();
+    assert_eq!(stepped.step(), 1);
+    assert_eq!(stepped.obj.stable_downcast_as::<Tally>().unwrap(), &Tally(7));
+    stepped.obj.stable_downcast_as_mut::<Tally>().unwrap().0 += 1;
+    assert_eq!(*stepped.obj.stable_downcast_into::<Tally>().unwrap(), Tally(8));
+
+    // Trait objects constructed with `TD_CanDowncast` still use
+    // the vtable registered with `register_cross_cast`.
+    let named: Named_TO<'static, RBox<()>> = Named_TO::from_value(Tally(9), TD_CanDowncast);
+    let stepped: Stepped_TO<'static, RBox<()>> = named.sabi_cross_cast_into().unwrap();
+    assert_eq!(stepped.obj.downcast_as::<Tally>().unwrap(), &Tally(9));
+}
+
+#[test]
+fn sabi_supertrait_vtables_are_prefix_fields() {
+    use self::sabi_supertraits::{old_counter, Counter_TO};
+
+    use crate::abi_stability::abi_checking::check_layout_compatibility;
+
+    let old = <old_counter::Counter_TO<'static, RBox<()>, u32> as StableAbi>::LAYOUT;
+    let new = <Counter_TO<'static, RBox<()>, u32> as StableAbi>::LAYOUT;
+
+    check_layout_compatibility(old, new).unwrap();
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+mod interface_queries {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(Debug))]
+    pub struct DebugInterface;
+
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(impl_InterfaceType(Clone, Debug, Display))]
+    pub struct CloneDisplayInterface;
+
+    #[sabi_trait]
+    pub trait Shown: Clone + Debug + Display {
+        fn shown(&self) -> u32;
+    }
+
+    impl Shown for u32 {
+        fn shown(&self) -> u32 {
+            *self
+        }
+    }
+
+    #[sabi_trait]
+    pub trait Debugged: Debug {
+        fn debugged(&self) -> u32;
+    }
+
+    impl Debugged for u32 {
+        fn debugged(&self) -> u32 {
+            *self
+        }
+    }
+}
+
+#[test]
+fn robject_interface_queries() {
+    use self::interface_queries::{CloneDisplayInterface, DebugInterface, Debugged_TO, Shown_TO};
+
+    let shown: Shown_TO<'static, RBox<()>> = Shown_TO::from_value(7u32, TD_Opaque);
+    let vtable = shown.obj.sabi_et_vtable();
+
+    // Simulating a host that only knows that the trait object implements `Debug`.
+    let narrow: crate::sabi_trait::RObject<'static, RBox<()>, DebugInterface, _> =
+        unsafe { shown.obj.sabi_replace_vtable(vtable) };
+
+    assert!(narrow.supports_interface::<CloneDisplayInterface>());
+    let wide = narrow.query_interface::<CloneDisplayInterface>().unwrap();
+    assert_eq!(format!("{} {:?}", wide.clone(), wide), "7 7");
+
+    let debugged: Debugged_TO<'static, RBox<()>> = Debugged_TO::from_value(8u32, TD_Opaque);
+    assert!(debugged.obj.supports_interface::<DebugInterface>());
+    assert!(!debugged.obj.supports_interface::<CloneDisplayInterface>());
+    assert!(debugged
+        .obj
+        .reborrow()
+        .query_interface::<CloneDisplayInterface>()
+        .is_none());
+    assert_eq!(debugged.debugged(), 8);
+}
+
+#[test]
+fn robject_stable_downcasting() {
+    use self::interface_queries::Debugged_TO;
+    use crate::{
+        std_types::RVec,
+        test_utils::{TD_ForeignCanDowncast, TD_ForeignStable},
+    };
+
+    let mut local: Debugged_TO<'static, RBox<()>> =
+        Debugged_TO::from_value(3u32, TD_StableDowncast);
+    assert_eq!(local.obj.downcast_as::<u32>().unwrap(), &3);
+    assert_eq!(local.obj.stable_downcast_as::<u32>().unwrap(), &3);
+    assert!(local.obj.stable_downcast_as::<RVec<u8>>().is_err());
+    *local.obj.stable_downcast_as_mut::<u32>().unwrap() += 1;
+    assert_eq!(local.debugged(), 4);
+
+    // Simulating a trait object constructed in another dynamic library.
+    let mut foreign: Debugged_TO<'static, RBox<()>> =
+        Debugged_TO::from_value(5u32, TD_ForeignStable);
+    assert!(foreign.obj.downcast_as::<u32>().is_err());
+    assert_eq!(foreign.obj.stable_downcast_as::<u32>().unwrap(), &5);
+    assert!(foreign.obj.stable_downcast_as::<i32>().is_err());
+    *foreign.obj.stable_downcast_as_mut::<u32>().unwrap() += 1;
+    assert_eq!(*foreign.obj.stable_downcast_into::<u32>().unwrap(), 6);
+
+    let foreign: Debugged_TO<'static, RBox<()>> =
+        Debugged_TO::from_value(8u32, TD_ForeignCanDowncast);
+    assert!(foreign.obj.stable_downcast_as::<u32>().is_err());
+
+    let opaque: Debugged_TO<'static, RBox<()>> = Debugged_TO::from_value(8u32, TD_Opaque);
+    assert!(opaque.obj.stable_downcast_as::<u32>().is_err());
+    assert_eq!(opaque.debugged(), 8);
+}
+
 mod has_docs {
     /// above
     #[crate::sabi_trait]
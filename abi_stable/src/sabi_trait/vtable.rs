@@ -2,8 +2,10 @@ use super::*;
 
 use crate::{
     erased_types::{FormattingMode, InterfaceType, MakeRequiredTraits},
-    marker_type::NonOwningPhantom,
-    std_types::{RResult, RString, UTypeId},
+    marker_type::{ErasedPrefix, NonOwningPhantom},
+    sabi_trait::cross_cast::find_cross_cast_vtable,
+    std_types::{ROption, RResult, RString, UTypeId},
+    type_layout::TypeLayout,
     type_level::{
         downcasting::GetUTID,
         impl_enum::{Implemented, Unimplemented},
@@ -74,6 +76,8 @@ where
         _sabi_clone: <I::Clone as InitCloneField<_Self, ErasedPtr, OrigPtr>>::VALUE,
         _sabi_debug: <I::Debug as InitDebugField<_Self, ErasedPtr, OrigPtr>>::VALUE,
         _sabi_display: <I::Display as InitDisplayField<_Self, ErasedPtr, OrigPtr>>::VALUE,
+        _sabi_stable_layout: <IA as GetUTID<_Self>>::STABLE_LAYOUT,
+        _sabi_cross_cast: Some(find_cross_cast_vtable),
     };
 }
 
@@ -121,6 +125,35 @@ pub struct RObjectVtable<_Self, ErasedPtr, I> {
             &mut RString,
         ) -> RResult<(), ()>,
     >,
+    /// The layout of the erased type,
+    /// only stored if the trait object was constructed with `TD_StableDowncast`.
+    pub _sabi_stable_layout: Option<&'static TypeLayout>,
+    /// Finds the vtable of another `#[sabi_trait]` trait object for the erased type,
+    /// registered with [`register_cross_cast`](crate::sabi_trait::register_cross_cast)
+    /// (or [`register_stable_cross_cast`](crate::sabi_trait::register_stable_cross_cast)
+    /// if the `bool` is true)
+    /// in the dynamic library/executable that constructed the trait object.
+    pub _sabi_cross_cast: Option<
+        extern "C" fn(
+            MaybeCmp<UTypeId>,
+            bool,
+            &'static TypeLayout,
+        ) -> ROption<PrefixRef<ErasedPrefix>>,
+    >,
+}
+
+impl<_Self, ErasedPtr, I> RObjectVtable_Ref<_Self, ErasedPtr, I> {
+    /// Whether this vtable has the functions for all the traits that `I2` requires,
+    /// out of the ones stored in this vtable (`Clone`, `Debug`, and `Display`).
+    pub fn has_fields_for<I2>(self) -> bool
+    where
+        I2: MakeRequiredTraits,
+    {
+        let required = <I2 as MakeRequiredTraits>::MAKE;
+        (!required.contains_clone() || self._sabi_clone().is_some())
+            && (!required.contains_debug() || self._sabi_debug().is_some())
+            && (!required.contains_display() || self._sabi_display().is_some())
+    }
 }
 
 /// The common prefix of all `#[trait_object]` derived vtables,
@@ -139,6 +172,34 @@ pub(super) struct BaseVtable<_Self, ErasedPtr, I> {
     pub _sabi_vtable: RObjectVtable_Ref<_Self, ErasedPtr, I>,
 }
 
+//////////////////////////////////////////////////////////////////////////////
+
+/// Gets the type of the vtable of a `#[sabi_trait]` generated trait object,
+/// used to store the vtables of `#[sabi_trait]` supertraits in the vtable of a subtrait.
+///
+/// # Safety
+///
+/// This trait must only be implemented by the `#[sabi_trait]` attribute.
+pub unsafe trait TraitObjectVTable {
+    /// The prefix of the vtable,with the `_Self` type parameter erased.
+    type Prefix;
+}
+
+/// Constructs the vtable of a `#[sabi_trait]` generated trait object.
+///
+/// The vtable is returned type-erased,
+/// so that the constraints of this trait don't prevent
+/// `<Self as TraitObjectVTable>::Prefix` from being normalized.
+///
+/// # Safety
+///
+/// This trait must only be implemented by the `#[sabi_trait]` attribute.
+pub unsafe trait MakeTraitObjectVTable<IA, _Self, ErasedPtr, OrigPtr> {
+    /// The vtable for `_Self`,
+    /// which is a `PrefixRef<<Self as TraitObjectVTable>::Prefix>` with an erased type.
+    const VTABLE: PrefixRef<ErasedPrefix>;
+}
+
 use self::trait_bounds::*;
 pub mod trait_bounds {
     use super::*;
@@ -101,6 +101,12 @@ impl<E> BitArray64<E> {
         }
     }
 
+    /// Converts this BitArray64 into a u64.
+    #[inline]
+    pub const fn to_u64(self) -> u64 {
+        self.bits
+    }
+
     /// Creates a BitArray64 where all elements are falsy.
     #[inline]
     pub const fn empty() -> Self {
@@ -5,23 +5,31 @@
 //! the [external_types module](../external_types/index.html)
 
 pub(crate) mod arc;
+pub(crate) mod arc_slice;
 pub(crate) mod boxed;
+pub mod btree_map;
+pub mod btree_set;
+pub mod closures;
 pub(crate) mod cmp_ordering;
 pub mod cow;
+pub mod future;
 pub mod map;
 pub(crate) mod option;
 pub(crate) mod range;
 pub(crate) mod result;
+pub mod set;
 pub(crate) mod slice_mut;
 pub(crate) mod slices;
 pub(crate) mod std_error;
 pub(crate) mod std_io;
 pub(crate) mod str;
 pub mod string;
+pub(crate) mod task;
 pub(crate) mod time;
 pub(crate) mod tuple;
 pub mod utypeid;
 pub mod vec;
+pub mod vec_deque;
 
 /// Some types from the `std::sync` module have ffi-safe equivalents in
 /// `abi_stable::external_types`.
@@ -36,21 +44,31 @@ pub mod sync {}
 
 #[doc(inline)]
 pub use self::{
-    arc::RArc,
+    arc::{RArc, RWeak},
+    arc_slice::{RArcSlice, RArcStr},
     boxed::RBox,
+    btree_map::RBTreeMap,
+    btree_set::RBTreeSet,
+    closures::{
+        RFn, RFnMut, RFnMut_, RFnOnce, RFnOnce_, RFn_, SendRFn, SendRFnMut, SendRFnOnce, SyncRFn,
+    },
     cmp_ordering::RCmpOrdering,
     cow::{RCow, RCowSlice, RCowStr, RCowVal},
+    future::{RFuture, RFuture_, RStream, RStream_, SendRFuture, SendRStream},
     map::RHashMap,
     option::{RNone, ROption, RSome},
     result::{RErr, ROk, RResult},
+    set::RHashSet,
     slice_mut::RSliceMut,
     slices::RSlice,
     std_error::{RBoxError, RBoxError_, SendRBoxError, UnsyncRBoxError},
     std_io::{RIoError, RIoErrorKind, RSeekFrom},
     str::RStr,
     string::RString,
+    task::{RContext, RPending, RPoll, RReady, RWaker},
     time::RDuration,
     tuple::{Tuple1, Tuple2, Tuple3, Tuple4},
     utypeid::UTypeId,
     vec::RVec,
+    vec_deque::RVecDeque,
 };
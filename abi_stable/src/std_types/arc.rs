@@ -1,6 +1,12 @@
 //! Contains the ffi-safe equivalent of `std::sync::Arc`.
 
-use std::{borrow::Borrow, marker::PhantomData, mem::ManuallyDrop, sync::Arc};
+use std::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    sync::{Arc, Weak},
+};
 
 use core_extensions::SelfOps;
 
@@ -13,6 +19,8 @@ use crate::{
     prefix_type::{PrefixRef, WithMetadata},
     std_types::{
         utypeid::{new_utypeid, UTypeId},
+        ROption,
+        ROption::{RNone, RSome},
         RResult,
     },
 };
@@ -133,15 +141,113 @@ mod private {
             unsafe { ArcVtable_Ref::<T>(self.vtable.cast()) }
         }
 
+        /// # Safety
+        ///
+        /// `data` must come from `Arc::into_raw`,
+        /// and `vtable` must be the vtable of the `RArc` it came from.
+        #[inline(always)]
+        pub(super) const unsafe fn from_raw_parts(
+            data: *const T,
+            vtable: ArcVtable_Ref<T>,
+        ) -> Self {
+            RArc {
+                data,
+                vtable: unsafe { vtable.0.cast() },
+                _marker: PhantomData,
+            }
+        }
+
         #[allow(dead_code)]
         #[cfg(test)]
         pub(super) fn set_vtable_for_testing(&mut self) {
             self.vtable = unsafe { VTableGetter::<T>::LIB_VTABLE_FOR_TESTING.0.cast() };
         }
     }
+
+    /// Ffi-safe version of `std::sync::Weak`,
+    /// a non-owning reference to the value in an [`RArc`].
+    ///
+    /// This is created with [`RArc::downgrade`],
+    /// and can be turned back into an `RArc` with [`upgrade`](#method.upgrade)
+    /// as long as the value hasn't been dropped.
+    ///
+    /// All the operations on an `RWeak<T>` go through the vtable of the `RArc<T>`
+    /// it was created from,
+    /// so an `RWeak` can be upgraded in a different dynamic library than the one
+    /// where the `RArc` was created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RArc, RWeak};
+    ///
+    /// let arc = RArc::new(100);
+    /// let weak: RWeak<u32> = RArc::downgrade(&arc);
+    ///
+    /// assert_eq!(weak.strong_count(), 1);
+    /// assert_eq!(weak.weak_count(), 1);
+    /// assert_eq!(weak.upgrade(), Some(RArc::new(100)));
+    ///
+    /// drop(arc);
+    ///
+    /// assert_eq!(weak.strong_count(), 0);
+    /// assert_eq!(weak.upgrade(), None);
+    ///
+    /// ```
+    ///
+    /// [`RArc`]: ./struct.RArc.html
+    /// [`RArc::downgrade`]: ./struct.RArc.html#method.downgrade
+    #[derive(StableAbi)]
+    #[repr(C)]
+    pub struct RWeak<T> {
+        data: *const T,
+        #[sabi(unsafe_change_type = ArcVtable_Ref<T>)]
+        vtable: PrefixRef<ErasedPrefix>,
+        _marker: PhantomData<T>,
+    }
+
+    impl_from_rust_repr! {
+        impl[T] From<Weak<T>> for RWeak<T> {
+            fn(this){
+                RWeak {
+                    data: Weak::into_raw(this),
+                    vtable: unsafe{ VTableGetter::<T>::LIB_VTABLE.0.cast() },
+                    _marker: Default::default(),
+                }
+            }
+        }
+    }
+
+    impl<T> RWeak<T> {
+        #[inline(always)]
+        pub(super) const fn data(&self) -> *const T {
+            self.data
+        }
+
+        #[inline(always)]
+        pub(crate) const fn vtable(&self) -> ArcVtable_Ref<T> {
+            unsafe { ArcVtable_Ref::<T>(self.vtable.cast()) }
+        }
+
+        /// # Safety
+        ///
+        /// `data` must come from `Weak::into_raw`,
+        /// and `vtable` must be the vtable of the `RArc`/`RWeak` it came from.
+        #[inline(always)]
+        pub(super) const unsafe fn from_raw_parts(
+            data: *const T,
+            vtable: ArcVtable_Ref<T>,
+        ) -> Self {
+            RWeak {
+                data,
+                vtable: unsafe { vtable.0.cast() },
+                _marker: PhantomData,
+            }
+        }
+    }
 }
 
-pub use self::private::RArc;
+pub use self::private::{RArc, RWeak};
 
 impl<T> RArc<T> {
     /// Constructs an `RArc` from a value.
@@ -158,6 +264,41 @@ impl<T> RArc<T> {
         Arc::new(this).into()
     }
 
+    /// Constructs an `RArc` with a value that can hold an [`RWeak`] to itself.
+    ///
+    /// The `RWeak` passed to `data_fn` can't be upgraded until
+    /// this function returns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RArc, RWeak};
+    ///
+    /// struct Node {
+    ///     this: RWeak<Node>,
+    ///     value: u32,
+    /// }
+    ///
+    /// let arc = RArc::new_cyclic(|this| {
+    ///     assert!(this.upgrade().is_none());
+    ///     Node {
+    ///         this: this.clone(),
+    ///         value: 3,
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(arc.this.upgrade().unwrap().value, 3);
+    ///
+    /// ```
+    ///
+    /// [`RWeak`]: ./struct.RWeak.html
+    pub fn new_cyclic<F>(data_fn: F) -> Self
+    where
+        F: FnOnce(&RWeak<T>) -> T,
+    {
+        Arc::new_cyclic(|weak| data_fn(&RWeak::from(weak.clone()))).into()
+    }
+
     /// Converts this `RArc<T>` into an `Arc<T>`
     ///
     /// # Allocators
@@ -310,7 +451,7 @@ impl<T> RArc<T> {
         unsafe { vtable.strong_count()(this) }
     }
 
-    /// Gets the number of `std::sync::Weak` that point to the value.
+    /// Gets the number of `RWeak`/`std::sync::Weak` that point to the value.
     ///
     /// # Example
     ///
@@ -333,6 +474,32 @@ impl<T> RArc<T> {
         let vtable = this.vtable();
         unsafe { vtable.weak_count()(this) }
     }
+
+    /// Creates a new [`RWeak`] pointer to the value.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the `RArc` was created by a dynamic library that
+    /// uses a version of abi_stable that doesn't support `RWeak`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArc;
+    ///
+    /// let arc = RArc::new(5);
+    /// let weak = RArc::downgrade(&arc);
+    ///
+    /// assert_eq!(RArc::weak_count(&arc), 1);
+    /// assert_eq!(weak.upgrade(), Some(arc));
+    ///
+    /// ```
+    ///
+    /// [`RWeak`]: ./struct.RWeak.html
+    pub fn downgrade(this: &Self) -> RWeak<T> {
+        let vtable = this.vtable();
+        unsafe { vtable.downgrade()(this) }
+    }
 }
 
 ////////////////////////////////////////////////////////////////////
@@ -402,6 +569,152 @@ impl<T> Unpin for RArc<T> {}
 
 /////////////////////////////////////////////////////////
 
+impl<T> RWeak<T> {
+    /// Constructs an `RWeak` that doesn't point to any value,
+    /// so [`upgrade`](#method.upgrade) always returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RWeak;
+    ///
+    /// let weak = RWeak::<u32>::new();
+    ///
+    /// assert_eq!(weak.upgrade(), None);
+    /// assert_eq!(weak.strong_count(), 0);
+    ///
+    /// ```
+    pub fn new() -> Self {
+        Weak::new().into()
+    }
+
+    /// Attempts to get an `RArc` to the value,
+    /// returning `None` if the value was already dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArc;
+    ///
+    /// let arc = RArc::new("hello");
+    /// let weak = RArc::downgrade(&arc);
+    ///
+    /// assert_eq!(weak.upgrade().as_deref(), Some(&"hello"));
+    ///
+    /// drop(arc);
+    /// assert_eq!(weak.upgrade(), None);
+    ///
+    /// ```
+    pub fn upgrade(&self) -> Option<RArc<T>> {
+        let vtable = self.vtable();
+        unsafe { vtable.weak_upgrade()(self).into_option() }
+    }
+
+    /// Gets the number of `RArc` that point to the value.
+    ///
+    /// This returns 0 if the value was dropped,
+    /// or if this was created with [`RWeak::new`](#method.new).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArc;
+    ///
+    /// let arc = RArc::new(0);
+    /// let weak = RArc::downgrade(&arc);
+    /// assert_eq!(weak.strong_count(), 1);
+    ///
+    /// let clone = RArc::clone(&arc);
+    /// assert_eq!(weak.strong_count(), 2);
+    ///
+    /// ```
+    pub fn strong_count(&self) -> usize {
+        let vtable = self.vtable();
+        unsafe { vtable.weak_strong_count()(self) }
+    }
+
+    /// Gets the number of `RWeak`/`std::sync::Weak` that point to the value.
+    ///
+    /// This returns 0 if the value was dropped,
+    /// or if this was created with [`RWeak::new`](#method.new).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArc;
+    ///
+    /// let arc = RArc::new(0);
+    /// let weak = RArc::downgrade(&arc);
+    /// assert_eq!(weak.weak_count(), 1);
+    ///
+    /// let clone = weak.clone();
+    /// assert_eq!(weak.weak_count(), 2);
+    ///
+    /// drop(arc);
+    /// assert_eq!(weak.weak_count(), 0);
+    ///
+    /// ```
+    pub fn weak_count(&self) -> usize {
+        let vtable = self.vtable();
+        unsafe { vtable.weak_weak_count()(self) }
+    }
+
+    /// Whether both `RWeak`s point to the same allocation,
+    /// or were both created with [`RWeak::new`](#method.new).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RArc, RWeak};
+    ///
+    /// let arc = RArc::new(0);
+    /// let weak = RArc::downgrade(&arc);
+    ///
+    /// assert!(weak.ptr_eq(&weak.clone()));
+    /// assert!(!weak.ptr_eq(&RArc::downgrade(&RArc::new(0))));
+    /// assert!(RWeak::<u32>::new().ptr_eq(&RWeak::new()));
+    ///
+    /// ```
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.data(), other.data())
+    }
+}
+
+impl<T> Default for RWeak<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for RWeak<T> {
+    fn clone(&self) -> Self {
+        unsafe { (self.vtable().weak_clone())(self) }
+    }
+}
+
+impl<T> Drop for RWeak<T> {
+    fn drop(&mut self) {
+        unsafe {
+            let vtable = self.vtable();
+            (vtable.weak_destructor())(self.data());
+        }
+    }
+}
+
+impl<T> Debug for RWeak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(RWeak)")
+    }
+}
+
+unsafe impl<T> Sync for RWeak<T> where T: Send + Sync {}
+
+unsafe impl<T> Send for RWeak<T> where T: Send + Sync {}
+
+impl<T> Unpin for RWeak<T> {}
+
+/////////////////////////////////////////////////////////
+
 mod vtable_mod {
     use super::*;
 
@@ -416,6 +729,12 @@ mod vtable_mod {
             try_unwrap: try_unwrap_arc::<T>,
             strong_count: strong_count_arc::<T>,
             weak_count: weak_count_arc::<T>,
+            downgrade: downgrade_arc::<T>,
+            weak_destructor: destructor_weak::<T>,
+            weak_clone: clone_weak::<T>,
+            weak_upgrade: upgrade_weak::<T>,
+            weak_strong_count: strong_count_weak::<T>,
+            weak_weak_count: weak_count_weak::<T>,
         };
 
         staticref! {
@@ -455,6 +774,12 @@ mod vtable_mod {
         pub(super) strong_count: unsafe extern "C" fn(&RArc<T>) -> usize,
         #[sabi(last_prefix_field)]
         pub(super) weak_count: unsafe extern "C" fn(&RArc<T>) -> usize,
+        pub(super) downgrade: unsafe extern "C" fn(&RArc<T>) -> RWeak<T>,
+        pub(super) weak_destructor: unsafe extern "C" fn(*const T),
+        pub(super) weak_clone: unsafe extern "C" fn(&RWeak<T>) -> RWeak<T>,
+        pub(super) weak_upgrade: unsafe extern "C" fn(&RWeak<T>) -> ROption<RArc<T>>,
+        pub(super) weak_strong_count: unsafe extern "C" fn(&RWeak<T>) -> usize,
+        pub(super) weak_weak_count: unsafe extern "C" fn(&RWeak<T>) -> usize,
     }
 
     unsafe extern "C" fn destructor_arc<T>(this: *const T, call_drop: CallReferentDrop) {
@@ -505,5 +830,53 @@ mod vtable_mod {
     unsafe extern "C" fn weak_count_arc<T>(this: &RArc<T>) -> usize {
         unsafe { with_arc_ref(this, |x| Arc::weak_count(x)) }
     }
+
+    unsafe extern "C" fn downgrade_arc<T>(this: &RArc<T>) -> RWeak<T> {
+        unsafe {
+            with_arc_ref(this, |x| {
+                RWeak::from_raw_parts(Weak::into_raw(Arc::downgrade(x)), this.vtable())
+            })
+        }
+    }
+
+    unsafe extern "C" fn destructor_weak<T>(this: *const T) {
+        extern_fn_panic_handling! {no_early_return; unsafe {
+            drop(Weak::from_raw(this));
+        }}
+    }
+
+    unsafe fn with_weak_ref<T, F, R>(this: &RWeak<T>, f: F) -> R
+    where
+        F: FnOnce(&Weak<T>) -> R,
+    {
+        let x = unsafe { Weak::from_raw(this.data()) };
+        let x = ManuallyDrop::new(x);
+        f(&x)
+    }
+
+    unsafe extern "C" fn clone_weak<T>(this: &RWeak<T>) -> RWeak<T> {
+        unsafe {
+            with_weak_ref(this, |x| {
+                RWeak::from_raw_parts(Weak::into_raw(Weak::clone(x)), this.vtable())
+            })
+        }
+    }
+
+    unsafe extern "C" fn upgrade_weak<T>(this: &RWeak<T>) -> ROption<RArc<T>> {
+        unsafe {
+            with_weak_ref(this, |x| match x.upgrade() {
+                Some(arc) => RSome(RArc::from_raw_parts(Arc::into_raw(arc), this.vtable())),
+                None => RNone,
+            })
+        }
+    }
+
+    unsafe extern "C" fn strong_count_weak<T>(this: &RWeak<T>) -> usize {
+        unsafe { with_weak_ref(this, |x| Weak::strong_count(x)) }
+    }
+
+    unsafe extern "C" fn weak_count_weak<T>(this: &RWeak<T>) -> usize {
+        unsafe { with_weak_ref(this, |x| Weak::weak_count(x)) }
+    }
 }
 use self::vtable_mod::{ArcVtable_Ref, VTableGetter};
@@ -130,6 +130,101 @@ fn make_mut() {
     assert_eq!(arc.value, 'c');
 }
 
+#[test]
+fn downgrade_upgrade() {
+    let count = Cell::new(1);
+    let dod = DecrementOnDrop(&count);
+
+    let arc = RArc::new(ValueAndDod {
+        value: 'a',
+        _dod: dod.clone(),
+    });
+    let weak = RArc::downgrade(&arc);
+    assert_eq!(dod.count(), 2);
+    assert_eq!(RArc::weak_count(&arc), 1);
+    assert_eq!(weak.strong_count(), 1);
+
+    {
+        let weak_clone = weak.clone();
+        assert_eq!(weak.weak_count(), 2);
+        assert!(weak.ptr_eq(&weak_clone));
+
+        let upgraded = weak_clone.upgrade().unwrap();
+        assert_eq!(upgraded.value, 'a');
+        assert_eq!(RArc::strong_count(&arc), 2);
+    }
+    assert_eq!(weak.weak_count(), 1);
+    assert_eq!(weak.strong_count(), 1);
+
+    drop(arc);
+    assert_eq!(dod.count(), 1);
+    assert_eq!(weak.strong_count(), 0);
+    assert_eq!(weak.weak_count(), 0);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn weak_new() {
+    let weak = RWeak::<u32>::new();
+    assert!(weak.upgrade().is_none());
+    assert_eq!(weak.strong_count(), 0);
+    assert_eq!(weak.weak_count(), 0);
+    assert!(weak.ptr_eq(&weak.clone()));
+    assert!(RWeak::<u32>::default().upgrade().is_none());
+
+    let weak: RWeak<u32> = Weak::new().into();
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn new_cyclic() {
+    struct Node {
+        this: RWeak<Node>,
+        value: u32,
+    }
+
+    let arc = RArc::new_cyclic(|this| {
+        assert!(this.upgrade().is_none());
+        assert_eq!(this.strong_count(), 0);
+        Node {
+            this: this.clone(),
+            value: 8,
+        }
+    });
+    assert_eq!(RArc::weak_count(&arc), 1);
+
+    let upgraded = arc.this.upgrade().unwrap();
+    assert_eq!(upgraded.value, 8);
+    assert_eq!(RArc::strong_count(&arc), 2);
+}
+
+#[test]
+fn weak_uses_arc_vtable() {
+    let orig_a = Arc::new(1000);
+    let a_addr = (&*orig_a) as *const _ as usize;
+    let mut reprc_a = orig_a.clone().piped(RArc::from);
+    reprc_a.set_vtable_for_testing();
+
+    let weak = RArc::downgrade(&reprc_a);
+    assert_eq!(Arc::weak_count(&orig_a), 1);
+
+    let upgraded = weak.clone().upgrade().unwrap();
+    assert_eq!(a_addr, refaddr(&*upgraded));
+    assert_eq!(Arc::strong_count(&orig_a), 3);
+
+    // The upgraded `RArc` uses the vtable of the `RArc` that the `RWeak` came from,
+    // so converting it into an `Arc` has to clone the value.
+    let back_to_a = upgraded.piped(RArc::into_arc);
+    assert_ne!(a_addr, refaddr(&*back_to_a));
+
+    drop(reprc_a);
+    assert_eq!(Arc::strong_count(&orig_a), 1);
+    assert_eq!(weak.strong_count(), 1);
+
+    drop(orig_a);
+    assert!(weak.upgrade().is_none());
+}
+
 /////////////////////////////////////////
 
 #[derive(Clone)]
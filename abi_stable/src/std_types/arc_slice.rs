@@ -0,0 +1,730 @@
+//! Contains the ffi-safe equivalents of `std::sync::Arc<[T]>` and `std::sync::Arc<str>`.
+
+use std::{
+    borrow::Borrow,
+    fmt::{self, Display},
+    marker::PhantomData,
+    ops::Deref,
+    ptr,
+    sync::Arc,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    marker_type::ErasedPrefix,
+    prefix_type::{PrefixRef, WithMetadata},
+    std_types::{RSlice, RStr, RString, RVec},
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod test;
+
+mod private {
+    use super::*;
+
+    /// Ffi-safe version of `std::sync::Arc<[T]>`,
+    /// a reference counted slice.
+    ///
+    /// Cloning an `RArcSlice` only increments the reference count,
+    /// regardless of which dynamic library created it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RArcSlice, RVec};
+    ///
+    /// let slice = RArcSlice::from(&[3, 5, 8][..]);
+    /// let clone = slice.clone();
+    ///
+    /// assert_eq!(slice, [3, 5, 8]);
+    /// assert!(RArcSlice::ptr_eq(&slice, &clone));
+    /// assert_eq!(RArcSlice::strong_count(&slice), 2);
+    ///
+    /// let from_vec = RArcSlice::from(RVec::from(vec![13, 21]));
+    /// assert_eq!(&from_vec[..], &[13, 21]);
+    ///
+    /// ```
+    #[derive(StableAbi)]
+    #[repr(C)]
+    pub struct RArcSlice<T> {
+        data: *const T,
+        len: usize,
+        #[sabi(unsafe_change_type = ArcSliceVtable_Ref<T>)]
+        vtable: PrefixRef<ErasedPrefix>,
+        _marker: PhantomData<T>,
+    }
+
+    impl_from_rust_repr! {
+        impl[T] From<Arc<[T]>> for RArcSlice<T> {
+            fn(this){
+                let len = this.len();
+                RArcSlice {
+                    data: Arc::into_raw(this) as *const T,
+                    len,
+                    vtable: unsafe{ VTableGetter::<T>::LIB_VTABLE.0.cast() },
+                    _marker: Default::default(),
+                }
+            }
+        }
+    }
+
+    impl<T> RArcSlice<T> {
+        #[inline(always)]
+        pub(super) const fn data(&self) -> *const T {
+            self.data
+        }
+
+        #[inline(always)]
+        pub(super) const fn len_(&self) -> usize {
+            self.len
+        }
+
+        #[inline(always)]
+        pub(super) const fn vtable(&self) -> ArcSliceVtable_Ref<T> {
+            unsafe { ArcSliceVtable_Ref::<T>(self.vtable.cast()) }
+        }
+
+        /// # Safety
+        ///
+        /// `data` and `len` must come from an `Arc<[T]>`,
+        /// and `vtable` must be the vtable of the `RArcSlice` it came from.
+        #[inline(always)]
+        pub(super) const unsafe fn from_raw_parts(
+            data: *const T,
+            len: usize,
+            vtable: ArcSliceVtable_Ref<T>,
+        ) -> Self {
+            RArcSlice {
+                data,
+                len,
+                vtable: unsafe { vtable.0.cast() },
+                _marker: PhantomData,
+            }
+        }
+    }
+
+    /// Ffi-safe version of `std::sync::Arc<str>`,
+    /// a reference counted string.
+    ///
+    /// Cloning an `RArcStr` only increments the reference count,
+    /// regardless of which dynamic library created it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RArcStr, RString};
+    ///
+    /// let name = RArcStr::from("hello");
+    /// let clone = name.clone();
+    ///
+    /// assert_eq!(name, "hello");
+    /// assert!(RArcStr::ptr_eq(&name, &clone));
+    ///
+    /// let from_string = RArcStr::from(RString::from("world"));
+    /// assert_eq!(from_string.as_str(), "world");
+    ///
+    /// ```
+    #[derive(Clone, StableAbi)]
+    #[repr(C)]
+    pub struct RArcStr {
+        inner: RArcSlice<u8>,
+    }
+
+    impl RArcStr {
+        /// # Safety
+        ///
+        /// `inner` must contain valid utf-8.
+        #[inline(always)]
+        pub(super) const unsafe fn from_utf8_unchecked(inner: RArcSlice<u8>) -> Self {
+            Self { inner }
+        }
+
+        #[inline(always)]
+        pub(super) const fn inner(&self) -> &RArcSlice<u8> {
+            &self.inner
+        }
+    }
+}
+
+pub use self::private::{RArcSlice, RArcStr};
+
+use self::vtable_mod::{ArcSliceVtable_Ref, VTableGetter};
+
+impl<T> RArcSlice<T> {
+    /// Constructs an `RArcSlice` by cloning the elements of `slice`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArcSlice;
+    ///
+    /// let slice = RArcSlice::new(&["foo", "bar"]);
+    ///
+    /// assert_eq!(slice, ["foo", "bar"]);
+    ///
+    /// ```
+    pub fn new(slice: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Arc::<[T]>::from(slice).into()
+    }
+
+    /// Gets a reference to the elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArcSlice;
+    ///
+    /// let slice = RArcSlice::from(vec![0, 1, 2]);
+    ///
+    /// assert_eq!(slice.as_slice(), &[0, 1, 2]);
+    ///
+    /// ```
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { &*ptr::slice_from_raw_parts(self.data(), self.len_()) }
+    }
+
+    /// Gets an `RSlice` of the elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RArcSlice, RSlice};
+    ///
+    /// let slice = RArcSlice::from(vec![0, 1, 2]);
+    ///
+    /// assert_eq!(slice.as_rslice(), RSlice::from_slice(&[0, 1, 2]));
+    ///
+    /// ```
+    #[inline]
+    pub fn as_rslice(&self) -> RSlice<'_, T> {
+        self.as_slice().into()
+    }
+
+    /// The number of elements in the slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArcSlice;
+    ///
+    /// assert_eq!(RArcSlice::<u8>::default().len(), 0);
+    /// assert_eq!(RArcSlice::from(vec![3, 5]).len(), 2);
+    ///
+    /// ```
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len_()
+    }
+
+    /// Whether the slice is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArcSlice;
+    ///
+    /// assert!(RArcSlice::<u8>::default().is_empty());
+    /// assert!(!RArcSlice::from(vec![3, 5]).is_empty());
+    ///
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len_() == 0
+    }
+
+    /// Gets the number of `RArcSlice` that point to the elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArcSlice;
+    ///
+    /// let slice = RArcSlice::from(vec![3, 5]);
+    /// assert_eq!(RArcSlice::strong_count(&slice), 1);
+    ///
+    /// let clone = slice.clone();
+    /// assert_eq!(RArcSlice::strong_count(&slice), 2);
+    ///
+    /// ```
+    pub fn strong_count(this: &Self) -> usize {
+        let vtable = this.vtable();
+        unsafe { vtable.strong_count()(this) }
+    }
+
+    /// Whether both `RArcSlice`s point to the same allocation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArcSlice;
+    ///
+    /// let slice = RArcSlice::from(vec![3, 5]);
+    ///
+    /// assert!(RArcSlice::ptr_eq(&slice, &slice.clone()));
+    /// assert!(!RArcSlice::ptr_eq(&slice, &RArcSlice::from(vec![3, 5])));
+    ///
+    /// ```
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        ptr::eq(this.data(), other.data()) && this.len_() == other.len_()
+    }
+}
+
+impl<T> Default for RArcSlice<T> {
+    fn default() -> Self {
+        Arc::<[T]>::from(Vec::new()).into()
+    }
+}
+
+impl<T> Clone for RArcSlice<T> {
+    fn clone(&self) -> Self {
+        unsafe { (self.vtable().clone_())(self) }
+    }
+}
+
+impl<T> Drop for RArcSlice<T> {
+    fn drop(&mut self) {
+        unsafe {
+            let vtable = self.vtable();
+            (vtable.destructor())(self.data(), self.len_());
+        }
+    }
+}
+
+impl<T> Deref for RArcSlice<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> Borrow<[T]> for RArcSlice<T> {
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> AsRef<[T]> for RArcSlice<T> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RArcSlice<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<T> From<&[T]> for RArcSlice<T>
+where
+    T: Clone,
+{
+    fn from(this: &[T]) -> Self {
+        Self::new(this)
+    }
+}
+
+impl<T> From<RSlice<'_, T>> for RArcSlice<T>
+where
+    T: Clone,
+{
+    fn from(this: RSlice<'_, T>) -> Self {
+        Self::new(this.as_slice())
+    }
+}
+
+impl<T> From<Vec<T>> for RArcSlice<T> {
+    fn from(this: Vec<T>) -> Self {
+        Arc::<[T]>::from(this).into()
+    }
+}
+
+impl<T> From<RVec<T>> for RArcSlice<T> {
+    fn from(this: RVec<T>) -> Self {
+        this.into_vec().into()
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for RArcSlice<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().collect::<Arc<[T]>>().into()
+    }
+}
+
+impl<'de, T> Deserialize<'de> for RArcSlice<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <Vec<T> as Deserialize<'de>>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl<T> Serialize for RArcSlice<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+unsafe impl<T> Sync for RArcSlice<T> where T: Send + Sync {}
+
+unsafe impl<T> Send for RArcSlice<T> where T: Send + Sync {}
+
+impl<T> Unpin for RArcSlice<T> {}
+
+slice_like_impl_cmp_traits! {
+    impl[] RArcSlice<T>,
+    where[];
+    Vec<U>,
+    [U],
+    &[U],
+    RSlice<'_, U>,
+    RVec<U>,
+}
+
+slice_like_impl_cmp_traits! {
+    impl[const N: usize] RArcSlice<T>,
+    where[];
+    [U; N],
+}
+
+#[allow(dead_code)]
+type ArcSlice<T> = Arc<[T]>;
+
+shared_impls! {
+    mod = arc_slice_impls
+    new_type = RArcSlice[][T],
+    original_type = ArcSlice,
+}
+
+/////////////////////////////////////////////////////////
+
+impl RArcStr {
+    /// Constructs an `RArcStr` by copying `str_`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArcStr;
+    ///
+    /// let str = RArcStr::new("foo bar");
+    ///
+    /// assert_eq!(str, "foo bar");
+    ///
+    /// ```
+    pub fn new(str_: &str) -> Self {
+        unsafe { Self::from_utf8_unchecked(RArcSlice::new(str_.as_bytes())) }
+    }
+
+    /// Gets the string as a `&str`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArcStr;
+    ///
+    /// let str = RArcStr::from("foo bar");
+    ///
+    /// assert_eq!(str.as_str(), "foo bar");
+    ///
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe { std::str::from_utf8_unchecked(self.inner().as_slice()) }
+    }
+
+    /// Gets the string as an `RStr`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RArcStr, RStr};
+    ///
+    /// let str = RArcStr::from("foo bar");
+    ///
+    /// assert_eq!(str.as_rstr(), RStr::from("foo bar"));
+    ///
+    /// ```
+    #[inline]
+    pub fn as_rstr(&self) -> RStr<'_> {
+        self.as_str().into()
+    }
+
+    /// The length of the string in bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArcStr;
+    ///
+    /// assert_eq!(RArcStr::default().len(), 0);
+    /// assert_eq!(RArcStr::from("hello").len(), 5);
+    ///
+    /// ```
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.inner().len()
+    }
+
+    /// Whether the string is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArcStr;
+    ///
+    /// assert!(RArcStr::default().is_empty());
+    /// assert!(!RArcStr::from("hello").is_empty());
+    ///
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.inner().is_empty()
+    }
+
+    /// Gets the number of `RArcStr` that point to the string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArcStr;
+    ///
+    /// let str = RArcStr::from("hello");
+    /// assert_eq!(RArcStr::strong_count(&str), 1);
+    ///
+    /// let clone = str.clone();
+    /// assert_eq!(RArcStr::strong_count(&str), 2);
+    ///
+    /// ```
+    pub fn strong_count(this: &Self) -> usize {
+        RArcSlice::strong_count(this.inner())
+    }
+
+    /// Whether both `RArcStr`s point to the same allocation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RArcStr;
+    ///
+    /// let str = RArcStr::from("hello");
+    ///
+    /// assert!(RArcStr::ptr_eq(&str, &str.clone()));
+    /// assert!(!RArcStr::ptr_eq(&str, &RArcStr::from("hello")));
+    ///
+    /// ```
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        RArcSlice::ptr_eq(this.inner(), other.inner())
+    }
+}
+
+impl Default for RArcStr {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl Deref for RArcStr {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for RArcStr {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl AsRef<str> for RArcStr {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl AsRef<[u8]> for RArcStr {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl From<&str> for RArcStr {
+    fn from(this: &str) -> Self {
+        Self::new(this)
+    }
+}
+
+impl From<RStr<'_>> for RArcStr {
+    fn from(this: RStr<'_>) -> Self {
+        Self::new(this.as_str())
+    }
+}
+
+impl From<String> for RArcStr {
+    fn from(this: String) -> Self {
+        unsafe { Self::from_utf8_unchecked(this.into_bytes().into()) }
+    }
+}
+
+impl From<RString> for RArcStr {
+    fn from(this: RString) -> Self {
+        unsafe { Self::from_utf8_unchecked(this.into_bytes().into()) }
+    }
+}
+
+impl From<Arc<str>> for RArcStr {
+    fn from(this: Arc<str>) -> Self {
+        // `Arc<str>` and `Arc<[u8]>` have the same layout,
+        // this is how `Arc<str>` is constructed in the standard library.
+        let bytes = unsafe { Arc::from_raw(Arc::into_raw(this) as *const [u8]) };
+        unsafe { Self::from_utf8_unchecked(bytes.into()) }
+    }
+}
+
+impl From<RArcStr> for String {
+    fn from(this: RArcStr) -> String {
+        this.as_str().into()
+    }
+}
+
+impl From<RArcStr> for RString {
+    fn from(this: RArcStr) -> RString {
+        this.as_str().into()
+    }
+}
+
+impl Display for RArcStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<'de> Deserialize<'de> for RArcStr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <String as Deserialize<'de>>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl Serialize for RArcStr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_str().serialize(serializer)
+    }
+}
+
+deref_coerced_impl_cmp_traits! {
+    RArcStr;
+    coerce_to = str,
+    [
+        String,
+        str,
+        &str,
+        RStr<'_>,
+        RString,
+        std::borrow::Cow<'_, str>,
+    ]
+}
+
+#[allow(dead_code)]
+type ArcStr = Arc<str>;
+
+shared_impls! {
+    mod = arc_str_impls
+    new_type = RArcStr[][],
+    original_type = ArcStr,
+}
+
+/////////////////////////////////////////////////////////
+
+mod vtable_mod {
+    use super::*;
+
+    pub(super) struct VTableGetter<'a, T>(&'a T);
+
+    impl<'a, T: 'a> VTableGetter<'a, T> {
+        const DEFAULT_VTABLE: ArcSliceVtable<T> = ArcSliceVtable {
+            destructor: destructor_arc_slice::<T>,
+            clone_: clone_arc_slice::<T>,
+            strong_count: strong_count_arc_slice::<T>,
+        };
+
+        staticref! {
+            const WM_DEFAULT: WithMetadata<ArcSliceVtable<T>> =
+                WithMetadata::new(Self::DEFAULT_VTABLE)
+        }
+
+        // The VTABLE for this type in this executable/library
+        pub(super) const LIB_VTABLE: ArcSliceVtable_Ref<T> =
+            { ArcSliceVtable_Ref(Self::WM_DEFAULT.as_prefix()) };
+    }
+
+    #[derive(StableAbi)]
+    #[repr(C)]
+    #[sabi(kind(Prefix))]
+    #[sabi(missing_field(panic))]
+    pub struct ArcSliceVtable<T> {
+        pub(super) destructor: unsafe extern "C" fn(*const T, usize),
+        pub(super) clone_: unsafe extern "C" fn(&RArcSlice<T>) -> RArcSlice<T>,
+        #[sabi(last_prefix_field)]
+        pub(super) strong_count: unsafe extern "C" fn(&RArcSlice<T>) -> usize,
+    }
+
+    unsafe extern "C" fn destructor_arc_slice<T>(data: *const T, len: usize) {
+        extern_fn_panic_handling! {no_early_return; unsafe {
+            drop(Arc::from_raw(ptr::slice_from_raw_parts(data, len)));
+        }}
+    }
+
+    unsafe fn with_arc_ref<T, F, R>(this: &RArcSlice<T>, f: F) -> R
+    where
+        F: FnOnce(&Arc<[T]>) -> R,
+    {
+        let x = ptr::slice_from_raw_parts(this.data(), this.len());
+        let x = unsafe { Arc::from_raw(x) };
+        let x = std::mem::ManuallyDrop::new(x);
+        f(&x)
+    }
+
+    unsafe extern "C" fn clone_arc_slice<T>(this: &RArcSlice<T>) -> RArcSlice<T> {
+        unsafe {
+            with_arc_ref(this, |x| {
+                let clone = Arc::into_raw(Arc::clone(x)) as *const T;
+                RArcSlice::from_raw_parts(clone, this.len(), this.vtable())
+            })
+        }
+    }
+
+    unsafe extern "C" fn strong_count_arc_slice<T>(this: &RArcSlice<T>) -> usize {
+        unsafe { with_arc_ref(this, Arc::strong_count) }
+    }
+}
@@ -0,0 +1,77 @@
+use super::*;
+
+use std::cell::Cell;
+
+struct DecrementOnDrop<'a>(&'a Cell<u32>);
+
+impl Clone for DecrementOnDrop<'_> {
+    fn clone(&self) -> Self {
+        self.0.set(self.0.get() + 1);
+        DecrementOnDrop(self.0)
+    }
+}
+
+impl Drop for DecrementOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() - 1);
+    }
+}
+
+#[test]
+fn clone_and_drop() {
+    let count = Cell::new(3);
+    let elems = (0..3).map(|_| DecrementOnDrop(&count)).collect::<RVec<_>>();
+
+    let slice = RArcSlice::from(elems);
+    let clone = slice.clone();
+    assert_eq!(count.get(), 3);
+    assert_eq!(RArcSlice::strong_count(&slice), 2);
+    assert!(RArcSlice::ptr_eq(&slice, &clone));
+
+    drop(slice);
+    assert_eq!(count.get(), 3);
+    assert_eq!(RArcSlice::strong_count(&clone), 1);
+
+    drop(clone);
+    assert_eq!(count.get(), 0);
+}
+
+#[test]
+fn zero_sized_and_empty() {
+    let units = RArcSlice::from(vec![(); 10]);
+    assert_eq!(units.len(), 10);
+    assert_eq!(units.clone().as_slice(), &[(); 10][..]);
+
+    let empty = RArcSlice::<String>::default();
+    assert!(empty.is_empty());
+    assert_eq!(empty.clone(), Vec::<String>::new());
+}
+
+#[test]
+fn conversions() {
+    let slice: RArcSlice<u32> = (0..4).collect();
+    assert_eq!(slice, [0, 1, 2, 3]);
+    assert_eq!(slice, RArcSlice::from(RSlice::from_slice(&[0, 1, 2, 3])));
+    assert_eq!(slice, RArcSlice::from(Arc::<[u32]>::from(vec![0, 1, 2, 3])));
+
+    let str_ = RArcStr::from(String::from("hello"));
+    assert_eq!(str_, "hello");
+    assert_eq!(str_, RArcStr::from(Arc::<str>::from("hello")));
+    assert_eq!(str_, RArcStr::from(RStr::from("hello")));
+    assert_eq!(RString::from(str_.clone()), "hello");
+    assert_eq!(str_.to_string(), "hello");
+    assert_eq!(format!("{:?}", str_), "\"hello\"");
+}
+
+#[test]
+fn serde() {
+    let slice = RArcSlice::from(vec![3u8, 5, 8]);
+    let json = serde_json::to_string(&slice).unwrap();
+    assert_eq!(json, "[3,5,8]");
+    assert_eq!(serde_json::from_str::<RArcSlice<u8>>(&json).unwrap(), slice);
+
+    let str_ = RArcStr::from("hello");
+    let json = serde_json::to_string(&str_).unwrap();
+    assert_eq!(json, "\"hello\"");
+    assert_eq!(serde_json::from_str::<RArcStr>(&json).unwrap(), str_);
+}
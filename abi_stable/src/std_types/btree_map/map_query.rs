@@ -0,0 +1,91 @@
+use super::*;
+
+/// A trait object used in methods that look up map entries with a borrowed key.
+#[derive(StableAbi)]
+#[repr(C)]
+pub struct MapQuery<'a, K> {
+    _marker: NotCopyNotClone,
+    compare: extern "C" fn(&K, RRef<'_, ErasedObject>) -> RCmpOrdering,
+    query: RRef<'a, ErasedObject>,
+}
+
+impl<'a, K> MapQuery<'a, K> {
+    #[inline]
+    pub(super) fn new<Q>(query: &'a &'a Q) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + 'a + ?Sized,
+    {
+        MapQuery {
+            _marker: NotCopyNotClone,
+            compare: compare::<K, Q>,
+            query: unsafe { RRef::new(query).transmute() },
+        }
+    }
+
+    #[inline]
+    pub(super) unsafe fn as_static(&self) -> &MapQuery<'static, K> {
+        unsafe { crate::utils::transmute_reference(self) }
+    }
+}
+
+impl<'a, K> MapQuery<'a, K> {
+    /// Compares `key` with the query, in that order.
+    #[inline]
+    pub(super) fn compare_key(&self, key: &K) -> Ordering {
+        (self.compare)(key, self.query).to_ordering()
+    }
+
+    #[inline]
+    pub(super) unsafe fn as_mapkey(&self) -> MapKey<K> {
+        MapKey::Query(NonNull::from(unsafe { self.as_static() }))
+    }
+}
+
+extern "C" fn compare<K, Q>(key: &K, query: RRef<'_, ErasedObject>) -> RCmpOrdering
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    extern_fn_panic_handling! {
+        let query = unsafe{ query.transmute_into_ref::<&Q>() };
+        RCmpOrdering::from_ordering(key.borrow().cmp(*query))
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// One of the bounds of a range of keys, used by `RBTreeMap::{range, range_mut}`.
+#[derive(StableAbi)]
+#[repr(u8)]
+pub enum QueryBound<'a, K> {
+    Included(MapQuery<'a, K>),
+    Excluded(MapQuery<'a, K>),
+    Unbounded,
+}
+
+impl<'a, K> QueryBound<'a, K> {
+    #[inline]
+    pub(super) fn new<Q>(bound: &'a Bound<&'a Q>) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + 'a + ?Sized,
+    {
+        match bound {
+            Bound::Included(query) => QueryBound::Included(MapQuery::new(query)),
+            Bound::Excluded(query) => QueryBound::Excluded(MapQuery::new(query)),
+            Bound::Unbounded => QueryBound::Unbounded,
+        }
+    }
+
+    #[inline]
+    pub(super) unsafe fn as_mapkey_bound(&self) -> Bound<MapKey<K>> {
+        unsafe {
+            match self {
+                QueryBound::Included(query) => Bound::Included(query.as_mapkey()),
+                QueryBound::Excluded(query) => Bound::Excluded(query.as_mapkey()),
+                QueryBound::Unbounded => Bound::Unbounded,
+            }
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use super::*;
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::std_types::RString;
+
+fn _covariant_btreemap<'a: 'b, 'b, T>(map: BTreeMap<&'a T, &'a T>) -> BTreeMap<&'b T, &'b T> {
+    map
+}
+
+fn _covariant_rbtreemap<'a: 'b, 'b, T>(
+    map: RBTreeMap<&'a T, &'a T>,
+) -> RBTreeMap<&'b T, &'b T> {
+    map
+}
+
+fn new_stdmap() -> BTreeMap<u32, u32> {
+    vec![(90, 40), (10, 20), (88, 30), (77, 22)]
+        .into_iter()
+        .collect()
+}
+
+fn new_map() -> RBTreeMap<RString, RString> {
+    vec![("90", "40"), ("10", "20"), ("88", "30"), ("77", "22")]
+        .into_iter()
+        .map(|(k, v)| (RString::from(k), RString::from(v)))
+        .collect()
+}
+
+#[test]
+fn test_default() {
+    let default_ = RBTreeMap::<u32, u32>::default();
+    let new_ = RBTreeMap::<u32, u32>::new();
+
+    assert_eq!(default_.len(), 0);
+    assert_eq!(default_, new_);
+}
+
+#[test]
+fn insert_get_remove() {
+    let mut map = RBTreeMap::<RString, u32>::new();
+
+    assert_eq!(map.insert("a".into(), 0), RNone);
+    assert_eq!(map.insert("b".into(), 1), RNone);
+    assert_eq!(map.insert("a".into(), 2), RSome(0));
+
+    assert_eq!(map.get("a"), Some(&2));
+    assert_eq!(map.get_p(&"b".into()), Some(&1));
+    assert_eq!(map.get("c"), None);
+
+    *map.get_mut("b").unwrap() += 10;
+    assert_eq!(map["b"], 11);
+    map["b"] += 1;
+    assert_eq!(map.index_p(&"b".into()), &12);
+
+    assert_eq!(map.remove("a"), RSome(2));
+    assert_eq!(map.remove("a"), RNone);
+    assert_eq!(map.remove_entry_p(&"b".into()), RSome(Tuple2("b".into(), 12)));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn ordered_iteration() {
+    let map = new_stdmap().into_iter().collect::<RBTreeMap<u32, u32>>();
+
+    let expected = new_stdmap().into_iter().collect::<Vec<_>>();
+
+    assert_eq!(
+        map.iter().map(|Tuple2(k, v)| (*k, *v)).collect::<Vec<_>>(),
+        expected,
+    );
+    assert_eq!(
+        map.iter().rev().map(|Tuple2(k, v)| (*k, *v)).collect::<Vec<_>>(),
+        expected.iter().cloned().rev().collect::<Vec<_>>(),
+    );
+    assert_eq!(
+        map.clone()
+            .into_iter()
+            .rev()
+            .map(Tuple2::into_tuple)
+            .collect::<Vec<_>>(),
+        expected.iter().cloned().rev().collect::<Vec<_>>(),
+    );
+
+    // Iterating from both ends.
+    let mut iter = map.iter();
+    assert_eq!(iter.next(), Some(Tuple2(&10, &20)));
+    assert_eq!(iter.next_back(), Some(Tuple2(&90, &40)));
+    assert_eq!(iter.next(), Some(Tuple2(&77, &22)));
+    assert_eq!(iter.next_back(), Some(Tuple2(&88, &30)));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    assert_eq!(map.keys().rev().collect::<Vec<_>>(), [&90, &88, &77, &10]);
+    assert_eq!(map.values().collect::<Vec<_>>(), [&20, &22, &30, &40]);
+}
+
+#[test]
+fn iter_mut() {
+    let mut map = new_stdmap().into_iter().collect::<RBTreeMap<u32, u32>>();
+
+    for Tuple2(k, v) in &mut map {
+        *v += *k;
+    }
+
+    assert_eq!(map.values().collect::<Vec<_>>(), [&30, &99, &118, &130]);
+}
+
+#[test]
+fn first_last() {
+    let mut map = RBTreeMap::<u32, u32>::new();
+    assert_eq!(map.first_key_value(), RNone);
+    assert_eq!(map.last_key_value(), RNone);
+
+    map.extend(new_stdmap());
+
+    assert_eq!(map.first_key_value(), RSome(Tuple2(&10, &20)));
+    assert_eq!(map.last_key_value(), RSome(Tuple2(&90, &40)));
+}
+
+#[test]
+fn range() {
+    let mut map = (0..10).map(|x| (x, x * 10)).collect::<RBTreeMap<u32, u32>>();
+
+    let keys = |iter: Iter<'_, u32, u32>| iter.map(|x| *x.0).collect::<Vec<_>>();
+
+    assert_eq!(keys(map.range(3..6)), [3, 4, 5]);
+    assert_eq!(keys(map.range(3..=6)), [3, 4, 5, 6]);
+    assert_eq!(keys(map.range(..2)), [0, 1]);
+    assert_eq!(keys(map.range(8..)), [8, 9]);
+    assert_eq!(keys(map.range(..)).len(), 10);
+    assert_eq!(keys(map.range(4..4)), Vec::<u32>::new());
+    assert_eq!(keys(map.range(20..)), Vec::<u32>::new());
+    assert_eq!(
+        keys(map.range((Bound::Excluded(3), Bound::Excluded(6)))),
+        [4, 5]
+    );
+    assert_eq!(
+        map.range(2..5).rev().map(|x| *x.0).collect::<Vec<_>>(),
+        [4, 3, 2]
+    );
+
+    for Tuple2(_, v) in map.range_mut(7..) {
+        *v = 0;
+    }
+    assert_eq!(
+        map.values().cloned().collect::<Vec<_>>(),
+        [0, 10, 20, 30, 40, 50, 60, 0, 0, 0]
+    );
+}
+
+#[test]
+fn range_borrowed_query() {
+    let map = new_map();
+
+    let keys = map
+        .range::<str, _>((Bound::Included("20"), Bound::Excluded("89")))
+        .map(|x| x.0.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(keys, ["77", "88"]);
+}
+
+#[test]
+fn range_invalid_bounds() {
+    let map = (0..10).map(|x| (x, x)).collect::<RBTreeMap<u32, u32>>();
+
+    #[allow(clippy::reversed_empty_ranges)]
+    let res = catch_unwind(AssertUnwindSafe(|| map.range(5..3).count()));
+    assert!(res.is_err());
+
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        map.range((Bound::Excluded(3), Bound::Excluded(3))).count()
+    }));
+    assert!(res.is_err());
+}
+
+#[test]
+fn from_into_btreemap() {
+    let std_map = new_stdmap();
+    let map = RBTreeMap::from(std_map.clone());
+
+    assert_eq!(map.len(), std_map.len());
+    for (k, v) in &std_map {
+        assert_eq!(map.get(k), Some(v));
+    }
+
+    assert_eq!(BTreeMap::from(map), std_map);
+}
+
+#[test]
+fn clone_eq_ord() {
+    let map = new_map();
+    let clone = map.clone();
+    assert_eq!(map, clone);
+
+    let mut other = map.clone();
+    other.insert("91".into(), "0".into());
+    assert_ne!(map, other);
+    assert!(map < other);
+}
+
+#[test]
+fn test_serde() {
+    let map = new_map();
+
+    let json = r##"{"10":"20","77":"22","88":"30","90":"40"}"##;
+
+    assert_eq!(serde_json::to_string(&map).unwrap(), json);
+
+    let deserialized = serde_json::from_str::<RBTreeMap<RString, RString>>(json).unwrap();
+    assert_eq!(deserialized, map);
+}
+
+fn assert_is_occupied<K, V>(map: &mut RBTreeMap<K, V>, k: K, v: V)
+where
+    K: Ord + Clone + Debug,
+    V: Clone + Debug + PartialEq,
+{
+    let mut entry = map.entry(k.clone());
+    assert_matches!(&entry, REntry::Occupied { .. });
+    assert_eq!(entry.key(), &k);
+    assert_eq!(entry.get().cloned(), Some(v.clone()));
+    assert_eq!(entry.get_mut().cloned(), Some(v));
+}
+
+fn assert_is_vacant<K, V>(map: &mut RBTreeMap<K, V>, k: K)
+where
+    K: Ord + Clone + Debug,
+    V: Clone + Debug + PartialEq,
+{
+    let mut entry = map.entry(k.clone());
+    assert_matches!(&entry, REntry::Vacant { .. });
+    assert_eq!(entry.key(), &k);
+    assert_eq!(entry.get().cloned(), None);
+    assert_eq!(entry.get_mut().cloned(), None);
+}
+
+#[test]
+fn entry() {
+    let mut map = new_map();
+
+    assert_is_occupied(&mut map, "90".into(), "40".into());
+    assert_is_vacant(&mut map, "12".into());
+
+    assert_eq!(
+        *map.entry("12".into()).or_insert("100".into()),
+        RString::from("100")
+    );
+    assert_is_occupied(&mut map, "12".into(), "100".into());
+
+    assert_eq!(
+        *map.entry("12".into())
+            .and_modify(|v| *v = "what".into())
+            .or_insert_with(|| unreachable!()),
+        RString::from("what")
+    );
+    assert_is_occupied(&mut map, "12".into(), "what".into());
+
+    match map.entry("12".into()) {
+        REntry::Occupied(entry) => assert_eq!(entry.remove(), "what"),
+        REntry::Vacant(_) => unreachable!(),
+    }
+    assert_is_vacant(&mut map, "12".into());
+
+    assert_eq!(
+        map.keys().map(|x| x.as_str()).collect::<Vec<_>>(),
+        ["10", "77", "88", "90"]
+    );
+}
@@ -0,0 +1,958 @@
+//! Contains the ffi-safe equivalent of `std::collections::BTreeSet`, and related items.
+#![allow(clippy::missing_const_for_fn)]
+
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    collections::BTreeSet,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    iter::{FromIterator, Peekable},
+    ops::{BitAnd, BitOr, BitXor, RangeBounds, Sub},
+};
+
+use crate::std_types::{
+    btree_map::{self, RBTreeMap},
+    ROption, Tuple2,
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod test;
+
+/// An ffi-safe ordered set, which wraps an [`RBTreeMap<T, ()>`](../struct.RBTreeMap.html),
+/// only requiring the `T: Ord` bound when constructing it.
+///
+/// All the operations go through the vtable of the wrapped `RBTreeMap`,
+/// so this can be passed to and used from other dynamic libraries.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::std_types::{RBTreeSet, RSome};
+///
+/// let mut primes = vec![7, 2, 5, 3].into_iter().collect::<RBTreeSet<u32>>();
+///
+/// assert!(primes.contains(&5));
+/// assert!(!primes.insert(2));
+/// assert!(primes.insert(11));
+///
+/// assert_eq!(primes.first(), RSome(&2));
+/// assert_eq!(primes.last(), RSome(&11));
+///
+/// assert_eq!(primes.iter().collect::<Vec<_>>(), [&2, &3, &5, &7, &11]);
+/// assert_eq!(primes.range(4..10).collect::<Vec<_>>(), [&5, &7]);
+///
+/// let odd = (1..10).step_by(2).collect::<RBTreeSet<u32>>();
+/// assert_eq!(primes.intersection(&odd).collect::<Vec<_>>(), [&3, &5, &7]);
+///
+/// ```
+///
+#[derive(StableAbi)]
+#[repr(C)]
+pub struct RBTreeSet<T> {
+    map: RBTreeMap<T, ()>,
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl<T> RBTreeSet<T> {
+    /// Constructs an empty RBTreeSet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RString};
+    ///
+    /// let mut set = RBTreeSet::<RString>::new();
+    /// assert!(set.is_empty());
+    /// set.insert("Hello".into());
+    /// assert_eq!(set.is_empty(), false);
+    ///
+    /// ```
+    #[inline]
+    pub fn new() -> RBTreeSet<T>
+    where
+        T: Ord,
+    {
+        RBTreeSet {
+            map: RBTreeMap::new(),
+        }
+    }
+}
+
+impl<T> RBTreeSet<T> {
+    /// Returns whether the set contains the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RString};
+    ///
+    /// let mut set = RBTreeSet::<RString>::new();
+    /// assert_eq!(set.contains("boo"), false);
+    /// set.insert("boo".into());
+    /// assert_eq!(set.contains("boo"), true);
+    ///
+    /// ```
+    pub fn contains<Q>(&self, query: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(query)
+    }
+
+    /// Removes the value from the set, returning whether it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(set.remove(&0), true);
+    /// assert_eq!(set.remove(&0), false);
+    ///
+    /// ```
+    pub fn remove<Q>(&mut self, query: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove_entry(query).is_some()
+    }
+
+    /// Removes the value from the set, returning it if it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RNone, RSome, RString};
+    ///
+    /// let mut set = RBTreeSet::<RString>::new();
+    /// set.insert("foo".into());
+    ///
+    /// assert_eq!(set.take("foo"), RSome(RString::from("foo")));
+    /// assert_eq!(set.take("foo"), RNone);
+    ///
+    /// ```
+    pub fn take<Q>(&mut self, query: &Q) -> ROption<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove_entry(query).map(|x| x.0)
+    }
+
+    /// Iterates over the elements of the set that are in `range`, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end,
+    /// or if the start and end of the range are equal and both excluded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let set = (0..10).collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(set.range(3..6).collect::<Vec<_>>(), [&3, &4, &5]);
+    /// assert_eq!(set.range(..=2).rev().collect::<Vec<_>>(), [&2, &1, &0]);
+    ///
+    /// ```
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Iter {
+            inner: self.map.range(range),
+        }
+    }
+}
+
+impl<T> RBTreeSet<T> {
+    /// Returns whether the set contains the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RString};
+    ///
+    /// let mut set = RBTreeSet::<RString>::new();
+    /// assert_eq!(set.contains_p(&RString::from("boo")), false);
+    /// set.insert("boo".into());
+    /// assert_eq!(set.contains_p(&RString::from("boo")), true);
+    ///
+    /// ```
+    pub fn contains_p(&self, value: &T) -> bool {
+        self.map.contains_key_p(value)
+    }
+
+    /// Removes the value from the set, returning whether it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(set.remove_p(&0), true);
+    /// assert_eq!(set.remove_p(&0), false);
+    ///
+    /// ```
+    pub fn remove_p(&mut self, value: &T) -> bool {
+        self.map.remove_entry_p(value).is_some()
+    }
+
+    /// Removes the value from the set, returning it if it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RNone, RSome};
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(set.take_p(&0), RSome(0));
+    /// assert_eq!(set.take_p(&0), RNone);
+    ///
+    /// ```
+    pub fn take_p(&mut self, value: &T) -> ROption<T> {
+        self.map.remove_entry_p(value).map(|x| x.0)
+    }
+
+    /// Inserts a value into the set, returning whether it wasn't already in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = RBTreeSet::<u32>::new();
+    ///
+    /// assert_eq!(set.insert(0), true);
+    /// assert_eq!(set.insert(0), false);
+    /// assert_eq!(set.len(), 1);
+    ///
+    /// ```
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    /// Removes all the elements in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// set.clear();
+    ///
+    /// assert!(set.is_empty());
+    ///
+    /// ```
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the amount of elements in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = RBTreeSet::<u32>::new();
+    ///
+    /// assert_eq!(set.len(), 0);
+    /// set.insert(0);
+    /// assert_eq!(set.len(), 1);
+    ///
+    /// ```
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns whether the set contains any elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let mut set = RBTreeSet::<u32>::new();
+    ///
+    /// assert_eq!(set.is_empty(), true);
+    /// set.insert(0);
+    /// assert_eq!(set.is_empty(), false);
+    ///
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the smallest element in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RNone, RSome};
+    ///
+    /// let mut set = RBTreeSet::<u32>::new();
+    /// assert_eq!(set.first(), RNone);
+    ///
+    /// set.extend(vec![3, 0, 5]);
+    /// assert_eq!(set.first(), RSome(&0));
+    ///
+    /// ```
+    pub fn first(&self) -> ROption<&T> {
+        self.map.first_key_value().map(|x| x.0)
+    }
+
+    /// Returns the largest element in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RBTreeSet, RNone, RSome};
+    ///
+    /// let mut set = RBTreeSet::<u32>::new();
+    /// assert_eq!(set.last(), RNone);
+    ///
+    /// set.extend(vec![3, 0, 5]);
+    /// assert_eq!(set.last(), RSome(&5));
+    ///
+    /// ```
+    pub fn last(&self) -> ROption<&T> {
+        self.map.last_key_value().map(|x| x.0)
+    }
+
+    /// Iterates over the elements in the set, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let set = vec![3, 0].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![&0, &3]);
+    /// assert_eq!(set.iter().rev().collect::<Vec<_>>(), vec![&3, &0]);
+    ///
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.map.iter(),
+        }
+    }
+
+    /// Iterates over the elements that are in `self` or `other`,
+    /// in order and without duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let left = vec![0, 1, 2].into_iter().collect::<RBTreeSet<u32>>();
+    /// let right = vec![1, 2, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(left.union(&right).collect::<Vec<_>>(), vec![&0, &1, &2, &3]);
+    ///
+    /// ```
+    pub fn union<'a>(&'a self, other: &'a RBTreeSet<T>) -> Union<'a, T> {
+        Union {
+            inner: MergeIter::new(self, other),
+        }
+    }
+
+    /// Iterates over the elements that are in both `self` and `other`, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let left = vec![0, 1, 2].into_iter().collect::<RBTreeSet<u32>>();
+    /// let right = vec![1, 2, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(left.intersection(&right).collect::<Vec<_>>(), vec![&1, &2]);
+    ///
+    /// ```
+    pub fn intersection<'a>(&'a self, other: &'a RBTreeSet<T>) -> Intersection<'a, T> {
+        Intersection {
+            inner: MergeIter::new(self, other),
+        }
+    }
+
+    /// Iterates over the elements that are in `self` but not in `other`, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let left = vec![0, 1, 2].into_iter().collect::<RBTreeSet<u32>>();
+    /// let right = vec![1, 2, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(left.difference(&right).collect::<Vec<_>>(), vec![&0]);
+    /// assert_eq!(right.difference(&left).collect::<Vec<_>>(), vec![&3]);
+    ///
+    /// ```
+    pub fn difference<'a>(&'a self, other: &'a RBTreeSet<T>) -> Difference<'a, T> {
+        Difference {
+            inner: MergeIter::new(self, other),
+        }
+    }
+
+    /// Iterates over the elements that are in either `self` or `other`,
+    /// but not in both, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let left = vec![0, 1, 2].into_iter().collect::<RBTreeSet<u32>>();
+    /// let right = vec![1, 2, 3].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert_eq!(
+    ///     left.symmetric_difference(&right).collect::<Vec<_>>(),
+    ///     vec![&0, &3],
+    /// );
+    ///
+    /// ```
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a RBTreeSet<T>,
+    ) -> SymmetricDifference<'a, T> {
+        SymmetricDifference {
+            inner: MergeIter::new(self, other),
+        }
+    }
+
+    /// Returns whether `self` and `other` have no elements in common.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let set = vec![0, 1].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert!(set.is_disjoint(&vec![2, 3].into_iter().collect()));
+    /// assert!(!set.is_disjoint(&vec![1, 2].into_iter().collect()));
+    ///
+    /// ```
+    pub fn is_disjoint(&self, other: &RBTreeSet<T>) -> bool
+    where
+        T: Ord,
+    {
+        self.intersection(other).next().is_none()
+    }
+
+    /// Returns whether all the elements in `self` are in `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let set = vec![0, 1].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert!(set.is_subset(&vec![0, 1, 2].into_iter().collect()));
+    /// assert!(!set.is_subset(&vec![1, 2].into_iter().collect()));
+    ///
+    /// ```
+    pub fn is_subset(&self, other: &RBTreeSet<T>) -> bool
+    where
+        T: Ord,
+    {
+        self.len() <= other.len() && self.difference(other).next().is_none()
+    }
+
+    /// Returns whether all the elements in `other` are in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RBTreeSet;
+    ///
+    /// let set = vec![0, 1, 2].into_iter().collect::<RBTreeSet<u32>>();
+    ///
+    /// assert!(set.is_superset(&vec![0, 1].into_iter().collect()));
+    /// assert!(!set.is_superset(&vec![2, 3].into_iter().collect()));
+    ///
+    /// ```
+    pub fn is_superset(&self, other: &RBTreeSet<T>) -> bool
+    where
+        T: Ord,
+    {
+        other.is_subset(self)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// An iterator over the elements of an `RBTreeSet`, in order.
+///
+/// This `struct` is created by the [`iter`] and [`range`] methods on [`RBTreeSet`].
+///
+/// [`iter`]: RBTreeSet::iter
+/// [`range`]: RBTreeSet::range
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct Iter<'a, T: 'a> {
+    inner: btree_map::Iter<'a, T, ()>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Iter {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next().map(|tuple| tuple.0)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.inner.next_back().map(|tuple| tuple.0)
+    }
+}
+
+/// An iterator over the elements of an `RBTreeSet` by value, in order.
+///
+/// This `struct` is created by the `into_iter` method on [`RBTreeSet`].
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct IntoIter<T> {
+    inner: btree_map::IntoIter<T, ()>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.next().map(|tuple| tuple.0)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.next_back().map(|tuple| tuple.0)
+    }
+}
+
+/// Walks two sorted iterators in lockstep,
+/// returning the next element of either (or both, when they're equal).
+struct MergeIter<'a, T> {
+    left: Peekable<Iter<'a, T>>,
+    right: Peekable<Iter<'a, T>>,
+}
+
+impl<'a, T> MergeIter<'a, T> {
+    fn new(left: &'a RBTreeSet<T>, right: &'a RBTreeSet<T>) -> Self {
+        MergeIter {
+            left: left.iter().peekable(),
+            right: right.iter().peekable(),
+        }
+    }
+}
+
+impl<T> Clone for MergeIter<'_, T> {
+    fn clone(&self) -> Self {
+        MergeIter {
+            left: self.left.clone(),
+            right: self.right.clone(),
+        }
+    }
+}
+
+impl<'a, T: Ord> MergeIter<'a, T> {
+    fn next_pair(&mut self) -> (Option<&'a T>, Option<&'a T>) {
+        let ordering = match (self.left.peek(), self.right.peek()) {
+            (Some(l), Some(r)) => l.cmp(r),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => return (None, None),
+        };
+        match ordering {
+            Ordering::Less => (self.left.next(), None),
+            Ordering::Equal => (self.left.next(), self.right.next()),
+            Ordering::Greater => (None, self.right.next()),
+        }
+    }
+}
+
+macro_rules! declare_set_op_iter {
+    (
+        $(#[$attr:meta])*
+        $name:ident,
+        |$this:ident| $next:expr
+    ) => {
+        $(#[$attr])*
+        pub struct $name<'a, T> {
+            inner: MergeIter<'a, T>,
+        }
+
+        impl<T> Clone for $name<'_, T> {
+            fn clone(&self) -> Self {
+                $name {
+                    inner: self.inner.clone(),
+                }
+            }
+        }
+
+        impl<T: Debug + Ord> Debug for $name<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries(self.clone()).finish()
+            }
+        }
+
+        impl<'a, T: Ord> Iterator for $name<'a, T> {
+            type Item = &'a T;
+
+            #[inline]
+            fn next(&mut self) -> Option<&'a T> {
+                let $this = &mut self.inner;
+                $next
+            }
+        }
+    };
+}
+
+declare_set_op_iter! {
+    /// An iterator over the elements in either of two `RBTreeSet`s, in order.
+    ///
+    /// This `struct` is created by the [`union`] method on [`RBTreeSet`].
+    ///
+    /// [`union`]: RBTreeSet::union
+    Union,
+    |this| match this.next_pair() {
+        (Some(x), _) | (None, Some(x)) => Some(x),
+        (None, None) => None,
+    }
+}
+
+declare_set_op_iter! {
+    /// An iterator over the elements in both of two `RBTreeSet`s, in order.
+    ///
+    /// This `struct` is created by the [`intersection`] method on [`RBTreeSet`].
+    ///
+    /// [`intersection`]: RBTreeSet::intersection
+    Intersection,
+    |this| loop {
+        match this.next_pair() {
+            (Some(x), Some(_)) => return Some(x),
+            (None, None) => return None,
+            _ => {}
+        }
+    }
+}
+
+declare_set_op_iter! {
+    /// An iterator over the elements in one `RBTreeSet` that aren't in another, in order.
+    ///
+    /// This `struct` is created by the [`difference`] method on [`RBTreeSet`].
+    ///
+    /// [`difference`]: RBTreeSet::difference
+    Difference,
+    |this| loop {
+        match this.next_pair() {
+            (Some(x), None) => return Some(x),
+            (None, None) => return None,
+            _ if this.left.peek().is_none() => return None,
+            _ => {}
+        }
+    }
+}
+
+declare_set_op_iter! {
+    /// An iterator over the elements in exactly one of two `RBTreeSet`s, in order.
+    ///
+    /// This `struct` is created by the [`symmetric_difference`] method on [`RBTreeSet`].
+    ///
+    /// [`symmetric_difference`]: RBTreeSet::symmetric_difference
+    SymmetricDifference,
+    |this| loop {
+        match this.next_pair() {
+            (Some(x), None) | (None, Some(x)) => return Some(x),
+            (None, None) => return None,
+            (Some(_), Some(_)) => {}
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl<T> IntoIterator for RBTreeSet<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            inner: self.map.into_iter(),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RBTreeSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> From<BTreeSet<T>> for RBTreeSet<T>
+where
+    T: Ord,
+{
+    fn from(set: BTreeSet<T>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<T> From<RBTreeSet<T>> for BTreeSet<T>
+where
+    T: Ord,
+{
+    fn from(this: RBTreeSet<T>) -> BTreeSet<T> {
+        this.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<T> for RBTreeSet<T>
+where
+    T: Ord,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T> Extend<T> for RBTreeSet<T> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.map.extend(iter.into_iter().map(|x| Tuple2(x, ())));
+    }
+}
+
+impl<'a, T> Extend<&'a T> for RBTreeSet<T>
+where
+    T: Copy + 'a,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> Default for RBTreeSet<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for RBTreeSet<T>
+where
+    T: Clone + Ord,
+{
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Debug for RBTreeSet<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Eq for RBTreeSet<T> where T: Eq {}
+
+impl<T> PartialEq for RBTreeSet<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Ord for RBTreeSet<T>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T> PartialOrd for RBTreeSet<T>
+where
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T> Hash for RBTreeSet<T>
+where
+    T: Hash,
+{
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.map.hash(state)
+    }
+}
+
+macro_rules! impl_set_operator {
+    ($trait_:ident, $method:ident, $set_method:ident) => {
+        impl<T> $trait_<&RBTreeSet<T>> for &RBTreeSet<T>
+        where
+            T: Clone + Ord,
+        {
+            type Output = RBTreeSet<T>;
+
+            fn $method(self, other: &RBTreeSet<T>) -> RBTreeSet<T> {
+                self.$set_method(other).cloned().collect()
+            }
+        }
+    };
+}
+
+impl_set_operator! {BitOr, bitor, union}
+impl_set_operator! {BitAnd, bitand, intersection}
+impl_set_operator! {BitXor, bitxor, symmetric_difference}
+impl_set_operator! {Sub, sub, difference}
+
+mod serde {
+    use super::*;
+
+    use ::serde::{
+        de::{SeqAccess, Visitor},
+        ser::SerializeSeq,
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use crate::marker_type::NonOwningPhantom;
+
+    struct RBTreeSetVisitor<T> {
+        _marker: NonOwningPhantom<RBTreeSet<T>>,
+    }
+
+    impl<T> RBTreeSetVisitor<T> {
+        fn new() -> Self {
+            RBTreeSetVisitor {
+                _marker: NonOwningPhantom::NEW,
+            }
+        }
+    }
+
+    impl<'de, T> Visitor<'de> for RBTreeSetVisitor<T>
+    where
+        T: Deserialize<'de> + Ord,
+    {
+        type Value = RBTreeSet<T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("an RBTreeSet")
+        }
+
+        fn visit_seq<A>(self, mut seq_access: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut set = RBTreeSet::new();
+
+            while let Some(x) = seq_access.next_element()? {
+                set.insert(x);
+            }
+
+            Ok(set)
+        }
+    }
+
+    impl<'de, T> Deserialize<'de> for RBTreeSet<T>
+    where
+        T: Deserialize<'de> + Ord,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(RBTreeSetVisitor::new())
+        }
+    }
+
+    impl<T> Serialize for RBTreeSet<T>
+    where
+        T: Serialize,
+    {
+        fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error>
+        where
+            Z: Serializer,
+        {
+            let mut seq = serializer.serialize_seq(Some(self.len()))?;
+            for x in self.iter() {
+                seq.serialize_element(x)?;
+            }
+            seq.end()
+        }
+    }
+}
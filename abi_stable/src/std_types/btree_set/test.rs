@@ -0,0 +1,96 @@
+use super::*;
+
+use crate::std_types::{RNone, RSome, RString};
+
+fn new_set(list: &[u32]) -> RBTreeSet<u32> {
+    list.iter().copied().collect()
+}
+
+#[test]
+fn insert_remove() {
+    let mut set = RBTreeSet::<RString>::new();
+
+    assert!(set.insert("foo".into()));
+    assert!(set.insert("bar".into()));
+    assert!(!set.insert("foo".into()));
+    assert_eq!(set.len(), 2);
+
+    assert!(set.contains("foo"));
+    assert!(set.contains_p(&RString::from("bar")));
+    assert!(!set.contains("baz"));
+
+    assert_eq!(set.take("foo"), RSome(RString::from("foo")));
+    assert_eq!(set.take("foo"), RNone);
+    assert!(set.remove_p(&RString::from("bar")));
+    assert!(set.is_empty());
+}
+
+#[test]
+fn ordered_iteration() {
+    let set = new_set(&[13, 3, 8, 5]);
+
+    assert_eq!(set.iter().copied().collect::<Vec<_>>(), [3, 5, 8, 13]);
+    assert_eq!(set.iter().rev().copied().collect::<Vec<_>>(), [13, 8, 5, 3]);
+    assert_eq!(set.iter().size_hint(), (4, Some(4)));
+    assert_eq!(set.range(4..=8).copied().collect::<Vec<_>>(), [5, 8]);
+
+    assert_eq!(set.first(), RSome(&3));
+    assert_eq!(set.last(), RSome(&13));
+
+    assert_eq!(
+        set.clone().into_iter().rev().collect::<Vec<_>>(),
+        [13, 8, 5, 3]
+    );
+}
+
+#[test]
+fn set_operations() {
+    let left = new_set(&[0, 1, 2, 3, 7]);
+    let right = new_set(&[2, 3, 4, 5, 9]);
+
+    let collect = |iter: &mut dyn Iterator<Item = &u32>| iter.copied().collect::<Vec<_>>();
+
+    assert_eq!(
+        collect(&mut left.union(&right)),
+        [0, 1, 2, 3, 4, 5, 7, 9]
+    );
+    assert_eq!(collect(&mut left.intersection(&right)), [2, 3]);
+    assert_eq!(collect(&mut left.difference(&right)), [0, 1, 7]);
+    assert_eq!(collect(&mut right.difference(&left)), [4, 5, 9]);
+    assert_eq!(
+        collect(&mut left.symmetric_difference(&right)),
+        [0, 1, 4, 5, 7, 9]
+    );
+
+    assert_eq!(&left | &right, new_set(&[0, 1, 2, 3, 4, 5, 7, 9]));
+    assert_eq!(&left & &right, new_set(&[2, 3]));
+    assert_eq!(&left - &right, new_set(&[0, 1, 7]));
+    assert_eq!(&left ^ &right, new_set(&[0, 1, 4, 5, 7, 9]));
+
+    assert!(!left.is_disjoint(&right));
+    assert!(left.is_disjoint(&new_set(&[4, 5])));
+    assert!(new_set(&[1, 7]).is_subset(&left));
+    assert!(!new_set(&[1, 8]).is_subset(&left));
+    assert!(left.is_superset(&new_set(&[0, 3])));
+    assert!(new_set(&[]).is_subset(&left));
+}
+
+#[test]
+fn from_into_btreeset() {
+    let std_set = vec![3, 5, 8].into_iter().collect::<BTreeSet<u32>>();
+    let set = RBTreeSet::from(std_set.clone());
+
+    assert_eq!(set, new_set(&[8, 5, 3]));
+    assert_eq!(BTreeSet::from(set), std_set);
+}
+
+#[test]
+fn test_serde() {
+    let set = new_set(&[8, 3, 5]);
+
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(json, "[3,5,8]");
+
+    let deserialized = serde_json::from_str::<RBTreeSet<u32>>("[5,3,8,3]").unwrap();
+    assert_eq!(deserialized, set);
+}
@@ -0,0 +1,609 @@
+//! Contains ffi-safe equivalents of `Box<dyn FnOnce(..) -> R>`,
+//! `Box<dyn FnMut(..) -> R>`, and `Box<dyn Fn(..) -> R>`.
+//!
+//! The arguments of these closures are passed as a tuple
+//! (`()`, `(A,)`, `(A, B)`, `(A, B, C)`, or `(A, B, C, D)`),
+//! which is converted to the equivalent ffi-safe tuple
+//! (`()`, `Tuple1<A>`, `Tuple2<A, B>`, etc.) when the closure is called,
+//! so that the layout of the closure types includes the argument and return types.
+
+use std::{fmt, marker::PhantomData, mem};
+
+use crate::{
+    marker_type::{ErasedObject, SyncSend, UnsyncSend, UnsyncUnsend},
+    sabi_types::{RMut, RRef},
+    std_types::{RBox, Tuple1, Tuple2, Tuple3, Tuple4},
+    StableAbi,
+};
+
+#[cfg(test)]
+mod test;
+
+/// A tuple of arguments that can be passed to the closures in this module.
+///
+/// This is implemented for tuples of up to 4 elements,
+/// converting them to and from the ffi-safe tuple types in `abi_stable::std_types`.
+pub trait FnArgs: Sized {
+    /// The ffi-safe equivalent of this tuple.
+    type CArgs: StableAbi;
+
+    /// Converts this tuple into its ffi-safe equivalent.
+    fn into_c_args(self) -> Self::CArgs;
+
+    /// Converts the ffi-safe equivalent of this tuple back into this tuple.
+    fn from_c_args(args: Self::CArgs) -> Self;
+}
+
+/// A closure that can be called once with a tuple of arguments.
+///
+/// This is implemented for every `FnOnce` closure taking up to 4 arguments.
+pub trait TupledFnOnce<Args, R> {
+    /// Calls this closure, passing the elements of `args` as the arguments.
+    fn call_tupled_once(self, args: Args) -> R;
+}
+
+/// A closure that can be called multiple times with a tuple of arguments,
+/// mutating its captured state.
+///
+/// This is implemented for every `FnMut` closure taking up to 4 arguments.
+pub trait TupledFnMut<Args, R>: TupledFnOnce<Args, R> {
+    /// Calls this closure, passing the elements of `args` as the arguments.
+    fn call_tupled_mut(&mut self, args: Args) -> R;
+}
+
+/// A closure that can be called multiple times with a tuple of arguments.
+///
+/// This is implemented for every `Fn` closure taking up to 4 arguments.
+pub trait TupledFn<Args, R>: TupledFnMut<Args, R> {
+    /// Calls this closure, passing the elements of `args` as the arguments.
+    fn call_tupled(&self, args: Args) -> R;
+}
+
+impl FnArgs for () {
+    type CArgs = ();
+
+    #[inline]
+    fn into_c_args(self) {}
+
+    #[inline]
+    fn from_c_args(_: ()) -> Self {}
+}
+
+impl<F, R> TupledFnOnce<(), R> for F
+where
+    F: FnOnce() -> R,
+{
+    #[inline]
+    fn call_tupled_once(self, _: ()) -> R {
+        self()
+    }
+}
+
+impl<F, R> TupledFnMut<(), R> for F
+where
+    F: FnMut() -> R,
+{
+    #[inline]
+    fn call_tupled_mut(&mut self, _: ()) -> R {
+        self()
+    }
+}
+
+impl<F, R> TupledFn<(), R> for F
+where
+    F: Fn() -> R,
+{
+    #[inline]
+    fn call_tupled(&self, _: ()) -> R {
+        self()
+    }
+}
+
+macro_rules! impl_fn_args {
+    ( $( $ctuple:ident [$($param:ident),*] )* ) => (
+        $(
+            impl<$($param,)*> FnArgs for ($($param,)*)
+            where
+                $($param: StableAbi,)*
+            {
+                type CArgs = $ctuple<$($param,)*>;
+
+                #[inline]
+                fn into_c_args(self) -> Self::CArgs {
+                    self.into()
+                }
+
+                #[inline]
+                fn from_c_args(args: Self::CArgs) -> Self {
+                    args.into_tuple()
+                }
+            }
+
+            #[allow(non_snake_case)]
+            impl<F, $($param,)* R> TupledFnOnce<($($param,)*), R> for F
+            where
+                F: FnOnce($($param,)*) -> R,
+            {
+                #[inline]
+                fn call_tupled_once(self, ($($param,)*): ($($param,)*)) -> R {
+                    self($($param,)*)
+                }
+            }
+
+            #[allow(non_snake_case)]
+            impl<F, $($param,)* R> TupledFnMut<($($param,)*), R> for F
+            where
+                F: FnMut($($param,)*) -> R,
+            {
+                #[inline]
+                fn call_tupled_mut(&mut self, ($($param,)*): ($($param,)*)) -> R {
+                    self($($param,)*)
+                }
+            }
+
+            #[allow(non_snake_case)]
+            impl<F, $($param,)* R> TupledFn<($($param,)*), R> for F
+            where
+                F: Fn($($param,)*) -> R,
+            {
+                #[inline]
+                fn call_tupled(&self, ($($param,)*): ($($param,)*)) -> R {
+                    self($($param,)*)
+                }
+            }
+        )*
+    )
+}
+
+impl_fn_args! {
+    Tuple1[A]
+    Tuple2[A, B]
+    Tuple3[A, B, C]
+    Tuple4[A, B, C, D]
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+macro_rules! declare_closure_type {
+    (
+        $(#[$attr:meta])*
+        struct $name:ident;
+
+        $call_field:ident : $erased_ty:ty,
+        $tupled_trait:ident,
+        $tupled_method:ident,
+    ) => (
+        $(#[$attr])*
+        #[repr(C)]
+        #[derive(StableAbi)]
+        #[sabi(
+            not_stableabi(Args),
+            bound(Args: FnArgs),
+            bound(<Args as FnArgs>::CArgs: StableAbi),
+        )]
+        pub struct $name<'a, Args, R, M = UnsyncUnsend>
+        where
+            Args: FnArgs,
+        {
+            closure: RBox<ErasedObject>,
+            $call_field: unsafe extern "C" fn($erased_ty, Args::CArgs) -> R,
+            _marker: PhantomData<(&'a (), M)>,
+        }
+
+        impl<'a, Args, R, M> $name<'a, Args, R, M>
+        where
+            Args: FnArgs,
+        {
+            fn new_inner<F>(closure: F) -> Self
+            where
+                F: $tupled_trait<Args, R> + 'a,
+            {
+                let closure = RBox::new(closure);
+                let closure = unsafe { mem::transmute::<RBox<F>, RBox<ErasedObject>>(closure) };
+                Self {
+                    closure,
+                    $call_field: $tupled_method::<F, Args, R>,
+                    _marker: PhantomData,
+                }
+            }
+        }
+
+        impl<'a, Args, R, M> fmt::Debug for $name<'a, Args, R, M>
+        where
+            Args: FnArgs,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+    )
+}
+
+declare_closure_type! {
+    /// Ffi-safe equivalent of `Box<dyn FnOnce(..) -> R + 'a>`,
+    /// whose `Send + Sync`ness is determined by the `M` type parameter.
+    ///
+    /// `Args` is the tuple of arguments that this closure takes,
+    /// eg: `RFnOnce<'a, (u32, RString), bool>` is equivalent to
+    /// `Box<dyn FnOnce(u32, RString) -> bool + 'a>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RFnOnce, RString, RVec};
+    ///
+    /// let list = RVec::from(vec![3, 5, 8]);
+    ///
+    /// let func: RFnOnce<'_, (RString,), RString> =
+    ///     RFnOnce::new(move |mut prefix: RString| {
+    ///         for x in list {
+    ///             prefix.push_str(&x.to_string());
+    ///         }
+    ///         prefix
+    ///     });
+    ///
+    /// assert_eq!(func.call_once((RString::from("n:"),)), "n:358");
+    ///
+    /// ```
+    struct RFnOnce_;
+
+    call_once: RBox<ErasedObject>,
+    TupledFnOnce,
+    call_once_closure,
+}
+
+declare_closure_type! {
+    /// Ffi-safe equivalent of `Box<dyn FnMut(..) -> R + 'a>`,
+    /// whose `Send + Sync`ness is determined by the `M` type parameter.
+    ///
+    /// `Args` is the tuple of arguments that this closure takes,
+    /// eg: `RFnMut<'a, (u32, u32), u64>` is equivalent to
+    /// `Box<dyn FnMut(u32, u32) -> u64 + 'a>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RFnMut;
+    ///
+    /// let mut total = 0;
+    ///
+    /// {
+    ///     let mut func: RFnMut<'_, (u32, u32), u32> = RFnMut::new(|x: u32, y: u32| {
+    ///         total += x * y;
+    ///         total
+    ///     });
+    ///
+    ///     assert_eq!(func.call_mut((2, 3)), 6);
+    ///     assert_eq!(func.call_mut((4, 5)), 26);
+    /// }
+    ///
+    /// assert_eq!(total, 26);
+    ///
+    /// ```
+    struct RFnMut_;
+
+    call_mut: RMut<'_, ErasedObject>,
+    TupledFnMut,
+    call_mut_closure,
+}
+
+declare_closure_type! {
+    /// Ffi-safe equivalent of `Box<dyn Fn(..) -> R + 'a>`,
+    /// whose `Send + Sync`ness is determined by the `M` type parameter.
+    ///
+    /// `Args` is the tuple of arguments that this closure takes,
+    /// eg: `RFn<'a, (RStr<'_>,), usize>` is equivalent to
+    /// `Box<dyn Fn(RStr<'_>) -> usize + 'a>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RFn, RStr};
+    ///
+    /// let separator = ',';
+    ///
+    /// let func: RFn<'_, (RStr<'_>,), usize> =
+    ///     RFn::new(|s: RStr<'_>| s.split(separator).count());
+    ///
+    /// assert_eq!(func.call(("a,b,c".into(),)), 3);
+    /// assert_eq!(func.call(("hello".into(),)), 1);
+    ///
+    /// ```
+    struct RFn_;
+
+    call: RRef<'_, ErasedObject>,
+    TupledFn,
+    call_closure,
+}
+
+/// Ffi-safe equivalent of `Box<dyn FnOnce(..) -> R + 'a>`.
+pub type RFnOnce<'a, Args, R> = RFnOnce_<'a, Args, R, UnsyncUnsend>;
+
+/// Ffi-safe equivalent of `Box<dyn FnOnce(..) -> R + Send + 'a>`.
+pub type SendRFnOnce<'a, Args, R> = RFnOnce_<'a, Args, R, UnsyncSend>;
+
+/// Ffi-safe equivalent of `Box<dyn FnMut(..) -> R + 'a>`.
+pub type RFnMut<'a, Args, R> = RFnMut_<'a, Args, R, UnsyncUnsend>;
+
+/// Ffi-safe equivalent of `Box<dyn FnMut(..) -> R + Send + 'a>`.
+pub type SendRFnMut<'a, Args, R> = RFnMut_<'a, Args, R, UnsyncSend>;
+
+/// Ffi-safe equivalent of `Box<dyn Fn(..) -> R + 'a>`.
+pub type RFn<'a, Args, R> = RFn_<'a, Args, R, UnsyncUnsend>;
+
+/// Ffi-safe equivalent of `Box<dyn Fn(..) -> R + Send + 'a>`.
+pub type SendRFn<'a, Args, R> = RFn_<'a, Args, R, UnsyncSend>;
+
+/// Ffi-safe equivalent of `Box<dyn Fn(..) -> R + Send + Sync + 'a>`.
+pub type SyncRFn<'a, Args, R> = RFn_<'a, Args, R, SyncSend>;
+
+macro_rules! impl_constructors {
+    (
+        $name:ident, $tupled_trait:ident;
+        $( $marker:ident [$($bounds:tt)*] $alias:ident, )*
+    ) => (
+        $(
+            impl<'a, Args, R> $name<'a, Args, R, $marker>
+            where
+                Args: FnArgs,
+            {
+                #[doc = concat!(
+                    "Constructs a `", stringify!($alias), "` from a closure.\n\n",
+                    "The types of the closure parameters may need to be annotated,\n",
+                    "since they can't be inferred from the `Args` type parameter."
+                )]
+                pub fn new<F>(closure: F) -> Self
+                where
+                    F: $tupled_trait<Args, R> + $($bounds)* 'a,
+                {
+                    Self::new_inner(closure)
+                }
+            }
+        )*
+    )
+}
+
+impl_constructors! {
+    RFnOnce_, TupledFnOnce;
+    UnsyncUnsend [] RFnOnce,
+    UnsyncSend [Send +] SendRFnOnce,
+}
+
+impl_constructors! {
+    RFnMut_, TupledFnMut;
+    UnsyncUnsend [] RFnMut,
+    UnsyncSend [Send +] SendRFnMut,
+}
+
+impl_constructors! {
+    RFn_, TupledFn;
+    UnsyncUnsend [] RFn,
+    UnsyncSend [Send +] SendRFn,
+    SyncSend [Send + Sync +] SyncRFn,
+}
+
+impl<'a, Args, R, M> RFnOnce_<'a, Args, R, M>
+where
+    Args: FnArgs,
+{
+    /// Calls this closure, passing the elements of `args` as the arguments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::SendRFnOnce;
+    ///
+    /// let func: SendRFnOnce<'_, (u32, u32), u32> = SendRFnOnce::new(|x: u32, y: u32| x + y);
+    ///
+    /// assert_eq!(func.call_once((3, 5)), 8);
+    ///
+    /// ```
+    pub fn call_once(self, args: Args) -> R {
+        let call_once = self.call_once;
+        unsafe { call_once(self.closure, args.into_c_args()) }
+    }
+}
+
+impl<'a, Args, R, M> RFnMut_<'a, Args, R, M>
+where
+    Args: FnArgs,
+{
+    /// Calls this closure, passing the elements of `args` as the arguments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::SendRFnMut;
+    ///
+    /// let mut count = 0;
+    /// let mut func: SendRFnMut<'_, (), u32> = SendRFnMut::new(move || {
+    ///     count += 1;
+    ///     count
+    /// });
+    ///
+    /// assert_eq!(func.call_mut(()), 1);
+    /// assert_eq!(func.call_mut(()), 2);
+    ///
+    /// ```
+    pub fn call_mut(&mut self, args: Args) -> R {
+        unsafe { (self.call_mut)(RMut::new(&mut *self.closure), args.into_c_args()) }
+    }
+
+    /// Converts this into an `RFnOnce_` with the same `Send + Sync`ness.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RFnMut, RFnOnce};
+    ///
+    /// let mut func: RFnMut<'_, (u8,), u8> = RFnMut::new(|x: u8| x * 2);
+    /// assert_eq!(func.call_mut((3,)), 6);
+    ///
+    /// let func: RFnOnce<'_, (u8,), u8> = func.into_fn_once();
+    /// assert_eq!(func.call_once((5,)), 10);
+    ///
+    /// ```
+    pub fn into_fn_once(self) -> RFnOnce_<'a, Args, R, M>
+    where
+        R: 'a,
+        Args: 'a,
+        M: 'a,
+    {
+        RFnOnce_::new_inner(self)
+    }
+}
+
+impl<'a, Args, R, M> RFn_<'a, Args, R, M>
+where
+    Args: FnArgs,
+{
+    /// Calls this closure, passing the elements of `args` as the arguments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::SyncRFn;
+    ///
+    /// let func: SyncRFn<'_, (i32, i32), i32> = SyncRFn::new(|x: i32, y: i32| x.max(y));
+    ///
+    /// assert_eq!(func.call((3, 5)), 5);
+    /// assert_eq!(func.call((8, -1)), 8);
+    ///
+    /// ```
+    pub fn call(&self, args: Args) -> R {
+        unsafe { (self.call)(RRef::new(&*self.closure), args.into_c_args()) }
+    }
+
+    /// Converts this into an `RFnMut_` with the same `Send + Sync`ness.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RFn, RFnMut};
+    ///
+    /// let func: RFn<'_, (u8,), u8> = RFn::new(|x: u8| x + 1);
+    ///
+    /// let mut func: RFnMut<'_, (u8,), u8> = func.into_fn_mut();
+    /// assert_eq!(func.call_mut((5,)), 6);
+    ///
+    /// ```
+    pub fn into_fn_mut(self) -> RFnMut_<'a, Args, R, M>
+    where
+        R: 'a,
+        Args: 'a,
+        M: 'a,
+    {
+        RFnMut_::new_inner(self)
+    }
+
+    /// Converts this into an `RFnOnce_` with the same `Send + Sync`ness.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RFn, RFnOnce};
+    ///
+    /// let func: RFn<'_, (u8,), u8> = RFn::new(|x: u8| x + 1);
+    ///
+    /// let func: RFnOnce<'_, (u8,), u8> = func.into_fn_once();
+    /// assert_eq!(func.call_once((5,)), 6);
+    ///
+    /// ```
+    pub fn into_fn_once(self) -> RFnOnce_<'a, Args, R, M>
+    where
+        R: 'a,
+        Args: 'a,
+        M: 'a,
+    {
+        RFnOnce_::new_inner(self)
+    }
+}
+
+impl<'a, Args, R, M> TupledFnOnce<Args, R> for RFnMut_<'a, Args, R, M>
+where
+    Args: FnArgs,
+{
+    fn call_tupled_once(mut self, args: Args) -> R {
+        self.call_mut(args)
+    }
+}
+
+impl<'a, Args, R, M> TupledFnMut<Args, R> for RFnMut_<'a, Args, R, M>
+where
+    Args: FnArgs,
+{
+    fn call_tupled_mut(&mut self, args: Args) -> R {
+        self.call_mut(args)
+    }
+}
+
+impl<'a, Args, R, M> TupledFnOnce<Args, R> for RFn_<'a, Args, R, M>
+where
+    Args: FnArgs,
+{
+    fn call_tupled_once(self, args: Args) -> R {
+        self.call(args)
+    }
+}
+
+impl<'a, Args, R, M> TupledFnMut<Args, R> for RFn_<'a, Args, R, M>
+where
+    Args: FnArgs,
+{
+    fn call_tupled_mut(&mut self, args: Args) -> R {
+        self.call(args)
+    }
+}
+
+impl<'a, Args, R, M> TupledFn<Args, R> for RFn_<'a, Args, R, M>
+where
+    Args: FnArgs,
+{
+    fn call_tupled(&self, args: Args) -> R {
+        self.call(args)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+unsafe extern "C" fn call_once_closure<F, Args, R>(
+    closure: RBox<ErasedObject>,
+    args: Args::CArgs,
+) -> R
+where
+    F: TupledFnOnce<Args, R>,
+    Args: FnArgs,
+{
+    extern_fn_panic_handling! {
+        let closure = unsafe { mem::transmute::<RBox<ErasedObject>, RBox<F>>(closure) };
+        RBox::into_inner(closure).call_tupled_once(Args::from_c_args(args))
+    }
+}
+
+unsafe extern "C" fn call_mut_closure<F, Args, R>(
+    closure: RMut<'_, ErasedObject>,
+    args: Args::CArgs,
+) -> R
+where
+    F: TupledFnMut<Args, R>,
+    Args: FnArgs,
+{
+    extern_fn_panic_handling! {
+        let closure = unsafe { closure.transmute_into_mut::<F>() };
+        closure.call_tupled_mut(Args::from_c_args(args))
+    }
+}
+
+unsafe extern "C" fn call_closure<F, Args, R>(
+    closure: RRef<'_, ErasedObject>,
+    args: Args::CArgs,
+) -> R
+where
+    F: TupledFn<Args, R>,
+    Args: FnArgs,
+{
+    extern_fn_panic_handling! {
+        let closure = unsafe { closure.transmute_into_ref::<F>() };
+        closure.call_tupled(Args::from_c_args(args))
+    }
+}
@@ -0,0 +1,173 @@
+use super::*;
+
+use std::{cell::Cell, rc::Rc, sync::Arc};
+
+use crate::{
+    abi_stability::abi_checking::check_layout_compatibility,
+    std_types::{RString, RVec},
+};
+
+fn assert_send<T: Send>(_: &T) {}
+fn assert_sync<T: Sync>(_: &T) {}
+
+#[test]
+fn call_fn_once() {
+    let list = vec![3, 5, 8];
+
+    let func: RFnOnce<'_, (u32, RString), RString> =
+        RFnOnce::new(move |factor: u32, mut acc: RString| {
+            for x in list {
+                acc.push_str(&(x * factor).to_string());
+                acc.push(',');
+            }
+            acc
+        });
+
+    assert_eq!(func.call_once((2, RString::from("["))), "[6,10,16,");
+}
+
+#[test]
+fn call_fn_mut() {
+    let mut list = RVec::new();
+    {
+        let mut func: RFnMut<'_, (u32,), usize> = RFnMut::new(|x: u32| {
+            list.push(x);
+            list.len()
+        });
+
+        assert_eq!(func.call_mut((3,)), 1);
+        assert_eq!(func.call_mut((5,)), 2);
+        assert_eq!(func.call_mut((8,)), 3);
+    }
+    assert_eq!(list, vec![3, 5, 8]);
+}
+
+#[test]
+fn call_fn() {
+    let calls = Cell::new(0);
+
+    let func: RFn<'_, (u8, u8, u8, u8), u32> = RFn::new(|a: u8, b: u8, c: u8, d: u8| {
+        calls.set(calls.get() + 1);
+        u32::from_le_bytes([a, b, c, d])
+    });
+
+    assert_eq!(func.call((1, 0, 0, 0)), 1);
+    assert_eq!(func.call((0, 0, 1, 0)), 0x10000);
+    assert_eq!(calls.get(), 2);
+
+    let unit: RFn<'_, (), &str> = RFn::new(|| "hello");
+    assert_eq!(unit.call(()), "hello");
+}
+
+#[test]
+fn conversions() {
+    let calls = Cell::new(0);
+    let add = |x: u32, y: u32| {
+        calls.set(calls.get() + 1);
+        x + y
+    };
+
+    let mut func_mut = RFn::<(u32, u32), u32>::new(add).into_fn_mut();
+    assert_eq!(func_mut.call_mut((3, 5)), 8);
+
+    let func_once = func_mut.into_fn_once();
+    assert_eq!(func_once.call_once((13, 21)), 34);
+
+    let func_once = RFn::<(u32, u32), u32>::new(add).into_fn_once();
+    assert_eq!(func_once.call_once((1, 2)), 3);
+
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn drops_captures() {
+    let rc = Rc::new(());
+
+    {
+        let captured = rc.clone();
+        let _func: RFnOnce<'_, (), ()> = RFnOnce::new(move || drop(captured));
+        assert_eq!(Rc::strong_count(&rc), 2);
+    }
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    {
+        let captured = rc.clone();
+        let func: RFnOnce<'_, (), usize> = RFnOnce::new(move || Rc::strong_count(&captured));
+        assert_eq!(func.call_once(()), 2);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    {
+        let captured = rc.clone();
+        let mut func: RFnMut<'_, (), usize> = RFnMut::new(move || Rc::strong_count(&captured));
+        assert_eq!(func.call_mut(()), 2);
+        assert_eq!(Rc::strong_count(&rc), 2);
+        drop(func);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    {
+        let captured = rc.clone();
+        let func: RFn<'_, (), usize> = RFn::new(move || Rc::strong_count(&captured));
+        assert_eq!(func.into_fn_once().call_once(()), 2);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+}
+
+#[test]
+fn send_sync() {
+    let arc = Arc::new(10);
+
+    let captured = arc.clone();
+    let func = SendRFnOnce::<(), u32>::new(move || *captured);
+    assert_send(&func);
+    assert_eq!(std::thread::spawn(move || func.call_once(())).join().unwrap(), 10);
+
+    let captured = arc.clone();
+    let mut func = SendRFnMut::<(u32,), u32>::new(move |x: u32| *captured + x);
+    assert_send(&func);
+    assert_eq!(func.call_mut((1,)), 11);
+
+    let captured = arc.clone();
+    let func = SendRFn::<(u32,), u32>::new(move |x: u32| *captured * x);
+    assert_send(&func);
+    assert_eq!(func.call((2,)), 20);
+
+    let captured = arc;
+    let func = SyncRFn::<(u32,), u32>::new(move |x: u32| *captured - x);
+    assert_send(&func);
+    assert_sync(&func);
+    let func = Arc::new(func);
+    let handles = (3..5)
+        .map(|x| {
+            let func = func.clone();
+            std::thread::spawn(move || func.call((x,)))
+        })
+        .collect::<Vec<_>>();
+    let results = handles.into_iter().map(|h| h.join().unwrap()).collect::<Vec<_>>();
+    assert_eq!(results, [7, 6]);
+}
+
+#[test]
+fn layout_includes_signature() {
+    let layout = <RFn<'static, (u32, RString), u32> as StableAbi>::LAYOUT;
+
+    check_layout_compatibility(layout, layout).unwrap();
+
+    let mismatched = [
+        <RFn<'static, (u32, RString), u64> as StableAbi>::LAYOUT,
+        <RFn<'static, (u64, RString), u32> as StableAbi>::LAYOUT,
+        <RFn<'static, (u32,), u32> as StableAbi>::LAYOUT,
+        <RFn<'static, (u32, RString, u8), u32> as StableAbi>::LAYOUT,
+        <SyncRFn<'static, (u32, RString), u32> as StableAbi>::LAYOUT,
+        <RFnMut<'static, (u32, RString), u32> as StableAbi>::LAYOUT,
+    ];
+
+    for other in mismatched {
+        assert!(
+            check_layout_compatibility(layout, other).is_err(),
+            "{:#?}",
+            other,
+        );
+    }
+}
@@ -0,0 +1,447 @@
+//! Contains ffi-safe equivalents of `Pin<Box<dyn Future<Output = T> + 'a>>`
+//! and of boxed streams.
+//!
+//! Futures and streams from either side of the ffi boundary can be wrapped in these types,
+//! and then be polled by any executor,
+//! since the `Waker` of the executor is passed through an [`RContext`].
+//!
+//! [`RContext`]: crate::std_types::RContext
+
+use std::{
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    marker_type::{ErasedObject, UnsyncSend, UnsyncUnsend},
+    sabi_types::RMut,
+    std_types::{RBox, RContext, ROption, RPoll},
+};
+
+#[cfg(test)]
+mod test;
+
+/// Ffi-safe equivalent of `Pin<Box<dyn Future<Output = T> + 'a>>`,
+/// whose `Send`ness is determined by the `M` type parameter.
+///
+/// # Panics
+///
+/// Panics inside the wrapped future abort the process,
+/// because they can't unwind across the ffi boundary.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::std_types::{RFuture, RString, SendRFuture};
+///
+/// # use std::{sync::Arc, task::{Context, Poll, Wake, Waker}, future::Future, pin::Pin};
+/// # struct NoopWaker;
+/// # impl Wake for NoopWaker { fn wake(self: Arc<Self>) {} }
+/// # fn block_on<F: Future + Unpin>(mut fut: F) -> F::Output {
+/// #     let waker = Waker::from(Arc::new(NoopWaker));
+/// #     let mut cx = Context::from_waker(&waker);
+/// #     loop {
+/// #         if let Poll::Ready(x) = Pin::new(&mut fut).poll(&mut cx) {
+/// #             return x;
+/// #         }
+/// #     }
+/// # }
+/// #
+/// // This would be an `extern "C" fn` exported by a dynamic library.
+/// extern "C" fn greet(name: RString) -> SendRFuture<'static, RString> {
+///     SendRFuture::new(async move { format!("Hello, {}!", name).into() })
+/// }
+///
+/// // `block_on` is an executor that polls the future to completion.
+/// let greeting = block_on(greet("world".into()));
+///
+/// assert_eq!(greeting, "Hello, world!");
+///
+/// ```
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RFuture_<'a, T, M = UnsyncUnsend> {
+    future: RBox<ErasedObject>,
+    poll: unsafe extern "C" fn(RMut<'_, ErasedObject>, RContext<'_>) -> RPoll<T>,
+    _marker: PhantomData<(&'a (), M)>,
+}
+
+/// Ffi-safe equivalent of `Pin<Box<dyn Future<Output = T> + 'a>>`.
+pub type RFuture<'a, T> = RFuture_<'a, T, UnsyncUnsend>;
+
+/// Ffi-safe equivalent of `Pin<Box<dyn Future<Output = T> + Send + 'a>>`.
+pub type SendRFuture<'a, T> = RFuture_<'a, T, UnsyncSend>;
+
+impl<'a, T> RFuture_<'a, T, UnsyncUnsend> {
+    /// Constructs an `RFuture` from a future.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RFuture;
+    ///
+    /// use std::rc::Rc;
+    ///
+    /// let rc = Rc::new(3);
+    /// let _future = RFuture::new(async move { *rc + 5 });
+    ///
+    /// ```
+    pub fn new<F>(future: F) -> Self
+    where
+        F: Future<Output = T> + 'a,
+    {
+        Self::new_inner(future)
+    }
+}
+
+impl<'a, T> RFuture_<'a, T, UnsyncSend> {
+    /// Constructs a `SendRFuture` from a `Send` future.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::SendRFuture;
+    ///
+    /// let _future = SendRFuture::new(async { 3 + 5 });
+    ///
+    /// ```
+    pub fn new<F>(future: F) -> Self
+    where
+        F: Future<Output = T> + Send + 'a,
+    {
+        Self::new_inner(future)
+    }
+}
+
+impl<'a, T, M> RFuture_<'a, T, M> {
+    fn new_inner<F>(future: F) -> Self
+    where
+        F: Future<Output = T> + 'a,
+    {
+        let future = RBox::new(future);
+        let future = unsafe { mem::transmute::<RBox<F>, RBox<ErasedObject>>(future) };
+        Self {
+            future,
+            poll: poll_future::<F>,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Polls the wrapped future with an `RContext`,
+    ///
+    /// This is what the `Future` impl of this type calls.
+    pub fn poll_with(&mut self, cx: RContext<'_>) -> RPoll<T> {
+        unsafe { (self.poll)(RMut::new(&mut *self.future), cx) }
+    }
+}
+
+// The future is stored in an `RBox`, so moving the `RFuture_` doesn't move it.
+impl<'a, T, M> Unpin for RFuture_<'a, T, M> {}
+
+impl<'a, T, M> Future for RFuture_<'a, T, M> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let this = self.get_mut();
+        RContext::scoped(cx, |cx| this.poll_with(cx)).into()
+    }
+}
+
+impl<'a, T, M> fmt::Debug for RFuture_<'a, T, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RFuture_").finish_non_exhaustive()
+    }
+}
+
+unsafe extern "C" fn poll_future<F>(
+    this: RMut<'_, ErasedObject>,
+    cx: RContext<'_>,
+) -> RPoll<F::Output>
+where
+    F: Future,
+{
+    extern_fn_panic_handling! {
+        // The future is never moved out of its `RBox`, so it can be pinned.
+        let this = unsafe { Pin::new_unchecked(this.transmute_into_mut::<F>()) };
+        cx.with_context(|cx| this.poll(cx)).into()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// An asynchronous iterator, with the same signature as the `Stream` trait
+/// from the `futures-core` crate.
+///
+/// The standard library doesn't have a `Stream` trait yet,
+/// so this trait exists to let [`RStream_`] be used without
+/// making `futures-core` a required dependency of this crate.
+///
+/// With the "futures" cargo feature enabled:
+///
+/// - [`RStream_`] also implements `futures_core::Stream`.
+///
+/// - `futures_core::Stream`s can be wrapped in [`FuturesStream`] to implement this trait.
+///
+pub trait Stream {
+    /// The type of the values this yields.
+    type Item;
+
+    /// Attempts to get the next value of this stream,
+    /// returning `Poll::Ready(None)` once the stream is exhausted.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+}
+
+impl<S> Stream for &mut S
+where
+    S: ?Sized + Stream + Unpin,
+{
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        Pin::new(&mut **self).poll_next(cx)
+    }
+}
+
+impl<S> Stream for Box<S>
+where
+    S: ?Sized + Stream + Unpin,
+{
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        Pin::new(&mut **self).poll_next(cx)
+    }
+}
+
+/// Ffi-safe equivalent of `Pin<Box<dyn Stream<Item = T> + 'a>>`,
+/// whose `Send`ness is determined by the `M` type parameter.
+///
+/// # Panics
+///
+/// Panics inside the wrapped stream abort the process,
+/// because they can't unwind across the ffi boundary.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::std_types::{
+///     future::Stream,
+///     RStream, RVec,
+/// };
+///
+/// use std::{pin::Pin, task::{Context, Poll}};
+///
+/// # use std::{sync::Arc, task::{Wake, Waker}, future::Future};
+/// # struct NoopWaker;
+/// # impl Wake for NoopWaker { fn wake(self: Arc<Self>) {} }
+/// # fn block_on<F: Future + Unpin>(mut fut: F) -> F::Output {
+/// #     let waker = Waker::from(Arc::new(NoopWaker));
+/// #     let mut cx = Context::from_waker(&waker);
+/// #     loop {
+/// #         if let Poll::Ready(x) = Pin::new(&mut fut).poll(&mut cx) {
+/// #             return x;
+/// #         }
+/// #     }
+/// # }
+/// #
+/// struct Countdown(u32);
+///
+/// impl Stream for Countdown {
+///     type Item = u32;
+///
+///     fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u32>> {
+///         let n = self.0;
+///         self.0 = n.saturating_sub(1);
+///         Poll::Ready(if n == 0 { None } else { Some(n) })
+///     }
+/// }
+///
+/// let mut stream = RStream::new(Countdown(3));
+///
+/// let list = block_on(Box::pin(async {
+///     let mut list = RVec::new();
+///     while let Some(x) = stream.next().await {
+///         list.push(x);
+///     }
+///     list
+/// }));
+///
+/// assert_eq!(list, [3, 2, 1]);
+///
+/// ```
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RStream_<'a, T, M = UnsyncUnsend> {
+    stream: RBox<ErasedObject>,
+    poll_next: unsafe extern "C" fn(RMut<'_, ErasedObject>, RContext<'_>) -> RPoll<ROption<T>>,
+    _marker: PhantomData<(&'a (), M)>,
+}
+
+/// Ffi-safe equivalent of `Pin<Box<dyn Stream<Item = T> + 'a>>`.
+pub type RStream<'a, T> = RStream_<'a, T, UnsyncUnsend>;
+
+/// Ffi-safe equivalent of `Pin<Box<dyn Stream<Item = T> + Send + 'a>>`.
+pub type SendRStream<'a, T> = RStream_<'a, T, UnsyncSend>;
+
+impl<'a, T> RStream_<'a, T, UnsyncUnsend> {
+    /// Constructs an `RStream` from a stream.
+    pub fn new<S>(stream: S) -> Self
+    where
+        S: Stream<Item = T> + 'a,
+    {
+        Self::new_inner(stream)
+    }
+}
+
+impl<'a, T> RStream_<'a, T, UnsyncSend> {
+    /// Constructs a `SendRStream` from a `Send` stream.
+    pub fn new<S>(stream: S) -> Self
+    where
+        S: Stream<Item = T> + Send + 'a,
+    {
+        Self::new_inner(stream)
+    }
+}
+
+impl<'a, T, M> RStream_<'a, T, M> {
+    fn new_inner<S>(stream: S) -> Self
+    where
+        S: Stream<Item = T> + 'a,
+    {
+        let stream = RBox::new(stream);
+        let stream = unsafe { mem::transmute::<RBox<S>, RBox<ErasedObject>>(stream) };
+        Self {
+            stream,
+            poll_next: poll_next_stream::<S>,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Polls the wrapped stream with an `RContext`,
+    ///
+    /// This is what the `Stream` impl of this type calls.
+    pub fn poll_next_with(&mut self, cx: RContext<'_>) -> RPoll<ROption<T>> {
+        unsafe { (self.poll_next)(RMut::new(&mut *self.stream), cx) }
+    }
+
+    /// Returns a future that resolves to the next value of this stream,
+    /// or `None` once the stream is exhausted.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Next<'_, Self> {
+        Next { stream: self }
+    }
+}
+
+// The stream is stored in an `RBox`, so moving the `RStream_` doesn't move it.
+impl<'a, T, M> Unpin for RStream_<'a, T, M> {}
+
+impl<'a, T, M> Stream for RStream_<'a, T, M> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        RContext::scoped(cx, |cx| this.poll_next_with(cx))
+            .map(ROption::into_option)
+            .into()
+    }
+}
+
+#[cfg(feature = "futures")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "futures")))]
+impl<'a, T, M> futures_core::Stream for RStream_<'a, T, M> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        Stream::poll_next(self, cx)
+    }
+}
+
+impl<'a, T, M> fmt::Debug for RStream_<'a, T, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RStream_").finish_non_exhaustive()
+    }
+}
+
+unsafe extern "C" fn poll_next_stream<S>(
+    this: RMut<'_, ErasedObject>,
+    cx: RContext<'_>,
+) -> RPoll<ROption<S::Item>>
+where
+    S: Stream,
+{
+    extern_fn_panic_handling! {
+        // The stream is never moved out of its `RBox`, so it can be pinned.
+        let this = unsafe { Pin::new_unchecked(this.transmute_into_mut::<S>()) };
+        RPoll::from(cx.with_context(|cx| this.poll_next(cx))).map(ROption::from)
+    }
+}
+
+/// Future returned by [`RStream_::next`],
+/// which resolves to the next value of the stream.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Next<'b, S: ?Sized> {
+    stream: &'b mut S,
+}
+
+impl<'b, S> Future for Next<'b, S>
+where
+    S: ?Sized + Stream + Unpin,
+{
+    type Output = Option<S::Item>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.get_mut().stream).poll_next(cx)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Wraps a `futures_core::Stream` so that it implements this module's [`Stream`] trait,
+/// allowing it to be converted into an [`RStream_`].
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::std_types::{future::FuturesStream, RStream};
+///
+/// struct Countdown(u32);
+///
+/// impl futures_core::Stream for Countdown {
+///     type Item = u32;
+///
+///     fn poll_next(
+///         mut self: std::pin::Pin<&mut Self>,
+///         _: &mut std::task::Context<'_>,
+///     ) -> std::task::Poll<Option<u32>> {
+///         let ret = self.0.checked_sub(1);
+///         self.0 = ret.unwrap_or(0);
+///         std::task::Poll::Ready(ret)
+///     }
+/// }
+///
+/// let _stream: RStream<'_, u32> = RStream::new(FuturesStream(Countdown(3)));
+/// ```
+#[cfg(feature = "futures")]
+#[cfg_attr(feature = "docsrs", doc(cfg(feature = "futures")))]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct FuturesStream<S>(pub S);
+
+#[cfg(feature = "futures")]
+impl<S> Stream for FuturesStream<S>
+where
+    S: futures_core::Stream,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        // `FuturesStream` is structurally pinned over its only field,
+        // it doesn't implement `Drop`, nor is it unconditionally `Unpin`.
+        unsafe { self.map_unchecked_mut(|this| &mut this.0) }.poll_next(cx)
+    }
+}
@@ -0,0 +1,200 @@
+use super::*;
+
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Wake, Waker},
+};
+
+use crate::{
+    abi_stability::abi_checking::check_layout_compatibility,
+    std_types::{RString, RVec},
+    StableAbi,
+};
+
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Polls `fut` until it's ready, returning its output and the amount of times it was woken up.
+fn block_on<F: Future + Unpin>(mut fut: F) -> (F::Output, usize) {
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(x) = Pin::new(&mut fut).poll(&mut cx) {
+            return (x, counter.0.load(Ordering::SeqCst));
+        }
+    }
+}
+
+/// A future that is pending `count` times, waking up the task each time.
+struct YieldNow {
+    count: u32,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.count == 0 {
+            Poll::Ready(())
+        } else {
+            self.count -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[test]
+fn future_output_and_wakeups() {
+    let name = RString::from("world");
+    let future = RFuture::new(async move {
+        YieldNow { count: 3 }.await;
+        format!("hello {}", name)
+    });
+
+    assert_eq!(block_on(future), ("hello world".to_string(), 3));
+}
+
+#[test]
+fn nested_futures() {
+    let inner = SendRFuture::new(async {
+        YieldNow { count: 2 }.await;
+        10
+    });
+    let outer = SendRFuture::new(async move {
+        let x = inner.await;
+        YieldNow { count: 1 }.await;
+        x * 2
+    });
+
+    assert_eq!(block_on(outer), (20, 3));
+}
+
+#[test]
+fn send_future() {
+    let future = SendRFuture::new(async {
+        YieldNow { count: 1 }.await;
+        RVec::from(vec![3, 5])
+    });
+
+    let handle = std::thread::spawn(move || block_on(future));
+    assert_eq!(handle.join().unwrap(), (RVec::from(vec![3, 5]), 1));
+}
+
+#[test]
+fn drop_unpolled_future() {
+    let rc = Rc::new(());
+    let captured = rc.clone();
+    let future = RFuture::new(async move { drop(captured) });
+    assert_eq!(Rc::strong_count(&rc), 2);
+    drop(future);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+struct Countdown {
+    remaining: u32,
+    pending: Rc<Cell<bool>>,
+}
+
+impl Stream for Countdown {
+    type Item = u32;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>> {
+        // Alternating between pending and ready, to test that wakeups are forwarded
+        if self.pending.replace(false) {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        self.pending.set(true);
+
+        let n = self.remaining;
+        self.remaining = n.saturating_sub(1);
+        Poll::Ready(if n == 0 { None } else { Some(n) })
+    }
+}
+
+#[test]
+fn stream_next() {
+    let mut stream = RStream::new(Countdown {
+        remaining: 3,
+        pending: Rc::new(Cell::new(true)),
+    });
+
+    let future = Box::pin(async move {
+        let mut list = Vec::new();
+        while let Some(x) = stream.next().await {
+            list.push(x);
+        }
+        assert_eq!(stream.next().await, None);
+        list
+    });
+
+    assert_eq!(block_on(future), (vec![3, 2, 1], 5));
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn futures_core_stream_interop() {
+    use std::marker::PhantomPinned;
+
+    struct PinnedCountdown {
+        inner: Countdown,
+        _pinned: PhantomPinned,
+    }
+
+    impl futures_core::Stream for PinnedCountdown {
+        type Item = u32;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>> {
+            unsafe { self.map_unchecked_mut(|this| &mut this.inner) }.poll_next(cx)
+        }
+    }
+
+    let stream = RStream::new(FuturesStream(PinnedCountdown {
+        inner: Countdown {
+            remaining: 2,
+            pending: Rc::new(Cell::new(true)),
+        },
+        _pinned: PhantomPinned,
+    }));
+
+    // polled through `futures_core::Stream`
+    let mut stream = FuturesStream(stream);
+    let future = Box::pin(async move {
+        let mut list = Vec::new();
+        while let Some(x) = (Next { stream: &mut stream }).await {
+            list.push(x);
+        }
+        list
+    });
+
+    assert_eq!(block_on(future), (vec![2, 1], 3));
+}
+
+#[test]
+fn layout_includes_output() {
+    let layout = <RFuture<'static, u32> as StableAbi>::LAYOUT;
+
+    check_layout_compatibility(layout, layout).unwrap();
+
+    let mismatched = [
+        <RFuture<'static, u64> as StableAbi>::LAYOUT,
+        <SendRFuture<'static, u32> as StableAbi>::LAYOUT,
+        <RStream<'static, u32> as StableAbi>::LAYOUT,
+    ];
+
+    for other in mismatched {
+        assert!(check_layout_compatibility(layout, other).is_err());
+    }
+}
@@ -0,0 +1,1016 @@
+//! Contains the ffi-safe equivalent of `std::collections::HashSet`, and related items.
+#![allow(clippy::missing_const_for_fn)]
+
+use std::{
+    borrow::Borrow,
+    collections::{hash_map::RandomState, HashSet},
+    fmt::{self, Debug},
+    hash::{BuildHasher, Hash},
+    iter::{Chain, FromIterator},
+    ops::{BitAnd, BitOr, BitXor, Sub},
+};
+
+use crate::std_types::{
+    map::{self, RHashMap},
+    ROption,
+};
+
+#[cfg(all(test, not(feature = "only_new_tests")))]
+mod test;
+
+/// An ffi-safe hashset, which wraps an [`RHashMap<T, (), S>`](../struct.RHashMap.html),
+/// only requiring the `T: Eq + Hash` bounds when constructing it.
+///
+/// All the operations go through the vtable of the wrapped `RHashMap`,
+/// so this can be passed to and used from other dynamic libraries.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::std_types::{RHashSet, RString};
+///
+/// let mut languages = RHashSet::<RString>::new();
+///
+/// languages.insert("Rust".into());
+/// languages.insert("C".into());
+/// languages.insert("Haskell".into());
+///
+/// assert!(languages.contains("Rust"));
+/// assert!(!languages.insert("C".into()));
+/// assert!(languages.remove("Haskell"));
+///
+/// let systems = vec!["C", "C++", "Rust"]
+///     .into_iter()
+///     .map(RString::from)
+///     .collect::<RHashSet<RString>>();
+///
+/// let mut both = languages.intersection(&systems).cloned().collect::<Vec<_>>();
+/// both.sort();
+/// assert_eq!(both, ["C", "Rust"]);
+///
+/// ```
+///
+#[derive(StableAbi)]
+#[repr(C)]
+#[sabi(
+    // The hasher doesn't matter
+    unsafe_unconstrained(S),
+)]
+pub struct RHashSet<T, S = RandomState> {
+    map: RHashMap<T, (), S>,
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl<T> RHashSet<T, RandomState> {
+    /// Constructs an empty RHashSet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RString};
+    ///
+    /// let mut set = RHashSet::<RString>::new();
+    /// assert!(set.is_empty());
+    /// set.insert("Hello".into());
+    /// assert_eq!(set.is_empty(), false);
+    ///
+    /// ```
+    #[inline]
+    pub fn new() -> RHashSet<T>
+    where
+        Self: Default,
+    {
+        Self::default()
+    }
+
+    /// Constructs an empty RHashSet with at least the passed capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RString};
+    ///
+    /// let mut set = RHashSet::<RString>::with_capacity(10);
+    /// assert!(set.capacity() >= 10);
+    ///
+    /// ```
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> RHashSet<T>
+    where
+        Self: Default,
+    {
+        let mut this = Self::default();
+        this.reserve(capacity);
+        this
+    }
+}
+
+impl<T, S> RHashSet<T, S> {
+    /// Constructs an empty RHashSet with the passed `hash_builder` to hash the elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RString};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let s = RandomState::new();
+    /// let mut set = RHashSet::<RString, _>::with_hasher(s);
+    /// assert!(set.is_empty());
+    /// set.insert("Hello".into());
+    /// assert_eq!(set.is_empty(), false);
+    /// ```
+    #[inline]
+    pub fn with_hasher(hash_builder: S) -> RHashSet<T, S>
+    where
+        T: Eq + Hash,
+        S: BuildHasher + Default,
+    {
+        RHashSet {
+            map: RHashMap::with_hasher(hash_builder),
+        }
+    }
+
+    /// Constructs an empty RHashSet with at least the passed capacity,
+    /// and the passed `hash_builder` to hash the elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RString};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let s = RandomState::new();
+    /// let mut set = RHashSet::<RString, _>::with_capacity_and_hasher(10, s);
+    /// assert!(set.capacity() >= 10);
+    ///
+    /// ```
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> RHashSet<T, S>
+    where
+        T: Eq + Hash,
+        S: BuildHasher + Default,
+    {
+        RHashSet {
+            map: RHashMap::with_capacity_and_hasher(capacity, hash_builder),
+        }
+    }
+}
+
+impl<T, S> RHashSet<T, S> {
+    /// Returns whether the set contains the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RString};
+    ///
+    /// let mut set = RHashSet::<RString>::new();
+    /// assert_eq!(set.contains("boo"), false);
+    /// set.insert("boo".into());
+    /// assert_eq!(set.contains("boo"), true);
+    ///
+    /// ```
+    pub fn contains<Q>(&self, query: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(query)
+    }
+
+    /// Removes the value from the set, returning whether it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert_eq!(set.remove(&0), true);
+    /// assert_eq!(set.remove(&0), false);
+    ///
+    /// ```
+    pub fn remove<Q>(&mut self, query: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove_entry(query).is_some()
+    }
+
+    /// Removes the value from the set, returning it if it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RNone, RSome, RString};
+    ///
+    /// let mut set = RHashSet::<RString>::new();
+    /// set.insert("foo".into());
+    ///
+    /// assert_eq!(set.take("foo"), RSome(RString::from("foo")));
+    /// assert_eq!(set.take("foo"), RNone);
+    ///
+    /// ```
+    pub fn take<Q>(&mut self, query: &Q) -> ROption<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove_entry(query).map(|x| x.0)
+    }
+}
+
+impl<T, S> RHashSet<T, S> {
+    /// Returns whether the set contains the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RString};
+    ///
+    /// let mut set = RHashSet::<RString>::new();
+    /// assert_eq!(set.contains_p(&RString::from("boo")), false);
+    /// set.insert("boo".into());
+    /// assert_eq!(set.contains_p(&RString::from("boo")), true);
+    ///
+    /// ```
+    pub fn contains_p(&self, value: &T) -> bool {
+        self.map.contains_key_p(value)
+    }
+
+    /// Removes the value from the set, returning whether it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert_eq!(set.remove_p(&0), true);
+    /// assert_eq!(set.remove_p(&0), false);
+    ///
+    /// ```
+    pub fn remove_p(&mut self, value: &T) -> bool {
+        self.map.remove_entry_p(value).is_some()
+    }
+
+    /// Removes the value from the set, returning it if it was in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RHashSet, RNone, RSome};
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert_eq!(set.take_p(&0), RSome(0));
+    /// assert_eq!(set.take_p(&0), RNone);
+    ///
+    /// ```
+    pub fn take_p(&mut self, value: &T) -> ROption<T> {
+        self.map.remove_entry_p(value).map(|x| x.0)
+    }
+
+    /// Inserts a value into the set, returning whether it wasn't already in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = RHashSet::<u32>::new();
+    ///
+    /// assert_eq!(set.insert(0), true);
+    /// assert_eq!(set.insert(0), false);
+    /// assert_eq!(set.len(), 1);
+    ///
+    /// ```
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    /// Reserves enough space to insert `reserved` extra elements without reallocating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = RHashSet::<u32>::new();
+    /// set.reserve(10);
+    ///
+    /// ```
+    pub fn reserve(&mut self, reserved: usize) {
+        self.map.reserve(reserved)
+    }
+
+    /// Removes all the elements in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// set.clear();
+    ///
+    /// assert!(set.is_empty());
+    ///
+    /// ```
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Returns the amount of elements in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = RHashSet::<u32>::new();
+    ///
+    /// assert_eq!(set.len(), 0);
+    /// set.insert(0);
+    /// assert_eq!(set.len(), 1);
+    ///
+    /// ```
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns the capacity of the set, the amount of elements it can store without reallocating.
+    ///
+    /// Note that this is a lower bound, since hash sets don't necessarily have an exact capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let set = RHashSet::<u32>::with_capacity(4);
+    ///
+    /// assert!(set.capacity() >= 4);
+    ///
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    /// Returns whether the set contains any elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = RHashSet::<u32>::new();
+    ///
+    /// assert_eq!(set.is_empty(), true);
+    /// set.insert(0);
+    /// assert_eq!(set.is_empty(), false);
+    ///
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterates over the elements in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let set = vec![0, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// let mut list = set.iter().collect::<Vec<_>>();
+    /// list.sort();
+    /// assert_eq!(list, vec![&0, &3]);
+    ///
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.map.iter(),
+        }
+    }
+
+    /// Clears the set, returning an iterator over all the elements that were removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let mut set = vec![0, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// let mut list = set.drain().collect::<Vec<_>>();
+    /// list.sort();
+    /// assert_eq!(list, vec![0, 3]);
+    ///
+    /// assert!(set.is_empty());
+    ///
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain {
+            inner: self.map.drain(),
+        }
+    }
+
+    /// Iterates over the elements that are in `self` or `other`,
+    /// without duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let left = vec![0, 1, 2].into_iter().collect::<RHashSet<u32>>();
+    /// let right = vec![1, 2, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// let mut list = left.union(&right).collect::<Vec<_>>();
+    /// list.sort();
+    /// assert_eq!(list, vec![&0, &1, &2, &3]);
+    ///
+    /// ```
+    pub fn union<'a>(&'a self, other: &'a RHashSet<T, S>) -> Union<'a, T, S> {
+        Union {
+            inner: self.iter().chain(other.difference(self)),
+        }
+    }
+
+    /// Iterates over the elements that are in both `self` and `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let left = vec![0, 1, 2].into_iter().collect::<RHashSet<u32>>();
+    /// let right = vec![1, 2, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// let mut list = left.intersection(&right).collect::<Vec<_>>();
+    /// list.sort();
+    /// assert_eq!(list, vec![&1, &2]);
+    ///
+    /// ```
+    pub fn intersection<'a>(&'a self, other: &'a RHashSet<T, S>) -> Intersection<'a, T, S> {
+        let (smaller, larger) = if self.len() <= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        Intersection {
+            iter: smaller.iter(),
+            other: larger,
+        }
+    }
+
+    /// Iterates over the elements that are in `self` but not in `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let left = vec![0, 1, 2].into_iter().collect::<RHashSet<u32>>();
+    /// let right = vec![1, 2, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert_eq!(left.difference(&right).collect::<Vec<_>>(), vec![&0]);
+    /// assert_eq!(right.difference(&left).collect::<Vec<_>>(), vec![&3]);
+    ///
+    /// ```
+    pub fn difference<'a>(&'a self, other: &'a RHashSet<T, S>) -> Difference<'a, T, S> {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Iterates over the elements that are in either `self` or `other`,
+    /// but not in both.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let left = vec![0, 1, 2].into_iter().collect::<RHashSet<u32>>();
+    /// let right = vec![1, 2, 3].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// let mut list = left.symmetric_difference(&right).collect::<Vec<_>>();
+    /// list.sort();
+    /// assert_eq!(list, vec![&0, &3]);
+    ///
+    /// ```
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a RHashSet<T, S>,
+    ) -> SymmetricDifference<'a, T, S> {
+        SymmetricDifference {
+            inner: self.difference(other).chain(other.difference(self)),
+        }
+    }
+
+    /// Returns whether `self` and `other` have no elements in common.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let set = vec![0, 1].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert!(set.is_disjoint(&vec![2, 3].into_iter().collect()));
+    /// assert!(!set.is_disjoint(&vec![1, 2].into_iter().collect()));
+    ///
+    /// ```
+    pub fn is_disjoint(&self, other: &RHashSet<T, S>) -> bool {
+        self.intersection(other).next().is_none()
+    }
+
+    /// Returns whether all the elements in `self` are in `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let set = vec![0, 1].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert!(set.is_subset(&vec![0, 1, 2].into_iter().collect()));
+    /// assert!(!set.is_subset(&vec![1, 2].into_iter().collect()));
+    ///
+    /// ```
+    pub fn is_subset(&self, other: &RHashSet<T, S>) -> bool {
+        self.len() <= other.len() && self.iter().all(|x| other.contains_p(x))
+    }
+
+    /// Returns whether all the elements in `other` are in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RHashSet;
+    ///
+    /// let set = vec![0, 1, 2].into_iter().collect::<RHashSet<u32>>();
+    ///
+    /// assert!(set.is_superset(&vec![0, 1].into_iter().collect()));
+    /// assert!(!set.is_superset(&vec![2, 3].into_iter().collect()));
+    ///
+    /// ```
+    pub fn is_superset(&self, other: &RHashSet<T, S>) -> bool {
+        other.is_subset(self)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// An iterator over the elements of an `RHashSet`.
+///
+/// This `struct` is created by the [`iter`] method on [`RHashSet`].
+///
+/// [`iter`]: RHashSet::iter
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct Iter<'a, T: 'a> {
+    inner: map::Iter<'a, T, ()>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Iter {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next().map(|tuple| tuple.0)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// An iterator over the elements removed from an `RHashSet`.
+///
+/// This `struct` is created by the [`drain`] method on [`RHashSet`].
+///
+/// [`drain`]: RHashSet::drain
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct Drain<'a, T: 'a> {
+    inner: map::Drain<'a, T, ()>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.next().map(|tuple| tuple.0)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// An iterator over the elements of an `RHashSet` by value.
+///
+/// This `struct` is created by the `into_iter` method on [`RHashSet`].
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct IntoIter<T> {
+    inner: map::IntoIter<T, ()>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.next().map(|tuple| tuple.0)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// An iterator over the elements in either of two `RHashSet`s.
+///
+/// This `struct` is created by the [`union`] method on [`RHashSet`].
+///
+/// [`union`]: RHashSet::union
+pub struct Union<'a, T, S> {
+    inner: Chain<Iter<'a, T>, Difference<'a, T, S>>,
+}
+
+impl<T, S> Clone for Union<'_, T, S> {
+    fn clone(&self) -> Self {
+        Union {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Debug, S> Debug for Union<'_, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T, S> Iterator for Union<'a, T, S> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+}
+
+/// An iterator over the elements in both of two `RHashSet`s.
+///
+/// This `struct` is created by the [`intersection`] method on [`RHashSet`].
+///
+/// [`intersection`]: RHashSet::intersection
+pub struct Intersection<'a, T, S> {
+    iter: Iter<'a, T>,
+    other: &'a RHashSet<T, S>,
+}
+
+impl<T, S> Clone for Intersection<'_, T, S> {
+    fn clone(&self) -> Self {
+        Intersection {
+            iter: self.iter.clone(),
+            other: self.other,
+        }
+    }
+}
+
+impl<T: Debug, S> Debug for Intersection<'_, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T, S> Iterator for Intersection<'a, T, S> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        self.iter.find(|x| other.contains_p(x))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// An iterator over the elements in one `RHashSet` that aren't in another.
+///
+/// This `struct` is created by the [`difference`] method on [`RHashSet`].
+///
+/// [`difference`]: RHashSet::difference
+pub struct Difference<'a, T, S> {
+    iter: Iter<'a, T>,
+    other: &'a RHashSet<T, S>,
+}
+
+impl<T, S> Clone for Difference<'_, T, S> {
+    fn clone(&self) -> Self {
+        Difference {
+            iter: self.iter.clone(),
+            other: self.other,
+        }
+    }
+}
+
+impl<T: Debug, S> Debug for Difference<'_, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T, S> Iterator for Difference<'a, T, S> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        self.iter.find(|x| !other.contains_p(x))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// An iterator over the elements in exactly one of two `RHashSet`s.
+///
+/// This `struct` is created by the [`symmetric_difference`] method on [`RHashSet`].
+///
+/// [`symmetric_difference`]: RHashSet::symmetric_difference
+pub struct SymmetricDifference<'a, T, S> {
+    inner: Chain<Difference<'a, T, S>, Difference<'a, T, S>>,
+}
+
+impl<T, S> Clone for SymmetricDifference<'_, T, S> {
+    fn clone(&self) -> Self {
+        SymmetricDifference {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Debug, S> Debug for SymmetricDifference<'_, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T, S> Iterator for SymmetricDifference<'a, T, S> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl<T, S> IntoIterator for RHashSet<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            inner: self.map.into_iter(),
+        }
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a RHashSet<T, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, S> From<HashSet<T, S>> for RHashSet<T, S>
+where
+    Self: Default,
+{
+    fn from(set: HashSet<T, S>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<T, S> From<RHashSet<T, S>> for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from(this: RHashSet<T, S>) -> HashSet<T, S> {
+        this.into_iter().collect()
+    }
+}
+
+impl<T, S> FromIterator<T> for RHashSet<T, S>
+where
+    Self: Default,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut set = Self::default();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S> Extend<T> for RHashSet<T, S> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.map.extend(iter.into_iter().map(|x| (x, ())));
+    }
+}
+
+impl<'a, T, S> Extend<&'a T> for RHashSet<T, S>
+where
+    T: Copy + 'a,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, S> Default for RHashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<T, S> Clone for RHashSet<T, S>
+where
+    T: Clone,
+    Self: Default,
+{
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T, S> Debug for RHashSet<T, S>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T, S> Eq for RHashSet<T, S> where T: Eq {}
+
+impl<T, S> PartialEq for RHashSet<T, S>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|x| other.contains_p(x))
+    }
+}
+
+macro_rules! impl_set_operator {
+    ($trait_:ident, $method:ident, $set_method:ident) => {
+        impl<T, S> $trait_<&RHashSet<T, S>> for &RHashSet<T, S>
+        where
+            T: Clone,
+            RHashSet<T, S>: Default,
+        {
+            type Output = RHashSet<T, S>;
+
+            fn $method(self, other: &RHashSet<T, S>) -> RHashSet<T, S> {
+                self.$set_method(other).cloned().collect()
+            }
+        }
+    };
+}
+
+impl_set_operator! {BitOr, bitor, union}
+impl_set_operator! {BitAnd, bitand, intersection}
+impl_set_operator! {BitXor, bitxor, symmetric_difference}
+impl_set_operator! {Sub, sub, difference}
+
+mod serde {
+    use super::*;
+
+    use ::serde::{
+        de::{SeqAccess, Visitor},
+        ser::SerializeSeq,
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use crate::marker_type::NonOwningPhantom;
+
+    struct RHashSetVisitor<T, S> {
+        _marker: NonOwningPhantom<RHashSet<T, S>>,
+    }
+
+    impl<T, S> RHashSetVisitor<T, S> {
+        fn new() -> Self {
+            RHashSetVisitor {
+                _marker: NonOwningPhantom::NEW,
+            }
+        }
+    }
+
+    impl<'de, T, S> Visitor<'de> for RHashSetVisitor<T, S>
+    where
+        T: Deserialize<'de>,
+        RHashSet<T, S>: Default,
+    {
+        type Value = RHashSet<T, S>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("an RHashSet")
+        }
+
+        fn visit_seq<A>(self, mut seq_access: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let capacity = seq_access.size_hint().unwrap_or(0);
+            let mut set = RHashSet::default();
+            set.reserve(capacity);
+
+            while let Some(x) = seq_access.next_element()? {
+                set.insert(x);
+            }
+
+            Ok(set)
+        }
+    }
+
+    impl<'de, T, S> Deserialize<'de> for RHashSet<T, S>
+    where
+        T: Deserialize<'de>,
+        Self: Default,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(RHashSetVisitor::new())
+        }
+    }
+
+    impl<T, S> Serialize for RHashSet<T, S>
+    where
+        T: Serialize,
+    {
+        fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error>
+        where
+            Z: Serializer,
+        {
+            let mut seq = serializer.serialize_seq(Some(self.len()))?;
+            for x in self.iter() {
+                seq.serialize_element(x)?;
+            }
+            seq.end()
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use super::*;
+
+use fnv::FnvBuildHasher as FnVBH;
+
+use crate::std_types::{RNone, RSome, RString};
+
+fn new_set<S>(list: &[u32]) -> RHashSet<u32, S>
+where
+    S: BuildHasher + Default,
+{
+    list.iter().copied().collect()
+}
+
+fn sorted<'a, I>(iter: I) -> Vec<u32>
+where
+    I: IntoIterator<Item = &'a u32>,
+{
+    let mut list = iter.into_iter().copied().collect::<Vec<_>>();
+    list.sort_unstable();
+    list
+}
+
+#[test]
+fn insert_remove() {
+    let mut set = RHashSet::<RString, FnVBH>::default();
+
+    assert!(set.insert("foo".into()));
+    assert!(set.insert("bar".into()));
+    assert!(!set.insert("foo".into()));
+    assert_eq!(set.len(), 2);
+
+    assert!(set.contains("foo"));
+    assert!(set.contains_p(&RString::from("bar")));
+    assert!(!set.contains("baz"));
+
+    assert_eq!(set.take("foo"), RSome(RString::from("foo")));
+    assert_eq!(set.take("foo"), RNone);
+    assert!(set.remove_p(&RString::from("bar")));
+    assert!(set.is_empty());
+}
+
+#[test]
+fn iteration() {
+    let mut set = new_set::<RandomState>(&[3, 5, 8, 13]);
+
+    assert_eq!(sorted(&set), [3, 5, 8, 13]);
+    assert_eq!(sorted(set.iter().clone()), [3, 5, 8, 13]);
+    assert_eq!(set.iter().size_hint(), (4, Some(4)));
+
+    let mut drained = set.drain().collect::<Vec<_>>();
+    drained.sort_unstable();
+    assert_eq!(drained, [3, 5, 8, 13]);
+    assert!(set.is_empty());
+
+    let set = new_set::<RandomState>(&[21, 34]);
+    let mut owned = set.into_iter().collect::<Vec<_>>();
+    owned.sort_unstable();
+    assert_eq!(owned, [21, 34]);
+}
+
+#[test]
+fn set_algebra() {
+    let left = new_set::<FnVBH>(&[0, 1, 2, 3]);
+    let right = new_set::<FnVBH>(&[2, 3, 4]);
+
+    assert_eq!(sorted(left.union(&right)), [0, 1, 2, 3, 4]);
+    assert_eq!(sorted(left.intersection(&right)), [2, 3]);
+    assert_eq!(sorted(right.intersection(&left)), [2, 3]);
+    assert_eq!(sorted(left.difference(&right)), [0, 1]);
+    assert_eq!(sorted(right.difference(&left)), [4]);
+    assert_eq!(sorted(left.symmetric_difference(&right)), [0, 1, 4]);
+
+    assert_eq!(&left | &right, new_set(&[0, 1, 2, 3, 4]));
+    assert_eq!(&left & &right, new_set(&[2, 3]));
+    assert_eq!(&left - &right, new_set(&[0, 1]));
+    assert_eq!(&left ^ &right, new_set(&[0, 1, 4]));
+
+    assert!(!left.is_disjoint(&right));
+    assert!(left.is_disjoint(&new_set(&[10, 11])));
+    assert!(new_set(&[1, 2]).is_subset(&left));
+    assert!(!right.is_subset(&left));
+    assert!(left.is_superset(&new_set(&[])));
+}
+
+#[test]
+fn conversions() {
+    let std_set = vec![3, 5, 8].into_iter().collect::<HashSet<u32>>();
+    let set = RHashSet::from(std_set.clone());
+
+    assert_eq!(set, new_set(&[3, 5, 8]));
+    assert_eq!(HashSet::from(set.clone()), std_set);
+
+    let mut extended = set.clone();
+    extended.extend(&[13, 21]);
+    extended.extend(vec![34]);
+    assert_eq!(sorted(&extended), [3, 5, 8, 13, 21, 34]);
+    assert_ne!(extended, set);
+
+    assert_eq!(format!("{:?}", new_set::<RandomState>(&[1])), "{1}");
+}
+
+#[test]
+fn serde_roundtrip() {
+    let set = new_set::<RandomState>(&[3, 5, 8]);
+
+    let json = serde_json::to_string(&set).unwrap();
+    let deserialized = serde_json::from_str::<RHashSet<u32>>(&json).unwrap();
+    assert_eq!(deserialized, set);
+
+    let deserialized = serde_json::from_str::<RHashSet<u32>>("[1, 2, 2]").unwrap();
+    assert_eq!(deserialized, new_set(&[1, 2]));
+}
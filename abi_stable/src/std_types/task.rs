@@ -0,0 +1,526 @@
+//! Contains ffi-safe equivalents of types from `std::task`.
+
+use std::{
+    fmt,
+    mem::ManuallyDrop,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+use crate::{
+    marker_type::ErasedObject,
+    prefix_type::WithMetadata,
+    std_types::utypeid::{new_utypeid, UTypeId},
+};
+
+#[cfg(test)]
+mod test;
+
+/// Ffi-safe equivalent of `std::task::Poll`.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::std_types::{RPending, RPoll, RReady};
+///
+/// use std::task::Poll;
+///
+/// assert_eq!(RPoll::from(Poll::Ready(3)), RReady(3));
+/// assert_eq!(RPoll::<u32>::from(Poll::Pending), RPending);
+///
+/// assert_eq!(Poll::from(RReady("hello")), Poll::Ready("hello"));
+///
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[repr(u8)]
+#[derive(StableAbi)]
+pub enum RPoll<T> {
+    /// Equivalent to `Poll::Ready`
+    RReady(T),
+    /// Equivalent to `Poll::Pending`
+    RPending,
+}
+
+pub use self::RPoll::*;
+
+impl<T> RPoll<T> {
+    /// Returns whether this is an `RReady`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RPending, RPoll, RReady};
+    ///
+    /// assert!(RReady(10).is_ready());
+    /// assert!(!RPending::<u32>.is_ready());
+    ///
+    /// ```
+    #[inline]
+    pub const fn is_ready(&self) -> bool {
+        matches!(self, RReady { .. })
+    }
+
+    /// Returns whether this is an `RPending`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RPending, RPoll, RReady};
+    ///
+    /// assert!(!RReady(10).is_pending());
+    /// assert!(RPending::<u32>.is_pending());
+    ///
+    /// ```
+    #[inline]
+    pub const fn is_pending(&self) -> bool {
+        matches!(self, RPending)
+    }
+
+    /// Maps the value inside an `RReady` with the `op` closure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::{RPending, RPoll, RReady};
+    ///
+    /// assert_eq!(RReady(10).map(|x| x * 2), RReady(20));
+    /// assert_eq!(RPending::<u32>.map(|x| x * 2), RPending);
+    ///
+    /// ```
+    #[inline]
+    pub fn map<U, F>(self, op: F) -> RPoll<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            RReady(x) => RReady(op(x)),
+            RPending => RPending,
+        }
+    }
+}
+
+impl_from_rust_repr! {
+    impl[T] From<Poll<T>> for RPoll<T> {
+        fn(this){
+            match this {
+                Poll::Ready(v) => RReady(v),
+                Poll::Pending => RPending,
+            }
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl[T] Into<Poll<T>> for RPoll<T> {
+        fn(this){
+            match this {
+                RReady(v) => Poll::Ready(v),
+                RPending => Poll::Pending,
+            }
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Ffi-safe equivalent of `std::task::Waker`.
+///
+/// Wakers from either side of the ffi boundary can be converted to and from this type:
+///
+/// - `RWaker::new`/`From<Waker>` wrap a `Waker`, and `RWaker::into_waker`
+///   unwraps it if the `RWaker` was constructed in the same dynamic library.
+///
+/// - `RWaker::into_waker` converts an `RWaker` from another dynamic library into a `Waker`
+///   that wakes up that `RWaker`.
+///
+/// # Example
+///
+/// ```
+/// use abi_stable::std_types::RWaker;
+///
+/// use std::{
+///     sync::{
+///         atomic::{AtomicUsize, Ordering},
+///         Arc,
+///     },
+///     task::{Wake, Waker},
+/// };
+///
+/// struct Counter(AtomicUsize);
+///
+/// impl Wake for Counter {
+///     fn wake(self: Arc<Self>) {
+///         self.0.fetch_add(1, Ordering::SeqCst);
+///     }
+/// }
+///
+/// let counter = Arc::new(Counter(AtomicUsize::new(0)));
+///
+/// let waker = RWaker::new(Waker::from(counter.clone()));
+/// waker.wake_by_ref();
+/// waker.clone().wake();
+/// waker.into_waker().wake();
+///
+/// assert_eq!(counter.0.load(Ordering::SeqCst), 3);
+///
+/// ```
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RWaker {
+    data: *const ErasedObject,
+    vtable: RWakerVTable_Ref,
+}
+
+unsafe impl Send for RWaker {}
+unsafe impl Sync for RWaker {}
+
+impl RWaker {
+    /// Constructs an `RWaker` from a `Waker`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RWaker;
+    ///
+    /// use std::{sync::Arc, task::{Wake, Waker}};
+    ///
+    /// struct NoopWaker;
+    ///
+    /// impl Wake for NoopWaker {
+    ///     fn wake(self: Arc<Self>) {}
+    /// }
+    ///
+    /// let waker = Waker::from(Arc::new(NoopWaker));
+    ///
+    /// let rwaker = RWaker::new(waker.clone());
+    /// assert!(rwaker.into_waker().will_wake(&waker));
+    ///
+    /// ```
+    pub fn new(waker: Waker) -> Self {
+        Self {
+            data: Box::into_raw(Box::new(waker)) as *const ErasedObject,
+            vtable: VTABLE_OWNED,
+        }
+    }
+
+    /// Calls `f` with an `RWaker` that borrows `waker`, without cloning it.
+    pub(crate) fn with_borrowed<R, F>(waker: &Waker, f: F) -> R
+    where
+        F: FnOnce(&RWaker) -> R,
+    {
+        let this = Self {
+            data: waker as *const Waker as *const ErasedObject,
+            vtable: VTABLE_BORROWED,
+        };
+        f(&this)
+    }
+
+    /// Wakes up the task associated with this `RWaker`.
+    pub fn wake(self) {
+        let this = ManuallyDrop::new(self);
+        unsafe { this.vtable.wake()(this.data) }
+    }
+
+    /// Wakes up the task associated with this `RWaker`, without consuming it.
+    pub fn wake_by_ref(&self) {
+        unsafe { self.vtable.wake_by_ref()(self.data) }
+    }
+
+    /// Converts this `RWaker` into a `Waker`.
+    ///
+    /// This unwraps the `Waker` that this was constructed from
+    /// if it was constructed in the same dynamic library,
+    /// otherwise it allocates a `Waker` that wakes up this `RWaker`.
+    pub fn into_waker(self) -> Waker {
+        if self.is_local(VTABLE_OWNED) {
+            let this = ManuallyDrop::new(self);
+            unsafe { *Box::from_raw(this.data as *mut Waker) }
+        } else if self.is_local(VTABLE_BORROWED) {
+            unsafe { (*(self.data as *const Waker)).clone() }
+        } else {
+            let raw = RawWaker::new(Box::into_raw(Box::new(self)) as *const (), &RAW_VTABLE_OWNED);
+            unsafe { Waker::from_raw(raw) }
+        }
+    }
+
+    /// Calls `f` with a `Waker` equivalent to this `RWaker`,
+    /// without allocating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RWaker;
+    ///
+    /// use std::{sync::Arc, task::{Wake, Waker}};
+    ///
+    /// struct NoopWaker;
+    ///
+    /// impl Wake for NoopWaker {
+    ///     fn wake(self: Arc<Self>) {}
+    /// }
+    ///
+    /// let waker = Waker::from(Arc::new(NoopWaker));
+    /// let rwaker = RWaker::new(waker.clone());
+    ///
+    /// assert!(rwaker.with_waker(|w| w.will_wake(&waker)));
+    ///
+    /// ```
+    pub fn with_waker<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&Waker) -> R,
+    {
+        if self.is_local(VTABLE_OWNED) || self.is_local(VTABLE_BORROWED) {
+            f(unsafe { &*(self.data as *const Waker) })
+        } else {
+            let raw = RawWaker::new(self as *const RWaker as *const (), &RAW_VTABLE_BORROWED);
+            let waker = ManuallyDrop::new(unsafe { Waker::from_raw(raw) });
+            f(&waker)
+        }
+    }
+
+    fn is_local(&self, vtable: RWakerVTable_Ref) -> bool {
+        self.vtable.type_id()() == vtable.type_id()()
+    }
+}
+
+impl Clone for RWaker {
+    fn clone(&self) -> Self {
+        unsafe { self.vtable.clone_()(self.data) }
+    }
+}
+
+impl Drop for RWaker {
+    fn drop(&mut self) {
+        unsafe { self.vtable.drop_()(self.data) }
+    }
+}
+
+impl fmt::Debug for RWaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RWaker")
+            .field("data", &self.data)
+            .finish_non_exhaustive()
+    }
+}
+
+impl From<Waker> for RWaker {
+    #[inline]
+    fn from(waker: Waker) -> Self {
+        Self::new(waker)
+    }
+}
+
+impl From<RWaker> for Waker {
+    #[inline]
+    fn from(waker: RWaker) -> Self {
+        waker.into_waker()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Ffi-safe equivalent of `std::task::Context`.
+///
+/// # Example
+///
+/// Polling a future by passing an `RContext` through an `extern "C"` function.
+///
+/// ```
+/// use abi_stable::std_types::{RContext, RPoll};
+///
+/// use std::{
+///     future::Future,
+///     pin::Pin,
+///     sync::Arc,
+///     task::{Context, Poll, Wake, Waker},
+/// };
+///
+/// extern "C" fn poll_ready(cx: RContext<'_>) -> RPoll<u32> {
+///     cx.with_context(|cx| Pin::new(&mut std::future::ready(100)).poll(cx).into())
+/// }
+///
+/// struct NoopWaker;
+///
+/// impl Wake for NoopWaker {
+///     fn wake(self: Arc<Self>) {}
+/// }
+///
+/// let waker = Waker::from(Arc::new(NoopWaker));
+/// let mut cx = Context::from_waker(&waker);
+///
+/// let poll = RContext::scoped(&mut cx, |cx| poll_ready(cx));
+/// assert_eq!(Poll::from(poll), Poll::Ready(100));
+///
+/// ```
+#[repr(C)]
+#[derive(Debug, Copy, Clone, StableAbi)]
+pub struct RContext<'a> {
+    waker: &'a RWaker,
+}
+
+impl<'a> RContext<'a> {
+    /// Constructs an `RContext` from an `RWaker`.
+    #[inline]
+    pub const fn from_waker(waker: &'a RWaker) -> Self {
+        Self { waker }
+    }
+
+    /// Gets the `RWaker` for the current task.
+    #[inline]
+    pub const fn waker(&self) -> &'a RWaker {
+        self.waker
+    }
+
+    /// Calls `f` with an `RContext` equivalent to `cx`.
+    ///
+    /// This does not allocate, the `RWaker` passed to `f` borrows the waker in `cx`.
+    pub fn scoped<R, F>(cx: &mut Context<'_>, f: F) -> R
+    where
+        F: FnOnce(RContext<'_>) -> R,
+    {
+        RWaker::with_borrowed(cx.waker(), |waker| f(RContext::from_waker(waker)))
+    }
+
+    /// Calls `f` with a `Context` equivalent to this `RContext`.
+    ///
+    /// This does not allocate, the `Context` passed to `f` borrows the waker in `self`.
+    pub fn with_context<R, F>(self, f: F) -> R
+    where
+        F: FnOnce(&mut Context<'_>) -> R,
+    {
+        self.waker
+            .with_waker(|waker| f(&mut Context::from_waker(waker)))
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix))]
+#[sabi(missing_field(panic))]
+struct RWakerVTable {
+    type_id: extern "C" fn() -> UTypeId,
+    clone_: unsafe extern "C" fn(*const ErasedObject) -> RWaker,
+    wake: unsafe extern "C" fn(*const ErasedObject),
+    wake_by_ref: unsafe extern "C" fn(*const ErasedObject),
+    #[sabi(last_prefix_field)]
+    drop_: unsafe extern "C" fn(*const ErasedObject),
+}
+
+// The vtable for `RWaker`s that own a `Waker`
+const VTABLE_OWNED: RWakerVTable_Ref = {
+    const WM: &WithMetadata<RWakerVTable> = &WithMetadata::new(RWakerVTable {
+        type_id: new_utypeid::<Waker>,
+        clone_: clone_waker,
+        wake: wake_waker,
+        wake_by_ref: wake_waker_by_ref,
+        drop_: drop_waker,
+    });
+    RWakerVTable_Ref(WM.static_as_prefix())
+};
+
+// The vtable for `RWaker`s that borrow a `Waker`,
+// these are only ever exposed by reference.
+const VTABLE_BORROWED: RWakerVTable_Ref = {
+    const WM: &WithMetadata<RWakerVTable> = &WithMetadata::new(RWakerVTable {
+        type_id: new_utypeid::<&'static Waker>,
+        clone_: clone_waker,
+        wake: wake_waker_by_ref,
+        wake_by_ref: wake_waker_by_ref,
+        drop_: drop_borrowed_waker,
+    });
+    RWakerVTable_Ref(WM.static_as_prefix())
+};
+
+// Used to test the code paths for `RWaker`s that come from other dynamic libraries.
+#[cfg(test)]
+const VTABLE_FOR_TESTING: RWakerVTable_Ref = {
+    const WM: &WithMetadata<RWakerVTable> = &WithMetadata::new(RWakerVTable {
+        type_id: new_utypeid::<Option<Waker>>,
+        clone_: clone_waker_for_testing,
+        wake: wake_waker,
+        wake_by_ref: wake_waker_by_ref,
+        drop_: drop_waker,
+    });
+    RWakerVTable_Ref(WM.static_as_prefix())
+};
+
+#[cfg(test)]
+impl RWaker {
+    fn new_for_testing(waker: Waker) -> Self {
+        Self {
+            data: Box::into_raw(Box::new(waker)) as *const ErasedObject,
+            vtable: VTABLE_FOR_TESTING,
+        }
+    }
+}
+
+unsafe extern "C" fn clone_waker(this: *const ErasedObject) -> RWaker {
+    extern_fn_panic_handling! {
+        let waker = unsafe { &*(this as *const Waker) };
+        RWaker::new(waker.clone())
+    }
+}
+
+#[cfg(test)]
+unsafe extern "C" fn clone_waker_for_testing(this: *const ErasedObject) -> RWaker {
+    extern_fn_panic_handling! {
+        let waker = unsafe { &*(this as *const Waker) };
+        RWaker::new_for_testing(waker.clone())
+    }
+}
+
+unsafe extern "C" fn wake_waker(this: *const ErasedObject) {
+    extern_fn_panic_handling! {
+        let waker = unsafe { Box::from_raw(this as *mut Waker) };
+        waker.wake();
+    }
+}
+
+unsafe extern "C" fn wake_waker_by_ref(this: *const ErasedObject) {
+    extern_fn_panic_handling! {
+        let waker = unsafe { &*(this as *const Waker) };
+        waker.wake_by_ref();
+    }
+}
+
+unsafe extern "C" fn drop_waker(this: *const ErasedObject) {
+    extern_fn_panic_handling! {
+        drop(unsafe { Box::from_raw(this as *mut Waker) });
+    }
+}
+
+unsafe extern "C" fn drop_borrowed_waker(_: *const ErasedObject) {}
+
+///////////////////////////////////////////////////////////////////////////////
+
+// The vtable for `Waker`s that own a boxed `RWaker`
+static RAW_VTABLE_OWNED: RawWakerVTable =
+    RawWakerVTable::new(clone_raw, wake_raw, wake_raw_by_ref, drop_raw);
+
+// The vtable for `Waker`s that borrow an `RWaker`,
+// these are only ever exposed by reference.
+static RAW_VTABLE_BORROWED: RawWakerVTable =
+    RawWakerVTable::new(clone_raw, wake_raw_by_ref, wake_raw_by_ref, drop_borrowed_raw);
+
+unsafe fn clone_raw(this: *const ()) -> RawWaker {
+    let this = unsafe { &*(this as *const RWaker) };
+    RawWaker::new(
+        Box::into_raw(Box::new(this.clone())) as *const (),
+        &RAW_VTABLE_OWNED,
+    )
+}
+
+unsafe fn wake_raw(this: *const ()) {
+    unsafe { Box::from_raw(this as *mut RWaker).wake() }
+}
+
+unsafe fn wake_raw_by_ref(this: *const ()) {
+    unsafe { (*(this as *const RWaker)).wake_by_ref() }
+}
+
+unsafe fn drop_raw(this: *const ()) {
+    drop(unsafe { Box::from_raw(this as *mut RWaker) });
+}
+
+unsafe fn drop_borrowed_raw(_: *const ()) {}
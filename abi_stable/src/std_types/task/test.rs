@@ -0,0 +1,108 @@
+use super::*;
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+struct CountingWaker(AtomicUsize);
+
+impl std::task::Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn counting_waker() -> (Arc<CountingWaker>, Waker) {
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker = Waker::from(counter.clone());
+    (counter, waker)
+}
+
+fn wakes(counter: &CountingWaker) -> usize {
+    counter.0.load(Ordering::SeqCst)
+}
+
+#[test]
+fn poll_conversions() {
+    assert_eq!(RPoll::from(Poll::Ready(3)), RReady(3));
+    assert_eq!(RPoll::<u32>::from(Poll::Pending), RPending);
+    assert_eq!(Poll::from(RReady(5)), Poll::Ready(5));
+    assert_eq!(Poll::<u32>::from(RPending), Poll::Pending);
+
+    assert!(RReady(0).is_ready());
+    assert!(RPending::<u32>.is_pending());
+    assert_eq!(RReady(3).map(|x| x + 1), RReady(4));
+}
+
+#[test]
+fn local_waker() {
+    let (counter, waker) = counting_waker();
+
+    let rwaker = RWaker::new(waker.clone());
+    rwaker.wake_by_ref();
+    assert_eq!(wakes(&counter), 1);
+
+    rwaker.clone().wake();
+    assert_eq!(wakes(&counter), 2);
+
+    assert!(rwaker.with_waker(|w| w.will_wake(&waker)));
+
+    let unwrapped = rwaker.into_waker();
+    assert!(unwrapped.will_wake(&waker));
+    unwrapped.wake();
+    assert_eq!(wakes(&counter), 3);
+
+    drop(waker);
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
+#[test]
+fn foreign_waker() {
+    let (counter, waker) = counting_waker();
+
+    let rwaker = RWaker::new_for_testing(waker.clone());
+    assert!(!rwaker.is_local(VTABLE_OWNED));
+
+    rwaker.clone().wake();
+    assert_eq!(wakes(&counter), 1);
+
+    rwaker.with_waker(|w| {
+        assert!(!w.will_wake(&waker));
+        w.wake_by_ref();
+        let cloned = w.clone();
+        cloned.wake();
+    });
+    assert_eq!(wakes(&counter), 3);
+
+    let wrapped = rwaker.into_waker();
+    assert!(!wrapped.will_wake(&waker));
+    wrapped.wake_by_ref();
+    let cloned = wrapped.clone();
+    cloned.wake();
+    wrapped.wake();
+    assert_eq!(wakes(&counter), 6);
+
+    drop(waker);
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
+#[test]
+fn borrowed_context() {
+    let (counter, waker) = counting_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let cloned = RContext::scoped(&mut cx, |rcx| {
+        assert!(rcx.waker().is_local(VTABLE_BORROWED));
+        rcx.waker().wake_by_ref();
+        rcx.with_context(|cx| assert!(cx.waker().will_wake(&waker)));
+        rcx.waker().clone()
+    });
+    assert_eq!(wakes(&counter), 1);
+    assert_eq!(Arc::strong_count(&counter), 3);
+
+    assert!(cloned.is_local(VTABLE_OWNED));
+    cloned.wake();
+    assert_eq!(wakes(&counter), 2);
+    assert_eq!(Arc::strong_count(&counter), 2);
+}
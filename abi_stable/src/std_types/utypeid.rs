@@ -106,8 +106,29 @@ impl UTypeId {
             type_id_array: get_typeid::<T>(),
         }
     }
+
+    /// Constructs the `UTypeId` of the `index`th `TypeLayout` reconstructed from a snapshot,
+    /// which is different from the `UTypeId` of every type.
+    pub(crate) fn for_snapshot(index: usize) -> Self {
+        let mut type_id_array = [0; MAX_TYPE_ID_SIZE];
+        let index = (index as u64).to_le_bytes();
+        type_id_array[..index.len()].copy_from_slice(&index);
+        Self {
+            executable_identity: &SNAPSHOT_IDENTITY,
+            type_id_array,
+        }
+    }
+
+    /// Whether this is the `UTypeId` of a `TypeLayout` reconstructed from a snapshot.
+    pub(crate) fn is_from_snapshot(&self) -> bool {
+        std::ptr::eq(self.executable_identity, &SNAPSHOT_IDENTITY)
+    }
 }
 
+/// The identity of the `TypeLayout`s reconstructed from snapshots,
+/// distinct from `EXECUTABLE_IDENTITY`.
+static SNAPSHOT_IDENTITY: AtomicUsize = AtomicUsize::new(1);
+
 /////////////////////////////////////////////////////////////////////////////
 
 type TypeIdArray = [u8; mem::size_of::<TypeId>()];
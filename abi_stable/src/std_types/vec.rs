@@ -1296,34 +1296,18 @@ impl<T> RVec<T> {
     where
         R: RangeBounds<usize>,
     {
-        unsafe {
-            let slice_start = match range.start_bound() {
-                Bound::Unbounded => 0,
-                Bound::Included(&n) => n,
-                Bound::Excluded(&n) => n.saturating_add(1),
-            };
-            let slice_end = match range.end_bound() {
-                Bound::Unbounded => self.length,
-                Bound::Included(&n) => n.saturating_add(1),
-                Bound::Excluded(&n) => n,
-            };
-            let slice_len = slice_end - slice_start;
-
-            let allocation_start = self.buffer.as_ptr();
-            let removed_start = allocation_start.add(slice_start);
-            let iter = RawValIter::new(removed_start, slice_len);
-            let old_length = self.length;
-            self.length = 0;
-
-            Drain {
-                removed_start,
-                slice_len,
-                iter,
-                vec_len: &mut self.length,
-                allocation_start,
-                len: old_length,
-            }
-        }
+        let slice_start = match range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.saturating_add(1),
+        };
+        let slice_end = match range.end_bound() {
+            Bound::Unbounded => self.length,
+            Bound::Included(&n) => n.saturating_add(1),
+            Bound::Excluded(&n) => n,
+        };
+
+        unsafe { Drain::new(self.buffer.as_ptr(), &mut self.length, slice_start, slice_end) }
     }
 }
 
@@ -1446,7 +1430,7 @@ impl io::Write for RVec<u8> {
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, StableAbi)]
-enum Exactness {
+pub(super) enum Exactness {
     Exact,
     Above,
 }
@@ -184,6 +184,34 @@ pub struct Drain<'a, T> {
 }
 
 impl<'a, T> Drain<'a, T> {
+    /// Constructs a `Drain` that removes the `slice_start..slice_end` range
+    /// of the `*vec_len` elements starting at `allocation_start`.
+    ///
+    /// # Safety
+    ///
+    /// `allocation_start` must point to `*vec_len` contiguous initialized elements,
+    /// which `Drain` takes ownership of until it's dropped.
+    pub(crate) unsafe fn new(
+        allocation_start: *mut T,
+        vec_len: &'a mut usize,
+        slice_start: usize,
+        slice_end: usize,
+    ) -> Self {
+        let slice_len = slice_end - slice_start;
+        let removed_start = unsafe { allocation_start.add(slice_start) };
+        let iter = unsafe { RawValIter::new(removed_start, slice_len) };
+        let len = mem::replace(vec_len, 0);
+
+        Drain {
+            removed_start,
+            slice_len,
+            iter,
+            vec_len,
+            allocation_start,
+            len,
+        }
+    }
+
     /// Returns a slice over the remainder of the `Vec<T>` that is being drained.
     ///
     /// # Example
@@ -0,0 +1,1403 @@
+//! Contains an ffi-safe equivalent of `std::collections::VecDeque<T>`.
+
+use std::{
+    cmp::Ordering,
+    collections::VecDeque,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FromIterator,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops::{Bound, Index, IndexMut, RangeBounds},
+    ptr::{self, NonNull},
+    slice,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    pointer_trait::CanTransmuteElement,
+    prefix_type::WithMetadata,
+    sabi_types::RMut,
+    std_types::{
+        utypeid::{new_utypeid, UTypeId},
+        vec::{self, Exactness},
+        RVec,
+    },
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Ffi-safe equivalent of `std::collections::VecDeque`,
+/// a double-ended queue implemented with a growable ring buffer.
+///
+/// # Example
+///
+/// Here is a queue of work items that is consumed from the front.
+///
+/// ```
+/// use abi_stable::{
+///     sabi_extern_fn,
+///     std_types::{RString, RVecDeque},
+/// };
+///
+/// #[sabi_extern_fn]
+/// pub fn next_job(queue: &mut RVecDeque<RString>) -> Option<RString> {
+///     queue.pop_front()
+/// }
+///
+/// let mut queue = RVecDeque::new();
+/// queue.push_back(RString::from("build"));
+/// queue.push_back(RString::from("test"));
+/// queue.push_front(RString::from("fetch"));
+///
+/// assert_eq!(next_job(&mut queue).unwrap(), "fetch");
+/// assert_eq!(next_job(&mut queue).unwrap(), "build");
+/// assert_eq!(queue.len(), 1);
+///
+/// ```
+///
+#[repr(C)]
+#[derive(StableAbi)]
+pub struct RVecDeque<T> {
+    buffer: NonNull<T>,
+    // The index in `buffer` of the first element.
+    head: usize,
+    length: usize,
+    capacity: usize,
+    vtable: VecDequeVTable_Ref,
+    _marker: PhantomData<T>,
+}
+
+impl<T> RVecDeque<T> {
+    /// Creates a new, empty `RVecDeque<T>`.
+    ///
+    /// This function does not allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let queue = RVecDeque::<u32>::new();
+    /// assert!(queue.is_empty());
+    ///
+    /// ```
+    pub const fn new() -> Self {
+        RVecDeque {
+            vtable: VTableGetter::<T>::LIB_VTABLE,
+            buffer: NonNull::dangling(),
+            head: 0,
+            length: 0,
+            capacity: 0_usize.wrapping_sub((mem::size_of::<T>() == 0) as usize),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new, empty `RVecDeque<T>`, with a capacity of `cap`.
+    ///
+    /// This function does not allocate if `cap == 0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let queue = RVecDeque::<u32>::with_capacity(7);
+    ///
+    /// assert_eq!(queue.len(), 0);
+    /// assert_eq!(queue.capacity(), 7);
+    ///
+    /// ```
+    pub fn with_capacity(cap: usize) -> Self {
+        Vec::with_capacity(cap).into()
+    }
+
+    #[allow(dead_code)]
+    // Used to test functions that change behavior when the vtable changes
+    fn set_vtable_for_testing(mut self) -> Self {
+        self.vtable = VTableGetter::<T>::LIB_VTABLE_FOR_TESTING;
+        self
+    }
+
+    /// Returns the amount of elements this `RVecDeque<T>` can store without reallocating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// assert_eq!(queue.capacity(), 0);
+    ///
+    /// queue.push_back(0);
+    /// assert_ne!(queue.capacity(), 0);
+    ///
+    /// ```
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the amount of elements in the `RVecDeque<T>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// assert_eq!(queue.len(), 0);
+    ///
+    /// queue.push_back(0);
+    /// queue.push_front(1);
+    /// assert_eq!(queue.len(), 2);
+    ///
+    /// ```
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns whether the `RVecDeque<T>` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// assert!(queue.is_empty());
+    ///
+    /// queue.push_back(0);
+    /// assert!(!queue.is_empty());
+    ///
+    /// ```
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    // Maps a logical index (`index < capacity`) to an index in the buffer.
+    #[inline]
+    fn physical_index(&self, index: usize) -> usize {
+        let to_end = self.capacity - self.head;
+        if index < to_end {
+            self.head + index
+        } else {
+            index - to_end
+        }
+    }
+
+    #[inline]
+    fn slot(&self, index: usize) -> *mut T {
+        unsafe { self.buffer.as_ptr().add(self.physical_index(index)) }
+    }
+
+    /// Returns a reference to the element at `index`,
+    /// where the front of the queue is at index 0.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// queue.push_back(3);
+    /// queue.push_front(5);
+    ///
+    /// assert_eq!(queue.get(0), Some(&5));
+    /// assert_eq!(queue.get(1), Some(&3));
+    /// assert_eq!(queue.get(2), None);
+    ///
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.length {
+            unsafe { Some(&*self.slot(index)) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the element at `index`,
+    /// where the front of the queue is at index 0.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// queue.push_back(3);
+    ///
+    /// *queue.get_mut(0).unwrap() += 10;
+    /// assert_eq!(queue.get(0), Some(&13));
+    /// assert_eq!(queue.get_mut(1), None);
+    ///
+    /// ```
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.length {
+            unsafe { Some(&mut *self.slot(index)) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the front element, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// assert_eq!(queue.front(), None);
+    ///
+    /// queue.push_back(3);
+    /// queue.push_back(5);
+    /// assert_eq!(queue.front(), Some(&3));
+    ///
+    /// ```
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a mutable reference to the front element, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// assert_eq!(queue.front_mut(), None);
+    ///
+    /// queue.push_back(3);
+    /// queue.push_back(5);
+    /// assert_eq!(queue.front_mut(), Some(&mut 3));
+    ///
+    /// ```
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Returns a reference to the back element, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// assert_eq!(queue.back(), None);
+    ///
+    /// queue.push_back(3);
+    /// queue.push_back(5);
+    /// assert_eq!(queue.back(), Some(&5));
+    ///
+    /// ```
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.length.wrapping_sub(1))
+    }
+
+    /// Returns a mutable reference to the back element, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// assert_eq!(queue.back_mut(), None);
+    ///
+    /// queue.push_back(3);
+    /// queue.push_back(5);
+    /// assert_eq!(queue.back_mut(), Some(&mut 5));
+    ///
+    /// ```
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.length.wrapping_sub(1))
+    }
+
+    /// Appends an element to the back of the queue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    ///
+    /// queue.push_back(3);
+    /// queue.push_back(5);
+    ///
+    /// assert_eq!(queue, [3, 5]);
+    ///
+    /// ```
+    pub fn push_back(&mut self, value: T) {
+        if self.length == self.capacity {
+            self.resize_capacity(self.length + 1, Exactness::Above);
+        }
+        unsafe {
+            ptr::write(self.slot(self.length), value);
+        }
+        self.length += 1;
+    }
+
+    /// Prepends an element to the front of the queue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    ///
+    /// queue.push_front(3);
+    /// queue.push_front(5);
+    ///
+    /// assert_eq!(queue, [5, 3]);
+    ///
+    /// ```
+    pub fn push_front(&mut self, value: T) {
+        if self.length == self.capacity {
+            self.resize_capacity(self.length + 1, Exactness::Above);
+        }
+        self.head = match self.head {
+            0 => self.capacity - 1,
+            head => head - 1,
+        };
+        unsafe {
+            ptr::write(self.slot(0), value);
+        }
+        self.length += 1;
+    }
+
+    /// Removes the back element of the queue and returns it,
+    /// returning `None` if the queue is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = vec![3, 5].into_iter().collect::<RVecDeque<u32>>();
+    ///
+    /// assert_eq!(queue.pop_back(), Some(5));
+    /// assert_eq!(queue.pop_back(), Some(3));
+    /// assert_eq!(queue.pop_back(), None);
+    ///
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+        self.length -= 1;
+        unsafe { Some(ptr::read(self.slot(self.length))) }
+    }
+
+    /// Removes the front element of the queue and returns it,
+    /// returning `None` if the queue is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = vec![3, 5].into_iter().collect::<RVecDeque<u32>>();
+    ///
+    /// assert_eq!(queue.pop_front(), Some(3));
+    /// assert_eq!(queue.pop_front(), Some(5));
+    /// assert_eq!(queue.pop_front(), None);
+    ///
+    /// ```
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+        let value = unsafe { ptr::read(self.slot(0)) };
+        self.head = self.physical_index(1);
+        self.length -= 1;
+        Some(value)
+    }
+
+    /// Swaps the elements at indices `a` and `b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = vec![3, 5, 8].into_iter().collect::<RVecDeque<u32>>();
+    ///
+    /// queue.swap(0, 2);
+    /// assert_eq!(queue, [8, 5, 3]);
+    ///
+    /// ```
+    pub fn swap(&mut self, a: usize, b: usize) {
+        assert!(a < self.length, "index out of bounds: {} >= {}", a, self.length);
+        assert!(b < self.length, "index out of bounds: {} >= {}", b, self.length);
+        unsafe { ptr::swap(self.slot(a), self.slot(b)) }
+    }
+
+    /// Returns the contents of the queue as a pair of slices,
+    /// the first slice contains the front of the queue.
+    ///
+    /// The second slice is only non-empty when the elements wrap around
+    /// the end of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::with_capacity(4);
+    /// queue.push_back(3);
+    /// queue.push_back(5);
+    /// queue.push_front(1);
+    ///
+    /// let (front, back) = queue.as_slices();
+    /// assert_eq!([front, back].concat(), [1, 3, 5]);
+    ///
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.raw_slices();
+        unsafe {
+            (
+                slice::from_raw_parts(front.0, front.1),
+                slice::from_raw_parts(back.0, back.1),
+            )
+        }
+    }
+
+    /// Returns the contents of the queue as a pair of mutable slices,
+    /// the first slice contains the front of the queue.
+    ///
+    /// The second slice is only non-empty when the elements wrap around
+    /// the end of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::with_capacity(4);
+    /// queue.push_back(3);
+    /// queue.push_front(1);
+    ///
+    /// let (front, back) = queue.as_mut_slices();
+    /// front.iter_mut().chain(back).for_each(|x| *x *= 10);
+    ///
+    /// assert_eq!(queue, [10, 30]);
+    ///
+    /// ```
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.raw_slices();
+        unsafe {
+            (
+                slice::from_raw_parts_mut(front.0, front.1),
+                slice::from_raw_parts_mut(back.0, back.1),
+            )
+        }
+    }
+
+    // The start and length of the two parts of the ring buffer.
+    fn raw_slices(&self) -> ((*mut T, usize), (*mut T, usize)) {
+        let buffer = self.buffer.as_ptr();
+        let to_end = self.capacity - self.head;
+        unsafe {
+            if self.length <= to_end {
+                ((buffer.add(self.head), self.length), (buffer, 0))
+            } else {
+                (
+                    (buffer.add(self.head), to_end),
+                    (buffer, self.length - to_end),
+                )
+            }
+        }
+    }
+
+    /// Rearranges the buffer so that the elements are stored contiguously,
+    /// starting at the beginning of the buffer,
+    /// returning a mutable slice of the elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::with_capacity(4);
+    /// queue.push_back(3);
+    /// queue.push_back(5);
+    /// queue.push_front(1);
+    ///
+    /// queue.make_contiguous().sort_unstable_by(|l, r| r.cmp(l));
+    ///
+    /// assert_eq!(queue.as_slices(), (&[5, 3, 1][..], &[][..]));
+    ///
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.head != 0 {
+            let buffer = self.buffer.as_ptr();
+            unsafe {
+                if mem::size_of::<T>() == 0 {
+                    // All the slots of a zero-sized type are the same
+                } else if self.length <= self.capacity - self.head {
+                    ptr::copy(buffer.add(self.head), buffer, self.length);
+                } else {
+                    // Rotating the whole buffer preserves the order of the
+                    // wrapped-around elements, moving the uninitialized gap to the end.
+                    slice::from_raw_parts_mut(buffer as *mut MaybeUninit<T>, self.capacity)
+                        .rotate_left(self.head);
+                }
+            }
+            self.head = 0;
+        }
+        unsafe { slice::from_raw_parts_mut(self.buffer.as_ptr(), self.length) }
+    }
+
+    /// Iterates over references to the elements, from front to back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// queue.push_back(3);
+    /// queue.push_front(1);
+    ///
+    /// assert_eq!(queue.iter().collect::<Vec<_>>(), [&1, &3]);
+    /// assert_eq!(queue.iter().rev().collect::<Vec<_>>(), [&3, &1]);
+    ///
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        Iter {
+            front: front.iter(),
+            back: back.iter(),
+        }
+    }
+
+    /// Iterates over mutable references to the elements, from front to back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::new();
+    /// queue.push_back(3);
+    /// queue.push_front(1);
+    ///
+    /// queue.iter_mut().for_each(|x| *x += 1);
+    ///
+    /// assert_eq!(queue, [2, 4]);
+    ///
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+        IterMut {
+            front: front.iter_mut(),
+            back: back.iter_mut(),
+        }
+    }
+
+    /// Creates a draining iterator that removes the specified range in
+    /// the `RVecDeque<T>` and yields the removed items.
+    ///
+    /// This makes the queue contiguous before removing the elements.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the index is out of bounds or if the start of the range is
+    /// greater than the end of the range.
+    ///
+    /// # Consumption
+    ///
+    /// The elements in the range will be removed even if the iterator
+    /// was dropped before yielding them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = (0..6).collect::<RVecDeque<u32>>();
+    ///
+    /// assert_eq!(queue.drain(2..4).collect::<Vec<_>>(), [2, 3]);
+    /// assert_eq!(queue, [0, 1, 4, 5]);
+    ///
+    /// assert_eq!(queue.drain(..).rev().collect::<Vec<_>>(), [5, 4, 1, 0]);
+    /// assert!(queue.is_empty());
+    ///
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.saturating_add(1),
+        };
+        let end = match range.end_bound() {
+            Bound::Unbounded => self.length,
+            Bound::Included(&n) => n.saturating_add(1),
+            Bound::Excluded(&n) => n,
+        };
+        assert!(
+            start <= end,
+            "drain start (is {}) should be <= end (is {})",
+            start,
+            end,
+        );
+        assert!(
+            end <= self.length,
+            "drain end (is {}) should be <= len (is {})",
+            end,
+            self.length,
+        );
+
+        self.make_contiguous();
+
+        Drain {
+            inner: unsafe { vec::Drain::new(self.buffer.as_ptr(), &mut self.length, start, end) },
+        }
+    }
+
+    /// Moves all the elements of `other` to the back of `self`, leaving `other` empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = (0..3).collect::<RVecDeque<u32>>();
+    /// let mut other = (3..5).collect::<RVecDeque<u32>>();
+    ///
+    /// queue.append(&mut other);
+    ///
+    /// assert_eq!(queue, [0, 1, 2, 3, 4]);
+    /// assert!(other.is_empty());
+    ///
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        self.extend(other.drain(..));
+    }
+
+    /// Shortens the queue, keeping the first `len` elements and dropping the rest.
+    ///
+    /// This does nothing if `len` is greater than or equal to the length of the queue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = (0..5).collect::<RVecDeque<u32>>();
+    ///
+    /// queue.truncate(2);
+    /// assert_eq!(queue, [0, 1]);
+    ///
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.length {
+            return;
+        }
+        let (front, back) = self.raw_slices();
+        self.length = len;
+        unsafe {
+            if len < front.1 {
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                    front.0.add(len),
+                    front.1 - len,
+                ));
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(back.0, back.1));
+            } else {
+                let back_len = len - front.1;
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                    back.0.add(back_len),
+                    back.1 - back_len,
+                ));
+            }
+        }
+    }
+
+    /// Removes all the elements from the queue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = (0..5).collect::<RVecDeque<u32>>();
+    ///
+    /// queue.clear();
+    /// assert!(queue.is_empty());
+    ///
+    /// ```
+    pub fn clear(&mut self) {
+        self.truncate(0);
+        self.head = 0;
+    }
+
+    /// Returns whether the queue contains an element equal to `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let queue = (0..5).collect::<RVecDeque<u32>>();
+    ///
+    /// assert!(queue.contains(&3));
+    /// assert!(!queue.contains(&5));
+    ///
+    /// ```
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let (front, back) = self.as_slices();
+        front.contains(value) || back.contains(value)
+    }
+
+    /// Reserves `àdditional` additional capacity for extra elements.
+    /// This may reserve more than necessary for the additional capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::<u32>::new();
+    ///
+    /// queue.reserve(10);
+    /// assert!(queue.capacity() >= 10);
+    ///
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        self.resize_capacity(self.length + additional, Exactness::Above)
+    }
+
+    /// Reserves `àdditional` additional capacity for extra elements.
+    ///
+    /// Prefer using `reserve` for most situations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::<u32>::new();
+    ///
+    /// queue.reserve_exact(17);
+    /// assert_eq!(queue.capacity(), 17);
+    ///
+    /// ```
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.resize_capacity(self.length + additional, Exactness::Exact)
+    }
+
+    /// Shrinks the capacity of the queue to match its length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::<u32>::with_capacity(7);
+    ///
+    /// queue.push_back(3);
+    /// queue.shrink_to_fit();
+    /// assert_eq!(queue.capacity(), 1);
+    ///
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        let vtable = self.vtable;
+        unsafe {
+            vtable.shrink_to_fit()(RMut::new(self).transmute_element_());
+        }
+    }
+
+    fn resize_capacity(&mut self, to: usize, exactness: Exactness) {
+        let vtable = self.vtable;
+        if self.capacity < to {
+            unsafe {
+                vtable.grow_capacity_to()(RMut::new(self).transmute_element_(), to, exactness);
+            }
+        }
+    }
+
+    /// Constructs a vec to do operations on the underlying buffer.
+    ///
+    /// # Safety
+    ///
+    /// This must not be called outside of functions that get stored in the vtable.
+    unsafe fn with_vec<U, F>(&mut self, f: F) -> U
+    where
+        F: FnOnce(&mut Vec<T>) -> U,
+    {
+        self.make_contiguous();
+        let old = ManuallyDrop::new(mem::take(self));
+        let mut list =
+            unsafe { Vec::<T>::from_raw_parts(old.buffer.as_ptr(), old.length, old.capacity) };
+        let ret = f(&mut list);
+        unsafe {
+            ptr::write(self, list.into());
+        }
+        ret
+    }
+
+    /// Converts this `RVecDeque<T>` into a `VecDeque<T>`.
+    ///
+    /// # Allocation
+    ///
+    /// If this is invoked outside of the dynamic library/binary that created it,
+    /// it will allocate a new `VecDeque<T>` and move the data into it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = RVecDeque::<u64>::new();
+    ///
+    /// queue.push_back(1);
+    /// queue.push_back(2);
+    /// queue.push_front(0);
+    ///
+    /// assert_eq!(queue.into_vecdeque(), vec![0, 1, 2]);
+    ///
+    /// ```
+    pub fn into_vecdeque(self) -> VecDeque<T> {
+        let mut this = ManuallyDrop::new(self);
+
+        unsafe {
+            let this_vtable = this.vtable;
+            let other_vtable = VTableGetter::<T>::LIB_VTABLE;
+            if ::std::ptr::eq(this_vtable.0.to_raw_ptr(), other_vtable.0.to_raw_ptr())
+                || this_vtable.type_id()() == other_vtable.type_id()()
+            {
+                this.make_contiguous();
+                Vec::from_raw_parts(this.buffer.as_ptr(), this.length, this.capacity).into()
+            } else {
+                let mut ret = VecDeque::with_capacity(this.length);
+                let (front, back) = this.as_slices();
+                ret.extend(front.iter().chain(back).map(|x| ptr::read(x)));
+                this.length = 0;
+                ManuallyDrop::drop(&mut this);
+                ret
+            }
+        }
+    }
+}
+
+impl_from_rust_repr! {
+    impl[T] From<VecDeque<T>> for RVecDeque<T> {
+        fn(this){
+            Vec::from(this).into()
+        }
+    }
+}
+
+impl_into_rust_repr! {
+    impl[T] Into<VecDeque<T>> for RVecDeque<T> {
+        fn(this){
+            this.into_vecdeque()
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for RVecDeque<T> {
+    fn from(this: Vec<T>) -> Self {
+        let mut this = ManuallyDrop::new(this);
+        RVecDeque {
+            vtable: VTableGetter::<T>::LIB_VTABLE,
+            buffer: unsafe { NonNull::new_unchecked(this.as_mut_ptr()) },
+            head: 0,
+            length: this.len(),
+            capacity: this.capacity(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> From<RVec<T>> for RVecDeque<T> {
+    fn from(this: RVec<T>) -> Self {
+        this.into_vec().into()
+    }
+}
+
+impl<T> Clone for RVecDeque<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Default for RVecDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for RVecDeque<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Eq for RVecDeque<T> where T: Eq {}
+
+impl<T, U> PartialEq<RVecDeque<U>> for RVecDeque<T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &RVecDeque<U>) -> bool {
+        self.length == other.length && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, U> PartialEq<VecDeque<U>> for RVecDeque<T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &VecDeque<U>) -> bool {
+        self.length == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, U> PartialEq<[U]> for RVecDeque<T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U]) -> bool {
+        self.length == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, U, const N: usize> PartialEq<[U; N]> for RVecDeque<T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T, U> PartialEq<Vec<U>> for RVecDeque<T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &Vec<U>) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T, U> PartialEq<RVecDeque<U>> for VecDeque<T>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &RVecDeque<U>) -> bool {
+        self.len() == other.length && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> PartialOrd for RVecDeque<T>
+where
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T> Ord for RVecDeque<T>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T> Hash for RVecDeque<T>
+where
+    T: Hash,
+{
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        state.write_usize(self.length);
+        self.iter().for_each(|elem| elem.hash(state));
+    }
+}
+
+impl<T> Index<usize> for RVecDeque<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("Out of bounds access")
+    }
+}
+
+impl<T> IndexMut<usize> for RVecDeque<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("Out of bounds access")
+    }
+}
+
+impl<T> FromIterator<T> for RVecDeque<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().collect::<Vec<T>>().into()
+    }
+}
+
+impl<T> Extend<T> for RVecDeque<T> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+impl<'a, T> Extend<&'a T> for RVecDeque<T>
+where
+    T: 'a + Copy,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+unsafe impl<T> Send for RVecDeque<T> where T: Send {}
+unsafe impl<T> Sync for RVecDeque<T> where T: Sync {}
+
+impl<T> Drop for RVecDeque<T> {
+    fn drop(&mut self) {
+        let vtable = self.vtable;
+        unsafe { vtable.destructor()(RMut::new(self).transmute_element_()) }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for RVecDeque<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <Vec<T>>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl<T> Serialize for RVecDeque<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// An iterator over references to the elements of an `RVecDeque<T>`,
+/// returned by [`RVecDeque::iter`].
+pub struct Iter<'a, T> {
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            front: self.front.clone(),
+            back: self.back.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+}
+
+/// An iterator over mutable references to the elements of an `RVecDeque<T>`,
+/// returned by [`RVecDeque::iter_mut`].
+pub struct IterMut<'a, T> {
+    front: slice::IterMut<'a, T>,
+    back: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+}
+
+/// An Iterator returned by `<RVecDeque<T> as IntoIterator>::into_iter`,
+/// which yields all the elements from the `RVecDeque<T>`,
+/// consuming it in the process.
+pub struct IntoIter<T> {
+    inner: RVecDeque<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// An Iterator returned by `RVecDeque::drain` ,
+/// which removes and yields all the elements in a range from the `RVecDeque<T>`.
+pub struct Drain<'a, T> {
+    inner: vec::Drain<'a, T>,
+}
+
+impl<T> Drain<'_, T> {
+    /// Returns a slice over the remainder of the `RVecDeque<T>` that is being drained.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use abi_stable::std_types::RVecDeque;
+    ///
+    /// let mut queue = (0..8).collect::<RVecDeque<u8>>();
+    /// let mut iter = queue.drain(3..7);
+    ///
+    /// assert_eq!(iter.as_slice(), &[3, 4, 5, 6]);
+    ///
+    /// assert_eq!(iter.next(), Some(3));
+    /// assert_eq!(iter.as_slice(), &[4, 5, 6]);
+    ///
+    /// drop(iter);
+    ///
+    /// assert_eq!(queue, [0, 1, 2, 7]);
+    ///
+    /// ```
+    pub fn as_slice(&self) -> &[T] {
+        self.inner.as_slice()
+    }
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> IntoIterator for RVecDeque<T> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { inner: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RVecDeque<T> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RVecDeque<T> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Dummy type used to create a statically allocated constant that can only be
+/// accessed of the lifetime of T.
+struct VTableGetter<'a, T>(PhantomData<&'a T>);
+
+impl<'a, T: 'a> VTableGetter<'a, T> {
+    const DEFAULT_VTABLE: VecDequeVTable = VecDequeVTable {
+        type_id: new_utypeid::<RVecDeque<()>>,
+        destructor: destructor_deque::<T>,
+        grow_capacity_to: grow_capacity_to_deque::<T>,
+        shrink_to_fit: shrink_to_fit_deque::<T>,
+    };
+
+    staticref! {
+        const WM_DEFAULT: WithMetadata<VecDequeVTable> = WithMetadata::new(Self::DEFAULT_VTABLE);
+    }
+
+    // The VTABLE for this type in this executable/library
+    const LIB_VTABLE: VecDequeVTable_Ref = VecDequeVTable_Ref(Self::WM_DEFAULT.as_prefix());
+
+    staticref! {
+        const WM_FOR_TESTING: WithMetadata<VecDequeVTable> =
+            WithMetadata::new(
+                VecDequeVTable {
+                    type_id: new_utypeid::<RVecDeque<i32>>,
+                    ..Self::DEFAULT_VTABLE
+                }
+            )
+    }
+
+    // Used to test functions that change behavior based on the vtable being used
+    const LIB_VTABLE_FOR_TESTING: VecDequeVTable_Ref =
+        VecDequeVTable_Ref(Self::WM_FOR_TESTING.as_prefix());
+}
+
+#[repr(C)]
+#[derive(StableAbi)]
+#[sabi(kind(Prefix))]
+#[sabi(missing_field(panic))]
+struct VecDequeVTable {
+    type_id: extern "C" fn() -> UTypeId,
+    destructor: unsafe extern "C" fn(RMut<'_, ()>),
+    grow_capacity_to: unsafe extern "C" fn(RMut<'_, ()>, usize, Exactness),
+    #[sabi(last_prefix_field)]
+    shrink_to_fit: unsafe extern "C" fn(RMut<'_, ()>),
+}
+
+unsafe extern "C" fn destructor_deque<T>(this: RMut<'_, ()>) {
+    extern_fn_panic_handling! {no_early_return; unsafe {
+        let this = this.transmute_into_mut::<RVecDeque<T>>();
+        let (front, back) = this.as_mut_slices();
+        ptr::drop_in_place(front);
+        ptr::drop_in_place(back);
+        drop(Vec::from_raw_parts(this.buffer.as_ptr(), 0, this.capacity));
+    }}
+}
+
+unsafe extern "C" fn grow_capacity_to_deque<T>(
+    this: RMut<'_, ()>,
+    to: usize,
+    exactness: Exactness,
+) {
+    extern_fn_panic_handling! {no_early_return; unsafe {
+        let this = this.transmute_into_mut::<RVecDeque<T>>();
+        this.with_vec(|list| {
+            let additional = to.saturating_sub(list.len());
+            match exactness {
+                Exactness::Above => list.reserve(additional),
+                Exactness::Exact => list.reserve_exact(additional),
+            }
+        })
+    }}
+}
+
+unsafe extern "C" fn shrink_to_fit_deque<T>(this: RMut<'_, ()>) {
+    extern_fn_panic_handling! {no_early_return; unsafe {
+        let this = this.transmute_into_mut::<RVecDeque<T>>();
+        this.with_vec(|list| {
+            list.shrink_to_fit();
+        })
+    }}
+}
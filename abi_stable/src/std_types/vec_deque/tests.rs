@@ -0,0 +1,213 @@
+use super::*;
+
+use std::sync::Arc;
+
+use crate::{
+    test_utils::{must_panic, ShouldHavePanickedAt},
+    traits::IntoReprC,
+};
+
+// Builds a queue whose elements wrap around the end of the buffer.
+fn wrapped_queue() -> RVecDeque<u32> {
+    let mut queue = RVecDeque::with_capacity(8);
+    queue.extend(10..16);
+    for _ in 0..4 {
+        queue.pop_front();
+    }
+    queue.extend(16..20);
+    queue.push_front(13);
+    assert_eq!(queue.capacity(), 8);
+    assert!(!queue.as_slices().1.is_empty());
+    queue
+}
+
+#[test]
+fn push_pop() {
+    let mut queue = RVecDeque::new();
+    let mut std_queue = VecDeque::new();
+
+    for i in 0..100u32 {
+        if i % 3 == 0 {
+            queue.push_front(i);
+            std_queue.push_front(i);
+        } else {
+            queue.push_back(i);
+            std_queue.push_back(i);
+        }
+        if i % 7 == 0 {
+            assert_eq!(queue.pop_front(), std_queue.pop_front());
+        }
+        if i % 11 == 0 {
+            assert_eq!(queue.pop_back(), std_queue.pop_back());
+        }
+        assert_eq!(queue, std_queue);
+        assert_eq!(queue.front(), std_queue.front());
+        assert_eq!(queue.back(), std_queue.back());
+    }
+
+    while let Some(x) = std_queue.pop_back() {
+        assert_eq!(queue.pop_back(), Some(x));
+    }
+    assert_eq!(queue.pop_back(), None);
+    assert_eq!(queue.pop_front(), None);
+}
+
+#[test]
+fn wrapping_slices() {
+    let mut queue = wrapped_queue();
+
+    assert_eq!(queue, [13, 14, 15, 16, 17, 18, 19]);
+    assert_eq!(queue[0], 13);
+    assert_eq!(queue[6], 19);
+    assert_eq!(queue.iter().rev().copied().collect::<Vec<_>>(), [19, 18, 17, 16, 15, 14, 13]);
+    assert_eq!(queue.iter().len(), 7);
+
+    let (front, back) = queue.as_slices();
+    assert_eq!([front, back].concat(), [13, 14, 15, 16, 17, 18, 19]);
+
+    // Growing a wrapped queue preserves the order of the elements.
+    queue.extend(20..30);
+    assert_eq!(queue, (13..30).collect::<Vec<_>>());
+}
+
+#[test]
+fn make_contiguous() {
+    let mut queue = wrapped_queue();
+
+    assert_eq!(queue.make_contiguous(), &mut [13, 14, 15, 16, 17, 18, 19]);
+    assert_eq!(queue.as_slices().1, &[] as &[u32]);
+    assert_eq!(queue, [13, 14, 15, 16, 17, 18, 19]);
+
+    let mut queue = RVecDeque::with_capacity(4);
+    queue.extend(0..3);
+    queue.pop_front();
+    assert_eq!(queue.make_contiguous(), &mut [1, 2]);
+    assert_eq!(queue.head, 0);
+    assert_eq!(queue.buffer.as_ptr() as *const u32, queue.as_slices().0.as_ptr());
+}
+
+#[test]
+fn drain() -> Result<(), ShouldHavePanickedAt> {
+    {
+        let mut queue = wrapped_queue();
+        assert_eq!(queue.drain(2..5).collect::<Vec<_>>(), [15, 16, 17]);
+        assert_eq!(queue, [13, 14, 18, 19]);
+    }
+    {
+        let mut queue = wrapped_queue();
+        let mut iter = queue.drain(1..);
+        assert_eq!(iter.next(), Some(14));
+        assert_eq!(iter.next_back(), Some(19));
+        drop(iter);
+        assert_eq!(queue, [13]);
+    }
+    {
+        let mut queue = wrapped_queue();
+        #[allow(clippy::reversed_empty_ranges)]
+        {
+            must_panic(|| queue.drain(3..2))?;
+        }
+        must_panic(|| queue.drain(..8))?;
+    }
+    Ok(())
+}
+
+#[test]
+fn truncate_drops() {
+    let counter = Arc::new(());
+    let mut queue = RVecDeque::with_capacity(4);
+    for _ in 0..3 {
+        queue.push_back(counter.clone());
+    }
+    queue.pop_front();
+    queue.push_back(counter.clone());
+    queue.push_back(counter.clone());
+    assert_eq!(Arc::strong_count(&counter), 5);
+
+    queue.truncate(1);
+    assert_eq!(Arc::strong_count(&counter), 2);
+
+    queue.push_front(counter.clone());
+    queue.clear();
+    assert_eq!(Arc::strong_count(&counter), 1);
+
+    queue.extend(vec![counter.clone(), counter.clone()]);
+    drop(queue);
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
+#[test]
+fn into_iter() {
+    let queue = wrapped_queue();
+    let mut iter = queue.clone().into_iter();
+    assert_eq!(iter.next(), Some(13));
+    assert_eq!(iter.next_back(), Some(19));
+    assert_eq!(iter.len(), 5);
+
+    let mut queue = queue;
+    for x in &mut queue {
+        *x *= 2;
+    }
+    assert_eq!((&queue).into_iter().sum::<u32>(), 2 * (13..20).sum::<u32>());
+}
+
+#[test]
+fn shrink_to_fit() {
+    let mut queue = wrapped_queue();
+    queue.shrink_to_fit();
+    assert_eq!(queue.capacity(), 7);
+    assert_eq!(queue, [13, 14, 15, 16, 17, 18, 19]);
+}
+
+#[test]
+fn into_vecdeque() {
+    let orig = (13..20).collect::<VecDeque<u32>>();
+    {
+        let queue = wrapped_queue();
+        let buffer_ptr = queue.buffer.as_ptr() as usize;
+        let std_queue = queue.into_vecdeque();
+        assert_eq!(buffer_ptr, std_queue.as_slices().0.as_ptr() as usize);
+        assert_eq!(std_queue, orig);
+    }
+    {
+        let queue = wrapped_queue().set_vtable_for_testing();
+        let buffer_ptr = queue.buffer.as_ptr() as usize;
+        let std_queue = queue.into_vecdeque();
+        assert_ne!(buffer_ptr, std_queue.as_slices().0.as_ptr() as usize);
+        assert_eq!(std_queue, orig);
+    }
+}
+
+#[test]
+fn from_vecdeque() {
+    let std_queue = (0..10).collect::<VecDeque<u32>>();
+    let queue = std_queue.clone().into_c();
+    assert_eq!(queue, std_queue);
+    assert_eq!(VecDeque::from(queue), std_queue);
+
+    let queue = RVecDeque::from(crate::rvec![3, 5, 8]);
+    assert_eq!(queue, [3, 5, 8]);
+}
+
+#[test]
+fn zero_sized() {
+    let mut queue = RVecDeque::new();
+    for _ in 0..10 {
+        queue.push_front(());
+        queue.push_back(());
+    }
+    assert_eq!(queue.len(), 20);
+    assert_eq!(queue.drain(5..).count(), 15);
+    assert_eq!(queue.iter().count(), 5);
+    assert_eq!(queue.into_vecdeque().len(), 5);
+}
+
+#[test]
+fn test_serde() {
+    let queue = wrapped_queue();
+    let json = serde_json::to_string(&queue).unwrap();
+    assert_eq!(json, "[13,14,15,16,17,18,19]");
+
+    let deserialized = serde_json::from_str::<RVecDeque<u32>>(&json).unwrap();
+    assert_eq!(deserialized, queue);
+}
@@ -5,6 +5,11 @@ use std::{
     fmt::{self, Debug, Display},
 };
 
+use crate::{
+    sabi_types::MaybeCmp, std_types::UTypeId, type_layout::TypeLayout,
+    type_level::downcasting::GetUTID, StableAbi,
+};
+
 #[allow(unused_imports)]
 pub use abi_stable_shared::test_utils::{must_panic, ShouldHavePanickedAt, ThreadError};
 
@@ -57,6 +62,37 @@ where
 
 //////////////////////////////////////////////////////////////////
 
+/// Downcasting mode that simulates constructing a trait object in another dynamic library
+/// with `TD_StableDowncast`, since its `UTypeId`s never equal the ones in this library.
+#[allow(non_camel_case_types)]
+pub struct TD_ForeignStable;
+
+/// Downcasting mode that simulates constructing a trait object in another dynamic library
+/// with `TD_CanDowncast`, since its `UTypeId`s never equal the ones in this library.
+#[allow(non_camel_case_types)]
+pub struct TD_ForeignCanDowncast;
+
+struct ForeignLibrary;
+
+extern "C" fn foreign_utypeid() -> MaybeCmp<UTypeId> {
+    MaybeCmp::Just(UTypeId::new::<ForeignLibrary>())
+}
+
+impl<T> GetUTID<T> for TD_ForeignStable
+where
+    T: StableAbi,
+{
+    const UID: extern "C" fn() -> MaybeCmp<UTypeId> = foreign_utypeid;
+
+    const STABLE_LAYOUT: Option<&'static TypeLayout> = Some(T::LAYOUT);
+}
+
+impl<T> GetUTID<T> for TD_ForeignCanDowncast {
+    const UID: extern "C" fn() -> MaybeCmp<UTypeId> = foreign_utypeid;
+}
+
+//////////////////////////////////////////////////////////////////
+
 macro_rules! if_impls_impls {
     ($($const:ident, $trait:path);* $(;)*) => {
         pub trait GetImplsHelper {
@@ -100,6 +136,10 @@ if_impls_impls! {
     IMPLS_IO_READ, std::io::Read;
     IMPLS_IO_BUF_READ, std::io::BufRead;
     IMPLS_ERROR, std::error::Error;
+    IMPLS_FUTURE, std::future::Future;
+    IMPLS_AS_REF_STR, AsRef<str>;
+    IMPLS_AS_REF_BYTES, AsRef<[u8]>;
+    IMPLS_BORROW_STR, std::borrow::Borrow<str>;
 }
 
 //////////////////////////////////////////////////////////////////
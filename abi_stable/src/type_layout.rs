@@ -28,6 +28,8 @@ mod iterators;
 mod printing;
 mod shared_vars;
 mod small_types;
+pub mod snapshot;
+pub mod stable_type_id;
 pub mod tagging;
 mod tl_data;
 mod tl_enums;
@@ -46,6 +48,8 @@ pub use self::{
     construction::{ItemInfo, _private_MonoTypeLayoutDerive, _private_TypeLayoutDerive},
     shared_vars::{MonoSharedVars, SharedVars},
     small_types::{OptionU16, OptionU8, StartLen, StartLenConverter, StartLenRepr},
+    snapshot::{LayoutSnapshot, SnapshotError},
+    stable_type_id::StableTypeId,
     tagging::Tag,
     tl_data::{GenericTLData, MismatchedTLDataVariant, MonoTLData, TLData, TLDataDiscriminant},
     tl_enums::{
@@ -87,6 +87,11 @@ impl ItemInfo {
         }
     }
 
+    /// Gets the package name and version,with the `package;version_number` format.
+    pub(super) const fn raw_package_and_version(&self) -> RStr<'static> {
+        self.package_and_version
+    }
+
     /// Gets the package name and an unparsed package version.
     pub fn package_and_version(&self) -> (&'static str, &'static str) {
         let pav = self.package_and_version.as_str();
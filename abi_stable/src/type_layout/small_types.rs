@@ -69,6 +69,12 @@ impl StartLen {
         Self { bits: n }
     }
 
+    /// Converts this `StartLen` into its internal representation.
+    #[inline]
+    pub const fn to_u32(self) -> StartLenRepr {
+        self.bits
+    }
+
     /// An empty range.
     pub const EMPTY: Self = Self::new(0, 0);
 
@@ -0,0 +1,1201 @@
+//! Serializable snapshots of `TypeLayout`s,
+//! which allow checking that a type is compatible with a previous version of itself,
+//! without the dynamic library (or binary) that the previous version was compiled into.
+//!
+//! # Example
+//!
+//! Checking that a type is compatible with a snapshot of the layout of a previous version.
+//!
+//! In an actual project, the json would be written to a file next to the crate,
+//! and checked in a unit test.
+//!
+#![cfg_attr(feature = "serde_json", doc = "```rust")]
+#![cfg_attr(not(feature = "serde_json"), doc = "```ignore")]
+//! use abi_stable::{
+//!     std_types::{RString, RVec},
+//!     type_layout::snapshot::LayoutSnapshot,
+//!     StableAbi,
+//! };
+//!
+//! let json = LayoutSnapshot::of::<RVec<u8>>().to_json();
+//!
+//! let snapshot = LayoutSnapshot::from_json(&json).unwrap();
+//!
+//! assert!(snapshot
+//!     .check_compatibility(<RVec<u8> as StableAbi>::LAYOUT)
+//!     .is_ok());
+//!
+//! assert!(snapshot
+//!     .check_compatibility(<RString as StableAbi>::LAYOUT)
+//!     .is_err());
+//!
+//! ```
+
+use super::*;
+
+use crate::{
+    abi_stability::{
+        abi_checking::{check_layout_compatibility_with_globals, CheckingGlobals},
+        AbiInstabilityReport, ConstGeneric, ConstGenericSnapshot,
+    },
+    prefix_type::FieldAccessibility,
+    std_types::UTypeId,
+    StableAbi,
+};
+
+use super::{
+    tagging::{KeyValue, Primitive, TagVariant},
+    tl_enums::DiscriminantsSnapshot,
+    tl_functions::CompTLFunctionSnapshot,
+    tl_other::CompGenericParamsSnapshot,
+};
+
+use std::{
+    any::Any,
+    collections::HashMap,
+    error::Error as ErrorTrait,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use parking_lot::Mutex;
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// The version of the snapshot format,
+/// which is incremented whenever it changes incompatibly.
+const FORMAT_VERSION: u32 = 1;
+
+/// A serializable snapshot of a `TypeLayout`,
+/// including every `TypeLayout` that it (directly or indirectly) references.
+///
+/// This stores the layouts in the same (compressed) representation as `TypeLayout`,
+/// so that [`to_layout`](#method.to_layout) can reconstruct them exactly,
+/// with these exceptions:
+///
+/// - The extra checks of a type (from the `#[sabi(extra_checks = ..)]` attribute)
+///   can't be reconstructed,since they are arbitrary code.
+///   Only their `Display` output is stored,
+///   which means that they are skipped by
+///   [`check_compatibility`](#method.check_compatibility)
+///   (which reports that with `SnapshotError::UncheckedExtraChecks`),
+///   and that [`to_layout`](#method.to_layout) returns an error for them.
+///
+/// - Const parameters are stored as their `Debug` output,
+///   and are compared with it.
+///
+/// The sizes and alignments of types are stored,
+/// so snapshots are only valid for the target they were taken in.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LayoutSnapshot {
+    format_version: u32,
+    /// The layout of the type the snapshot was taken of is the first one,
+    /// followed by the layouts it references.
+    layouts: Vec<LayoutNode>,
+}
+
+impl LayoutSnapshot {
+    /// The maximum amount of `TypeLayout`s reconstructed from snapshots
+    /// that can exist at the same time.
+    ///
+    /// Every type in a snapshot takes up one of these,
+    /// permanently for [`to_layout`](#method.to_layout),
+    /// and while it runs for [`check_compatibility`](#method.check_compatibility).
+    pub const RECONSTRUCTED_LAYOUT_CAPACITY: usize = SLOT_COUNT;
+
+    /// Takes a snapshot of `layout`,and of every `TypeLayout` it references.
+    pub fn new(layout: &'static TypeLayout) -> Self {
+        let mut builder = SnapshotBuilder {
+            indices: HashMap::new(),
+            pending: Vec::new(),
+        };
+        builder.index_of(layout);
+
+        let mut layouts = Vec::new();
+        while let Some(&layout) = builder.pending.get(layouts.len()) {
+            let node = builder.node(layout);
+            layouts.push(node);
+        }
+
+        Self {
+            format_version: FORMAT_VERSION,
+            layouts,
+        }
+    }
+
+    /// Takes a snapshot of the layout of `T`.
+    pub fn of<T>() -> Self
+    where
+        T: StableAbi,
+    {
+        Self::new(T::LAYOUT)
+    }
+
+    /// The name of the type that the snapshot was taken of,without generic parameters.
+    pub fn name(&self) -> &str {
+        self.layouts.first().map_or("", |x| &x.mono.name)
+    }
+
+    /// Serializes this snapshot to pretty-printed json.
+    #[cfg(feature = "serde_json")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "serde_json")))]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("LayoutSnapshot is always serializable")
+    }
+
+    /// Deserializes a snapshot from json.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if `json` isn't a serialized `LayoutSnapshot`.
+    #[cfg(feature = "serde_json")]
+    #[cfg_attr(feature = "docsrs", doc(cfg(feature = "serde_json")))]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Reconstructs the `TypeLayout` that the snapshot was taken of,
+    /// leaking it (and every `TypeLayout` it references).
+    ///
+    /// The returned `TypeLayout` can be checked against the layout of any type,
+    /// eg: with [`check_layout_compatibility`].
+    /// Its `UTypeId` is different from the `UTypeId` of every type.
+    ///
+    /// Reconstructing an equal snapshot multiple times returns the same `TypeLayout`,
+    /// so the memory is only leaked once for each snapshot.
+    /// Prefer [`check_compatibility`](#method.check_compatibility)
+    /// to only check a type against the snapshot,
+    /// since it deallocates the reconstructed layouts afterwards.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if:
+    ///
+    /// - The snapshot was serialized with an incompatible format.
+    ///
+    /// - The snapshot is malformed,eg: it has out of bounds indices.
+    ///
+    /// - Any of the snapshotted types has extra checks,
+    ///   since the reconstructed layout would be incompatible with
+    ///   every layout that has extra checks.
+    ///
+    /// - Reconstructing the snapshot would exceed
+    ///   [`RECONSTRUCTED_LAYOUT_CAPACITY`](#associatedconstant.RECONSTRUCTED_LAYOUT_CAPACITY).
+    ///
+    /// [`check_layout_compatibility`]:
+    /// ../../abi_stability/abi_checking/fn.check_layout_compatibility.html
+    ///
+    /// # Capacity
+    ///
+    /// The reconstructed layouts are leaked,
+    /// and each of them permanently takes up one of the
+    /// [`RECONSTRUCTED_LAYOUT_CAPACITY`](#associatedconstant.RECONSTRUCTED_LAYOUT_CAPACITY)
+    /// slots shared by all reconstructed layouts,
+    /// one for every type in the snapshot.
+    ///
+    /// Calling this again with an equal snapshot reuses the layout reconstructed before,
+    /// so it doesn't take up more slots.
+    pub fn to_layout(&self) -> Result<&'static TypeLayout, SnapshotError> {
+        self.validate()?;
+
+        if let Some(node) = self.layouts.iter().find(|x| x.extra_checks.is_some()) {
+            return Err(SnapshotError::UnsupportedExtraChecks {
+                type_name: node.mono.name.clone(),
+            });
+        }
+
+        let mut loaded = LOADED_SNAPSHOTS.lock();
+
+        if let Some((_, layout)) = loaded.iter().find(|(x, _)| x == self) {
+            return Ok(layout);
+        }
+
+        // Safety: the allocations are leaked,so the layout is valid for `'static`.
+        let (layout, allocations) = unsafe { self.build()? };
+        allocations.leak();
+
+        loaded.push((self.clone(), layout));
+
+        Ok(layout)
+    }
+
+    /// Checks that `implementation` is compatible with the snapshotted layout,
+    /// using the snapshot as the interface.
+    ///
+    /// This uses its own layout checking state,
+    /// so it's independent of the libraries loaded by the process.
+    ///
+    /// The extra checks of the snapshotted types are skipped,
+    /// since they can't be reconstructed.
+    ///
+    /// The layouts reconstructed from the snapshot are deallocated before this returns,
+    /// releasing the slots that they took up
+    /// (this takes as many slots as [`to_layout`](#method.to_layout) while it runs).
+    ///
+    /// # Errors
+    ///
+    /// This returns `SnapshotError::Incompatible` if the layouts are incompatible,
+    /// and the errors from [`to_layout`](#method.to_layout) otherwise
+    /// (except for `SnapshotError::UnsupportedExtraChecks`).
+    ///
+    /// If the layouts are otherwise compatible,
+    /// but any of the snapshotted types has extra checks,
+    /// this returns `SnapshotError::UncheckedExtraChecks`,
+    /// which callers can treat as a warning.
+    pub fn check_compatibility(
+        &self,
+        implementation: &'static TypeLayout,
+    ) -> Result<(), SnapshotError> {
+        self.validate()?;
+
+        let (interface, allocations) = unsafe { self.build()? };
+
+        let globals = CheckingGlobals::new();
+        let result = check_layout_compatibility_with_globals(interface, implementation, &globals)
+            .map_err(|e| SnapshotError::Incompatible(Box::new(e.to_report())));
+
+        // The checking state references the reconstructed layouts,
+        // so it must be dropped before them.
+        drop(globals);
+        drop(allocations);
+
+        result?;
+
+        let mut type_names = Vec::<String>::new();
+        for node in self.layouts.iter().filter(|x| x.extra_checks.is_some()) {
+            if !type_names.contains(&node.mono.name) {
+                type_names.push(node.mono.name.clone());
+            }
+        }
+        if type_names.is_empty() {
+            Ok(())
+        } else {
+            Err(SnapshotError::UncheckedExtraChecks { type_names })
+        }
+    }
+
+    /// Reconstructs all the layouts in the snapshot.
+    ///
+    /// # Safety
+    ///
+    /// The returned `TypeLayout` must not be used after
+    /// the returned `SnapshotAllocations` is dropped.
+    unsafe fn build(&self) -> Result<(&'static TypeLayout, SnapshotAllocations), SnapshotError> {
+        let slots = SLOT_POOL.lock().acquire(self.layouts.len())?;
+
+        // If any layout fails to build,
+        // dropping the builder releases the slots and deallocates the built layouts.
+        let mut builder = LayoutBuilder {
+            snapshot: self,
+            allocations: SnapshotAllocations::new(slots),
+            built: vec![None; self.layouts.len()],
+            building: vec![false; self.layouts.len()],
+        };
+        for index in 0..self.layouts.len() {
+            unsafe {
+                builder.build(index)?;
+            }
+        }
+        let layout = builder.built[0].expect("all layouts were built");
+
+        Ok((layout, builder.allocations))
+    }
+
+    fn validate(&self) -> Result<(), SnapshotError> {
+        if self.format_version != FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedFormatVersion {
+                expected: FORMAT_VERSION,
+                found: self.format_version,
+            });
+        }
+        if self.layouts.is_empty() {
+            return Err(SnapshotError::malformed("it contains no layouts"));
+        }
+
+        let layout_count = self.layouts.len();
+        for node in &self.layouts {
+            let const_layouts = node.constants.iter().map(|x| x.layout);
+            if let Some(index) = node
+                .type_layouts
+                .iter()
+                .copied()
+                .chain(const_layouts)
+                .find(|&i| i >= layout_count)
+            {
+                return Err(SnapshotError::malformed(format!(
+                    "`{}` references layout {}, out of {} layouts",
+                    node.mono.name, index, layout_count,
+                )));
+            }
+
+            let too_long = node.type_layouts.len() > u16::MAX as usize
+                || node.constants.len() > u16::MAX as usize
+                || node.mono.strings.len() > u16::MAX as usize
+                || node.mono.lifetime_indices.len() > u16::MAX as usize
+                || node.mono.phantom_fields.len() > u8::MAX as usize;
+            if too_long {
+                return Err(SnapshotError::malformed(format!(
+                    "`{}` has more elements than a TypeLayout can store",
+                    node.mono.name,
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// The error returned when reconstructing or checking a [`LayoutSnapshot`].
+///
+/// [`LayoutSnapshot`]: ./struct.LayoutSnapshot.html
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotError {
+    /// The snapshot was serialized with an incompatible version of the snapshot format.
+    UnsupportedFormatVersion {
+        /// The format version used by this version of abi_stable.
+        expected: u32,
+        /// The format version of the snapshot.
+        found: u32,
+    },
+    /// The snapshot is malformed,eg: it has out of bounds indices.
+    Malformed {
+        /// A description of what's wrong with the snapshot.
+        reason: String,
+    },
+    /// There are too many `TypeLayout`s reconstructed by this process.
+    TooManyLayouts {
+        /// The maximum amount of reconstructed `TypeLayout`s that can exist at the same time.
+        capacity: usize,
+    },
+    /// A snapshotted type has extra checks,which can't be reconstructed.
+    UnsupportedExtraChecks {
+        /// The name of the type with extra checks.
+        type_name: String,
+    },
+    /// The snapshotted layout is compatible with the checked layout,
+    /// but the extra checks of some snapshotted types were skipped,
+    /// since they can't be reconstructed.
+    UncheckedExtraChecks {
+        /// The names of the types whose extra checks were skipped.
+        type_names: Vec<String>,
+    },
+    /// The layout of the snapshot and the checked layout are incompatible.
+    Incompatible(Box<AbiInstabilityReport>),
+}
+
+impl SnapshotError {
+    fn malformed<S>(reason: S) -> Self
+    where
+        S: Into<String>,
+    {
+        SnapshotError::Malformed {
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::UnsupportedFormatVersion { expected, found } => write!(
+                f,
+                "Unsupported layout snapshot format version.\n\
+                 Expected version {}, found version {}.",
+                expected, found,
+            ),
+            SnapshotError::Malformed { reason } => {
+                write!(f, "Malformed layout snapshot, {}.", reason)
+            }
+            SnapshotError::TooManyLayouts { capacity } => write!(
+                f,
+                "Cannot have more than {} TypeLayouts reconstructed from snapshots at the same time.",
+                capacity,
+            ),
+            SnapshotError::UnsupportedExtraChecks { type_name } => write!(
+                f,
+                "Cannot reconstruct the layout of `{}`, since it has extra checks.",
+                type_name,
+            ),
+            SnapshotError::UncheckedExtraChecks { type_names } => write!(
+                f,
+                "The layouts are compatible, \
+                 but the extra checks of these types were skipped: {}.",
+                type_names.join(", "),
+            ),
+            SnapshotError::Incompatible(e) => f.write_str(&e.message),
+        }
+    }
+}
+
+impl ErrorTrait for SnapshotError {}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+struct LayoutNode {
+    mono: MonoSnapshot,
+    /// Indices of `LayoutSnapshot.layouts`.
+    type_layouts: Vec<usize>,
+    constants: Vec<ConstSnapshot>,
+    is_nonzero: bool,
+    alignment_power_of_two: u8,
+    size: usize,
+    tag: Option<TagSnapshot>,
+    data: GenericDataSnapshot,
+    extra_checks: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+struct ConstSnapshot {
+    /// An index of `LayoutSnapshot.layouts`.
+    layout: usize,
+    /// The `Debug` representation of the constant.
+    value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+struct MonoSnapshot {
+    name: String,
+    package_and_version: String,
+    line: u32,
+    mod_path: String,
+    strings: String,
+    lifetime_indices: Vec<LifetimeIndexPairRepr>,
+    data: MonoDataSnapshot,
+    generics: CompGenericParamsSnapshot,
+    phantom_fields: Vec<CompTLFieldRepr>,
+    repr_attr: ReprAttr,
+    mod_refl_mode: ModReflMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+enum MonoDataSnapshot {
+    Primitive(TLPrimitive),
+    Opaque,
+    Struct {
+        fields: FieldsSnapshot,
+    },
+    Union {
+        fields: FieldsSnapshot,
+    },
+    Enum {
+        variant_names: StartLenRepr,
+        field_count: Vec<u8>,
+        fields: FieldsSnapshot,
+    },
+    PrefixType {
+        first_suffix_field: u8,
+        conditional_prefix_fields: u64,
+        fields: FieldsSnapshot,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+struct FieldsSnapshot {
+    fields: Vec<CompTLFieldRepr>,
+    functions: Option<FunctionsSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+struct FunctionsSnapshot {
+    functions: Vec<CompTLFunctionSnapshot>,
+    field_fn_ranges: Vec<StartLenRepr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+enum GenericDataSnapshot {
+    Primitive,
+    Opaque,
+    Struct,
+    Union,
+    Enum {
+        nonexhaustive: Option<NonExhaustiveSnapshot>,
+        discriminants: DiscriminantsSnapshot,
+    },
+    PrefixType {
+        accessible_fields: u64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+struct NonExhaustiveSnapshot {
+    original_size: usize,
+    original_alignment_pow2: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+enum TagSnapshot {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    String(String),
+    Ignored(Box<TagSnapshot>),
+    Array(Vec<TagSnapshot>),
+    Set(Vec<TagSnapshot>),
+    Map(Vec<(TagSnapshot, TagSnapshot)>),
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                      Taking snapshots
+////////////////////////////////////////////////////////////////////////////////
+
+struct SnapshotBuilder {
+    indices: HashMap<*const TypeLayout, usize>,
+    /// The layouts in the order that they were first referenced.
+    pending: Vec<&'static TypeLayout>,
+}
+
+impl SnapshotBuilder {
+    fn index_of(&mut self, layout: &'static TypeLayout) -> usize {
+        let pending = &mut self.pending;
+        *self
+            .indices
+            .entry(layout as *const TypeLayout)
+            .or_insert_with(|| {
+                pending.push(layout);
+                pending.len() - 1
+            })
+    }
+
+    fn node(&mut self, layout: &'static TypeLayout) -> LayoutNode {
+        let shared_vars = layout.shared_vars;
+
+        let type_layouts = shared_vars
+            .type_layouts()
+            .iter()
+            .map(|get_layout| self.index_of(get_layout()))
+            .collect();
+
+        let constants = shared_vars
+            .constants()
+            .iter()
+            .map(|constant| ConstSnapshot {
+                layout: self.index_of(constant.layout()),
+                value: format!("{:?}", constant),
+            })
+            .collect();
+
+        LayoutNode {
+            mono: MonoSnapshot::new(layout.mono),
+            type_layouts,
+            constants,
+            is_nonzero: layout.is_nonzero,
+            alignment_power_of_two: layout.alignment_power_of_two,
+            size: layout.size,
+            tag: layout.tag.map(TagSnapshot::new),
+            data: GenericDataSnapshot::new(layout.data),
+            extra_checks: layout.extra_checks().map(|x| x.to_string()),
+        }
+    }
+}
+
+impl MonoSnapshot {
+    fn new(mono: &MonoTypeLayout) -> Self {
+        let item_info = mono.item_info();
+        let phantom_fields = unsafe {
+            std::slice::from_raw_parts(mono.phantom_fields, mono.phantom_fields_len as usize)
+        };
+
+        Self {
+            name: mono.name().into(),
+            package_and_version: item_info.raw_package_and_version().as_str().into(),
+            line: item_info.line,
+            mod_path: item_info.mod_path.to_string(),
+            strings: mono.shared_vars.strings().into(),
+            lifetime_indices: mono
+                .shared_vars
+                .lifetime_indices()
+                .iter()
+                .map(|x| x.to_u8())
+                .collect(),
+            data: MonoDataSnapshot::new(mono.data),
+            generics: CompGenericParamsSnapshot::new(mono.generics),
+            phantom_fields: phantom_fields.iter().map(|x| x.to_u64()).collect(),
+            repr_attr: mono.repr_attr,
+            mod_refl_mode: mono.mod_refl_mode,
+        }
+    }
+}
+
+impl MonoDataSnapshot {
+    fn new(data: MonoTLData) -> Self {
+        match data {
+            MonoTLData::Primitive(x) => MonoDataSnapshot::Primitive(x),
+            MonoTLData::Opaque => MonoDataSnapshot::Opaque,
+            MonoTLData::Struct { fields } => MonoDataSnapshot::Struct {
+                fields: FieldsSnapshot::new(fields),
+            },
+            MonoTLData::Union { fields } => MonoDataSnapshot::Union {
+                fields: FieldsSnapshot::new(fields),
+            },
+            MonoTLData::Enum(x) => MonoDataSnapshot::Enum {
+                variant_names: x.variant_names_start_len().to_u32(),
+                field_count: x.field_count().to_vec(),
+                fields: FieldsSnapshot::new(x.fields),
+            },
+            MonoTLData::PrefixType(x) => MonoDataSnapshot::PrefixType {
+                first_suffix_field: x.first_suffix_field,
+                conditional_prefix_fields: x.conditional_prefix_fields.to_u64(),
+                fields: FieldsSnapshot::new(x.fields),
+            },
+        }
+    }
+}
+
+impl FieldsSnapshot {
+    fn new(fields: CompTLFields) -> Self {
+        Self {
+            fields: fields.comp_fields().iter().map(|x| x.to_u64()).collect(),
+            functions: fields.functions().map(FunctionsSnapshot::new),
+        }
+    }
+}
+
+impl FunctionsSnapshot {
+    fn new(functions: &'static TLFunctions) -> Self {
+        Self {
+            functions: functions
+                .functions()
+                .iter()
+                .map(CompTLFunctionSnapshot::new)
+                .collect(),
+            field_fn_ranges: functions
+                .field_fn_ranges()
+                .iter()
+                .map(|x| x.to_u32())
+                .collect(),
+        }
+    }
+}
+
+impl GenericDataSnapshot {
+    fn new(data: GenericTLData) -> Self {
+        match data {
+            GenericTLData::Primitive => GenericDataSnapshot::Primitive,
+            GenericTLData::Opaque => GenericDataSnapshot::Opaque,
+            GenericTLData::Struct => GenericDataSnapshot::Struct,
+            GenericTLData::Union => GenericDataSnapshot::Union,
+            GenericTLData::Enum(x) => GenericDataSnapshot::Enum {
+                nonexhaustive: x.exhaustiveness().as_nonexhaustive().map(|x| {
+                    NonExhaustiveSnapshot {
+                        original_size: x.original_size(),
+                        original_alignment_pow2: x.original_alignment_pow2(),
+                    }
+                }),
+                discriminants: DiscriminantsSnapshot::new(x.discriminants()),
+            },
+            GenericTLData::PrefixType(x) => GenericDataSnapshot::PrefixType {
+                accessible_fields: x.accessible_fields.to_u64(),
+            },
+        }
+    }
+}
+
+impl TagSnapshot {
+    fn new(tag: &Tag) -> Self {
+        match *tag.variant() {
+            TagVariant::Primitive(Primitive::Null) => TagSnapshot::Null,
+            TagVariant::Primitive(Primitive::Bool(x)) => TagSnapshot::Bool(x),
+            TagVariant::Primitive(Primitive::Int(x)) => TagSnapshot::Int(x),
+            TagVariant::Primitive(Primitive::UInt(x)) => TagSnapshot::UInt(x),
+            TagVariant::Primitive(Primitive::String_(x)) => TagSnapshot::String(x.into()),
+            TagVariant::Ignored(x) => TagSnapshot::Ignored(Box::new(TagSnapshot::new(x))),
+            TagVariant::Array(x) => TagSnapshot::Array(x.iter().map(TagSnapshot::new).collect()),
+            TagVariant::Set(x) => TagSnapshot::Set(x.iter().map(TagSnapshot::new).collect()),
+            TagVariant::Map(x) => TagSnapshot::Map(
+                x.iter()
+                    .map(|kv| (TagSnapshot::new(&kv.key), TagSnapshot::new(&kv.value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                      Reconstructing layouts
+////////////////////////////////////////////////////////////////////////////////
+
+struct LayoutBuilder<'a> {
+    snapshot: &'a LayoutSnapshot,
+    allocations: SnapshotAllocations,
+    built: Vec<Option<&'static TypeLayout>>,
+    /// Used to detect const parameters whose type depends on the type itself.
+    building: Vec<bool>,
+}
+
+impl LayoutBuilder<'_> {
+    /// # Safety
+    ///
+    /// The returned `TypeLayout` must not be used after `self.allocations` is dropped.
+    unsafe fn build(&mut self, index: usize) -> Result<&'static TypeLayout, SnapshotError> {
+        if let Some(layout) = self.built[index] {
+            return Ok(layout);
+        }
+        let node = &self.snapshot.layouts[index];
+        if std::mem::replace(&mut self.building[index], true) {
+            return Err(SnapshotError::malformed(format!(
+                "the type of a const parameter of `{}` depends on itself",
+                node.mono.name,
+            )));
+        }
+
+        // The layouts of const parameters are needed to construct the ConstGeneric,
+        // so they're built before this layout.
+        let mut constants = Vec::with_capacity(node.constants.len());
+        for constant in &node.constants {
+            let layout = unsafe { self.build(constant.layout)? };
+            let constant = ConstGenericSnapshot::new(layout, &constant.value);
+            constants
+                .push(unsafe { ConstGeneric::from_snapshot(self.allocations.alloc(constant)) });
+        }
+
+        let slots = &self.allocations.slots;
+        let type_layouts = node
+            .type_layouts
+            .iter()
+            .map(|&i| layout_fn(slots[i]))
+            .collect::<Vec<_>>();
+        let slot = slots[index];
+
+        let allocations = &mut self.allocations;
+        let layout = unsafe {
+            let mono = node.mono.build(allocations);
+            let type_layouts = allocations.alloc_vec(type_layouts);
+            let constants = allocations.alloc_vec(constants);
+            let shared_vars = allocations.alloc(SharedVars::new(
+                mono.shared_vars_static(),
+                type_layouts,
+                constants,
+            ));
+            let tag = match &node.tag {
+                Some(tag) => {
+                    let tag = tag.build(allocations);
+                    Some(allocations.alloc(tag))
+                }
+                None => None,
+            };
+            let data = node.data.build(allocations);
+
+            allocations.alloc(TypeLayout {
+                shared_vars,
+                mono,
+                is_nonzero: node.is_nonzero,
+                alignment_power_of_two: node.alignment_power_of_two,
+                size: node.size,
+                tag,
+                data,
+                extra_checks: CmpIgnored::new(None),
+                type_id: type_id_fn(slot),
+            })
+        };
+
+        SLOTS[slot].store(
+            layout as *const TypeLayout as *mut TypeLayout,
+            Ordering::Release,
+        );
+        self.built[index] = Some(layout);
+
+        Ok(layout)
+    }
+}
+
+// The `build` methods below must only be called by `LayoutBuilder::build`,
+// and have the same safety requirements:
+// the returned values must not be used after `allocations` is dropped.
+
+impl MonoSnapshot {
+    unsafe fn build(&self, allocations: &mut SnapshotAllocations) -> &'static MonoTypeLayout {
+        unsafe {
+            let strings = allocations.alloc_str(&self.strings);
+            let item_info = ItemInfo::new(
+                allocations.alloc_str(&self.package_and_version),
+                self.line,
+                ModPath::inside(allocations.alloc_nul_str(&self.mod_path)),
+            );
+
+            let mono = MonoTypeLayout::from_derive(_private_MonoTypeLayoutDerive {
+                name: RStr::from_str(allocations.alloc_str(&self.name)),
+                item_info,
+                data: self.data.build(allocations),
+                generics: self
+                    .generics
+                    .to_comp(allocations.alloc_nul_str(self.generics.lifetimes())),
+                repr_attr: self.repr_attr,
+                mod_refl_mode: self.mod_refl_mode,
+                phantom_fields: allocations.alloc_slice(&self.phantom_fields),
+                shared_vars: MonoSharedVars::new(
+                    RStr::from_str(strings),
+                    allocations.alloc_slice(&self.lifetime_indices),
+                ),
+            });
+            allocations.alloc(mono)
+        }
+    }
+}
+
+impl MonoDataSnapshot {
+    unsafe fn build(&self, allocations: &mut SnapshotAllocations) -> MonoTLData {
+        unsafe {
+            match self {
+                MonoDataSnapshot::Primitive(x) => MonoTLData::Primitive(*x),
+                MonoDataSnapshot::Opaque => MonoTLData::Opaque,
+                MonoDataSnapshot::Struct { fields } => {
+                    MonoTLData::struct_derive(fields.build(allocations))
+                }
+                MonoDataSnapshot::Union { fields } => {
+                    MonoTLData::union_derive(fields.build(allocations))
+                }
+                MonoDataSnapshot::Enum {
+                    variant_names,
+                    field_count,
+                    fields,
+                } => MonoTLData::Enum(MonoTLEnum::new(
+                    StartLen::from_u32(*variant_names),
+                    allocations.alloc_slice(field_count),
+                    fields.build(allocations),
+                )),
+                MonoDataSnapshot::PrefixType {
+                    first_suffix_field,
+                    conditional_prefix_fields,
+                    fields,
+                } => MonoTLData::prefix_type_derive(
+                    *first_suffix_field as usize,
+                    *conditional_prefix_fields,
+                    fields.build(allocations),
+                ),
+            }
+        }
+    }
+}
+
+impl FieldsSnapshot {
+    unsafe fn build(&self, allocations: &mut SnapshotAllocations) -> CompTLFields {
+        unsafe {
+            let functions = match &self.functions {
+                Some(functions) => {
+                    let functions = functions.build(allocations);
+                    Some(allocations.alloc(functions))
+                }
+                None => None,
+            };
+            CompTLFields::new(allocations.alloc_slice(&self.fields), functions)
+        }
+    }
+}
+
+impl FunctionsSnapshot {
+    unsafe fn build(&self, allocations: &mut SnapshotAllocations) -> TLFunctions {
+        let functions = self
+            .functions
+            .iter()
+            .map(CompTLFunctionSnapshot::to_comp)
+            .collect();
+        unsafe {
+            TLFunctions::new(
+                allocations.alloc_vec(functions),
+                allocations.alloc_slice(&self.field_fn_ranges),
+            )
+        }
+    }
+}
+
+impl GenericDataSnapshot {
+    unsafe fn build(&self, allocations: &mut SnapshotAllocations) -> GenericTLData {
+        match self {
+            GenericDataSnapshot::Primitive => GenericTLData::Primitive,
+            GenericDataSnapshot::Opaque => GenericTLData::Opaque,
+            GenericDataSnapshot::Struct => GenericTLData::Struct,
+            GenericDataSnapshot::Union => GenericTLData::Union,
+            GenericDataSnapshot::Enum {
+                nonexhaustive,
+                discriminants,
+            } => unsafe {
+                let exhaustiveness = match nonexhaustive {
+                    Some(x) => IsExhaustive::nonexhaustive(allocations.alloc(
+                        TLNonExhaustive::from_size_and_alignment_pow2(
+                            x.original_size,
+                            x.original_alignment_pow2,
+                        ),
+                    )),
+                    None => IsExhaustive::exhaustive(),
+                };
+                let discriminants = discriminants.build(allocations);
+                GenericTLData::Enum(GenericTLEnum::new(exhaustiveness, discriminants))
+            },
+            GenericDataSnapshot::PrefixType { accessible_fields } => {
+                GenericTLData::prefix_type_derive(FieldAccessibility::from_u64(*accessible_fields))
+            }
+        }
+    }
+}
+
+impl TagSnapshot {
+    unsafe fn build(&self, allocations: &mut SnapshotAllocations) -> Tag {
+        unsafe {
+            match self {
+                TagSnapshot::Null => Tag::null(),
+                TagSnapshot::Bool(x) => Tag::bool_(*x),
+                TagSnapshot::Int(x) => Tag::int(*x),
+                TagSnapshot::UInt(x) => Tag::uint(*x),
+                TagSnapshot::String(x) => Tag::str(allocations.alloc_str(x)),
+                TagSnapshot::Ignored(x) => {
+                    let tag = x.build(allocations);
+                    Tag::ignored(allocations.alloc(tag))
+                }
+                TagSnapshot::Array(x) => Tag::arr(TagSnapshot::build_all(x, allocations)),
+                TagSnapshot::Set(x) => Tag::set(TagSnapshot::build_all(x, allocations)),
+                TagSnapshot::Map(x) => {
+                    let pairs = x
+                        .iter()
+                        .map(|(key, value)| {
+                            KeyValue::new(key.build(allocations), value.build(allocations))
+                        })
+                        .collect();
+                    Tag::map(allocations.alloc_vec(pairs))
+                }
+            }
+        }
+    }
+
+    unsafe fn build_all(
+        tags: &[TagSnapshot],
+        allocations: &mut SnapshotAllocations,
+    ) -> RSlice<'static, Tag> {
+        unsafe {
+            let tags = tags.iter().map(|x| x.build(allocations)).collect();
+            allocations.alloc_vec(tags)
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                      Allocations
+////////////////////////////////////////////////////////////////////////////////
+
+/// Owns everything allocated to reconstruct the `TypeLayout`s of a snapshot,
+/// and the slots that they use.
+///
+/// Dropping this releases the slots and deallocates the `TypeLayout`s,
+/// so the references returned by its methods must not be used afterwards.
+pub(super) struct SnapshotAllocations {
+    /// The slot of each layout in the snapshot,in the same order.
+    slots: Vec<usize>,
+    allocations: Vec<Box<dyn Any>>,
+}
+
+impl SnapshotAllocations {
+    fn new(slots: Vec<usize>) -> Self {
+        Self {
+            slots,
+            allocations: Vec::new(),
+        }
+    }
+
+    /// Keeps the allocations and slots for the rest of the process.
+    fn leak(self) {
+        std::mem::forget(self);
+    }
+
+    /// # Safety
+    ///
+    /// The returned reference must not be used after `self` is dropped.
+    unsafe fn alloc<T: 'static>(&mut self, value: T) -> &'static T {
+        self.allocations.push(Box::new(value));
+        let value = self
+            .allocations
+            .last()
+            .unwrap()
+            .downcast_ref::<T>()
+            .unwrap();
+        // The value is boxed,so it doesn't move until `self` is dropped.
+        unsafe { &*(value as *const T) }
+    }
+
+    /// # Safety
+    ///
+    /// The returned slice must not be used after `self` is dropped.
+    unsafe fn alloc_vec<T: 'static>(&mut self, vec: Vec<T>) -> RSlice<'static, T> {
+        let slice: &[T] = unsafe { self.alloc(vec.into_boxed_slice()) };
+        RSlice::from_slice(slice)
+    }
+
+    /// # Safety
+    ///
+    /// The returned slice must not be used after `self` is dropped.
+    pub(super) unsafe fn alloc_slice<T: Clone + 'static>(
+        &mut self,
+        slice: &[T],
+    ) -> RSlice<'static, T> {
+        unsafe { self.alloc_vec(slice.to_vec()) }
+    }
+
+    /// # Safety
+    ///
+    /// The returned string must not be used after `self` is dropped.
+    unsafe fn alloc_str(&mut self, str: &str) -> &'static str {
+        unsafe { self.alloc(Box::<str>::from(str)) }
+    }
+
+    /// # Safety
+    ///
+    /// The returned string must not be used after `self` is dropped.
+    unsafe fn alloc_nul_str(&mut self, str: &str) -> NulStr<'static> {
+        unsafe { NulStr::from_str(self.alloc_str(&format!("{}\0", str))) }
+    }
+}
+
+impl Drop for SnapshotAllocations {
+    fn drop(&mut self) {
+        for &slot in &self.slots {
+            SLOTS[slot].store(ptr::null_mut(), Ordering::Release);
+        }
+        SLOT_POOL.lock().release(&self.slots);
+    }
+}
+
+/// The snapshots reconstructed by `LayoutSnapshot::to_layout`.
+static LOADED_SNAPSHOTS: Mutex<Vec<(LayoutSnapshot, &'static TypeLayout)>> =
+    parking_lot::const_mutex(Vec::new());
+
+////////////////////////////////////////////////////////////////////////////////
+//                      Function pointer slots
+////////////////////////////////////////////////////////////////////////////////
+
+// `TypeLayout`s reference other `TypeLayout`s (and their `UTypeId`) through
+// `extern "C" fn` pointers that take no arguments,
+// so every reconstructed `TypeLayout` needs its own pair of functions.
+//
+// Since functions can't be created at runtime,
+// there is a fixed amount of slots with one function of each kind,
+// which are assigned to reconstructed `TypeLayout`s,
+// and released when those `TypeLayout`s are deallocated.
+
+type LayoutFn = extern "C" fn() -> &'static TypeLayout;
+type TypeIdFn = extern "C" fn() -> UTypeId;
+
+const SLOT_COUNT: usize = 1024;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_SLOT: AtomicPtr<TypeLayout> = AtomicPtr::new(ptr::null_mut());
+
+static SLOTS: [AtomicPtr<TypeLayout>; SLOT_COUNT] = [EMPTY_SLOT; SLOT_COUNT];
+
+struct SlotPool {
+    /// The slots starting from this one have never been used.
+    next_unused: usize,
+    released: Vec<usize>,
+}
+
+impl SlotPool {
+    fn acquire(&mut self, count: usize) -> Result<Vec<usize>, SnapshotError> {
+        if self.released.len() + (SLOT_COUNT - self.next_unused) < count {
+            return Err(SnapshotError::TooManyLayouts {
+                capacity: SLOT_COUNT,
+            });
+        }
+
+        let reused = count.min(self.released.len());
+        let mut slots = self.released.split_off(self.released.len() - reused);
+
+        let unused = count - reused;
+        slots.extend(self.next_unused..self.next_unused + unused);
+        self.next_unused += unused;
+
+        Ok(slots)
+    }
+
+    fn release(&mut self, slots: &[usize]) {
+        self.released.extend_from_slice(slots);
+    }
+}
+
+static SLOT_POOL: Mutex<SlotPool> = parking_lot::const_mutex(SlotPool {
+    next_unused: 0,
+    released: Vec::new(),
+});
+
+extern "C" fn slot_layout<const SLOT: usize>() -> &'static TypeLayout {
+    match NonNull::new(SLOTS[SLOT].load(Ordering::Acquire)) {
+        // Slots are only set to `TypeLayout`s that live until the slot is released.
+        Some(layout) => unsafe { &*layout.as_ptr() },
+        // Unreachable,since the function pointers of a slot are only reachable
+        // while the slot is set.
+        None => std::process::abort(),
+    }
+}
+
+extern "C" fn slot_type_id<const SLOT: usize>() -> UTypeId {
+    UTypeId::for_snapshot(SLOT)
+}
+
+macro_rules! slot_fns_16 {
+    ($function:ident, $base:expr) => {
+        [
+            $function::<{ $base }>,
+            $function::<{ $base + 1 }>,
+            $function::<{ $base + 2 }>,
+            $function::<{ $base + 3 }>,
+            $function::<{ $base + 4 }>,
+            $function::<{ $base + 5 }>,
+            $function::<{ $base + 6 }>,
+            $function::<{ $base + 7 }>,
+            $function::<{ $base + 8 }>,
+            $function::<{ $base + 9 }>,
+            $function::<{ $base + 10 }>,
+            $function::<{ $base + 11 }>,
+            $function::<{ $base + 12 }>,
+            $function::<{ $base + 13 }>,
+            $function::<{ $base + 14 }>,
+            $function::<{ $base + 15 }>,
+        ]
+    };
+}
+
+macro_rules! slot_fns_256 {
+    ($function:ident, $base:expr) => {
+        [
+            slot_fns_16!($function, $base),
+            slot_fns_16!($function, $base + 16),
+            slot_fns_16!($function, $base + 16 * 2),
+            slot_fns_16!($function, $base + 16 * 3),
+            slot_fns_16!($function, $base + 16 * 4),
+            slot_fns_16!($function, $base + 16 * 5),
+            slot_fns_16!($function, $base + 16 * 6),
+            slot_fns_16!($function, $base + 16 * 7),
+            slot_fns_16!($function, $base + 16 * 8),
+            slot_fns_16!($function, $base + 16 * 9),
+            slot_fns_16!($function, $base + 16 * 10),
+            slot_fns_16!($function, $base + 16 * 11),
+            slot_fns_16!($function, $base + 16 * 12),
+            slot_fns_16!($function, $base + 16 * 13),
+            slot_fns_16!($function, $base + 16 * 14),
+            slot_fns_16!($function, $base + 16 * 15),
+        ]
+    };
+}
+
+macro_rules! slot_fns_1024 {
+    ($function:ident) => {
+        [
+            slot_fns_256!($function, 0),
+            slot_fns_256!($function, 256),
+            slot_fns_256!($function, 256 * 2),
+            slot_fns_256!($function, 256 * 3),
+        ]
+    };
+}
+
+static LAYOUT_FNS: [[[LayoutFn; 16]; 16]; 4] = slot_fns_1024!(slot_layout);
+
+static TYPE_ID_FNS: [[[TypeIdFn; 16]; 16]; 4] = slot_fns_1024!(slot_type_id);
+
+fn layout_fn(slot: usize) -> LayoutFn {
+    LAYOUT_FNS[slot / 256][slot / 16 % 16][slot % 16]
+}
+
+fn type_id_fn(slot: usize) -> TypeIdFn {
+    TYPE_ID_FNS[slot / 256][slot / 16 % 16][slot % 16]
+}
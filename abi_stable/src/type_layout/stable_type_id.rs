@@ -0,0 +1,181 @@
+//! A type identity derived from the `TypeLayout` of a type,
+//! which is the same for a type in every dynamic library that uses it.
+//!
+//! # Example
+//!
+//! ```rust
+//! use abi_stable::{
+//!     std_types::{RString, RVec},
+//!     type_layout::StableTypeId,
+//! };
+//!
+//! assert_eq!(StableTypeId::new::<RString>(), StableTypeId::new::<RString>());
+//! assert_eq!(StableTypeId::new::<RVec<u8>>(), StableTypeId::new::<RVec<u8>>());
+//!
+//! assert_ne!(StableTypeId::new::<RString>(), StableTypeId::new::<RVec<u8>>());
+//! assert_ne!(StableTypeId::new::<RVec<u8>>(), StableTypeId::new::<RVec<u16>>());
+//!
+//! ```
+
+use super::*;
+
+use crate::{abi_stability::abi_checking::check_layout_compatibility, StableAbi};
+
+/// An identity for a type that is computed from its [`TypeLayout`],
+/// so that it's equal for the same type in different dynamic libraries.
+///
+/// As opposed to [`UTypeId`],
+/// which never compares equal for types from different dynamic libraries.
+///
+/// A `StableTypeId` is composed of these parts of a `TypeLayout`:
+///
+/// - The name of the package that declared the type.
+///
+/// - The semver compatible part of the package version
+/// (`x` for `x.*.*` versions, `0.y` for `0.y.*` versions).
+///
+/// - The module path of the type.
+///
+/// - The name of the type, with the generic arguments recorded in its layout.
+///
+/// - A hash of the layout of the type
+/// (its size, alignment, kind of type, and the name and type of every field),
+/// which distinguishes the type arguments that are only used in fields.
+///
+/// Equal `StableTypeId`s don't guarantee that the types have compatible layouts,
+/// that has to be checked with
+/// [`check_layout_compatibility`](crate::abi_stability::abi_checking::check_layout_compatibility).
+///
+/// For an example, look at the [module-level documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StableTypeId {
+    package: &'static str,
+    compatible_version: String,
+    mod_path: String,
+    full_type: String,
+    layout_hash: u64,
+}
+
+impl StableTypeId {
+    /// Constructs the `StableTypeId` of `T`.
+    pub fn new<T>() -> Self
+    where
+        T: StableAbi,
+    {
+        Self::from_layout(T::LAYOUT)
+    }
+
+    /// Constructs the `StableTypeId` of the type that `layout` describes.
+    pub fn from_layout(layout: &'static TypeLayout) -> Self {
+        let (package, version) = layout.package_and_version();
+        let compatible_version = match version.parsed() {
+            Ok(v) if v.major == 0 => format!("0.{}", v.minor),
+            Ok(v) => v.major.to_string(),
+            Err(_) => version.to_string(),
+        };
+
+        Self {
+            package: package.as_str(),
+            compatible_version,
+            mod_path: layout.mod_path().to_string(),
+            full_type: layout.full_type().to_string(),
+            layout_hash: hash_layout(layout),
+        }
+    }
+
+    /// The name of the package that declared the type.
+    pub const fn package(&self) -> &'static str {
+        self.package
+    }
+
+    /// The semver compatible part of the version of the package that declared the type.
+    pub fn compatible_version(&self) -> &str {
+        &self.compatible_version
+    }
+
+    /// The module path of the type.
+    pub fn mod_path(&self) -> &str {
+        &self.mod_path
+    }
+
+    /// The name of the type, with the generic arguments recorded in its layout.
+    pub fn full_type(&self) -> &str {
+        &self.full_type
+    }
+
+    /// The hash of the layout of the type.
+    pub const fn layout_hash(&self) -> u64 {
+        self.layout_hash
+    }
+}
+
+impl Display for StableTypeId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::{} (package: {} {}, layout hash: {:016X})",
+            self.mod_path, self.full_type, self.package, self.compatible_version, self.layout_hash,
+        )
+    }
+}
+
+/// Whether `found` describes the same type as `expected`,
+/// possibly from a different dynamic library,
+/// by comparing their `StableTypeId`s and checking that their layouts are compatible.
+pub(crate) fn is_same_stable_type(
+    expected: &'static TypeLayout,
+    found: &'static TypeLayout,
+) -> bool {
+    StableTypeId::from_layout(expected) == StableTypeId::from_layout(found)
+        && check_layout_compatibility(expected, found).is_ok()
+}
+
+/// Hashes the layout of a type with the FNV-1a algorithm,
+/// which is used because it produces the same value in every compilation.
+fn hash_layout(layout: &'static TypeLayout) -> u64 {
+    let mut hasher = FnvHasher::new();
+    hasher.write_usize(layout.size());
+    hasher.write_usize(layout.alignment());
+    hasher.write_bytes(&[layout.data_discriminant() as u8]);
+    if let Some(fields) = layout.get_fields() {
+        for field in fields.iter() {
+            hasher.write_str(field.name());
+            hasher.write_str(&field.full_type().to_string());
+            hasher.write_usize(field.layout().size());
+            hasher.write_usize(field.layout().alignment());
+        }
+    }
+    hasher.hash
+}
+
+struct FnvHasher {
+    hash: u64,
+}
+
+impl FnvHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    const fn new() -> Self {
+        Self {
+            hash: Self::OFFSET_BASIS,
+        }
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.hash ^= u64::from(byte);
+            self.hash = self.hash.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_bytes(&(n as u64).to_le_bytes());
+    }
+
+    fn write_str(&mut self, s: &str) {
+        self.write_bytes(s.as_bytes());
+        // Separates consecutive strings, so that "ab","c" and "a","bc" hash differently.
+        self.write_bytes(&[0xFF]);
+    }
+}
@@ -355,6 +355,11 @@ impl Tag {
         Self { variant }
     }
 
+    /// The variant of this `Tag`.
+    pub(super) const fn variant(&self) -> &TagVariant {
+        &self.variant
+    }
+
     /// Constructs the Null variant.
     pub const NULL: &'static Tag = &Tag::null();
 
@@ -6,6 +6,8 @@ use crate::{
     std_types::{RSlice, RString, RVec},
 };
 
+use super::snapshot::SnapshotAllocations;
+
 ///////////////////////////
 
 /// The parts of the layout of an enum,that don't depend on generic parameters.
@@ -52,6 +54,11 @@ impl MonoTLEnum {
         unsafe { RSlice::from_raw_parts(self.field_count, self.field_count_len as usize) }
     }
 
+    /// Gets the range of the ';' separated list of variant names in `SharedVars`'s strings.
+    pub(super) const fn variant_names_start_len(&self) -> StartLen {
+        self.variant_names
+    }
+
     /// Expands this into a TLEnum,with all the properties of an enum definition.
     pub fn expand(self, other: GenericTLEnum, shared_vars: &'static SharedVars) -> TLEnum {
         TLEnum {
@@ -90,6 +97,16 @@ impl GenericTLEnum {
     pub const fn exhaustive(discriminants: TLDiscriminants) -> Self {
         Self::new(IsExhaustive::exhaustive(), discriminants)
     }
+
+    /// The exhaustiveness of this enum.
+    pub(super) const fn exhaustiveness(&self) -> IsExhaustive {
+        self.exhaustiveness
+    }
+
+    /// The discriminants of the variants in the enum.
+    pub(super) const fn discriminants(&self) -> &TLDiscriminants {
+        &self.discriminants
+    }
 }
 
 ///////////////////////////
@@ -228,6 +245,46 @@ macro_rules! declare_tl_discriminants {
 
         impl Eq for TLDiscriminants{}
 
+        /// An owned version of `TLDiscriminants`,stored in layout snapshots.
+        #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+        pub(super) enum DiscriminantsSnapshot{
+            $(
+                $variant(Vec<$ty>),
+            )*
+        }
+
+        impl DiscriminantsSnapshot{
+            pub(super) fn new(discriminants:&TLDiscriminants)->Self{
+                match discriminants.inner {
+                    $(
+                        TLDiscrsInner::$variant{discriminants,len}=>unsafe{
+                            let slice=std::slice::from_raw_parts(discriminants,len as usize);
+                            DiscriminantsSnapshot::$variant(slice.to_vec())
+                        }
+                    )*
+                }
+            }
+
+            /// Converts this into a `TLDiscriminants`,
+            /// storing the discriminants in `allocations`.
+            ///
+            /// # Safety
+            ///
+            /// The returned value must not be used after `allocations` is dropped.
+            pub(super) unsafe fn build(
+                &self,
+                allocations:&mut SnapshotAllocations,
+            )->TLDiscriminants{
+                match self {
+                    $(
+                        DiscriminantsSnapshot::$variant(discrs)=>unsafe{
+                            TLDiscriminants::$method(allocations.alloc_slice(discrs))
+                        }
+                    )*
+                }
+            }
+        }
+
         impl TLDiscriminants{
 
             $(
@@ -390,7 +447,7 @@ pub enum TLDiscriminant {
 
 /// How the discriminant of an enum is represented.
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi, Deserialize, Serialize)]
 #[sabi(unsafe_sabi_opaque_fields)]
 pub enum DiscriminantRepr {
     /// The type of the discriminant for a `#[repr(u8)]`enum
@@ -472,11 +529,27 @@ impl TLNonExhaustive {
         }
     }
 
+    /// Constructs a `TLNonExhaustive` from the size and the log2 of the alignment
+    /// of the enum.
+    pub(super) const fn from_size_and_alignment_pow2(
+        original_size: usize,
+        original_alignment_pow2: u8,
+    ) -> Self {
+        Self {
+            original_size,
+            original_alignment_pow2,
+        }
+    }
+
     #[inline]
-    const fn original_size(&self) -> usize {
+    pub(super) const fn original_size(&self) -> usize {
         self.original_size
     }
     #[inline]
+    pub(super) const fn original_alignment_pow2(&self) -> u8 {
+        self.original_alignment_pow2
+    }
+    #[inline]
     const fn original_alignment(&self) -> usize {
         1_usize << (self.original_alignment_pow2 as u32)
     }
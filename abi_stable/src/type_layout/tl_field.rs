@@ -239,6 +239,11 @@ abi_stable_shared::declare_comp_tl_field! {
 }
 
 impl CompTLField {
+    /// Converts this `CompTLField` into its internal representation.
+    pub const fn to_u64(self) -> CompTLFieldRepr {
+        self.bits0
+    }
+
     /// Gets the name of the field from `SharedVars`'s string slice.
     pub fn name(&self, strings: &'static str) -> &'static str {
         &strings[self.name_start_len().to_range()]
@@ -52,6 +52,11 @@ impl CompTLFields {
         unsafe { slice::from_raw_parts(self.comp_fields, self.comp_fields_len as usize) }
     }
 
+    /// All the function pointer types in the fields.
+    pub(super) const fn functions(&self) -> Option<&'static TLFunctions> {
+        self.functions
+    }
+
     /// Accesses a slice of all the compressed fields in this `CompTLFields`.
     pub const fn comp_fields_rslice(&self) -> RSlice<'static, CompTLField> {
         unsafe { RSlice::from_raw_parts(self.comp_fields, self.comp_fields_len as usize) }
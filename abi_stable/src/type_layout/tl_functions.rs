@@ -47,11 +47,11 @@ impl TLFunctions {
         }
     }
 
-    fn functions(&self) -> &'static [CompTLFunction] {
+    pub(super) fn functions(&self) -> &'static [CompTLFunction] {
         unsafe { std::slice::from_raw_parts(self.functions, self.functions_len as usize) }
     }
 
-    fn field_fn_ranges(&self) -> &'static [StartLen] {
+    pub(super) fn field_fn_ranges(&self) -> &'static [StartLen] {
         unsafe {
             std::slice::from_raw_parts(self.field_fn_ranges, self.field_fn_ranges_len as usize)
         }
@@ -289,6 +289,47 @@ impl CompTLFunction {
     }
 }
 
+/// An owned version of `CompTLFunction`,stored in layout snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub(super) struct CompTLFunctionSnapshot {
+    name: StartLenRepr,
+    contiguous_strings_offset: u16,
+    bound_lifetimes_len: u16,
+    param_names_len: u16,
+    return_type_layout: u16,
+    paramret_lifetime_range: u32,
+    param_type_layouts: u64,
+    qualifiers: u16,
+}
+
+impl CompTLFunctionSnapshot {
+    pub(super) fn new(function: &CompTLFunction) -> Self {
+        Self {
+            name: function.name.to_u32(),
+            contiguous_strings_offset: function.contiguous_strings_offset,
+            bound_lifetimes_len: function.bound_lifetimes_len,
+            param_names_len: function.param_names_len,
+            return_type_layout: function.return_type_layout,
+            paramret_lifetime_range: function.paramret_lifetime_range.to_u21(),
+            param_type_layouts: function.param_type_layouts.to_u64(),
+            qualifiers: function.fn_qualifs.0,
+        }
+    }
+
+    pub(super) fn to_comp(&self) -> CompTLFunction {
+        CompTLFunction::new(
+            self.name,
+            self.contiguous_strings_offset,
+            self.bound_lifetimes_len,
+            self.param_names_len,
+            self.return_type_layout,
+            self.paramret_lifetime_range,
+            self.param_type_layouts,
+            TLFunctionQualifiers(self.qualifiers),
+        )
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 
 /// A function pointer in a field.
@@ -6,7 +6,7 @@ use crate::{abi_stability::ConstGeneric, sabi_types::Constructor};
 
 /// The `repr(..)` attribute used on a type.
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi, Deserialize, Serialize)]
 #[sabi(unsafe_sabi_opaque_fields)]
 pub enum ReprAttr {
     /// This is an `Option<NonZeroType>`.
@@ -104,6 +104,42 @@ impl CompGenericParams {
     }
 }
 
+/// An owned version of `CompGenericParams`,stored in layout snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub(super) struct CompGenericParamsSnapshot {
+    lifetimes: String,
+    lifetime_count: u8,
+    types: StartLenRepr,
+    consts: StartLenRepr,
+}
+
+impl CompGenericParamsSnapshot {
+    pub(super) fn new(generics: CompGenericParams) -> Self {
+        Self {
+            lifetimes: generics.lifetime.to_str().into(),
+            lifetime_count: generics.lifetime_count,
+            types: generics.types.to_u32(),
+            consts: generics.consts.to_u32(),
+        }
+    }
+
+    /// The names of the lifetimes declared by the type,separated by `,`.
+    pub(super) fn lifetimes(&self) -> &str {
+        &self.lifetimes
+    }
+
+    /// Converts this into a `CompGenericParams`,
+    /// with `lifetime` being a nul-terminated copy of `self.lifetimes()`.
+    pub(super) fn to_comp(&self, lifetime: NulStr<'static>) -> CompGenericParams {
+        CompGenericParams::new(
+            lifetime,
+            self.lifetime_count,
+            StartLen::from_u32(self.types),
+            StartLen::from_u32(self.consts),
+        )
+    }
+}
+
 /// The generic parameters of a type.
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct GenericParams {
@@ -172,7 +208,7 @@ impl Display for GenericParams {
 
 /// Types defined in the compiler
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, StableAbi, Deserialize, Serialize)]
 #[sabi(unsafe_sabi_opaque_fields)]
 pub enum TLPrimitive {
     ///
@@ -86,6 +86,30 @@ pub mod trait_marker {
     /// Represents the [`std::marker::Unpin`] trait.
     pub struct Unpin;
 
+    /// Represents the [`std::future::Future`] trait.
+    pub struct Future;
+
+    /// Represents the [`AsRef<str>`](std::convert::AsRef) trait.
+    pub struct AsRefStr;
+
+    /// Represents the [`AsRef<[u8]>`](std::convert::AsRef) trait.
+    pub struct AsRefBytes;
+
+    /// Represents the [`Borrow<str>`](std::borrow::Borrow) trait.
+    pub struct BorrowStr;
+
+    /// Represents the [`std::ops::Deref`] trait.
+    pub struct Deref;
+
+    /// Represents the [`std::ops::FnOnce`] trait.
+    pub struct FnOnce;
+
+    /// Represents the [`std::ops::FnMut`] trait.
+    pub struct FnMut;
+
+    /// Represents the [`std::ops::Fn`] trait.
+    pub struct Fn;
+
     #[doc(hidden)]
     #[allow(non_camel_case_types)]
     pub struct define_this_in_the_impl_InterfaceType_macro;
@@ -158,4 +182,14 @@ pub mod impl_enum {
     impl<T: ?Sized> Implementability for Unimplemented<T> {
         const IS_IMPLD: bool = false;
     }
+
+    /// Implemented by an [`Implementability`] that requires at most as much as `Other`.
+    ///
+    /// `Unimplemented<T>` is a subset of both `Unimplemented<T>` and `Implemented<T>`,
+    /// while `Implemented<T>` is only a subset of `Implemented<T>`.
+    pub trait IsSubsetOf<Other: ?Sized>: Implementability {}
+
+    impl<T: ?Sized> IsSubsetOf<Implemented<T>> for Implemented<T> {}
+
+    impl<T: ?Sized, Other: ?Sized + Implementability> IsSubsetOf<Other> for Unimplemented<T> {}
 }
@@ -1,6 +1,8 @@
 use crate::{
     sabi_types::MaybeCmp,
     std_types::utypeid::{no_utypeid, some_utypeid, UTypeId},
+    type_layout::TypeLayout,
+    StableAbi,
 };
 
 /// Passed to trait object constructors to make the
@@ -65,6 +67,45 @@ pub struct TD_CanDowncast;
 #[derive(Copy, Clone)]
 pub struct TD_Opaque;
 
+/// Passed to trait object constructors to make the trait object downcast capable,
+/// including in dynamic libraries other than the one that constructed it,
+/// as opposed to [`TD_CanDowncast`](./struct.TD_CanDowncast.html).
+///
+/// This requires the type of the value to implement [`StableAbi`],
+/// storing its layout in the vtable.
+///
+/// The `stable_downcast_*` methods of
+/// [`DynTrait`](crate::DynTrait#method.stable_downcast_into) and
+/// [`RObject`](crate::sabi_trait::RObject#method.stable_downcast_into)
+/// can downcast the trait object in any dynamic library,
+/// so long as the type has the same
+/// [`StableTypeId`](crate::type_layout::StableTypeId)
+/// and a compatible layout in both dynamic libraries.
+///
+/// The regular `downcast_*` methods treat this the same as `TD_CanDowncast`.
+///
+/// # Example
+///
+/// ```rust
+/// use abi_stable::{
+///     sabi_trait::doc_examples::Action_TO,
+///     std_types::{RBox, RString},
+///     type_level::downcasting::TD_StableDowncast,
+/// };
+///
+/// // The type annotation is purely for the reader.
+/// let object: Action_TO<'static, RBox<()>> =
+///     Action_TO::from_value(100_usize, TD_StableDowncast);
+///
+/// assert_eq!(object.obj.stable_downcast_as::<u8>().ok(), None);
+/// assert_eq!(object.obj.stable_downcast_as::<RString>().ok(), None);
+/// assert_eq!(object.obj.stable_downcast_as::<usize>().ok(), Some(&100_usize));
+///
+/// ```
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone)]
+pub struct TD_StableDowncast;
+
 /// Gets a function optionally returning the `UTypeId` of `T`.
 ///
 /// Whether the function returns `MaybeCmp::Just(typeid)` is determined by implementors:
@@ -72,9 +113,17 @@ pub struct TD_Opaque;
 /// - `TD_CanDowncast`: the function always returns `MaybeCmp::Just(typeid)`.
 ///
 /// - `TD_Opaque`: the function always returns `MaybeCmp::Nothing`.
+///
+/// - `TD_StableDowncast`: the function always returns `MaybeCmp::Just(typeid)`.
 pub trait GetUTID<T> {
     /// the function.
     const UID: extern "C" fn() -> MaybeCmp<UTypeId>;
+
+    /// The layout of `T`,
+    /// used to downcast trait objects constructed in other dynamic libraries.
+    ///
+    /// This is only `Some` for `TD_StableDowncast`.
+    const STABLE_LAYOUT: Option<&'static TypeLayout> = None;
 }
 
 impl<T> GetUTID<T> for TD_CanDowncast
@@ -87,3 +136,12 @@ where
 impl<T> GetUTID<T> for TD_Opaque {
     const UID: extern "C" fn() -> MaybeCmp<UTypeId> = no_utypeid;
 }
+
+impl<T> GetUTID<T> for TD_StableDowncast
+where
+    T: StableAbi + 'static,
+{
+    const UID: extern "C" fn() -> MaybeCmp<UTypeId> = some_utypeid::<T>;
+
+    const STABLE_LAYOUT: Option<&'static TypeLayout> = Some(T::LAYOUT);
+}
@@ -0,0 +1,121 @@
+use abi_stable::{
+    abi_stability::{abi_checking::check_layout_compatibility, AbiInstabilityReport},
+    std_types::{RBoxError, RNone, RSome, RString, RVec},
+    StableAbi,
+};
+
+mod before {
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Outer {
+        pub inner: Inner,
+    }
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Inner {
+        pub x: u32,
+        pub y: u32,
+    }
+}
+
+mod after {
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Outer {
+        pub inner: Inner,
+    }
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Inner {
+        pub x: u32,
+        pub y: u64,
+    }
+}
+
+fn make_report() -> AbiInstabilityReport {
+    check_layout_compatibility(
+        <before::Outer as StableAbi>::LAYOUT,
+        <after::Outer as StableAbi>::LAYOUT,
+    )
+    .unwrap_err()
+    .to_report()
+}
+
+fn field_path(list: &[&str]) -> RVec<RString> {
+    list.iter().map(|x| RString::from(*x)).collect()
+}
+
+#[test]
+fn report_contents() {
+    let report = make_report();
+
+    assert!(report
+        .interface
+        .ends_with("instability_reports::before::Outer"));
+    assert!(report
+        .implementation
+        .ends_with("instability_reports::after::Outer"));
+    assert_eq!(report.errors.len(), 3);
+
+    let root = &report.errors[0];
+    assert_eq!(root.type_path, report.implementation);
+    assert_eq!(root.field_path, field_path(&[]));
+
+    let inner = &report.errors[1];
+    assert!(inner
+        .type_path
+        .ends_with("instability_reports::after::Inner"));
+    assert_eq!(inner.field_path, field_path(&["inner"]));
+    {
+        let size = &inner.instabilities[0];
+        assert_eq!(size.kind, "Size");
+        assert_eq!(size.expected, RSome(RString::from("8")));
+        assert_eq!(size.found, RSome(RString::from("16")));
+        assert_eq!(size.extra, RNone);
+    }
+
+    let field = &report.errors[2];
+    assert_eq!(field.field_path, field_path(&["inner", "y"]));
+    assert_eq!(field.instabilities.len(), 1);
+    assert_eq!(field.instabilities[0].kind, "Name");
+    assert_eq!(field.instabilities[0].expected, RSome(RString::from("u32")));
+    assert_eq!(field.instabilities[0].found, RSome(RString::from("u64")));
+}
+
+#[test]
+fn report_from_check_error() {
+    let interface = <before::Outer as StableAbi>::LAYOUT;
+    let implementation = <after::Outer as StableAbi>::LAYOUT;
+
+    let errors = check_layout_compatibility(interface, implementation).unwrap_err();
+
+    let expected = errors.to_report();
+
+    let from_boxed = AbiInstabilityReport::from_check_error(
+        interface,
+        implementation,
+        &RBoxError::new(errors.clone()),
+    );
+    assert_eq!(from_boxed, expected);
+
+    // Errors that can't be downcasted are checked once more.
+    let from_formatted = AbiInstabilityReport::from_check_error(
+        interface,
+        implementation,
+        &RBoxError::new(errors).to_formatted_error(),
+    );
+    assert_eq!(from_formatted.errors, expected.errors);
+    assert_eq!(from_formatted.message, expected.message);
+}
+
+#[test]
+fn report_serde_roundtrip() {
+    let report = make_report();
+
+    let json = serde_json::to_string(&report).unwrap();
+    let deserialized: AbiInstabilityReport = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(report, deserialized);
+}
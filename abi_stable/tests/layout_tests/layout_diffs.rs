@@ -0,0 +1,308 @@
+use abi_stable::{
+    abi_stability::{
+        abi_checking::{check_layout_compatibility_with_globals, CheckingGlobals},
+        layout_diff::{ChangeKind, Compatibility, LayoutChange, LayoutDiff},
+    },
+    nonexhaustive_enum::examples::{command_a, command_b},
+    std_types::{RString, RVec},
+    type_layout::TypeLayout,
+    StableAbi,
+};
+
+mod v1 {
+    use abi_stable::std_types::{RString, RVec};
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Point {
+        pub x: u32,
+        pub y: u32,
+    }
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Bytes {
+        pub data: RVec<u8>,
+    }
+
+    #[repr(u8)]
+    #[derive(abi_stable::StableAbi)]
+    pub enum Shape {
+        Circle { radius: f32 },
+        Named(RString),
+    }
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    #[sabi(kind(Prefix))]
+    pub struct Module {
+        #[sabi(last_prefix_field)]
+        pub name: extern "C" fn() -> RString,
+        pub area: extern "C" fn(&Shape) -> f32,
+    }
+}
+
+mod v2 {
+    use abi_stable::std_types::{RString, RVec};
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Point {
+        pub x: u32,
+        pub z: u32,
+        pub w: u32,
+    }
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Bytes {
+        pub data: RVec<u16>,
+    }
+
+    #[repr(u8)]
+    #[derive(abi_stable::StableAbi)]
+    pub enum Shape {
+        Circle { radius: f32 },
+        Named(RString),
+        Square { side: f32 },
+    }
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    #[sabi(kind(Prefix))]
+    pub struct Module {
+        #[sabi(last_prefix_field)]
+        pub name: extern "C" fn() -> RString,
+        pub area: extern "C" fn(&super::v1::Shape) -> f32,
+        pub perimeter: extern "C" fn(&super::v1::Shape) -> f32,
+    }
+}
+
+mod v3 {
+    use abi_stable::std_types::RString;
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    #[sabi(kind(Prefix))]
+    pub struct Module {
+        #[sabi(last_prefix_field)]
+        pub name: extern "C" fn() -> RString,
+        pub area: extern "C" fn(&super::v2::Shape) -> f64,
+    }
+}
+
+mod v4 {
+    use abi_stable::std_types::RString;
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    #[sabi(kind(Prefix))]
+    pub struct Module {
+        #[sabi(last_prefix_field)]
+        pub name: extern "C" fn() -> RString,
+        #[sabi(accessible_if = false)]
+        pub area: extern "C" fn(&super::v1::Shape) -> f32,
+    }
+}
+
+mod v5 {
+    use abi_stable::std_types::RString;
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    #[sabi(kind(Prefix))]
+    pub struct Module {
+        #[sabi(last_prefix_field)]
+        pub name: extern "C" fn() -> RString,
+        #[sabi(accessible_if = false)]
+        pub area: extern "C" fn(&super::v1::Shape) -> f64,
+    }
+}
+
+fn diff(interface: &'static TypeLayout, implementation: &'static TypeLayout) -> LayoutDiff {
+    LayoutDiff::new(interface, implementation)
+}
+
+fn find(diff: &LayoutDiff, pred: impl Fn(&ChangeKind) -> bool) -> &LayoutChange {
+    diff.changes
+        .iter()
+        .find(|x| pred(&x.kind))
+        .unwrap_or_else(|| panic!("change not found in:\n{}", diff))
+}
+
+#[test]
+fn identical_layouts() {
+    for layout in [
+        <u8>::LAYOUT,
+        <RVec<RString>>::LAYOUT,
+        <v1::Shape>::LAYOUT,
+        <v1::Module_Ref>::LAYOUT,
+        <command_a::Foo_NE>::LAYOUT,
+    ] {
+        let diff = diff(layout, layout);
+        assert!(diff.is_empty(), "{}", diff);
+        assert!(diff.is_compatible());
+    }
+}
+
+#[test]
+fn struct_fields() {
+    let diff = diff(<v1::Point>::LAYOUT, <v2::Point>::LAYOUT);
+    assert!(!diff.is_compatible());
+
+    let renamed = find(&diff, |x| matches!(x, ChangeKind::FieldRenamed { .. }));
+    assert_eq!(renamed.path, "Point");
+    assert_eq!(renamed.compatibility, Compatibility::Breaking);
+    assert_eq!(
+        renamed.to_string(),
+        "field `Point::y` renamed to `z` (breaking)"
+    );
+
+    let added = find(&diff, |x| matches!(x, ChangeKind::FieldAdded { .. }));
+    assert_eq!(added.compatibility, Compatibility::Breaking);
+    assert_eq!(
+        added.to_string(),
+        "field `Point::w` of type `u32` added (breaking)"
+    );
+
+    let size = find(&diff, |x| matches!(x, ChangeKind::SizeChanged { .. }));
+    assert_eq!(size.kind, ChangeKind::SizeChanged { from: 8, to: 12 });
+
+    // The reverse direction removes a field.
+    let diff = self::diff(<v2::Point>::LAYOUT, <v1::Point>::LAYOUT);
+    let removed = find(&diff, |x| matches!(x, ChangeKind::FieldRemoved { .. }));
+    assert_eq!(removed.compatibility, Compatibility::Breaking);
+}
+
+#[test]
+fn field_type_changed() {
+    let diff = diff(<v1::Bytes>::LAYOUT, <v2::Bytes>::LAYOUT);
+
+    assert!(!diff.is_compatible());
+
+    // `RVec` doesn't store its type parameter in its layout,
+    // so the change is found in its fields.
+    let change = &diff.changes[0];
+    assert_eq!(change.path, "RVec::buffer");
+    assert_eq!(change.field_path, vec!["Bytes::data".to_string()]);
+    assert_eq!(
+        change.to_string(),
+        "type of `RVec::buffer` changed from `NonNull<u8>` to `NonNull<u16>` (breaking)",
+    );
+    assert!(diff.to_string().contains("    inside: Bytes::data\n"));
+}
+
+#[test]
+fn enum_variants() {
+    let diff = diff(<v1::Shape>::LAYOUT, <v2::Shape>::LAYOUT);
+
+    let added = find(&diff, |x| matches!(x, ChangeKind::VariantAdded { .. }));
+    assert_eq!(
+        added.to_string(),
+        "variant `Shape::Square` added to exhaustive enum (breaking)",
+    );
+    assert!(!diff.is_compatible());
+
+    let diff = self::diff(<v2::Shape>::LAYOUT, <v1::Shape>::LAYOUT);
+    let removed = find(&diff, |x| matches!(x, ChangeKind::VariantRemoved { .. }));
+    assert_eq!(removed.compatibility, Compatibility::Breaking);
+}
+
+#[test]
+fn nonexhaustive_enum_variants() {
+    let diff = diff(<command_a::Foo_NE>::LAYOUT, <command_b::Foo_NE>::LAYOUT);
+
+    let added = find(&diff, |x| matches!(x, ChangeKind::VariantAdded { .. }));
+    assert_eq!(added.compatibility, Compatibility::Compatible);
+    assert!(added
+        .to_string()
+        .contains("added to nonexhaustive enum (compatible)"));
+    assert!(diff.is_compatible(), "{}", diff);
+
+    let diff = self::diff(<command_b::Foo_NE>::LAYOUT, <command_a::Foo_NE>::LAYOUT);
+    let removed = find(&diff, |x| matches!(x, ChangeKind::VariantRemoved { .. }));
+    assert_eq!(removed.compatibility, Compatibility::Breaking);
+    assert!(!diff.is_compatible());
+}
+
+#[test]
+fn prefix_type_fields() {
+    let diff = diff(<v1::Module_Ref>::LAYOUT, <v2::Module_Ref>::LAYOUT);
+
+    assert!(diff.is_compatible(), "{}", diff);
+    let added = find(&diff, |x| matches!(x, ChangeKind::PrefixFieldAdded { .. }));
+    assert_eq!(added.path, "Module");
+    assert_eq!(added.compatibility, Compatibility::Compatible);
+
+    // Removing fields from a prefix type isn't compatible.
+    let diff = self::diff(<v2::Module_Ref>::LAYOUT, <v1::Module_Ref>::LAYOUT);
+    let removed = find(&diff, |x| matches!(x, ChangeKind::FieldRemoved { .. }));
+    assert_eq!(removed.compatibility, Compatibility::Breaking);
+}
+
+#[test]
+fn function_signatures() {
+    let diff = diff(<v1::Module_Ref>::LAYOUT, <v3::Module_Ref>::LAYOUT);
+
+    let changed = find(&diff, |x| matches!(x, ChangeKind::FunctionChanged { .. }));
+    assert_eq!(changed.path, "Module");
+    assert_eq!(changed.compatibility, Compatibility::Breaking);
+    let message = changed.to_string();
+    assert!(
+        message.starts_with("signature of `Module::area` changed"),
+        "{}",
+        message
+    );
+    assert!(message.contains("-> f32` to `"), "{}", message);
+    assert!(message.ends_with("-> f64` (breaking)"), "{}", message);
+    assert!(!diff.is_compatible());
+}
+
+#[test]
+fn inaccessible_fields() {
+    // The layout checker doesn't check the type of fields that can't be accessed.
+    let diff = diff(<v4::Module_Ref>::LAYOUT, <v5::Module_Ref>::LAYOUT);
+
+    let changed = find(&diff, |x| matches!(x, ChangeKind::FunctionChanged { .. }));
+    assert_eq!(changed.compatibility, Compatibility::Compatible);
+    assert!(diff.is_compatible(), "{}", diff);
+}
+
+#[test]
+fn agrees_with_the_layout_checker() {
+    let layouts = [
+        <v1::Point>::LAYOUT,
+        <v2::Point>::LAYOUT,
+        <v1::Bytes>::LAYOUT,
+        <v2::Bytes>::LAYOUT,
+        <v1::Shape>::LAYOUT,
+        <v2::Shape>::LAYOUT,
+        <v1::Module_Ref>::LAYOUT,
+        <v2::Module_Ref>::LAYOUT,
+        <v3::Module_Ref>::LAYOUT,
+        <v4::Module_Ref>::LAYOUT,
+        <v5::Module_Ref>::LAYOUT,
+        <command_a::Foo_NE>::LAYOUT,
+        <command_b::Foo_NE>::LAYOUT,
+    ];
+
+    for interface in layouts {
+        for implementation in layouts {
+            let diff = diff(interface, implementation);
+            // Using separate globals,like `LayoutDiff` does,
+            // so that the checks don't depend on each other.
+            let globals = CheckingGlobals::new();
+            let checked =
+                check_layout_compatibility_with_globals(interface, implementation, &globals);
+            assert_eq!(diff.is_compatible(), checked.is_ok(), "{}", diff);
+        }
+    }
+}
+
+#[test]
+fn serialization() {
+    let diff = diff(<v1::Point>::LAYOUT, <v2::Point>::LAYOUT);
+    let json = serde_json::to_string(&diff).unwrap();
+    let deserialized: LayoutDiff = serde_json::from_str(&json).unwrap();
+    assert_eq!(diff, deserialized);
+}
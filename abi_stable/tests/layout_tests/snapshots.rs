@@ -0,0 +1,313 @@
+use abi_stable::{
+    abi_stability::abi_checking::check_layout_compatibility,
+    nonexhaustive_enum::examples::{command_a, command_b},
+    std_types::{RArc, RBox, RDuration, ROption, RResult, RStr, RString, RVec},
+    type_layout::{
+        snapshot::{LayoutSnapshot, SnapshotError},
+        TypeLayout,
+    },
+    StableAbi,
+};
+
+mod v1 {
+    use abi_stable::{
+        std_types::{RString, RVec},
+        tag,
+    };
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Point {
+        pub x: u32,
+        pub y: u32,
+    }
+
+    #[repr(u8)]
+    #[derive(abi_stable::StableAbi)]
+    pub enum Shape {
+        Circle { radius: f32 } = 3,
+        Polygon(RVec<Point>),
+        Named(RString) = 10,
+    }
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    #[sabi(kind(Prefix))]
+    pub struct Module {
+        pub name: extern "C" fn() -> RString,
+        #[sabi(last_prefix_field)]
+        pub area: extern "C" fn(&Shape, u32) -> f32,
+        pub shapes: extern "C" fn(&mut RVec<Shape>),
+    }
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    #[sabi(tag = tag!{{ "color" => "red", "sizes" => tag![[ 1, 2, 3 ]], "traits" => tag!{{ "Copy", "Eq" }} }})]
+    pub struct Tagged {
+        pub value: u8,
+    }
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct WithConst<const N: usize> {
+        pub array: [u16; N],
+    }
+}
+
+mod v2 {
+    use abi_stable::std_types::{RString, RVec};
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Point {
+        pub x: u32,
+        pub y: u64,
+    }
+
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    #[sabi(kind(Prefix))]
+    pub struct Module {
+        pub name: extern "C" fn() -> RString,
+        #[sabi(last_prefix_field)]
+        pub area: extern "C" fn(&super::v1::Shape, u32) -> f32,
+        pub shapes: extern "C" fn(&mut RVec<super::v1::Shape>),
+        pub clear: extern "C" fn(),
+    }
+}
+
+#[abi_stable::sabi_trait]
+pub trait Describe {
+    fn describe(&self) -> RString;
+
+    fn rename(&mut self, name: RStr<'_>) -> ROption<RString>;
+}
+
+fn roundtrip(layout: &'static TypeLayout) -> LayoutSnapshot {
+    let snapshot = LayoutSnapshot::new(layout);
+    let json = snapshot.to_json();
+    let deserialized = LayoutSnapshot::from_json(&json).unwrap();
+    assert_eq!(snapshot, deserialized);
+    deserialized
+}
+
+fn has_extra_checks(snapshot: &LayoutSnapshot) -> bool {
+    snapshot.to_json().contains("\"extra_checks\": \"")
+}
+
+/// Checks compatibility with the snapshot,
+/// treating the skipped extra checks as a warning.
+fn check_snapshot(
+    snapshot: &LayoutSnapshot,
+    implementation: &'static TypeLayout,
+) -> Result<(), SnapshotError> {
+    match snapshot.check_compatibility(implementation) {
+        Err(SnapshotError::UncheckedExtraChecks { type_names }) => {
+            assert!(has_extra_checks(snapshot));
+            assert!(!type_names.is_empty());
+            Ok(())
+        }
+        Ok(()) => {
+            assert!(!has_extra_checks(snapshot));
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn layouts() -> Vec<&'static TypeLayout> {
+    vec![
+        <u8>::LAYOUT,
+        <()>::LAYOUT,
+        <&'static u32>::LAYOUT,
+        <RVec<u8>>::LAYOUT,
+        <RString>::LAYOUT,
+        <RBox<RArc<u64>>>::LAYOUT,
+        <RDuration>::LAYOUT,
+        <RResult<u32, RString>>::LAYOUT,
+        <ROption<[v1::Point; 4]>>::LAYOUT,
+        <v1::Shape>::LAYOUT,
+        <v1::Module_Ref>::LAYOUT,
+        <v2::Module_Ref>::LAYOUT,
+        <v1::Tagged>::LAYOUT,
+        <v1::WithConst<3>>::LAYOUT,
+        <command_a::Foo_NE>::LAYOUT,
+        <command_b::Foo_NE>::LAYOUT,
+        <Describe_TO<'static, RBox<()>>>::LAYOUT,
+    ]
+}
+
+#[test]
+fn snapshots_are_compatible_with_themselves() {
+    for layout in layouts() {
+        let snapshot = roundtrip(layout);
+        assert_eq!(snapshot.name(), layout.name());
+
+        if let Err(e) = check_snapshot(&snapshot, layout) {
+            panic!("{}\n{}", layout.full_type(), e);
+        }
+    }
+}
+
+#[test]
+fn reconstructed_layouts() {
+    for layout in layouts() {
+        let snapshot = roundtrip(layout);
+
+        // Layouts with extra checks can't be reconstructed,
+        // since they'd be incompatible with the layouts of the snapshotted types.
+        if has_extra_checks(&snapshot) {
+            assert!(matches!(
+                snapshot.to_layout().unwrap_err(),
+                SnapshotError::UnsupportedExtraChecks { .. },
+            ));
+            continue;
+        }
+
+        let reconstructed = snapshot.to_layout().unwrap();
+
+        assert_eq!(
+            reconstructed.full_type().to_string(),
+            layout.full_type().to_string(),
+        );
+        assert_eq!(reconstructed.size(), layout.size());
+        assert_eq!(reconstructed.alignment(), layout.alignment());
+        assert_eq!(reconstructed.tag(), layout.tag());
+        assert_ne!(reconstructed.get_utypeid(), layout.get_utypeid());
+
+        // Taking a snapshot of the reconstructed layout is lossless.
+        assert_eq!(LayoutSnapshot::new(reconstructed), snapshot);
+
+        // Reconstructing the same snapshot again reuses the layout.
+        assert!(std::ptr::eq(snapshot.to_layout().unwrap(), reconstructed));
+    }
+}
+
+#[test]
+fn same_results_as_live_layouts() {
+    let pairs: Vec<(&'static TypeLayout, &'static TypeLayout)> = vec![
+        (<v1::Point>::LAYOUT, <v2::Point>::LAYOUT),
+        (<v1::Module_Ref>::LAYOUT, <v2::Module_Ref>::LAYOUT),
+        (<v2::Module_Ref>::LAYOUT, <v1::Module_Ref>::LAYOUT),
+        (<v1::WithConst<3>>::LAYOUT, <v1::WithConst<4>>::LAYOUT),
+        (<command_a::Foo_NE>::LAYOUT, <command_b::Foo_NE>::LAYOUT),
+        (<command_b::Foo_NE>::LAYOUT, <command_a::Foo_NE>::LAYOUT),
+        (<RVec<u8>>::LAYOUT, <RString>::LAYOUT),
+        (<RVec<u8>>::LAYOUT, <RVec<i8>>::LAYOUT),
+        (<v1::Tagged>::LAYOUT, <u8>::LAYOUT),
+    ];
+
+    for (interface, implementation) in pairs {
+        let live = check_layout_compatibility(interface, implementation);
+        let snapshot = check_snapshot(&roundtrip(interface), implementation);
+
+        assert_eq!(
+            live.is_ok(),
+            snapshot.is_ok(),
+            "\ninterface:{}\nimplementation:{}\n",
+            interface.full_type(),
+            implementation.full_type(),
+        );
+
+        if let Err(SnapshotError::Incompatible(e)) = &snapshot {
+            assert!(!e.errors.is_empty());
+        } else if let Err(e) = snapshot {
+            panic!("unexpected error: {}", e);
+        }
+    }
+}
+
+#[test]
+fn checking_releases_reconstructed_layouts() {
+    let snapshot = roundtrip(<Describe_TO<'static, RBox<()>>>::LAYOUT);
+
+    // More `TypeLayout`s are reconstructed in total than can exist at the same time.
+    for _ in 0..1000 {
+        check_snapshot(&snapshot, <Describe_TO<'static, RBox<()>>>::LAYOUT).unwrap();
+        snapshot
+            .check_compatibility(<RVec<u8>>::LAYOUT)
+            .unwrap_err();
+    }
+}
+
+#[test]
+fn unchecked_extra_checks() {
+    let snapshot = roundtrip(<command_a::Foo_NE>::LAYOUT);
+    assert!(has_extra_checks(&snapshot));
+
+    match snapshot.check_compatibility(<command_a::Foo_NE>::LAYOUT) {
+        Err(SnapshotError::UncheckedExtraChecks { type_names }) => {
+            assert!(
+                type_names.iter().any(|x| x == "NonExhaustive"),
+                "{:?}",
+                type_names
+            );
+        }
+        x => panic!("expected UncheckedExtraChecks, found {:?}", x),
+    }
+
+    // Incompatibilities take priority over the skipped extra checks.
+    assert!(matches!(
+        snapshot.check_compatibility(<u8>::LAYOUT),
+        Err(SnapshotError::Incompatible(_)),
+    ));
+
+    assert_eq!(
+        roundtrip(<RVec<u8>>::LAYOUT).check_compatibility(<RVec<u8>>::LAYOUT),
+        Ok(()),
+    );
+}
+
+#[test]
+fn reconstructed_layout_capacity() {
+    let snapshot = roundtrip(<RVec<u8>>::LAYOUT);
+    let mut json: serde_json::Value = serde_json::from_str(&snapshot.to_json()).unwrap();
+    let first = json["layouts"][0].clone();
+    let layouts = json["layouts"].as_array_mut().unwrap();
+    while layouts.len() <= LayoutSnapshot::RECONSTRUCTED_LAYOUT_CAPACITY {
+        layouts.push(first.clone());
+    }
+    let snapshot: LayoutSnapshot = serde_json::from_value(json).unwrap();
+
+    assert_eq!(
+        snapshot.check_compatibility(<RVec<u8>>::LAYOUT),
+        Err(SnapshotError::TooManyLayouts {
+            capacity: LayoutSnapshot::RECONSTRUCTED_LAYOUT_CAPACITY,
+        }),
+    );
+}
+
+#[test]
+fn malformed_snapshots() {
+    let json = LayoutSnapshot::of::<RVec<u8>>().to_json();
+
+    {
+        let json = json.replacen("\"format_version\": 1", "\"format_version\": 1000", 1);
+        let snapshot = LayoutSnapshot::from_json(&json).unwrap();
+        assert_eq!(
+            snapshot.to_layout().unwrap_err(),
+            SnapshotError::UnsupportedFormatVersion {
+                expected: 1,
+                found: 1000,
+            },
+        );
+    }
+    {
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["layouts"][0]["type_layouts"][0] = 1000.into();
+        let snapshot: LayoutSnapshot = serde_json::from_value(value).unwrap();
+        assert!(matches!(
+            snapshot.to_layout().unwrap_err(),
+            SnapshotError::Malformed { .. },
+        ));
+    }
+    {
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["layouts"] = serde_json::Value::Array(vec![]);
+        let snapshot: LayoutSnapshot = serde_json::from_value(value).unwrap();
+        assert!(matches!(
+            snapshot.to_layout().unwrap_err(),
+            SnapshotError::Malformed { .. },
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+use abi_stable::{
+    std_types::{RArc, RBox, ROption, RString, RVec},
+    type_layout::StableTypeId,
+    StableAbi,
+};
+
+mod v1 {
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Point {
+        pub x: u32,
+        pub y: u32,
+    }
+
+    #[repr(transparent)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Wrapper<T>(pub T);
+}
+
+mod v2 {
+    #[repr(C)]
+    #[derive(abi_stable::StableAbi)]
+    pub struct Point {
+        pub x: u32,
+        pub y: u32,
+    }
+}
+
+fn id_of<T: StableAbi>() -> StableTypeId {
+    StableTypeId::from_layout(T::LAYOUT)
+}
+
+#[test]
+fn same_type_same_id() {
+    assert_eq!(id_of::<RString>(), StableTypeId::new::<RString>());
+    assert_eq!(id_of::<RVec<u8>>(), id_of::<RVec<u8>>());
+    assert_eq!(id_of::<RBox<RString>>(), id_of::<RBox<RString>>());
+    assert_eq!(id_of::<v1::Point>(), id_of::<v1::Point>());
+}
+
+#[test]
+fn different_types_different_ids() {
+    let list = vec![
+        id_of::<RString>(),
+        id_of::<RVec<u8>>(),
+        id_of::<RVec<u16>>(),
+        id_of::<RArc<u8>>(),
+        id_of::<ROption<u8>>(),
+        id_of::<ROption<u16>>(),
+        id_of::<v1::Point>(),
+        id_of::<v2::Point>(),
+        id_of::<v1::Wrapper<u32>>(),
+        id_of::<v1::Wrapper<i32>>(),
+        id_of::<u32>(),
+        id_of::<i32>(),
+    ];
+
+    for (i, l) in list.iter().enumerate() {
+        for (j, r) in list.iter().enumerate() {
+            assert_eq!(i == j, l == r, "\nleft: {}\nright: {}", l, r);
+        }
+    }
+}
+
+#[test]
+fn id_parts() {
+    let id = id_of::<v1::Point>();
+    assert_eq!(id.package(), "abi_stable");
+    assert_eq!(id.full_type(), "Point");
+    assert!(id.mod_path().ends_with("v1"), "{}", id.mod_path());
+    assert!(!id.compatible_version().is_empty());
+
+    let other = id_of::<v2::Point>();
+    assert_eq!(id.layout_hash(), other.layout_hash());
+    assert_ne!(id.layout_hash(), id_of::<RString>().layout_hash());
+
+    let displayed = id.to_string();
+    assert!(displayed.contains("Point"), "{}", displayed);
+    assert!(displayed.contains("abi_stable"), "{}", displayed);
+
+    // type arguments that only show up in fields are distinguished by the layout hash
+    assert_ne!(
+        id_of::<v1::Wrapper<u32>>().layout_hash(),
+        id_of::<v1::Wrapper<i32>>().layout_hash(),
+    );
+}
@@ -42,4 +42,16 @@ mod layout_tests {
 
     #[cfg(all(test, not(feature = "only_new_tests")))]
     mod shared_types;
+
+    #[cfg(all(test, not(feature = "only_new_tests")))]
+    mod instability_reports;
+
+    #[cfg(all(test, not(feature = "only_new_tests")))]
+    mod snapshots;
+
+    #[cfg(all(test, not(feature = "only_new_tests")))]
+    mod layout_diffs;
+
+    #[cfg(all(test, not(feature = "only_new_tests")))]
+    mod stable_type_ids;
 }
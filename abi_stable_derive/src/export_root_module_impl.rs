@@ -1,26 +1,45 @@
-//! The implementation of the `#[export_root_module]` attribute.
+//! The implementation of the `#[export_root_module]` attribute,
+//! and of the `export_root_modules` macro.
 
 use super::*;
 
 use as_derive_utils::return_spanned_err;
 
-use syn::Ident;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Ident, LitStr, Token,
+};
 
 use proc_macro2::Span;
 
-use abi_stable_shared::mangled_root_module_loader_name;
+use abi_stable_shared::{mangled_root_module_index_name, mangled_root_module_loader_name};
+
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[doc(hidden)]
-pub fn export_root_module_attr(_attr: TokenStream1, item: TokenStream1) -> TokenStream1 {
-    parse_or_compile_err(item, export_root_module_inner).into()
+pub fn export_root_module_attr(attr: TokenStream1, item: TokenStream1) -> TokenStream1 {
+    syn::parse::<LibMetadataArgs>(attr)
+        .and_then(|metadata| export_root_module_inner(syn::parse(item)?, metadata))
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
 
 #[cfg(test)]
 fn export_root_module_str(item: &str) -> Result<TokenStream2, syn::Error> {
-    syn::parse_str(item).and_then(export_root_module_inner)
+    export_root_module_with_args_str("", item)
+}
+
+#[cfg(test)]
+fn export_root_module_with_args_str(attr: &str, item: &str) -> Result<TokenStream2, syn::Error> {
+    let metadata = syn::parse_str::<LibMetadataArgs>(attr)?;
+    syn::parse_str(item).and_then(|item| export_root_module_inner(item, metadata))
 }
 
-fn export_root_module_inner(mut input: ItemFn) -> Result<TokenStream2, syn::Error> {
+fn export_root_module_inner(
+    mut input: ItemFn,
+    metadata: LibMetadataArgs,
+) -> Result<TokenStream2, syn::Error> {
     let vis = &input.vis;
 
     let unsafe_no_layout_constant_path =
@@ -52,6 +71,8 @@ fn export_root_module_inner(mut input: ItemFn) -> Result<TokenStream2, syn::Erro
 
     let export_name = Ident::new(&mangled_root_module_loader_name(), Span::call_site());
 
+    let metadata = metadata.tokenize();
+
     Ok(quote!(
         #input
 
@@ -68,11 +89,308 @@ fn export_root_module_inner(mut input: ItemFn) -> Result<TokenStream2, syn::Erro
                     _sabi_erased_module,
                     ::abi_stable::library::CheckTypeLayout::#check_ty_layout_variant,
                 )
+            }.with_metadata(#metadata)
+        };
+    ))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// The metadata of a library,
+/// passed as arguments to the `#[export_root_module]` attribute,
+/// or in the `#[metadata(...)]` attribute of the `export_root_modules` macro.
+#[derive(Default)]
+struct LibMetadataArgs {
+    description: Option<LitStr>,
+    authors: Option<LitStr>,
+    license: Option<LitStr>,
+    features: Vec<LitStr>,
+    extra: Vec<(LitStr, LitStr)>,
+}
+
+impl Parse for LibMetadataArgs {
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let mut this = Self::default();
+
+        while !input.is_empty() {
+            let ident = input.parse::<Ident>()?;
+            let ident_str = ident.to_string();
+            match &*ident_str {
+                "description" | "authors" | "license" => {
+                    input.parse::<Token![=]>()?;
+                    let value = input.parse::<LitStr>()?;
+                    let field = match &*ident_str {
+                        "description" => &mut this.description,
+                        "authors" => &mut this.authors,
+                        _ => &mut this.license,
+                    };
+                    if field.replace(value).is_some() {
+                        return_spanned_err!(ident, "This argument was passed multiple times")
+                    }
+                }
+                "features" => {
+                    input.parse::<Token![=]>()?;
+                    let content;
+                    syn::bracketed!(content in input);
+                    let features = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+                    this.features.extend(features);
+                }
+                "extra" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    while !content.is_empty() {
+                        let key = content.parse::<LitStr>()?;
+                        content.parse::<Token![=]>()?;
+                        let value = content.parse::<LitStr>()?;
+                        if this.extra.iter().any(|(k, _)| k.value() == key.value()) {
+                            return_spanned_err!(key, "This key is used multiple times")
+                        }
+                        this.extra.push((key, value));
+                        if !content.is_empty() {
+                            content.parse::<Token![,]>()?;
+                        }
+                    }
+                }
+                _ => return_spanned_err!(
+                    ident,
+                    "Expected one of: `description`, `authors`, `license`, `features`, `extra`"
+                ),
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(this)
+    }
+}
+
+impl LibMetadataArgs {
+    /// Outputs an expression that evaluates to a `LibMetadata_Ref`.
+    fn tokenize(&self) -> TokenStream2 {
+        let from_env_or = |value: &Option<LitStr>, env_var: &str| match value {
+            Some(value) => quote!(#value),
+            None => quote!(match ::std::option_env!(#env_var) {
+                ::std::option::Option::Some(x) => x,
+                ::std::option::Option::None => "",
+            }),
+        };
+        let description = from_env_or(&self.description, "CARGO_PKG_DESCRIPTION");
+        let authors = from_env_or(&self.authors, "CARGO_PKG_AUTHORS");
+        let license = from_env_or(&self.license, "CARGO_PKG_LICENSE");
+        let build_timestamp = build_timestamp();
+        let features = &self.features;
+        let extra_keys = self.extra.iter().map(|(k, _)| k);
+        let extra_values = self.extra.iter().map(|(_, v)| v);
+
+        quote!({
+            use ::abi_stable::{
+                library::{LibMetadata, LibMetadata_Ref},
+                prefix_type::WithMetadata,
+                std_types::{ROption, RSlice, RStr, Tuple2},
+            };
+
+            const _SABI_FEATURE_FLAGS: &[(&str, bool)] = &[
+                #( (#features, cfg!(feature = #features)), )*
+            ];
+            const _SABI_FEATURES: &[
+                RStr<'static>;
+                ::abi_stable::library::__count_enabled_features(_SABI_FEATURE_FLAGS)
+            ] = &::abi_stable::library::__enabled_features(_SABI_FEATURE_FLAGS);
+
+            const _SABI_EXTRA: &[Tuple2<RStr<'static>, RStr<'static>>] = &[
+                #( Tuple2(RStr::from_str(#extra_keys), RStr::from_str(#extra_values)), )*
+            ];
+
+            const _SABI_METADATA: &WithMetadata<LibMetadata> =
+                &WithMetadata::new(LibMetadata {
+                    description: RStr::from_str(#description),
+                    authors: RStr::from_str(#authors),
+                    license: RStr::from_str(#license),
+                    build_timestamp: ROption::RSome(#build_timestamp),
+                    features: RSlice::from_slice(_SABI_FEATURES),
+                    extra: RSlice::from_slice(_SABI_EXTRA),
+                    ..LibMetadata::new()
+                });
+
+            LibMetadata_Ref(_SABI_METADATA.static_as_prefix())
+        })
+    }
+}
+
+/// The time that the library is compiled at,
+/// using the `SOURCE_DATE_EPOCH` environment variable if it's set,
+/// for reproducible builds.
+fn build_timestamp() -> u64 {
+    std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|x| x.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |x| x.as_secs())
+        })
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[doc(hidden)]
+pub fn export_root_modules_macro(input: TokenStream1) -> TokenStream1 {
+    parse_or_compile_err(input, export_root_modules_inner).into()
+}
+
+#[cfg(test)]
+fn export_root_modules_str(input: &str) -> Result<TokenStream2, syn::Error> {
+    syn::parse_str(input).and_then(export_root_modules_inner)
+}
+
+/// The root modules passed to the `export_root_modules` macro.
+struct RootModuleList {
+    modules: Punctuated<RootModuleEntry, Token![,]>,
+}
+
+/// A root module passed to the `export_root_modules` macro,
+/// with the syntax
+/// `#[unsafe_no_layout_constant]? #[metadata(...)]? ("key" =>)? path::to::function`.
+struct RootModuleEntry {
+    check_layout: bool,
+    metadata: LibMetadataArgs,
+    key: Option<LitStr>,
+    loader: syn::Path,
+}
+
+impl Parse for RootModuleList {
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        Ok(Self {
+            modules: Punctuated::parse_terminated(input)?,
+        })
+    }
+}
+
+impl Parse for RootModuleEntry {
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let unsafe_no_layout_constant_path =
+            syn::parse_str::<syn::Path>("unsafe_no_layout_constant").expect("BUG");
+
+        let metadata_path = syn::parse_str::<syn::Path>("metadata").expect("BUG");
+
+        let mut check_layout = true;
+        let mut metadata = LibMetadataArgs::default();
+        for attr in input.call(Attribute::parse_outer)? {
+            if attr.path == unsafe_no_layout_constant_path {
+                check_layout = false;
+            } else if attr.path == metadata_path {
+                metadata = attr.parse_args()?;
+            } else {
+                return_spanned_err!(
+                    attr,
+                    "Expected the `#[unsafe_no_layout_constant]` or `#[metadata(...)]` attributes"
+                )
+            }
+        }
+
+        let key = if input.peek(LitStr) {
+            let key = input.parse::<LitStr>()?;
+            input.parse::<Token![=>]>()?;
+            Some(key)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            check_layout,
+            metadata,
+            key,
+            loader: input.parse()?,
+        })
+    }
+}
+
+fn export_root_modules_inner(list: RootModuleList) -> Result<TokenStream2, syn::Error> {
+    if list.modules.is_empty() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "Expected at least one root module loader function",
+        ));
+    }
+
+    let mut keys = std::collections::HashSet::new();
+    for key in list.modules.iter().filter_map(|m| m.key.as_ref()) {
+        if !keys.insert(key.value()) {
+            return_spanned_err!(key, "This key is used for multiple root modules")
+        }
+    }
+
+    let export_name = Ident::new(&mangled_root_module_index_name(), Span::call_site());
+
+    let module_count = list.modules.len();
+
+    let mut loader_fns = Vec::with_capacity(module_count);
+    let mut headers = Vec::with_capacity(module_count);
+    let mut entries = Vec::with_capacity(module_count);
+    let mut resolved_keys = Vec::with_capacity(module_count);
+
+    for (i, module) in list.modules.iter().enumerate() {
+        let loader = &module.loader;
+        let erased_fn = Ident::new(&format!("_sabi_erased_module_{}", i), Span::call_site());
+        let header = Ident::new(&format!("_SABI_LIB_HEADER_{}", i), Span::call_site());
+        let check_ty_layout_variant = Ident::new(
+            if module.check_layout { "Yes" } else { "No" },
+            Span::call_site(),
+        );
+        let metadata = module.metadata.tokenize();
+        let key = match &module.key {
+            Some(key) => quote!(::std::option::Option::Some(#key)),
+            None => quote!(::std::option::Option::None),
+        };
+
+        loader_fns.push(quote!(
+            pub extern "C" fn #erased_fn()-> ::abi_stable::library::RootModuleResult {
+                ::abi_stable::library::__call_root_module_loader(#loader)
             }
+        ));
+        headers.push(quote!(
+            static #header: ::abi_stable::library::LibHeader = unsafe{
+                ::abi_stable::library::LibHeader::__from_loader_fn(
+                    #loader,
+                    #erased_fn,
+                    ::abi_stable::library::CheckTypeLayout::#check_ty_layout_variant,
+                )
+            }.with_metadata(#metadata);
+        ));
+        resolved_keys.push(quote!(
+            ::abi_stable::library::__root_module_key(#loader, #key)
+        ));
+        entries.push(quote!(
+            ::abi_stable::library::NamedRootModule::__new(#loader, #key, &#header)
+        ));
+    }
+
+    Ok(quote!(
+        #[no_mangle]
+        pub static #export_name: ::abi_stable::library::RootModuleIndex = {
+            #(#loader_fns)*
+
+            #(#headers)*
+
+            // Also catches modules exported with the same `RootModule::NAME`,
+            // which can't be compared before the constants are evaluated.
+            const _: () = ::abi_stable::library::__assert_unique_root_module_keys(
+                &[ #(#resolved_keys,)* ]
+            );
+
+            static _SABI_ROOT_MODULES:
+                [::abi_stable::library::NamedRootModule; #module_count] =
+                [ #(#entries,)* ];
+
+            ::abi_stable::library::RootModuleIndex::__new(&_SABI_ROOT_MODULES)
         };
     ))
 }
 
+////////////////////////////////////////////////////////////////////////////////
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,4 +448,105 @@ mod tests {
             assert!(str_out.contains(expected_const));
         }
     }
+
+    #[test]
+    fn test_root_modules_output() {
+        let normalize = |s: String| s.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+
+        let str_out = export_root_modules_str(
+            r##"
+                get_codec,
+                "storage" => storage::get_module,
+                #[unsafe_no_layout_constant]
+                #[metadata(description = "the metrics")]
+                "metrics" => get_metrics,
+            "##,
+        )
+        .unwrap()
+        .to_string()
+        .piped(normalize);
+
+        assert!(str_out.contains(&mangled_root_module_index_name()));
+        assert!(str_out.contains("NamedRootModule::__new(get_codec,::std::option::Option::None,"));
+        assert!(str_out.contains(
+            "NamedRootModule::__new(storage::get_module,::std::option::Option::Some(\"storage\"),"
+        ));
+        assert!(str_out.contains("[::abi_stable::library::NamedRootModule;3usize]"));
+        assert_eq!(str_out.matches("CheckTypeLayout::Yes").count(), 2);
+        assert_eq!(str_out.matches("CheckTypeLayout::No").count(), 1);
+        assert_eq!(str_out.matches(".with_metadata(").count(), 3);
+        assert!(str_out.contains("description:RStr::from_str(\"themetrics\")"));
+    }
+
+    #[test]
+    fn test_root_modules_errors() {
+        let list = vec![
+            "",
+            "#[hello] get_codec",
+            "#[metadata(hello = \"world\")] get_codec",
+            r#""codec" => get_codec, "codec" => get_other_codec"#,
+            r#""codec" get_codec"#,
+        ];
+
+        for input in list {
+            assert!(export_root_modules_str(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_metadata_output() {
+        let normalize = |s: String| s.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+
+        let str_out = export_root_module_with_args_str(
+            r##"
+                description = "does things",
+                features = ["foo", "bar"],
+                extra("homepage" = "example.com", "channel" = "beta"),
+            "##,
+            "pub fn hello()->RString{}",
+        )
+        .unwrap()
+        .to_string()
+        .piped(normalize);
+
+        assert!(str_out.contains("description:RStr::from_str(\"doesthings\")"));
+        assert!(str_out.contains("option_env!(\"CARGO_PKG_AUTHORS\")"));
+        assert!(str_out.contains("option_env!(\"CARGO_PKG_LICENSE\")"));
+        assert!(!str_out.contains("option_env!(\"CARGO_PKG_DESCRIPTION\")"));
+        assert!(
+            str_out.contains("(\"foo\",cfg!(feature=\"foo\")),(\"bar\",cfg!(feature=\"bar\")),")
+        );
+        assert!(str_out.contains(
+            "Tuple2(RStr::from_str(\"homepage\"),RStr::from_str(\"example.com\")),\
+             Tuple2(RStr::from_str(\"channel\"),RStr::from_str(\"beta\")),"
+        ));
+        assert!(str_out.contains("CheckTypeLayout::Yes"));
+
+        let default_out = export_root_module_str("pub fn hello()->RString{}")
+            .unwrap()
+            .to_string()
+            .piped(normalize);
+        assert!(default_out.contains("option_env!(\"CARGO_PKG_DESCRIPTION\")"));
+        assert!(default_out.contains("const_SABI_FEATURE_FLAGS:&[(&str,bool)]=&[];"));
+    }
+
+    #[test]
+    fn test_metadata_errors() {
+        let list = vec![
+            "hello = \"world\"",
+            "description = \"a\", description = \"b\"",
+            "description = 10",
+            "features = \"foo\"",
+            "extra(\"a\" = \"b\", \"a\" = \"c\")",
+            "license \"MIT\"",
+        ];
+
+        for attr in list {
+            assert!(
+                export_root_module_with_args_str(attr, "pub fn hello()->RString{}").is_err(),
+                "{}",
+                attr
+            );
+        }
+    }
 }
@@ -215,6 +215,14 @@ usable_traits! {
     io_buf_read=(IoBufRead,"::std::io::BufRead",false,UB::DYN_TRAIT),
     error=(Error,"::std::error::Error",false,UB::ROBJECT_AND_DYN_TRAIT),
     unpin=(Unpin,"::std::marker::Unpin",false,UB::ROBJECT_AND_DYN_TRAIT),
+    future=(Future,"::std::future::Future",false,UB::DYN_TRAIT),
+    as_ref_str=(AsRefStr,"::std::convert::AsRef<str>",false,UB::DYN_TRAIT),
+    as_ref_bytes=(AsRefBytes,"::std::convert::AsRef<[u8]>",false,UB::DYN_TRAIT),
+    borrow_str=(BorrowStr,"::std::borrow::Borrow<str>",false,UB::DYN_TRAIT),
+    deref=(Deref,"::std::ops::Deref",false,UB::DYN_TRAIT),
+    fn_once=(FnOnce,"::std::ops::FnOnce",false,UB::DYN_TRAIT),
+    fn_mut=(FnMut,"::std::ops::FnMut",false,UB::DYN_TRAIT),
+    fn_=(Fn,"::std::ops::Fn",false,UB::DYN_TRAIT),
 }
 
 pub(crate) fn private_associated_type() -> syn::Ident {
@@ -75,6 +75,16 @@ pub(crate) fn parse_impl_interfacetype(
                         impld_struct.display = true;
                         impld_struct.debug = true;
                     }
+                    WhichTrait::Future => {
+                        impld_struct.unpin = true;
+                    }
+                    WhichTrait::FnMut => {
+                        impld_struct.fn_once = true;
+                    }
+                    WhichTrait::Fn => {
+                        impld_struct.fn_once = true;
+                        impld_struct.fn_mut = true;
+                    }
                     _ => {}
                 }
             }
@@ -26,6 +26,24 @@ pub fn export_root_module(attr: TokenStream1, item: TokenStream1) -> TokenStream
     crate::export_root_module_impl::export_root_module_attr(attr, item)
 }
 
+#[proc_macro]
+pub fn export_root_modules(input: TokenStream1) -> TokenStream1 {
+    crate::export_root_module_impl::export_root_modules_macro(input)
+}
+
+/// This is used by tests to access the exported `RootModuleIndex` static,
+/// taking the path of the module that the static is declared in (`crate` by default).
+#[doc(hidden)]
+#[proc_macro]
+pub fn get_root_module_index_static(input: TokenStream1) -> TokenStream1 {
+    let module = module_path_or_crate(input);
+    let export_name = syn::Ident::new(
+        &abi_stable_shared::mangled_root_module_index_name(),
+        proc_macro2::Span::call_site(),
+    );
+    quote!( #module::#export_name ).into()
+}
+
 #[proc_macro_attribute]
 pub fn sabi_extern_fn(attr: TokenStream1, item: TokenStream1) -> TokenStream1 {
     crate::sabi_extern_fn_impl::sabi_extern_fn(attr, item)
@@ -52,26 +70,37 @@ pub fn derive_get_static_equivalent(input: TokenStream1) -> TokenStream1 {
 pub fn construct_abi_header(_: TokenStream1) -> TokenStream1 {
     let abi_major = env!("CARGO_PKG_VERSION_MAJOR").parse::<u32>().unwrap();
     let abi_minor = env!("CARGO_PKG_VERSION_MINOR").parse::<u32>().unwrap();
+    // The revision at the end of the magic string is bumped whenever the layout of
+    // `AbiHeader` or `LibHeader` changes without a new abi_stable abi version,
+    // so that libraries with the previous layout are rejected instead of misread.
+    //
+    // Revisions:
+    // - (none): the layout of abi_stable 0.11.0
+    // - r1: added `LibHeader.metadata`, `AbiHeader.target_info`,
+    //   and `RootModuleConsts.dependencies`
     quote!(
         pub const ABI_HEADER:AbiHeader=AbiHeader{
-            magic_string:*b"abi stable library for Rust     ",
+            magic_string:*b"abi stable library for Rust   r1",
             abi_major:#abi_major,
             abi_minor:#abi_minor,
+            target_info:TargetInfo::VALUE,
             _priv:(),
         };
     )
     .into()
 }
 
-/// This is used by testing/version_compatibility to access the exported static.
+/// This is used by testing/version_compatibility to access the exported static,
+/// taking the path of the module that the static is declared in (`crate` by default).
 #[doc(hidden)]
 #[proc_macro]
-pub fn get_root_module_static(_: TokenStream1) -> TokenStream1 {
+pub fn get_root_module_static(input: TokenStream1) -> TokenStream1 {
+    let module = module_path_or_crate(input);
     let export_name = syn::Ident::new(
         &abi_stable_shared::mangled_root_module_loader_name(),
         proc_macro2::Span::call_site(),
     );
-    quote!( crate::#export_name ).into()
+    quote!( #module::#export_name ).into()
 }
 
 #[doc(hidden)]
@@ -80,9 +109,14 @@ pub fn __const_mangled_root_module_loader_name(_: TokenStream1) -> TokenStream1
     let name = abi_stable_shared::mangled_root_module_loader_name();
     let name_nulled = format!("{}\0", name);
 
+    let index_name = abi_stable_shared::mangled_root_module_index_name();
+    let index_name_nulled = format!("{}\0", index_name);
+
     quote!(
         const PRIV_MANGLED_ROOT_MODULE_LOADER_NAME: &str = #name;
         const PRIV_MANGLED_ROOT_MODULE_LOADER_NAME_NUL: &str = #name_nulled;
+        const PRIV_MANGLED_ROOT_MODULE_INDEX_NAME: &str = #index_name;
+        const PRIV_MANGLED_ROOT_MODULE_INDEX_NAME_NUL: &str = #index_name_nulled;
     )
     .into()
 }
@@ -147,6 +181,15 @@ pub(crate) fn derive_sabi_trait_str(item: &str) -> Result<TokenStream2, syn::Err
 
 ////////////////////////////////////////////////////////////////////////////////
 
+fn module_path_or_crate(input: TokenStream1) -> TokenStream2 {
+    let module = TokenStream2::from(input);
+    if module.is_empty() {
+        quote!(crate)
+    } else {
+        module
+    }
+}
+
 fn parse_or_compile_err<P, F>(input: TokenStream1, f: F) -> TokenStream2
 where
     P: syn::parse::Parse,
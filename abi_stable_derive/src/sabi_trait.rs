@@ -59,6 +59,7 @@ struct TokenizerParams<'a> {
     trait_interface: &'a syn::Ident,
     make_vtable_ident: &'a syn::Ident,
     trait_cto_ident: &'a syn::Ident,
+    type_vtable_ident: &'a syn::Ident,
     /// TokenStreams that don't have a `'lt,` if the trait object requires
     /// `'static` to be constructed.
     lt_tokens: &'a LifetimeTokens,
@@ -81,8 +82,12 @@ pub fn derive_sabi_trait(item: ItemTrait) -> Result<TokenStream2, syn::Error> {
     let submod_vis = trait_def.submod_vis;
 
     let totrait_def = &trait_def.replace_self(WhichItem::TraitObjectImpl)?;
-    let vtable_trait_decl = &trait_def.replace_self(WhichItem::VtableDecl)?;
-    let vtable_trait_impl = &trait_def.replace_self(WhichItem::VtableImpl)?;
+    let vtable_trait_decl = &trait_def
+        .replace_self(WhichItem::VtableDecl)?
+        .monomorphize(arenas);
+    let vtable_trait_impl = &trait_def
+        .replace_self(WhichItem::VtableImpl)?
+        .monomorphize(arenas);
 
     let generated_mod = &parse_str_as_ident(&format!("{}_trait", trait_ident));
     let trait_to = &parse_str_as_ident(&format!("{}_TO", trait_ident));
@@ -90,6 +95,7 @@ pub fn derive_sabi_trait(item: ItemTrait) -> Result<TokenStream2, syn::Error> {
     let trait_interface = &parse_str_as_ident(&format!("{}_Interface", trait_ident));
     let make_vtable_ident = &parse_str_as_ident(&format!("{}_MV", trait_ident));
     let trait_cto_ident = &parse_str_as_ident(&format!("{}_CTO", trait_ident));
+    let type_vtable_ident = &parse_str_as_ident(&format!("{}_TypeVTable", trait_ident));
 
     let mut mod_contents = TokenStream2::default();
 
@@ -110,6 +116,7 @@ pub fn derive_sabi_trait(item: ItemTrait) -> Result<TokenStream2, syn::Error> {
         trait_interface,
         make_vtable_ident,
         trait_cto_ident,
+        type_vtable_ident,
     };
 
     first_items(tokenizer_params, &mut mod_contents);
@@ -120,10 +127,20 @@ pub fn derive_sabi_trait(item: ItemTrait) -> Result<TokenStream2, syn::Error> {
 
     methods_impls(tokenizer_params, &mut mod_contents)?;
 
+    method_instances_items(tokenizer_params, &mut mod_contents);
+
     declare_vtable(tokenizer_params, &mut mod_contents);
 
+    type_vtable_items(tokenizer_params, &mut mod_contents);
+
+    const_vtable_items(tokenizer_params, &mut mod_contents);
+
+    super_vtables_items(tokenizer_params, &mut mod_contents);
+
     vtable_impl(tokenizer_params, &mut mod_contents);
 
+    supertrait_items(tokenizer_params, &mut mod_contents);
+
     impl_delegations::delegated_impls(tokenizer_params, &mut mod_contents);
 
     let doc_hidden_attr = config.doc_hidden_attr;
@@ -141,6 +158,13 @@ pub fn derive_sabi_trait(item: ItemTrait) -> Result<TokenStream2, syn::Error> {
     }
     .into_iter();
 
+    let reexported_type_vtable = if trait_def.static_methods.is_empty() {
+        None
+    } else {
+        Some(type_vtable_ident)
+    }
+    .into_iter();
+
     let mut tokens = quote!(
         #doc_hidden_attr
         #[doc(inline)]
@@ -148,6 +172,7 @@ pub fn derive_sabi_trait(item: ItemTrait) -> Result<TokenStream2, syn::Error> {
             #trait_to,
             #trait_ident,
             #trait_cto_ident,
+            #(#reexported_type_vtable,)*
         };
 
         #doc_hidden_attr
@@ -343,6 +368,59 @@ fn first_items(
     }
     let one_lt = &lt_tokens.one_lt;
 
+    // Allows this trait to be used as a `#[sabi_trait]` supertrait.
+    let trait_object_vtable_impl =
+        if trait_def.which_object == WhichObject::RObject && trait_def.assoc_tys.is_empty() {
+            let gen_params_header = trait_def.generics_tokenizer(
+                InWhat::ImplHeader,
+                WithAssocTys::No,
+                &lt_tokens.lt_erasedptr,
+            );
+
+            let gen_params_use = trait_def.generics_tokenizer(
+                InWhat::ItemUse,
+                WithAssocTys::No,
+                &lt_tokens.lt_erasedptr,
+            );
+
+            let where_preds = (&trait_def.where_preds).into_iter();
+            let where_preds_b = where_preds.clone();
+
+            let trait_name = trait_ident.to_string();
+
+            Some(quote!(
+                unsafe impl<#gen_params_header> __sabi_re::TraitObjectVTable
+                for #trait_to<#gen_params_use>
+                where
+                    _ErasedPtr:__GetPointerKind,
+                    #(#where_preds,)*
+                {
+                    type Prefix = VTable_Prefix<#vtable_args>;
+                }
+
+                unsafe impl<#gen_params_header> __sabi_re::CrossCastTarget<#one_lt>
+                for #trait_to<#gen_params_use>
+                where
+                    _ErasedPtr: __sabi_re::AsPtr<PtrTarget = ()>,
+                    Self: __sabi_re::StableAbi,
+                    #(#where_preds_b,)*
+                {
+                    type ErasedPtr = _ErasedPtr;
+
+                    const TRAIT_NAME: &'static str = #trait_name;
+
+                    unsafe fn from_erased_vtable<I, V>(
+                        obj: __sabi_re::RObject<#one_lt _ErasedPtr, I, V>,
+                        vtable: __sabi_re::PrefixRef<__sabi_re::ErasedPrefix>,
+                    ) -> Self {
+                        Self::from_sabi(unsafe{ obj.sabi_replace_vtable(vtable.cast()) })
+                    }
+                }
+            ))
+        } else {
+            None
+        };
+
     quote!(
         use super::*;
 
@@ -392,6 +470,8 @@ fn first_items(
             _marker:__sabi_re::UnsafeIgnoredType< __sabi_re::#send_syncness >,
         }
 
+        #trait_object_vtable_impl
+
         const __inside_generated_mod:()={
             use abi_stable::{
                 InterfaceType,
@@ -526,6 +606,49 @@ fn constructor_items(params: TokenizerParams<'_>, mod_: &mut TokenStream2) {
         WhichObject::RObject => quote!(),
     };
 
+    let type_vtable_constraints_ptr = type_vtable_constraints(
+        totrait_def,
+        trait_interface,
+        &trait_interface_use,
+        lt_tokens,
+        quote!(_OrigPtr::PtrTarget),
+    );
+
+    let supertrait_constraints_ptr = supertrait_vtable_constraints(
+        totrait_def,
+        lt_tokens,
+        quote!(Downcasting),
+        quote!(_OrigPtr::PtrTarget),
+        quote!(_ErasedPtr),
+        quote!(_OrigPtr),
+    );
+
+    let supertrait_constraints_value = supertrait_vtable_constraints(
+        totrait_def,
+        lt_tokens,
+        quote!(Downcasting),
+        quote!(_Self),
+        quote!(__sabi_re::RBox<()>),
+        quote!(__sabi_re::RBox<_Self>),
+    );
+
+    let supertrait_constraints_const = supertrait_vtable_constraints(
+        totrait_def,
+        lt_tokens,
+        quote!(Downcasting),
+        quote!(_Self),
+        quote!(__sabi_re::RRef<'_sub, ()>),
+        quote!(&'_sub _Self),
+    );
+
+    let type_vtable_constraints_value = type_vtable_constraints(
+        totrait_def,
+        trait_interface,
+        &trait_interface_use,
+        lt_tokens,
+        quote!(_Self),
+    );
+
     let gen_params_header = totrait_def.generics_tokenizer(
         InWhat::ImplHeader,
         WithAssocTys::Yes(WhichSelf::NoSelf),
@@ -644,6 +767,28 @@ fn constructor_items(params: TokenizerParams<'_>, mod_: &mut TokenStream2) {
         }),
     };
 
+    let one_lt = &lt_tokens.one_lt;
+
+    let cross_cast_into_method = match totrait_def.which_object {
+        WhichObject::DynTrait => None,
+        WhichObject::RObject => Some(quote!(
+            /// Converts this trait object into the trait object of another `#[sabi_trait]` trait,
+            /// if the erased type was registered as implementing that trait.
+            ///
+            /// This method is automatically generated,
+            /// for more documentation you can look at
+            /// [`abi_stable::docs::sabi_trait_inherent#sabi_cross_cast_into-method`]
+            #submod_vis fn sabi_cross_cast_into<_To>(
+                self,
+            ) -> ::std::result::Result<_To, __sabi_re::CrossCastError<Self>>
+            where
+                _To: __sabi_re::CrossCastTarget<#one_lt ErasedPtr = _ErasedPtr>,
+            {
+                self.obj.sabi_cross_cast_into().map_err(|e| e.map_object(Self::from_sabi))
+            }
+        )),
+    };
+
     quote!(
         impl<#gen_params_header> #trait_to<#gen_params_use_to>
         where
@@ -667,6 +812,8 @@ fn constructor_items(params: TokenizerParams<'_>, mod_: &mut TokenStream2) {
                         Downcasting,_OrigPtr::PtrTarget,_ErasedPtr,_OrigPtr
                     >,
                 #extra_constraints_ptr
+                #type_vtable_constraints_ptr
+                #supertrait_constraints_ptr
             {
                 let _can_it_downcast=can_it_downcast;
                 unsafe{
@@ -692,6 +839,8 @@ fn constructor_items(params: TokenizerParams<'_>, mod_: &mut TokenStream2) {
                 }
             }
 
+            #cross_cast_into_method
+
             #reborrow_methods
         }
 
@@ -711,6 +860,8 @@ fn constructor_items(params: TokenizerParams<'_>, mod_: &mut TokenStream2) {
                         Downcasting,_Self,__sabi_re::RBox<()>,__sabi_re::RBox<_Self>
                     >,
                 #extra_constraints_value
+                #type_vtable_constraints_value
+                #supertrait_constraints_value
             {
                 Self::from_ptr::<
                     __sabi_re::RBox<_Self>,
@@ -736,6 +887,8 @@ fn constructor_items(params: TokenizerParams<'_>, mod_: &mut TokenStream2) {
                         Downcasting, _Self, __sabi_re::RRef<'_sub, ()>, &'_sub _Self
                     >,
                 #extra_constraints_const
+                #type_vtable_constraints_value
+                #supertrait_constraints_const
             {
                 unsafe{
                     Self{
@@ -835,11 +988,17 @@ fn trait_and_impl(
     let methods_tokenizer_impl = trait_def.methods_tokenizer(WhichItem::TraitImpl);
     let lifetime_bounds_a = trait_def.lifetime_bounds.iter();
     let lifetime_bounds_c = trait_def.lifetime_bounds.iter();
-    let super_traits_a = trait_def.impld_traits.iter().map(|t| &t.bound);
+    let super_traits_a = trait_def
+        .impld_traits
+        .iter()
+        .map(|t| &t.bound)
+        .chain(trait_def.sabi_supertraits.iter().map(|t| t.bound));
     let super_traits_b = super_traits_a.clone();
 
     let assoc_tys_a = trait_def.assoc_tys.values().map(|x| &x.assoc_ty);
 
+    let assoc_consts_a = trait_def.assoc_consts.iter().map(|x| x.item);
+
     let unsafety = trait_def.item.unsafety;
 
     let erased_ptr_bounds = trait_def.erased_ptr_preds();
@@ -855,6 +1014,8 @@ fn trait_and_impl(
         {
             #( #assoc_tys_a )*
 
+            #( #assoc_consts_a )*
+
             #methods_tokenizer_def
         }
     )
@@ -863,7 +1024,14 @@ fn trait_and_impl(
     let gen_params_use_trait =
         trait_def.generics_tokenizer(InWhat::ItemUse, WithAssocTys::No, &ctokens.empty_ts);
 
-    if !trait_def.disable_trait_impl {
+    // Static methods can't be called on the trait object,
+    // so it only implements the trait if all of them have a default implementation.
+    let has_required_static_methods = trait_def
+        .static_methods
+        .iter()
+        .any(|method| method.default.is_none());
+
+    if !trait_def.disable_trait_impl && !has_required_static_methods {
         let gen_params_header = trait_def.generics_tokenizer(
             InWhat::ImplHeader,
             WithAssocTys::Yes(WhichSelf::NoSelf),
@@ -878,6 +1046,27 @@ fn trait_and_impl(
         let assoc_ty_named_a = trait_def.assoc_tys.values().map(|x| &x.assoc_ty.ident);
         let assoc_ty_named_b = assoc_ty_named_a.clone();
 
+        // The values of associated constants are only known at runtime for the trait object,
+        // so this errors at compile-time if they're used.
+        //
+        // This is a post-monomorphization error,
+        // which is only reported when the constant is used in code that gets compiled to
+        // machine code (`cargo check` doesn't report it, and neither do unused generic functions).
+        let assoc_consts = trait_def.assoc_consts.iter().map(|assoc_const| {
+            let name = assoc_const.name;
+            let ty = &assoc_const.ty;
+            let message = format!(
+                "`{trait_to}` doesn't have a compile-time value for `{trait_}::{name}`, \
+                 use the `{trait_to}::{name}` method to get it at runtime.",
+                trait_to = trait_to,
+                trait_ = trait_ident,
+                name = name,
+            );
+            quote_spanned!(name.span()=>
+                const #name: #ty = ::std::panic!(#message);
+            )
+        });
+
         quote!(
             #[deny(unsafe_op_in_unsafe_fn)]
             #[allow(
@@ -893,6 +1082,8 @@ fn trait_and_impl(
             {
                 #( type #assoc_ty_named_a=#assoc_ty_named_b; )*
 
+                #( #assoc_consts )*
+
                 #methods_tokenizer_impl
             }
         )
@@ -934,6 +1125,53 @@ fn methods_impls(param: TokenizerParams, mod_: &mut TokenStream2) -> Result<(),
 
     let methods_tokenizer_def = totrait_def.methods_tokenizer(WhichItem::TraitObjectImpl);
 
+    let type_vtable_accessor = if totrait_def.static_methods.is_empty() {
+        None
+    } else {
+        let TokenizerParams {
+            submod_vis,
+            type_vtable_ident,
+            ..
+        } = param;
+
+        let type_vtable_generics = totrait_def.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::Yes(WhichSelf::NoSelf),
+            &lt_tokens.lt,
+        );
+
+        Some(quote!(
+            /// Gets the static methods of the type that this trait object was constructed from.
+            ///
+            /// This method is automatically generated,
+            /// for more documentation you can look at
+            /// [`abi_stable::docs::sabi_trait_inherent#sabi_type_vtable-method`]
+            #submod_vis fn sabi_type_vtable(&self) -> #type_vtable_ident<#type_vtable_generics> {
+                self.sabi_vtable()._sabi_type_vtable()
+            }
+        ))
+    };
+
+    let const_accessors = totrait_def.assoc_consts.iter().map(|assoc_const| {
+        let submod_vis = param.submod_vis;
+        let name = assoc_const.name;
+        let field_name = assoc_const.field_ident();
+        let ty = &assoc_const.ty;
+        let docs = assoc_const
+            .item
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("doc"));
+
+        quote_spanned!(name.span()=>
+            #(#docs)*
+            #[allow(non_snake_case)]
+            #submod_vis fn #name(&self) -> #ty {
+                unsafe{ (self.sabi_vtable()._sabi_consts().#field_name())() }
+            }
+        )
+    });
+
     quote!(
         #[allow(clippy::needless_lifetimes, clippy::new_ret_no_self)]
         impl<#gen_params_header> #trait_to<#gen_params_use_to>
@@ -951,6 +1189,10 @@ fn methods_impls(param: TokenizerParams, mod_: &mut TokenStream2) -> Result<(),
                 }
             }
 
+            #type_vtable_accessor
+
+            #( #const_accessors )*
+
             #methods_tokenizer_def
         }
     )
@@ -966,6 +1208,8 @@ fn declare_vtable(
         vtable_trait_decl,
         submod_vis,
         trait_interface,
+        type_vtable_ident,
+        lt_tokens,
         ..
     }: TokenizerParams,
     mod_: &mut TokenStream2,
@@ -1016,6 +1260,56 @@ fn declare_vtable(
         >
     );
 
+    // The type vtable is stored after the methods,
+    // so that adding the first static method doesn't move the methods.
+    //
+    // The type vtable is stored with a `'static` lifetime argument
+    // because the vtable doesn't have a lifetime parameter,
+    // `Trait_TO::sabi_type_vtable` returns it with the lifetime of the trait object.
+    let type_vtable_field = if vtable_trait_decl.static_methods.is_empty() {
+        None
+    } else {
+        let type_vtable_generics = vtable_trait_decl.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::Yes(WhichSelf::NoSelf),
+            &lt_tokens.staticlt,
+        );
+        Some(quote!(
+            _sabi_type_vtable: #type_vtable_ident<#type_vtable_generics>,
+        ))
+    };
+
+    // The const vtable is stored after the methods and the type vtable,
+    // so that adding the first associated constant doesn't move them.
+    let consts_field = if vtable_trait_decl.assoc_consts.is_empty() {
+        None
+    } else {
+        let const_vtable_generics = vtable_trait_decl.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::Yes(WhichSelf::NoSelf),
+            &ctokens.empty_ts,
+        );
+        Some(quote!(
+            _sabi_consts: ConstVTable_Ref<#const_vtable_generics>,
+        ))
+    };
+
+    // The vtables of the `#[sabi_trait]` supertraits are stored in a prefix type
+    // before the methods,so that adding methods doesn't move them,
+    // and supertraits can be added after the first one without moving the methods.
+    let supertraits_field = if vtable_trait_decl.sabi_supertraits.is_empty() {
+        None
+    } else {
+        let super_vtables_generics = vtable_trait_decl.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::Yes(WhichSelf::NoSelf),
+            &ctokens.ts_self_erasedptr,
+        );
+        Some(quote!(
+            _sabi_supertraits: SuperVTables_Ref<#super_vtables_generics>,
+        ))
+    };
+
     quote!(
 
         #[repr(C)]
@@ -1034,7 +1328,13 @@ fn declare_vtable(
 
             _sabi_vtable:#robject_vtable,
 
+            #supertraits_field
+
             #methods_tokenizer
+
+            #type_vtable_field
+
+            #consts_field
         }
     )
     .to_tokens(mod_);
@@ -1052,7 +1352,9 @@ fn vtable_impl(
         vtable_trait_impl,
         trait_interface,
         trait_ident,
+        trait_to,
         make_vtable_ident,
+        type_vtable_ident,
         lt_tokens,
         ..
     }: TokenizerParams,
@@ -1110,6 +1412,64 @@ fn vtable_impl(
 
     let one_lt = &lt_tokens.one_lt;
 
+    let type_vtable_tokens = type_vtable_impl_tokenizer(
+        vtable_trait_impl,
+        ctokens,
+        lt_tokens,
+        trait_to,
+        type_vtable_ident,
+    );
+
+    let type_vtable_field = if vtable_trait_impl.static_methods.is_empty() {
+        None
+    } else {
+        Some(quote!( _sabi_type_vtable: Self::TYPE_VTABLE_STATIC, ))
+    };
+
+    let consts_field = if vtable_trait_impl.assoc_consts.is_empty() {
+        None
+    } else {
+        Some(quote!( _sabi_consts: Self::CONST_VTABLE, ))
+    };
+
+    let const_vtable_tokens = const_vtable_impl_tokenizer(vtable_trait_impl, ctokens);
+
+    // The static methods construct trait objects,which requires `_Self: 'lt`.
+    let self_lt_bound = if vtable_trait_impl.static_methods.is_empty() {
+        None
+    } else {
+        Some(&lt_tokens.plus_lt)
+    };
+
+    let type_vtable_constraints = type_vtable_constraints(
+        vtable_trait_impl,
+        trait_interface,
+        &trait_interface_use,
+        lt_tokens,
+        quote!(_Self),
+    );
+
+    let supertrait_constraints = supertrait_vtable_constraints(
+        vtable_trait_impl,
+        lt_tokens,
+        quote!(IA),
+        quote!(_Self),
+        quote!(_ErasedPtr),
+        quote!(_OrigPtr),
+    );
+
+    let supertraits_field = if vtable_trait_impl.sabi_supertraits.is_empty() {
+        None
+    } else {
+        Some(quote!( _sabi_supertraits: Self::SUPER_VTABLES, ))
+    };
+
+    let super_vtables_tokens = super_vtables_impl_tokenizer(
+        vtable_trait_impl,
+        lt_tokens,
+        &withmetadata_generics,
+    );
+
     let extra_constraints = match vtable_trait_impl.which_object {
         WhichObject::DynTrait => quote!(
             #trait_interface<#trait_interface_use>:
@@ -1130,19 +1490,53 @@ fn vtable_impl(
         WhichObject::RObject => quote!(),
     };
 
+    let make_vtable_where_preds = quote!(
+        _Self: #trait_ident<#trait_generics> #self_lt_bound,
+        _OrigPtr:
+            __sabi_re::CanTransmuteElement<(), PtrTarget = _Self, TransmutedPtr = _ErasedPtr>,
+        _ErasedPtr:__sabi_re::AsPtr<PtrTarget=()>,
+        #trait_interface<#trait_interface_use>:
+            __sabi_re::GetRObjectVTable<IA,_Self,_ErasedPtr,_OrigPtr>,
+        #extra_constraints
+        #type_vtable_constraints
+        #supertrait_constraints
+    );
+
+    // Allows this trait to be used as a `#[sabi_trait]` supertrait.
+    let make_trait_object_vtable_impl = if vtable_trait_impl.which_object == WhichObject::RObject
+        && vtable_trait_impl.assoc_tys.is_empty()
+    {
+        let to_generics = vtable_trait_impl.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::No,
+            &lt_tokens.lt_erasedptr,
+        );
+
+        Some(quote!(
+            unsafe impl<#impl_header_generics>
+                __sabi_re::MakeTraitObjectVTable<IA, _Self, _ErasedPtr, _OrigPtr>
+            for #trait_to<#to_generics>
+            where
+                #make_vtable_where_preds
+            {
+                const VTABLE: __sabi_re::PrefixRef<__sabi_re::ErasedPrefix> = unsafe{
+                    #make_vtable_ident::<#makevtable_generics>::VTABLE_INNER.cast()
+                };
+            }
+        ))
+    } else {
+        None
+    };
+
     quote!(
         struct #make_vtable_ident<#struct_decl_generics>(#dummy_struct_tys);
 
+        #make_trait_object_vtable_impl
+
         #[deny(unsafe_op_in_unsafe_fn)]
         impl<#impl_header_generics> #make_vtable_ident<#makevtable_generics>
         where
-            _Self: #trait_ident<#trait_generics>,
-            _OrigPtr:
-                __sabi_re::CanTransmuteElement<(), PtrTarget = _Self, TransmutedPtr = _ErasedPtr>,
-            _ErasedPtr:__sabi_re::AsPtr<PtrTarget=()>,
-            #trait_interface<#trait_interface_use>:
-                __sabi_re::GetRObjectVTable<IA,_Self,_ErasedPtr,_OrigPtr>,
-            #extra_constraints
+            #make_vtable_where_preds
         {
             const TMP0: __sabi_re::WithMetadata<
                 VTable<#withmetadata_generics>
@@ -1151,9 +1545,12 @@ fn vtable_impl(
                     VTable{
                         _sabi_tys: __sabi_re::NonOwningPhantom::NEW,
                         _sabi_vtable:__sabi_re::GetRObjectVTable::ROBJECT_VTABLE,
+                        #supertraits_field
                         #(
                             #method_names_a:Self::#method_names_b,
                         )*
+                        #type_vtable_field
+                        #consts_field
                     }
                 )
             };
@@ -1163,12 +1560,762 @@ fn vtable_impl(
                     .cast() // erasing the `_Self` parameter
             };
 
+            #type_vtable_tokens
+
+            #const_vtable_tokens
+
+            #super_vtables_tokens
+
             #methods_tokenizer
         }
     )
     .to_tokens(mod_);
 }
 
+/// Outputs the conversions from the trait object into
+/// the trait objects of its `#[sabi_trait]` supertraits.
+fn supertrait_items(
+    TokenizerParams {
+        totrait_def,
+        trait_to,
+        lt_tokens,
+        ..
+    }: TokenizerParams,
+    mod_: &mut TokenStream2,
+) {
+    let gen_params_header = totrait_def.generics_tokenizer(
+        InWhat::ImplHeader,
+        WithAssocTys::Yes(WhichSelf::NoSelf),
+        &lt_tokens.lt_erasedptr,
+    );
+    let gen_params_use_to = totrait_def.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::Yes(WhichSelf::NoSelf),
+        &lt_tokens.lt_erasedptr,
+    );
+
+    for supertrait in &totrait_def.sabi_supertraits {
+        let super_to = supertrait.trait_object(&lt_tokens.lt, &quote!(_ErasedPtr));
+        let field = supertrait.field_ident();
+        let supertrait_name = supertrait.name().to_string();
+        let where_preds = (&totrait_def.where_preds).into_iter();
+
+        quote!(
+            #[allow(clippy::needless_lifetimes)]
+            impl<#gen_params_header> __sabi_re::CrossCast<#super_to>
+            for #trait_to<#gen_params_use_to>
+            where
+                _ErasedPtr: __sabi_re::AsPtr<PtrTarget = ()>,
+                #(#where_preds,)*
+            {
+                fn sabi_cross_cast(
+                    self,
+                ) -> ::std::result::Result<#super_to, __sabi_re::CrossCastError<Self>> {
+                    let vtable = unsafe{
+                        VTable_Ref(self.obj.sabi_et_vtable())._sabi_supertraits().#field()
+                    };
+                    match vtable {
+                        ::std::option::Option::Some(vtable) => {
+                            let obj = unsafe{ self.obj.sabi_replace_vtable(vtable) };
+                            ::std::result::Result::Ok(<#super_to>::from_sabi(obj))
+                        }
+                        ::std::option::Option::None => {
+                            ::std::result::Result::Err(
+                                __sabi_re::CrossCastError::new(self, #supertrait_name)
+                            )
+                        }
+                    }
+                }
+            }
+        )
+        .to_tokens(mod_);
+    }
+}
+
+/// The constraints required to construct the `TypeVTable` of `self_ty`,
+/// which are empty if the trait doesn't have static methods.
+///
+/// These constraints are spelled out in every item that (transitively)
+/// constructs the `TypeVTable`,because relying on the
+/// `GetRObjectVTable<IA,_Self,_ErasedPtr,_OrigPtr>` bound of the item
+/// prevents the associated types of the interface from being normalized.
+fn type_vtable_constraints(
+    trait_def: &TraitDefinition<'_>,
+    trait_interface: &syn::Ident,
+    trait_interface_use: &dyn ToTokens,
+    lt_tokens: &LifetimeTokens,
+    self_ty: TokenStream2,
+) -> TokenStream2 {
+    if trait_def.static_methods.is_empty() {
+        return TokenStream2::new();
+    }
+
+    let one_lt = &lt_tokens.one_lt;
+
+    let extra_constraints = match trait_def.which_object {
+        WhichObject::DynTrait => quote!(
+            __sabi_re::DynTraitVTable_Ref<
+                #one_lt
+                __sabi_re::RBox<()>,
+                #trait_interface<#trait_interface_use>,
+            >:
+                __sabi_re::MakeDynTraitVTable<
+                    #one_lt
+                    #self_ty,
+                    __sabi_re::RBox<#self_ty>,
+                    __sabi_re::TD_Opaque
+                >,
+        ),
+        WhichObject::RObject => quote!(),
+    };
+
+    let supertrait_constraints = supertrait_vtable_constraints(
+        trait_def,
+        lt_tokens,
+        quote!(__sabi_re::TD_Opaque),
+        self_ty.clone(),
+        quote!(__sabi_re::RBox<()>),
+        quote!(__sabi_re::RBox<#self_ty>),
+    );
+
+    quote!(
+        #trait_interface<#trait_interface_use>:
+            __sabi_re::GetRObjectVTable<
+                __sabi_re::TD_Opaque, #self_ty, __sabi_re::RBox<()>, __sabi_re::RBox<#self_ty>
+            >,
+        #extra_constraints
+        #supertrait_constraints
+    )
+}
+
+/// The constraints required to construct the vtables of the `#[sabi_trait]` supertraits,
+/// which are stored in the vtable of the trait.
+///
+/// The arguments are the generic arguments passed to `MakeTraitObjectVTable`.
+fn supertrait_vtable_constraints(
+    trait_def: &TraitDefinition<'_>,
+    lt_tokens: &LifetimeTokens,
+    ia: TokenStream2,
+    self_ty: TokenStream2,
+    erased_ptr: TokenStream2,
+    orig_ptr: TokenStream2,
+) -> TokenStream2 {
+    let bounds = trait_def.sabi_supertraits.iter().map(|supertrait| {
+        let trait_object = supertrait.trait_object(&lt_tokens.lt, &erased_ptr);
+        quote!(
+            #trait_object: __sabi_re::MakeTraitObjectVTable<
+                #ia, #self_ty, #erased_ptr, #orig_ptr
+            >,
+        )
+    });
+    quote!( #(#bounds)* )
+}
+
+/// Returns a tokenizer for the constants and functions used to construct the type vtable,
+/// inside the impl block of `Trait_MV`.
+fn type_vtable_impl_tokenizer<'a>(
+    vtable_trait_impl: &'a TraitDefinition<'a>,
+    ctokens: &'a CommonTokens,
+    lt_tokens: &'a LifetimeTokens,
+    trait_to: &'a syn::Ident,
+    type_vtable_ident: &'a syn::Ident,
+) -> impl ToTokens + 'a {
+    ToTokenFnMut::new(move |ts| {
+        let static_methods = &vtable_trait_impl.static_methods;
+        if static_methods.is_empty() {
+            return;
+        }
+
+        let type_vtable_generics = vtable_trait_impl.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::Yes(WhichSelf::Underscore),
+            &lt_tokens.lt,
+        );
+
+        let type_vtable_generics_static = vtable_trait_impl.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::Yes(WhichSelf::Underscore),
+            &lt_tokens.staticlt,
+        );
+
+        let trait_generics = vtable_trait_impl.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::No,
+            &ctokens.empty_ts,
+        );
+
+        let to_generics = vtable_trait_impl.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::Yes(WhichSelf::Underscore),
+            &lt_tokens.lt_rbox,
+        );
+
+        let method_names_a = static_methods.iter().map(|m| m.name);
+        let method_names_b = method_names_a.clone();
+
+        let methods = static_methods.iter().map(|method| {
+            let method_name = method.name;
+            let method_span = method_name.span();
+            let lifetimes = Some(&method.lifetimes)
+                .filter(|l| !l.is_empty())
+                .into_iter();
+            let param_names_a = method.params.iter().map(|param| param.name);
+            let param_names_b = param_names_a.clone();
+            let param_ty = method.params.iter().map(|param| &param.ty);
+
+            let mut call = quote_spanned!(method_span=>
+                <_Self as __Trait<#trait_generics>>::#method_name(#(#param_names_b,)*)
+            );
+            if let Some(unsafety) = method.unsafety {
+                call = quote_spanned!(unsafety.span=> #unsafety{ #call });
+            }
+
+            let return_ty = if method.returns_self {
+                call = quote_spanned!(method_span=>
+                    #trait_to::from_value(#call, __sabi_re::TD_Opaque)
+                );
+                Some(quote_spanned!(method_span=> #trait_to<#to_generics> ))
+            } else {
+                method.output.as_ref().map(ToTokens::into_token_stream)
+            }
+            .into_iter();
+
+            quote_spanned!(method_span=>
+                #[doc(hidden)]
+                unsafe extern "C" fn #method_name #(< #(#lifetimes,)* >)* (
+                    #( #param_names_a:#param_ty ,)*
+                ) #(-> #return_ty )* {
+                    ::abi_stable::extern_fn_panic_handling!{no_early_return;
+                        #call
+                    }
+                }
+            )
+        });
+
+        quote!(
+            const TYPE_TMP0: __sabi_re::WithMetadata<TypeVTable<#type_vtable_generics>> = {
+                __sabi_re::WithMetadata::new(
+                    TypeVTable{
+                        _sabi_tys: __sabi_re::NonOwningPhantom::NEW,
+                        #(
+                            #method_names_a:Self::#method_names_b,
+                        )*
+                    }
+                )
+            };
+
+            const TYPE_VTABLE: #type_vtable_ident<#type_vtable_generics> = unsafe{
+                #type_vtable_ident{
+                    vtable: TypeVTable_Ref(
+                        __sabi_re::WithMetadata::raw_as_prefix(&Self::TYPE_TMP0)
+                    ),
+                }
+            };
+
+            const TYPE_VTABLE_STATIC: #type_vtable_ident<#type_vtable_generics_static> = unsafe{
+                #type_vtable_ident{
+                    vtable: TypeVTable_Ref(
+                        __sabi_re::WithMetadata::raw_as_prefix(&Self::TYPE_TMP0)
+                            .cast() // changing the `'lt` parameter to `'static`
+                    ),
+                }
+            };
+
+            #(#methods)*
+        )
+        .to_tokens(ts);
+    })
+}
+
+/// Outputs the traits that are implemented for the types that
+/// generic methods are instantiated with.
+fn method_instances_items(
+    TokenizerParams {
+        trait_def,
+        submod_vis,
+        ..
+    }: TokenizerParams,
+    mod_: &mut TokenStream2,
+) {
+    for method in &trait_def.methods {
+        if method.type_param.is_none() {
+            continue;
+        }
+
+        let instance_trait = trait_def.instance_trait_ident(method);
+        let indices = 0..method.instances.len();
+        let instances = method.instances.iter();
+
+        let docs = format!(
+            "Implemented for the types that \
+             [`{trait_}::{method}`](trait@{trait_}#tymethod.{method}) can be called with,\
+             as listed in its `#[sabi(instantiate(...))]` attribute.\n\
+             \n\
+             # Safety\n\
+             \n\
+             This trait must only be implemented by the `#[sabi_trait]` attribute.\n\
+            ",
+            trait_ = trait_def.name,
+            method = method.name,
+        );
+
+        quote!(
+            #[doc = #docs]
+            #[allow(non_camel_case_types, clippy::missing_safety_doc)]
+            #submod_vis unsafe trait #instance_trait {
+                #[doc(hidden)]
+                const __SABI_INSTANCE_INDEX: usize;
+            }
+
+            #(
+                unsafe impl #instance_trait for #instances {
+                    const __SABI_INSTANCE_INDEX: usize = #indices;
+                }
+            )*
+        )
+        .to_tokens(mod_);
+    }
+}
+
+/// Outputs the type vtable,
+/// which contains the static methods of the type that the trait object was constructed from.
+fn type_vtable_items(
+    TokenizerParams {
+        arenas,
+        config,
+        ctokens,
+        lt_tokens,
+        trait_def,
+        vtable_trait_decl,
+        submod_vis,
+        trait_ident,
+        trait_to,
+        trait_interface,
+        make_vtable_ident,
+        type_vtable_ident,
+        ..
+    }: TokenizerParams,
+    mod_: &mut TokenStream2,
+) {
+    let static_methods = &vtable_trait_decl.static_methods;
+    if static_methods.is_empty() {
+        return;
+    }
+
+    let lt_dummy: &TokenStream2 = match trait_def.is_static {
+        IsStaticTrait::Yes => &ctokens.empty_ts,
+        IsStaticTrait::No => arenas.alloc(quote!(&'lt (),)),
+    };
+
+    let generics_decl = vtable_trait_decl.generics_tokenizer(
+        InWhat::ItemDecl,
+        WithAssocTys::Yes(WhichSelf::NoSelf),
+        &lt_tokens.lt,
+    );
+
+    let generics_header = vtable_trait_decl.generics_tokenizer(
+        InWhat::ImplHeader,
+        WithAssocTys::Yes(WhichSelf::NoSelf),
+        &lt_tokens.lt,
+    );
+
+    let generics_use = vtable_trait_decl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::Yes(WhichSelf::NoSelf),
+        &lt_tokens.lt,
+    );
+
+    let mut generics_dummy = vtable_trait_decl.generics_tokenizer(
+        InWhat::DummyStruct,
+        WithAssocTys::Yes(WhichSelf::NoSelf),
+        lt_dummy,
+    );
+    generics_dummy.set_no_bounds();
+
+    let to_generics = vtable_trait_decl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::Yes(WhichSelf::NoSelf),
+        &lt_tokens.lt_rbox_path,
+    );
+
+    let trait_params = vtable_trait_decl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        &ctokens.empty_ts,
+    );
+
+    let trait_interface_use = vtable_trait_decl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::Yes(WhichSelf::NoSelf),
+        &ctokens.ts_empty,
+    );
+
+    let mut make_vtable_args = vtable_trait_decl.generics_tokenizer(
+        InWhat::ItemUse,
+        WithAssocTys::No,
+        arenas.alloc(quote!(
+            __sabi_re::TD_Opaque, _Self, __sabi_re::RBox<()>, __sabi_re::RBox<_Self>,
+        )),
+    );
+    make_vtable_args.skip_lifetimes();
+
+    let assoc_tys_a = vtable_trait_decl.assoc_tys.keys();
+    let assoc_tys_b = assoc_tys_a.clone();
+
+    let plus_lt = &lt_tokens.plus_lt;
+
+    let type_vtable_constraints = type_vtable_constraints(
+        vtable_trait_decl,
+        trait_interface,
+        &trait_interface_use,
+        lt_tokens,
+        quote!(_Self),
+    );
+
+    let return_tys = static_methods
+        .iter()
+        .map(|method| {
+            if method.returns_self {
+                Some(quote!( #trait_to<#to_generics> ))
+            } else {
+                method.output.as_ref().map(ToTokens::into_token_stream)
+            }
+        })
+        .collect::<Vec<Option<TokenStream2>>>();
+
+    let fields = static_methods.iter().zip(&return_tys).map(|(method, return_ty)| {
+        let method_name = method.name;
+        let lifetimes = Some(&method.lifetimes)
+            .filter(|l| !l.is_empty())
+            .into_iter();
+        let param_names = method.params.iter().map(|param| param.name);
+        let param_ty = method.params.iter().map(|param| &param.ty);
+        let return_ty = return_ty.iter();
+        let derive_attrs = method.derive_attrs;
+
+        quote_spanned!(method_name.span()=>
+            #(#derive_attrs)*
+            #submod_vis #method_name:
+                #(for< #(#lifetimes,)* >)*
+                unsafe extern "C" fn(
+                    #( #param_names:#param_ty ,)*
+                ) #(-> #return_ty )*,
+        )
+    });
+
+    let methods = static_methods.iter().zip(&return_tys).map(|(method, return_ty)| {
+        let method_name = method.name;
+        let lifetimes = Some(&method.lifetimes)
+            .filter(|l| !l.is_empty())
+            .into_iter();
+        let param_names_a = method.params.iter().map(|param| param.name);
+        let param_names_b = param_names_a.clone();
+        let param_ty = method.params.iter().map(|param| &param.ty);
+        let return_ty = return_ty.iter();
+        let unsafety = method.unsafety;
+
+        let docs = format!(
+            "Calls [the static method of the same name](trait@{TN}#tymethod.{TM}) \
+             of the type that this vtable was constructed from.",
+            TN = trait_ident,
+            TM = method_name,
+        );
+
+        quote_spanned!(method_name.span()=>
+            #[doc = #docs]
+            #submod_vis #unsafety fn #method_name #(< #(#lifetimes,)* >)* (
+                self,
+                #( #param_names_a:#param_ty ,)*
+            ) #(-> #return_ty )* {
+                unsafe{ (self.vtable.#method_name())(#(#param_names_b,)*) }
+            }
+        )
+    });
+
+    let mut type_vtable_docs = String::new();
+    let mut new_docs = String::new();
+    if config.doc_hidden_attr.is_none() {
+        type_vtable_docs = format!(
+            "The static methods of a type that implements [`{trait_}`](trait@{trait_}).\n\
+             \n\
+             This is constructed with [`{type_vtable}::from_type`],\
+             or gotten from a trait object with \
+             [`{trait_to}::sabi_type_vtable`](struct@{trait_to}#method.sabi_type_vtable).\n\
+            ",
+            trait_ = trait_ident,
+            type_vtable = type_vtable_ident,
+            trait_to = trait_to,
+        );
+        new_docs = format!(
+            "Constructs the vtable with the static methods of `_Self`.\n\
+             \n\
+             The `{trait_to}` returned by the static methods \
+             is constructed with `TD_Opaque`,so it can't be downcast.\n\
+            ",
+            trait_to = trait_to,
+        );
+    }
+
+    quote!(
+        #[repr(C)]
+        #[derive(abi_stable::StableAbi)]
+        #[sabi(kind(Prefix(prefix_ref = TypeVTable_Ref)))]
+        #[sabi(missing_field(panic))]
+        #[doc(hidden)]
+        #submod_vis struct TypeVTable<#generics_decl> {
+            _sabi_tys: __sabi_re::NonOwningPhantom<(#generics_dummy)>,
+
+            #(#fields)*
+        }
+
+        #[doc = #type_vtable_docs]
+        #[repr(transparent)]
+        #[derive(abi_stable::StableAbi)]
+        #submod_vis struct #type_vtable_ident<#generics_decl> {
+            vtable: TypeVTable_Ref<#generics_use>,
+        }
+
+        impl<#generics_header> Copy for #type_vtable_ident<#generics_use> {}
+
+        impl<#generics_header> Clone for #type_vtable_ident<#generics_use> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<#generics_header> #type_vtable_ident<#generics_use> {
+            #[doc = #new_docs]
+            #submod_vis const fn from_type<_Self>() -> Self
+            where
+                _Self:
+                    #trait_ident<#trait_params #( #assoc_tys_a = #assoc_tys_b, )* >
+                    #plus_lt,
+                #type_vtable_constraints
+            {
+                #make_vtable_ident::<#make_vtable_args>::TYPE_VTABLE
+            }
+
+            #(#methods)*
+        }
+    )
+    .to_tokens(mod_);
+}
+
+/// Returns a tokenizer for the constants used to construct the vtable of
+/// associated constants,inside the impl block of `Trait_MV`.
+fn const_vtable_impl_tokenizer<'a>(
+    vtable_trait_impl: &'a TraitDefinition<'a>,
+    ctokens: &'a CommonTokens,
+) -> impl ToTokens + 'a {
+    ToTokenFnMut::new(move |ts| {
+        let assoc_consts = &vtable_trait_impl.assoc_consts;
+        if assoc_consts.is_empty() {
+            return;
+        }
+
+        let const_vtable_generics = vtable_trait_impl.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::Yes(WhichSelf::Underscore),
+            &ctokens.empty_ts,
+        );
+
+        let trait_generics = vtable_trait_impl.generics_tokenizer(
+            InWhat::ItemUse,
+            WithAssocTys::No,
+            &ctokens.empty_ts,
+        );
+
+        let field_names_a = assoc_consts.iter().map(|x| x.field_ident());
+        let field_names_b = field_names_a.clone();
+
+        let getters = assoc_consts.iter().map(|assoc_const| {
+            let name = assoc_const.name;
+            let field_name = assoc_const.field_ident();
+            let ty = &assoc_const.ty;
+            quote_spanned!(name.span()=>
+                #[doc(hidden)]
+                unsafe extern "C" fn #field_name() -> #ty {
+                    <_Self as __Trait<#trait_generics>>::#name
+                }
+            )
+        });
+
+        quote!(
+            const CONST_TMP0: __sabi_re::WithMetadata<ConstVTable<#const_vtable_generics>> = {
+                __sabi_re::WithMetadata::new(
+                    ConstVTable{
+                        _sabi_tys: __sabi_re::NonOwningPhantom::NEW,
+                        #(
+                            #field_names_a: Self::#field_names_b,
+                        )*
+                    }
+                )
+            };
+
+            const CONST_VTABLE: ConstVTable_Ref<#const_vtable_generics> = unsafe{
+                ConstVTable_Ref(__sabi_re::WithMetadata::raw_as_prefix(&Self::CONST_TMP0))
+            };
+
+            #(#getters)*
+        )
+        .to_tokens(ts);
+    })
+}
+
+/// Outputs the vtable with functions that return the values of the associated constants,
+/// which is a prefix type so that constants can be added in minor versions.
+///
+/// This stores functions instead of the values of the constants because
+/// references to constants with generic types aren't promoted to `'static`.
+fn const_vtable_items(
+    TokenizerParams {
+        ctokens,
+        vtable_trait_decl,
+        submod_vis,
+        ..
+    }: TokenizerParams,
+    mod_: &mut TokenStream2,
+) {
+    let assoc_consts = &vtable_trait_decl.assoc_consts;
+    if assoc_consts.is_empty() {
+        return;
+    }
+
+    let generics_decl = vtable_trait_decl.generics_tokenizer(
+        InWhat::ItemDecl,
+        WithAssocTys::Yes(WhichSelf::NoSelf),
+        &ctokens.empty_ts,
+    );
+
+    let mut generics_dummy = vtable_trait_decl.generics_tokenizer(
+        InWhat::DummyStruct,
+        WithAssocTys::Yes(WhichSelf::NoSelf),
+        &ctokens.empty_ts,
+    );
+    generics_dummy.set_no_bounds();
+
+    let field_names = assoc_consts.iter().map(|x| x.field_ident());
+    let const_tys = assoc_consts.iter().map(|x| &x.ty);
+
+    quote!(
+        #[repr(C)]
+        #[derive(abi_stable::StableAbi)]
+        #[sabi(kind(Prefix(prefix_ref = ConstVTable_Ref)))]
+        #[sabi(missing_field(panic))]
+        #[doc(hidden)]
+        #submod_vis struct ConstVTable<#generics_decl> {
+            _sabi_tys: __sabi_re::NonOwningPhantom<(#generics_dummy)>,
+
+            #( #submod_vis #field_names: unsafe extern "C" fn() -> #const_tys, )*
+        }
+    )
+    .to_tokens(mod_);
+}
+
+/// Returns a tokenizer for the constants used to construct the vtables of the
+/// `#[sabi_trait]` supertraits,inside the impl block of `Trait_MV`.
+fn super_vtables_impl_tokenizer<'a>(
+    vtable_trait_impl: &'a TraitDefinition<'a>,
+    lt_tokens: &'a LifetimeTokens,
+    withmetadata_generics: &'a dyn ToTokens,
+) -> impl ToTokens + 'a {
+    ToTokenFnMut::new(move |ts| {
+        let sabi_supertraits = &vtable_trait_impl.sabi_supertraits;
+        if sabi_supertraits.is_empty() {
+            return;
+        }
+
+        let fields = sabi_supertraits.iter().map(|supertrait| {
+            let field = supertrait.field_ident();
+            let trait_object = supertrait.trait_object(&lt_tokens.lt, &quote!(_ErasedPtr));
+            quote!(
+                #field: unsafe{
+                    <#trait_object as
+                        __sabi_re::MakeTraitObjectVTable<IA, _Self, _ErasedPtr, _OrigPtr>
+                    >::VTABLE
+                        .cast()
+                },
+            )
+        });
+
+        quote!(
+            const SUPER_TMP0: __sabi_re::WithMetadata<SuperVTables<#withmetadata_generics>> = {
+                __sabi_re::WithMetadata::new(
+                    SuperVTables{
+                        _sabi_tys: __sabi_re::NonOwningPhantom::NEW,
+                        #(#fields)*
+                    }
+                )
+            };
+
+            const SUPER_VTABLES: SuperVTables_Ref<#withmetadata_generics> = unsafe{
+                SuperVTables_Ref(__sabi_re::WithMetadata::raw_as_prefix(&Self::SUPER_TMP0))
+            };
+        )
+        .to_tokens(ts);
+    })
+}
+
+/// Outputs the prefix type with the vtables of the `#[sabi_trait]` supertraits.
+///
+/// The vtables of the supertraits are optional,
+/// so that `Trait_TO::sabi_cross_cast` can check at runtime whether they're available.
+fn super_vtables_items(
+    TokenizerParams {
+        ctokens,
+        vtable_trait_decl,
+        submod_vis,
+        lt_tokens,
+        ..
+    }: TokenizerParams,
+    mod_: &mut TokenStream2,
+) {
+    let sabi_supertraits = &vtable_trait_decl.sabi_supertraits;
+    if sabi_supertraits.is_empty() {
+        return;
+    }
+
+    let generics_decl = vtable_trait_decl.generics_tokenizer(
+        InWhat::ItemDecl,
+        WithAssocTys::Yes(WhichSelf::NoSelf),
+        &ctokens.ts_self_erasedptr,
+    );
+
+    let mut generics_dummy = vtable_trait_decl.generics_tokenizer(
+        InWhat::DummyStruct,
+        WithAssocTys::Yes(WhichSelf::NoSelf),
+        &ctokens.ts_self_erasedptr,
+    );
+    generics_dummy.set_no_bounds();
+
+    let fields = sabi_supertraits.iter().map(|supertrait| {
+        let field = supertrait.field_ident();
+        let trait_object = supertrait.trait_object(&lt_tokens.staticlt, &quote!(_ErasedPtr));
+        quote!(
+            #submod_vis #field: ::abi_stable::prefix_type::PrefixRef<
+                <#trait_object as ::abi_stable::sabi_trait::vtable::TraitObjectVTable>::Prefix
+            >,
+        )
+    });
+
+    quote!(
+        #[repr(C)]
+        #[derive(abi_stable::StableAbi)]
+        #[sabi(kind(Prefix(prefix_ref = SuperVTables_Ref)))]
+        #[sabi(missing_field(option))]
+        #[doc(hidden)]
+        #submod_vis struct SuperVTables<#generics_decl>
+        where
+            _ErasedPtr:__GetPointerKind,
+        {
+            _sabi_tys: __sabi_re::NonOwningPhantom<(#generics_dummy)>,
+
+            #(#fields)*
+        }
+    )
+    .to_tokens(mod_);
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum SelfParam<'a> {
     ByRef {
@@ -1176,6 +2323,8 @@ pub(crate) enum SelfParam<'a> {
         is_mutable: bool,
     },
     ByVal,
+    /// For static methods,which don't have a `self` parameter.
+    Static,
 }
 
 /// Which item this is refering to.
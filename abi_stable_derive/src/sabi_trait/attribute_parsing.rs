@@ -47,6 +47,8 @@ mod kw {
     syn::custom_keyword! {use_dyntrait}
     syn::custom_keyword! {use_dyn_trait}
     syn::custom_keyword! {no_trait_impl}
+    syn::custom_keyword! {sabi_supertraits}
+    syn::custom_keyword! {instantiate}
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -67,9 +69,19 @@ pub(crate) struct OwnedDeriveAndOtherAttrs {
 pub(crate) struct MethodWithAttrs<'a> {
     /// The attributes used in the vtable,and the trait.
     pub(crate) attrs: OwnedDeriveAndOtherAttrs,
+    /// The types that the type parameter of a generic method is instantiated with,
+    /// from the `#[sabi(instantiate(T = Type, ...))]` attribute.
+    pub(crate) instances: Vec<MethodInstanceAttr>,
     pub(crate) item: &'a TraitItemMethod,
 }
 
+/// A `T = Type` pair in a `#[sabi(instantiate(...))]` attribute.
+#[derive(Debug, Clone)]
+pub(crate) struct MethodInstanceAttr {
+    pub(crate) param: syn::Ident,
+    pub(crate) ty: syn::Type,
+}
+
 impl<'a> MethodWithAttrs<'a> {
     /// Constructs a `MethodWithAttrs` with no attributes.
     fn new(item: &'a TraitItemMethod) -> Self {
@@ -78,6 +90,7 @@ impl<'a> MethodWithAttrs<'a> {
                 derive_attrs: Vec::new(),
                 other_attrs: Vec::new(),
             },
+            instances: Vec::new(),
             item,
         }
     }
@@ -99,6 +112,9 @@ pub(super) struct SabiTraitAttrs<'a> {
     pub(super) which_object: WhichObject,
     /// If true,removes the `impl Trait for Trait_TO`
     pub(super) disable_trait_impl: bool,
+    /// The supertraits that are `#[sabi_trait]` traits,
+    /// from the `#[sabi(sabi_supertraits(...))]` attribute.
+    pub(super) sabi_supertraits: Vec<syn::Ident>,
     /// If true,doesn't use the default implementation of methods when
     /// the vtable entry is absent.
     pub(super) disable_inherent_default: Vec<bool>,
@@ -234,11 +250,38 @@ fn parse_sabi_trait_attr<'a>(
         this.debug_print_trait = true;
     } else if input.check_parse(kw::debug_output_tokens)? {
         this.debug_output_tokens = true;
+    } else if input.peek(kw::instantiate) {
+        let kw_span = input.parse::<kw::instantiate>()?.span;
+        let method = match pctx {
+            ParseContext::Method { .. } => this.methods_with_attrs.last_mut().unwrap(),
+            ParseContext::TraitAttr => {
+                return Err(syn::Error::new(
+                    kw_span,
+                    "`instantiate` can only be used on generic methods.",
+                ));
+            }
+        };
+        input.parse_paren_with(|input| {
+            input.for_each_separated(syn::Token!(,), |input| {
+                let param = input.parse::<syn::Ident>()?;
+                input.parse::<syn::Token!(=)>()?;
+                let ty = input.parse::<syn::Type>()?;
+                method.instances.push(MethodInstanceAttr { param, ty });
+                Ok(())
+            })
+        })?;
     } else if let ParseContext::TraitAttr = pctx {
         if input.check_parse(kw::use_dyntrait)? || input.check_parse(kw::use_dyn_trait)? {
             this.which_object = WhichObject::DynTrait;
         } else if input.check_parse(kw::no_trait_impl)? {
             this.disable_trait_impl = true;
+        } else if input.check_parse(kw::sabi_supertraits)? {
+            input.parse_paren_with(|input| {
+                input.for_each_separated(syn::Token!(,), |input| {
+                    this.sabi_supertraits.push(input.parse::<syn::Ident>()?);
+                    Ok(())
+                })
+            })?;
         } else {
             push_attr(this, pctx, input, attr.clone());
         }
@@ -205,6 +205,9 @@ declare_lifetime_tokens! {
         lt="",
         lt_erasedptr="_ErasedPtr,",
         lt_rbox="__sabi_re::RBox<()>,",
+        // `__sabi_re` refers to another module inside the code generated by
+        // `#[derive(StableAbi)]`,so the full path is used in the fields of derived types.
+        lt_rbox_path="::abi_stable::std_types::RBox<()>,",
         lt_rref="__sabi_re::RRef<'_sub,()>,",
         lt_rmut="__sabi_re::RMut<'_sub,()>,",
         lt_sub_lt="'_sub,",
@@ -213,6 +216,7 @@ declare_lifetime_tokens! {
         one_lt="",
     ]
     static_lifetime_tokens=[
+        staticlt="",
         staticlt_erasedptr2="_ErasedPtr2,",
         staticlt_erasedptr="_ErasedPtr,",
     ]
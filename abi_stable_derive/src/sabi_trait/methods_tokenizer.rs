@@ -41,7 +41,15 @@ pub struct MethodTokenizer<'a> {
 
 impl<'a> ToTokens for MethodsTokenizer<'a> {
     fn to_tokens(&self, ts: &mut TokenStream2) {
-        for method in &self.trait_def.methods {
+        // Static methods are only printed in the trait,
+        // the trait object doesn't implement the trait if it has non-defaulted static methods,
+        // and the type vtable is generated separately.
+        let static_methods = match self.which_item {
+            WhichItem::Trait => &self.trait_def.static_methods[..],
+            _ => &[],
+        };
+
+        for method in self.trait_def.methods.iter().chain(static_methods) {
             MethodTokenizer {
                 trait_def: self.trait_def,
                 method,
@@ -102,6 +110,27 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
             }
         }
 
+        let erased_self_param = match &method.self_param {
+            SelfParam::ByRef {
+                lifetime,
+                is_mutable: false,
+            } => {
+                let lifetime = WriteLifetime(*lifetime);
+                quote_spanned!(method_span=> _self: __sabi_re::RRef<#lifetime, ()>,)
+            }
+            SelfParam::ByRef {
+                lifetime,
+                is_mutable: true,
+            } => {
+                let lifetime = WriteLifetime(*lifetime);
+                quote_spanned!(method_span=> _self: __sabi_re::RMut<#lifetime, ()>,)
+            }
+            SelfParam::ByVal => {
+                quote_spanned!(method_span=> _self:*mut (),)
+            }
+            SelfParam::Static => quote!(),
+        };
+
         let self_param = match (is_method, &method.self_param) {
             (
                 true,
@@ -110,7 +139,7 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                     is_mutable: false,
                 },
             ) => {
-                quote_spanned!(method_span=> & #lifetime self)
+                quote_spanned!(method_span=> & #lifetime self,)
             }
             (
                 true,
@@ -119,36 +148,44 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                     is_mutable: true,
                 },
             ) => {
-                quote_spanned!(method_span=> & #lifetime mut self)
+                quote_spanned!(method_span=> & #lifetime mut self,)
             }
             (true, SelfParam::ByVal) => {
-                quote_spanned!(method_span=> self)
-            }
-            (
-                false,
-                SelfParam::ByRef {
-                    lifetime,
-                    is_mutable: false,
-                },
-            ) => {
-                let lifetime = WriteLifetime(*lifetime);
-                quote_spanned!(method_span=> _self: __sabi_re::RRef<#lifetime, ()>)
-            }
-            (
-                false,
-                SelfParam::ByRef {
-                    lifetime,
-                    is_mutable: true,
-                },
-            ) => {
-                let lifetime = WriteLifetime(*lifetime);
-                quote_spanned!(method_span=> _self: __sabi_re::RMut<#lifetime, ()>)
-            }
-            (false, SelfParam::ByVal) => {
-                quote_spanned!(method_span=> _self:*mut ())
+                quote_spanned!(method_span=> self,)
             }
+            (true, SelfParam::Static) => quote!(),
+            (false, _) => erased_self_param.clone(),
+        };
+
+        // The type parameter of generic methods is only declared in the trait,
+        // and in the trait object,
+        // the vtable has one entry for each type that the method is instantiated with.
+        let type_param = method.type_param.filter(|_| is_method);
+
+        // The type parameter is bounded by the trait implemented for
+        // the types that the method is instantiated with.
+        let bounded_type_param = type_param.map(|type_param| {
+            let instance_trait = trait_def.instance_trait_ident(method);
+            let mut type_param = type_param.clone();
+            type_param.colon_token.get_or_insert_with(Default::default);
+            type_param
+                .bounds
+                .push(syn::parse_quote_spanned!(method_span=> #instance_trait));
+            type_param
+        });
+
+        let generic_params = if method.lifetimes.is_empty() && type_param.is_none() {
+            None
+        } else {
+            let lifetimes = &method.lifetimes;
+            Some(quote_spanned!(method_span=> < #(#lifetimes,)* #bounded_type_param >))
         };
 
+        let turbofish = type_param.map(|type_param| {
+            let type_param = &type_param.ident;
+            quote_spanned!(method_span=> ::<#type_param> )
+        });
+
         let param_names_a = method.params.iter().map(move |param| {
             ToTokenFnMut::new(move |ts| match which_item {
                 WhichItem::Trait => {
@@ -195,7 +232,7 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                 #vis #method_name:
                     #(for< #(#lifetimes,)* >)*
                     unsafe extern "C" fn(
-                        #self_param,
+                        #self_param
                         #( #param_names_a:#param_ty ,)*
                     ) #(-> #return_ty )*
             )
@@ -225,8 +262,8 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                 #[allow(clippy::let_and_return)]
                 #(#other_attrs)*
                 #inherent_method_docs
-                #vis #unsafety #abi fn #method_name #(< #(#lifetimes,)* >)* (
-                    #self_param,
+                #vis #unsafety #abi fn #method_name #generic_params (
+                    #self_param
                     #( #param_names_a:#param_ty ,)*
                 ) #(-> #return_ty )*
                 where
@@ -244,6 +281,7 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                 is_mutable: true, ..
             } => &ctokens.ptr_mut_bound,
             SelfParam::ByVal => &ctokens.ptr_val_bound,
+            SelfParam::Static => &ctokens.empty_ts,
         };
 
         let output_safety = |output: &mut TokenStream2, input: TokenStream2| {
@@ -254,16 +292,28 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
             });
         };
 
+        let trait_method = match &method.instance_of {
+            Some(instance) => {
+                let generic_name = instance.generic_name;
+                let type_arg = &instance.type_arg;
+                quote_spanned!(method_span=> __Trait::#generic_name::<#type_arg> )
+            }
+            None => quote_spanned!(method_span=> __Trait::#method_name ),
+        };
+
         match (which_item, &method.self_param) {
             (WhichItem::Trait, _) => {
                 method.default.as_ref().map(|x| x.block).to_tokens(ts);
                 method.semicolon.to_tokens(ts);
             }
+            (WhichItem::TraitImpl, SelfParam::Static) => {
+                unreachable!("static methods aren't in the trait impl")
+            }
             (WhichItem::TraitImpl, _) => {
                 output_safety(
                     ts,
                     quote_spanned!(method_span =>
-                        self.#method_name(#(#param_names_c,)*)
+                        self.#method_name #turbofish (#(#param_names_c,)*)
                     ),
                 );
             }
@@ -293,6 +343,54 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                             )
                         )
                     }
+                    SelfParam::Static => unreachable!("static methods aren't in the vtable"),
+                };
+
+                // Generic methods pick the vtable entry for the type they're called with.
+                let vtable_method = match type_param {
+                    Some(type_param) => {
+                        let type_param = &type_param.ident;
+                        let instance_trait = trait_def.instance_trait_ident(method);
+                        let lifetimes = Some(&method.lifetimes)
+                            .filter(|l| !l.is_empty())
+                            .into_iter();
+                        let param_names = method.params.iter().map(|param| param.name);
+                        let param_ty = method.params.iter().map(|param| &param.ty);
+                        let return_ty = method.output.iter();
+
+                        let mut fn_ty = quote_spanned!(method_span=>
+                            #(for< #(#lifetimes,)* >)*
+                            unsafe extern "C" fn(
+                                #erased_self_param
+                                #( #param_names:#param_ty ,)*
+                            ) #(-> #return_ty )*
+                        );
+                        if default_.is_some() {
+                            fn_ty = quote_spanned!(method_span=> Option<#fn_ty> );
+                        }
+
+                        let indices = 0..method.instances.len();
+                        let instance_names =
+                            indices.clone().map(|index| method.instance_ident(index));
+
+                        quote_spanned!(method_span=> {
+                            // The `__SABI_INSTANCE_INDEX`th vtable entry takes and returns
+                            // `#type_param` where the generic method does.
+                            #[allow(clippy::missing_transmute_annotations)]
+                            let __method: #fn_ty = unsafe {
+                                match <#type_param as #instance_trait>::__SABI_INSTANCE_INDEX {
+                                    #(
+                                        #indices => __sabi_re::transmute(
+                                            self.sabi_vtable().#instance_names()
+                                        ),
+                                    )*
+                                    _ => ::std::unreachable!(),
+                                }
+                            };
+                            __method
+                        })
+                    }
+                    None => quote_spanned!(method_span=> self.sabi_vtable().#method_name() ),
                 };
 
                 match default_ {
@@ -301,7 +399,7 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                         ts.append_all(quote_spanned!(method_span=>
                                 #ptr_constraint
                             {
-                                match self.sabi_vtable().#method_name() {
+                                match #vtable_method {
                                     Some(__method)=>{
                                         unsafe{
                                             #method_call
@@ -321,7 +419,7 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                         ts.append_all(quote_spanned!(method_span=>
                                 #ptr_constraint
                             {
-                                let __method=self.sabi_vtable().#method_name();
+                                let __method=#vtable_method;
                                 unsafe{
                                     #method_call
                                 }
@@ -363,7 +461,7 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
                 ts.append_all(quote_spanned!(method_span=>{
                     unsafe{
                         let #ret = ::abi_stable::extern_fn_panic_handling!{no_early_return;
-                            __Trait::#method_name(
+                            #trait_method(
                                 &#mut_token *_self.transmute_into_raw::<#self_ty>(),
                                 #(#param_names_c,)*
                             )
@@ -376,12 +474,15 @@ impl<'a> ToTokens for MethodTokenizer<'a> {
             (WhichItem::VtableImpl, SelfParam::ByVal) => {
                 ts.append_all(quote_spanned!(method_span=>{
                     ::abi_stable::extern_fn_panic_handling!{no_early_return; unsafe{
-                        __Trait::#method_name(
+                        #trait_method(
                             (_self as *mut #self_ty).read(),#(#param_names_c,)*
                         )
                     }}
                 }));
             }
+            (WhichItem::VtableImpl, SelfParam::Static) => {
+                unreachable!("static methods aren't in the vtable")
+            }
         }
     }
 }
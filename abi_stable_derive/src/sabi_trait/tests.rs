@@ -28,9 +28,72 @@ fn must_not_pass() {
         ",
         "
             trait Bar {
-                const X: usize;
+                foo!{}
+            }
+        ",
+        // Generic methods must list the types they're instantiated with.
+        "
+            trait Generic {
+                fn get<T>(&self) -> T;
+            }
+        ",
+        "
+            trait Generic {
+                #[sabi(instantiate(U = u32))]
+                fn get<T>(&self) -> T;
+            }
+        ",
+        "
+            trait Generic {
+                #[sabi(instantiate(T = u32, U = u64))]
+                fn get<T, U>(&self) -> (T, U);
+            }
+        ",
+        "
+            trait Generic {
+                #[sabi(instantiate(T = u32))]
+                fn get(&self) -> u32;
+            }
+        ",
+        "
+            trait Generic {
+                #[sabi(instantiate(T = u32))]
+                fn new<T>() -> Self where Self: Sized;
             }
         ",
+        // `#[sabi_trait]` supertraits are only supported by RObject-based trait objects.
+        "
+            #[sabi(use_dyntrait)]
+            #[sabi(sabi_supertraits(Super))]
+            trait Sub: Super {}
+        ",
+        "
+            #[sabi(use_dyntrait)]
+            trait Sub: Super {}
+        ",
+        // `#[sabi_trait]` supertraits must be listed in the `sabi_supertraits` attribute.
+        "
+            trait Sub: Super {}
+        ",
+        "
+            trait Sub: Default {}
+        ",
+        "
+            #[sabi(sabi_supertraits(Super))]
+            trait Sub: Other {}
+        ",
+        "
+            #[sabi(sabi_supertraits(Super))]
+            trait Sub: Super<Item = u32> {}
+        ",
+        "
+            #[sabi(sabi_supertraits(Super))]
+            trait Sub: for<'a> Super<'a> {}
+        ",
+        "
+            #[sabi(sabi_supertraits(Fn))]
+            trait Sub: Fn(u32) -> u32 {}
+        ",
     ];
     for elem in list {
         if derive_sabi_trait(elem).is_ok() {
@@ -52,6 +115,35 @@ fn must_pass() {
                 fn baz(self);
             }
         ",
+        "
+            trait Bar {
+                const X: usize;
+                const Y: RStr<'static> = RStr::from_str(\"hello\");
+                fn bar(&self);
+            }
+        ",
+        "
+            trait Static {
+                fn new(x: u32) -> Self where Self: Sized;
+                unsafe fn version() -> u32 where Self: Sized;
+                fn get(&self) -> u32;
+            }
+        ",
+        "
+            trait Generic<'a, U> {
+                #[sabi(instantiate(T = u32, T = RString))]
+                fn get<T>(&self, x: &U) -> T;
+
+                #[sabi(instantiate(T = u8))]
+                fn set<T>(&mut self, x: T) {}
+            }
+        ",
+        "
+            #[sabi(sabi_supertraits(Super))]
+            trait Sub<'a, T>: Super + other::Super<'a, T> + Debug {
+                fn sub(&self);
+            }
+        ",
     ];
 
     for elem in list {
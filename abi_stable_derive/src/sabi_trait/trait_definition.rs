@@ -19,7 +19,7 @@ use std::{
     iter,
 };
 
-use core_extensions::{matches, IteratorExt};
+use core_extensions::matches;
 
 use syn::{
     punctuated::Punctuated,
@@ -27,7 +27,7 @@ use syn::{
     token::Unsafe,
     token::{Colon, Comma, Semi},
     visit_mut::VisitMut,
-    Abi, Attribute, Block, FnArg, Ident, ItemTrait, Lifetime, LifetimeDef, TraitItem,
+    Abi, Attribute, Block, FnArg, Ident, ItemTrait, Lifetime, LifetimeDef, TraitItem, TypeParam,
     TypeParamBound, WherePredicate,
 };
 
@@ -39,6 +39,62 @@ pub struct AssocTyWithIndex {
     pub assoc_ty: syn::TraitItemType,
 }
 
+/// An associated constant of the trait,which is stored in the vtable.
+#[derive(Debug, Clone)]
+pub(crate) struct AssocConst<'a> {
+    /// The associated constant as declared in the trait.
+    pub(crate) item: &'a syn::TraitItemConst,
+    /// The name of the constant.
+    pub(crate) name: &'a Ident,
+    /// The type of the constant,
+    /// where usages of associated types are replaced by `TraitDefinition::replace_self`.
+    pub(crate) ty: syn::Type,
+}
+
+/// A supertrait that isn't one of the traits in `TRAIT_LIST`,
+/// which is assumed to be another `#[sabi_trait]` trait.
+#[derive(Debug, Clone)]
+pub(crate) struct SabiSupertrait<'a> {
+    /// The supertrait bound,as written in the trait definition.
+    pub(crate) bound: &'a syn::TraitBound,
+    /// The position of this supertrait among the `#[sabi_trait]` supertraits.
+    pub(crate) index: usize,
+}
+
+impl<'a> SabiSupertrait<'a> {
+    /// The name of the supertrait.
+    pub fn name(&self) -> &'a Ident {
+        &self.bound.path.segments.last().expect("BUG").ident
+    }
+
+    /// The name of the field for the vtable of this supertrait in the vtable of the trait.
+    pub fn field_ident(&self) -> Ident {
+        Ident::new(&format!("_sabi_super_{}", self.index), self.name().span())
+    }
+
+    /// The `Trait_TO` type of this supertrait.
+    ///
+    /// `lifetime` is either empty or a lifetime followed by a comma,
+    /// it's passed as the first generic argument,
+    /// and `erased_ptr` is passed after the lifetime arguments of the supertrait.
+    pub fn trait_object(&self, lifetime: &TokenStream2, erased_ptr: &dyn ToTokens) -> TokenStream2 {
+        let mut path = self.bound.path.clone();
+        let last = path.segments.last_mut().expect("BUG");
+        let arguments = std::mem::replace(&mut last.arguments, syn::PathArguments::None);
+        last.ident = Ident::new(&format!("{}_TO", last.ident), last.ident.span());
+
+        let (lifetime_args, other_args) = match arguments {
+            syn::PathArguments::AngleBracketed(args) => args
+                .args
+                .into_iter()
+                .partition::<Vec<_>, _>(|arg| matches!(arg, syn::GenericArgument::Lifetime(_))),
+            _ => (Vec::new(), Vec::new()),
+        };
+
+        quote!( #path< #lifetime #(#lifetime_args,)* #erased_ptr, #(#other_args,)* > )
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Represents a trait for use in `#[sabi_trait]`.
@@ -68,6 +124,9 @@ pub(crate) struct TraitDefinition<'a> {
     pub(crate) impld_traits: Vec<TraitImplness<'a>>,
     /// The traits this doesn't have as supertraits.
     pub(crate) unimpld_traits: Vec<&'a Ident>,
+    /// The `#[sabi_trait]` traits this has as supertraits,
+    /// whose vtables are stored in the vtable of this trait.
+    pub(crate) sabi_supertraits: Vec<SabiSupertrait<'a>>,
     /// A struct describing the traits this does and doesn't have as supertraits
     /// (true means implemented,false means unimplemented)
     pub(crate) trait_flags: TraitStruct<bool>,
@@ -84,8 +143,13 @@ pub(crate) struct TraitDefinition<'a> {
     pub(crate) submod_vis: RelativeVis<'a>,
     // The keys use the proginal identifier for the associated type.
     pub(crate) assoc_tys: HashMap<&'a Ident, AssocTyWithIndex>,
+    /// The associated constants,in declaration order.
+    pub(crate) assoc_consts: Vec<AssocConst<'a>>,
     ///
     pub(crate) methods: Vec<TraitMethod<'a>>,
+    /// The methods without a `self` receiver,
+    /// which are stored in the type vtable instead of the trait object vtable.
+    pub(crate) static_methods: Vec<TraitMethod<'a>>,
     /// Whether this has by mutable reference methods.
     pub(crate) has_mut_methods: bool,
     /// Whether this has by-value methods.
@@ -110,6 +174,7 @@ impl<'a> TraitDefinition<'a> {
             which_object,
             disable_trait_impl,
             disable_inherent_default,
+            sabi_supertraits: declared_sabi_supertraits,
             ..
         }: SabiTraitAttrs<'a>,
         arenas: &'a Arenas,
@@ -118,7 +183,9 @@ impl<'a> TraitDefinition<'a> {
         let vis = VisibilityKind::new(&trait_.vis);
         let submod_vis = vis.submodule_level(1);
         let mut assoc_tys = HashMap::default();
+        let mut assoc_consts = Vec::<AssocConst<'a>>::new();
         let mut methods = Vec::<TraitMethod<'a>>::new();
+        let mut static_methods = Vec::<TraitMethod<'a>>::new();
 
         let mut errors = LinearResult::ok(());
 
@@ -134,7 +201,13 @@ impl<'a> TraitDefinition<'a> {
                     }
                 }
             })
-            .extending(&mut methods);
+            .for_each(|method| {
+                if method.self_param == SelfParam::Static {
+                    static_methods.push(method);
+                } else {
+                    methods.push(method);
+                }
+            });
 
         /////////////////////////////////////////////////////
         ////         Processing the supertrait bounds
@@ -151,6 +224,7 @@ impl<'a> TraitDefinition<'a> {
         let GetSupertraits {
             impld_traits,
             unimpld_traits,
+            sabi_supertraits,
             mut lifetime_bounds,
             iterator_item,
             deserialize_bound,
@@ -160,6 +234,7 @@ impl<'a> TraitDefinition<'a> {
         } = get_supertraits(
             &trait_.supertraits,
             &lifetime_params,
+            &declared_sabi_supertraits,
             which_object,
             arenas,
             ctokens,
@@ -202,6 +277,13 @@ impl<'a> TraitDefinition<'a> {
 
                     assoc_ty_index += 1;
                 }
+                TraitItem::Const(assoc_const) => {
+                    assoc_consts.push(AssocConst {
+                        item: assoc_const,
+                        name: &assoc_const.ident,
+                        ty: assoc_const.ty.clone(),
+                    });
+                }
                 item => errors.push_err(spanned_err!(
                     item,
                     "Associated item not compatible with #[sabi_trait]",
@@ -248,12 +330,15 @@ impl<'a> TraitDefinition<'a> {
             deserialize_bound,
             impld_traits,
             unimpld_traits,
+            sabi_supertraits,
             trait_flags,
             trait_spans,
             vis,
             submod_vis,
             assoc_tys,
+            assoc_consts,
             methods,
+            static_methods,
             has_mut_methods,
             has_val_methods,
             disable_trait_impl,
@@ -303,7 +388,16 @@ impl<'a> TraitDefinition<'a> {
             .combine_into_err(&mut errors);
         }
 
-        for method in &mut this.methods {
+        for assoc_const in &mut this.assoc_consts {
+            replace_self_path::replace_self_path(
+                &mut assoc_const.ty,
+                replace_with.clone(),
+                is_assoc_type,
+            )
+            .combine_into_err(&mut errors);
+        }
+
+        for method in this.methods.iter_mut().chain(&mut this.static_methods) {
             method
                 .replace_self(replace_with.clone(), is_assoc_type)
                 .combine_into_err(&mut errors);
@@ -311,6 +405,51 @@ impl<'a> TraitDefinition<'a> {
         errors.into_result().map(|_| this)
     }
 
+    /// Returns a clone of `self`,
+    /// where every generic method is replaced with one method for each type
+    /// in its `#[sabi(instantiate(...))]` attribute.
+    pub fn monomorphize(&self, arenas: &'a Arenas) -> Self {
+        let mut this = self.clone();
+        this.methods = Vec::with_capacity(self.methods.len());
+
+        for method in &self.methods {
+            let type_param = match method.type_param {
+                Some(x) => &x.ident,
+                None => {
+                    this.methods.push(method.clone());
+                    continue;
+                }
+            };
+
+            for (index, type_arg) in method.instances.iter().enumerate() {
+                let mut replacer = ReplaceTypeParam {
+                    param: type_param,
+                    with: type_arg,
+                };
+
+                let mut instance = method.clone();
+                for ty in instance
+                    .params
+                    .iter_mut()
+                    .map(|x| &mut x.ty)
+                    .chain(instance.output.as_mut())
+                {
+                    replacer.visit_type_mut(ty);
+                }
+                instance.name = arenas.alloc(method.instance_ident(index));
+                instance.type_param = None;
+                instance.instances = Vec::new();
+                instance.instance_of = Some(MethodInstance {
+                    generic_name: method.name,
+                    type_arg: type_arg.clone(),
+                });
+                this.methods.push(instance);
+            }
+        }
+
+        this
+    }
+
     /// Returns a tokenizer for the generic parameters in this trait.
     ///
     /// # Parameters
@@ -382,6 +521,15 @@ impl<'a> TraitDefinition<'a> {
         errors.into_result().map(|_| where_preds)
     }
 
+    /// The name of the trait that is implemented for the types that
+    /// the generic `method` is instantiated with.
+    pub fn instance_trait_ident(&self, method: &TraitMethod<'_>) -> Ident {
+        Ident::new(
+            &format!("{}_{}_Instance", self.name, method.name),
+            method.name.span(),
+        )
+    }
+
     /// Returns a tokenizer that outputs the method definitions inside the `which_item` item.
     pub fn methods_tokenizer(&self, which_item: WhichItem) -> MethodsTokenizer<'_> {
         MethodsTokenizer {
@@ -408,9 +556,18 @@ pub(crate) struct TraitMethod<'a> {
     pub(crate) self_param: SelfParam<'a>,
     /// The lifetime parameters of this method.
     pub(crate) lifetimes: Vec<&'a LifetimeDef>,
+    /// The type parameter of this method,if it's generic.
+    pub(crate) type_param: Option<&'a TypeParam>,
+    /// The types that `type_param` is instantiated with.
+    pub(crate) instances: Vec<syn::Type>,
+    /// Which generic method this is an instance of,
+    /// only set in the monomorphized vtable methods.
+    pub(crate) instance_of: Option<MethodInstance<'a>>,
     pub(crate) params: Vec<MethodParam<'a>>,
     /// The return type of this method,if None this returns `()`.
     pub(crate) output: Option<syn::Type>,
+    /// Whether this is a static method that returns `Self`.
+    pub(crate) returns_self: bool,
 
     /// Whether the return type borrows from self
     pub(crate) return_borrow_kind: Option<BorrowKind>,
@@ -423,6 +580,15 @@ pub(crate) struct TraitMethod<'a> {
     pub(crate) semicolon: Option<&'a Semi>,
 }
 
+/// Describes which generic method a monomorphized vtable method comes from.
+#[derive(Debug, Clone)]
+pub(crate) struct MethodInstance<'a> {
+    /// The name of the generic method.
+    pub(crate) generic_name: &'a Ident,
+    /// The type that the type parameter of the generic method is instantiated with.
+    pub(crate) type_arg: syn::Type,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct DefaultMethod<'a> {
     pub(crate) block: &'a Block,
@@ -453,44 +619,114 @@ impl<'a> TraitMethod<'a> {
 
         let mut errors = LinearResult::ok(());
 
-        let push_error_msg = |errors: &mut Result<(), syn::Error>| {
+        let where_clause = decl
+            .generics
+            .where_clause
+            .as_ref()
+            .and_then(|wc| match MethodWhereClause::new(wc, ctokens) {
+                Ok(x) => Some(x),
+                Err(e) => {
+                    errors.push_err(e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        let mut input_iter = decl.inputs.iter().peekable();
+
+        let mut self_param = match input_iter.peek() {
+            Some(FnArg::Receiver(receiver)) => {
+                input_iter.next();
+                match &receiver.reference {
+                    Some((_, lifetime)) => SelfParam::ByRef {
+                        lifetime: lifetime.as_ref(),
+                        is_mutable: receiver.mutability.is_some(),
+                    },
+                    None => SelfParam::ByVal,
+                }
+            }
+            _ => {
+                if !where_clause.requires_self_sized {
+                    errors.push_err(spanned_err!(
+                        method_signature.ident,
+                        "Methods without a `self`/`&self`/`&mut self` receiver (static methods) \
+                         require a `where Self: Sized` bound in #[sabi_trait] traits."
+                    ));
+                }
+                SelfParam::Static
+            }
+        };
+
+        let mut lifetimes: Vec<&'a syn::LifetimeDef> = decl.generics.lifetimes().collect();
+
+        let mut type_param = None::<&'a TypeParam>;
+        for param in decl.generics.type_params() {
+            if type_param.is_some() {
+                errors.push_err(spanned_err!(
+                    param,
+                    "Methods with more than one type parameter are not supported."
+                ));
+            } else if self_param == SelfParam::Static {
+                errors.push_err(spanned_err!(
+                    param,
+                    "Static methods can't have type parameters."
+                ));
+            }
+            type_param = Some(param);
+        }
+        for param in decl.generics.const_params() {
             errors.push_err(spanned_err!(
-                method_signature.ident,
-                "Cannot define #[sabi_trait]traits containing methods \
-                 without a `self`/`&self`/`&mut self` receiver (static methods)."
+                param,
+                "Methods with const parameters are not supported."
             ));
-        };
-        if decl.inputs.is_empty() {
-            push_error_msg(&mut errors);
         }
 
-        let mut input_iter = decl.inputs.iter();
-
-        let mut self_param = match input_iter.next() {
-            Some(FnArg::Receiver(receiver)) => match &receiver.reference {
-                Some((_, lifetime)) => SelfParam::ByRef {
-                    lifetime: lifetime.as_ref(),
-                    is_mutable: receiver.mutability.is_some(),
-                },
-                None => SelfParam::ByVal,
-            },
-            Some(FnArg::Typed { .. }) => {
-                push_error_msg(&mut errors);
-                SelfParam::ByVal
+        let mut instances = Vec::<syn::Type>::with_capacity(mwa.instances.len());
+        match type_param {
+            Some(type_param) => {
+                if mwa.instances.is_empty() {
+                    errors.push_err(spanned_err!(
+                        type_param,
+                        "Generic methods require a \
+                         `#[sabi(instantiate({} = SomeType, ...))]` attribute \
+                         listing the types that the method can be called with.",
+                        type_param.ident,
+                    ));
+                }
+                for instance in mwa.instances {
+                    if instance.param != type_param.ident {
+                        errors.push_err(spanned_err!(
+                            instance.param,
+                            "Expected the `{}` type parameter.",
+                            type_param.ident,
+                        ));
+                    }
+                    instances.push(instance.ty);
+                }
             }
             None => {
-                push_error_msg(&mut errors);
-                return errors.into_result().map(|_| unreachable!());
+                if let Some(instance) = mwa.instances.first() {
+                    errors.push_err(spanned_err!(
+                        instance.param,
+                        "`instantiate` can only be used on methods with a type parameter."
+                    ));
+                }
             }
-        };
-
-        let mut lifetimes: Vec<&'a syn::LifetimeDef> = decl.generics.lifetimes().collect();
+        }
 
         let mut return_borrow_kind = None::<BorrowKind>;
 
+        let mut returns_self = false;
+
         let output = match &decl.output {
             syn::ReturnType::Default => None,
             syn::ReturnType::Type(_, ty) => {
+                if self_param == SelfParam::Static {
+                    if let syn::Type::Path(path) = &**ty {
+                        returns_self = path.qself.is_none() && path.path.is_ident("Self");
+                    }
+                }
+
                 let mut ty: syn::Type = (**ty).clone();
                 if let SelfParam::ByRef { lifetime, .. } = &mut self_param {
                     let visit_data = LifetimeUnelider::new(lifetime).visit_type(&mut ty);
@@ -511,19 +747,6 @@ impl<'a> TraitMethod<'a> {
             .as_ref()
             .map(|block| DefaultMethod { block });
 
-        let where_clause = decl
-            .generics
-            .where_clause
-            .as_ref()
-            .and_then(|wc| match MethodWhereClause::new(wc, ctokens) {
-                Ok(x) => Some(x),
-                Err(e) => {
-                    errors.push_err(e);
-                    None
-                }
-            })
-            .unwrap_or_default();
-
         let mut params = Vec::<MethodParam<'a>>::with_capacity(input_iter.len());
 
         for (param_i, param) in input_iter.enumerate() {
@@ -556,9 +779,13 @@ impl<'a> TraitMethod<'a> {
             other_attrs: arena.alloc(mwa.attrs.other_attrs),
             name,
             lifetimes,
+            type_param,
+            instances,
+            instance_of: None,
             self_param,
             params,
             output,
+            returns_self,
             return_borrow_kind,
             where_clause,
             default,
@@ -581,17 +808,47 @@ impl<'a> TraitMethod<'a> {
     {
         let mut errors = LinearResult::ok(());
 
-        for param in self
-            .params
-            .iter_mut()
-            .map(|x| &mut x.ty)
-            .chain(self.output.as_mut())
-        {
+        let output = self.output.as_mut().filter(|_| !self.returns_self);
+        for param in self.params.iter_mut().map(|x| &mut x.ty).chain(output) {
             replace_self_path::replace_self_path(param, replace_with.clone(), &mut is_assoc_type)
                 .combine_into_err(&mut errors);
         }
         errors.into()
     }
+
+    /// The name of the vtable entry for the `index`th instance of this generic method.
+    pub fn instance_ident(&self, index: usize) -> Ident {
+        Ident::new(&format!("{}_instance{}", self.name, index), self.name.span())
+    }
+}
+
+impl<'a> AssocConst<'a> {
+    /// The name of the field for this constant in the vtable of associated constants.
+    ///
+    /// This is snake case so that the accessors generated for the field don't
+    /// trigger the `non_snake_case` lint.
+    pub fn field_ident(&self) -> Ident {
+        let name = self.name.to_string().to_lowercase();
+        Ident::new(&format!("const_{}", name), self.name.span())
+    }
+}
+
+/// Replaces the type parameter of a generic method with one of its instances.
+struct ReplaceTypeParam<'a> {
+    param: &'a Ident,
+    with: &'a syn::Type,
+}
+
+impl VisitMut for ReplaceTypeParam<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if let syn::Type::Path(path) = ty {
+            if path.qself.is_none() && path.path.is_ident(self.param) {
+                *ty = self.with.clone();
+                return;
+            }
+        }
+        syn::visit_mut::visit_type_mut(self, ty);
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -690,6 +947,7 @@ pub(crate) struct DeserializeBound;
 struct GetSupertraits<'a> {
     impld_traits: Vec<TraitImplness<'a>>,
     unimpld_traits: Vec<&'a Ident>,
+    sabi_supertraits: Vec<SabiSupertrait<'a>>,
     lifetime_bounds: Punctuated<&'a Lifetime, Comma>,
     iterator_item: Option<&'a syn::Type>,
     deserialize_bound: Option<DeserializeBound>,
@@ -711,6 +969,7 @@ pub(crate) struct TraitImplness<'a> {
 fn get_supertraits<'a, I>(
     supertraits: I,
     lifetime_params: &HashSet<&'a Lifetime>,
+    declared_sabi_supertraits: &[Ident],
     which_object: WhichObject,
     arenas: &'a Arenas,
     _ctokens: &'a CommonTokens,
@@ -733,6 +992,8 @@ where
     });
 
     let mut lifetime_bounds = Punctuated::<&'a Lifetime, Comma>::new();
+    let mut sabi_supertraits = Vec::<SabiSupertrait<'a>>::new();
+    let mut is_declared_used = vec![false; declared_sabi_supertraits.len()];
     let mut iterator_item = None;
     let mut errors = LinearResult::ok(());
     let deserialize_bound = None;
@@ -746,6 +1007,32 @@ where
                 };
                 let trait_ident = &last_path_component.ident;
 
+                let declared_index = declared_sabi_supertraits
+                    .iter()
+                    .position(|x| x == trait_ident);
+
+                if let Some(declared_index) = declared_index {
+                    is_declared_used[declared_index] = true;
+
+                    if which_object == WhichObject::DynTrait {
+                        errors.push_err(spanned_err!(
+                            trait_bound.path,
+                            "`#[sabi_trait]` supertraits can't be used along with \
+                             the #[sabi(use_dyntrait)] attribute."
+                        ));
+                        continue;
+                    }
+
+                    match check_sabi_supertrait(trait_bound) {
+                        Ok(()) => sabi_supertraits.push(SabiSupertrait {
+                            bound: trait_bound,
+                            index: sabi_supertraits.len(),
+                        }),
+                        Err(e) => errors.push_err(e),
+                    }
+                    continue;
+                }
+
                 match trait_map.get(trait_ident) {
                     Some(&which_trait) => {
                         let usable_by = which_trait.usable_by();
@@ -799,6 +1086,19 @@ where
                                     "Serialize is not currently supported."
                                 ));
                             }
+                            WhichTrait::Future
+                            | WhichTrait::AsRefStr
+                            | WhichTrait::AsRefBytes
+                            | WhichTrait::BorrowStr
+                            | WhichTrait::Deref
+                            | WhichTrait::FnOnce
+                            | WhichTrait::FnMut
+                            | WhichTrait::Fn => {
+                                errors.push_err(spanned_err!(
+                                    trait_bound.path,
+                                    "This trait is not currently supported as a supertrait."
+                                ));
+                            }
                             WhichTrait::Eq | WhichTrait::PartialOrd => {
                                 set_impld(&mut trait_struct.partial_eq, span);
                             }
@@ -823,10 +1123,19 @@ where
                             .map(|x| x.to_string())
                             .collect::<Vec<String>>();
 
+                        let sabi_trait_hint = match which_object {
+                            WhichObject::RObject => {
+                                "\nor a `#[sabi_trait]` trait listed in the \
+                                 `#[sabi(sabi_supertraits(...))]` attribute."
+                            }
+                            WhichObject::DynTrait => "",
+                        };
+
                         errors.push_err(spanned_err!(
                             supertrait_bound,
-                            "Unexpected supertrait bound.\nExpected one of:\n{}",
+                            "Unexpected supertrait bound.\nExpected one of:\n{}{}",
                             list.join("/"),
+                            sabi_trait_hint,
                         ));
                         break;
                     }
@@ -846,6 +1155,18 @@ where
         };
     }
 
+    for (declared, _) in declared_sabi_supertraits
+        .iter()
+        .zip(&is_declared_used)
+        .filter(|(_, is_used)| !**is_used)
+    {
+        errors.push_err(spanned_err!(
+            declared,
+            "`{}` is not a supertrait of this trait.",
+            declared,
+        ));
+    }
+
     let iter_trait = &mut trait_struct.iterator;
     let de_iter_trait = &mut trait_struct.double_ended_iterator;
     if iter_trait.is_implemented || de_iter_trait.is_implemented {
@@ -886,6 +1207,7 @@ where
     GetSupertraits {
         impld_traits,
         unimpld_traits,
+        sabi_supertraits,
         lifetime_bounds,
         iterator_item,
         deserialize_bound,
@@ -895,6 +1217,48 @@ where
     }
 }
 
+/// Checks that a `#[sabi_trait]` supertrait only uses the syntax that's supported for it.
+fn check_sabi_supertrait(trait_bound: &syn::TraitBound) -> Result<(), syn::Error> {
+    if let syn::TraitBoundModifier::Maybe(_) = trait_bound.modifier {
+        return_spanned_err!(
+            trait_bound,
+            "`?Trait` bounds are not supported as supertraits."
+        );
+    }
+    if trait_bound.lifetimes.is_some() {
+        return_spanned_err!(
+            trait_bound,
+            "Higher ranked `#[sabi_trait]` supertraits are not supported."
+        );
+    }
+
+    let last_path_component = trait_bound.path.segments.last().expect("BUG");
+    match &last_path_component.arguments {
+        syn::PathArguments::None => {}
+        syn::PathArguments::AngleBracketed(args) => {
+            for arg in &args.args {
+                match arg {
+                    syn::GenericArgument::Binding(_) | syn::GenericArgument::Constraint(_) => {
+                        return_spanned_err!(
+                            arg,
+                            "`#[sabi_trait]` supertraits with associated types \
+                             are not supported."
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        }
+        syn::PathArguments::Parenthesized(args) => {
+            return_spanned_err!(
+                args,
+                "Unexpected supertrait bound,expected a `#[sabi_trait]` trait."
+            );
+        }
+    }
+    Ok(())
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Extracts the Iterator::Item out of a path component.
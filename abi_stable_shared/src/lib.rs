@@ -81,3 +81,12 @@ where
 pub fn mangled_root_module_loader_name() -> String {
     mangle_ident("lib_header", "root module loader")
 }
+
+/// Gets the name of the static that contains the RootModuleIndex of an abi_stable library,
+/// which lists the named root modules that the library exports.
+///
+/// This does not have a trailing `'\0'`,
+/// you need to append it to pass the name to C APIs.
+pub fn mangled_root_module_index_name() -> String {
+    mangle_ident("lib_header", "root module index")
+}
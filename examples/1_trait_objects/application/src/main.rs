@@ -7,7 +7,7 @@ use std::{
 
 use abi_stable::{
     external_types::crossbeam_channel::{self, RReceiver, RSender},
-    library::{lib_header_from_path, LibraryError, LibrarySuffix, RawLibrary},
+    library::{LibraryError, LibraryPath, LibrarySuffix, RawLibrary, RootModule},
     sabi_trait::prelude::TD_Opaque,
     std_types::{RErr, ROk, RResult, RSome, RStr, RString, RVec},
 };
@@ -194,13 +194,10 @@ fn main() -> io::Result<()> {
             }
         };
 
-        let res = (|| {
-            let header = lib_header_from_path(&library_path)?;
-            header.init_root_module::<PluginMod_Ref>()
-        })();
+        let res = PluginMod_Ref::load_instance_from(LibraryPath::FullPath(&library_path));
 
         let root_module = match res {
-            Ok(x) => x,
+            Ok((root_module, _)) => root_module,
             Err(e) => {
                 library_errs.push((named.clone(), e));
                 continue;
@@ -19,6 +19,16 @@ use core_extensions::SelfOps;
 /// is compatible with the layout of it in the binary that loads this library.
 #[export_root_module]
 pub fn get_library() -> TestingMod_Ref {
+    get_named_library()
+}
+
+// Also exports the root module as a named root module,
+// to test `RootModule::load_named_instance_from`.
+abi_stable::export_root_modules! {
+    get_named_library,
+}
+
+pub fn get_named_library() -> TestingMod_Ref {
     TestingMod {
         greeter,
         for_tests,
@@ -9,19 +9,30 @@
 //! and then all the modules inside of the library.
 
 use abi_stable::{
-    library::RootModule,
+    library::{LibraryError, RootModule},
     package_version_strings,
     sabi_types::VersionStrings,
     std_types::{RArc, RBox, RStr, RString, RVec},
     StableAbi,
 };
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// How many times a `TestingMod_Ref` was initialized in the loader,
+/// used to test that root modules are only initialized once.
+pub static INITIALIZATION_COUNT: AtomicUsize = AtomicUsize::new(0);
+
 impl RootModule for TestingMod_Ref {
     abi_stable::declare_root_module_statics! {TestingMod_Ref}
 
     const BASE_NAME: &'static str = "testing";
     const NAME: &'static str = "testing";
     const VERSION_STRINGS: VersionStrings = package_version_strings!();
+
+    fn initialization(self) -> Result<Self, LibraryError> {
+        INITIALIZATION_COUNT.fetch_add(1, Ordering::SeqCst);
+        Ok(self)
+    }
 }
 
 #[repr(C)]
@@ -1,13 +1,20 @@
-use std::{io, sync::Arc};
+use std::{
+    fs, io,
+    path::Path,
+    sync::{atomic::Ordering, Arc},
+};
 
 use core_extensions::SelfOps;
 
 use abi_stable::{
-    library::{development_utils::compute_library_path, RootModule},
+    library::{
+        development_utils::compute_library_path, LibraryPath, LoadedLibrary, ReloadError,
+        RootModule,
+    },
     std_types::{RArc, RBox, RString, RVec},
 };
 
-use testing_interface_0::{PrefixTypeMod1_Ref, TestingMod_Ref};
+use testing_interface_0::{PrefixTypeMod1_Ref, TestingMod_Ref, INITIALIZATION_COUNT};
 
 fn main() -> io::Result<()> {
     let target: &std::path::Path = "../../../target/".as_ref();
@@ -18,9 +25,131 @@ fn main() -> io::Result<()> {
 
     run_dynamic_library_tests(mods);
 
+    run_load_instance_tests(&library_path);
+
+    run_load_named_instance_tests(&library_path);
+
+    run_loaded_library_tests(&library_path);
+
     Ok(())
 }
 
+/// Tests that loading the same library multiple times with `load_instance_from`
+/// reuses the library and root module from the first time.
+pub fn run_load_instance_tests(library_path: &std::path::Path) {
+    let where_ = LibraryPath::Directory(library_path);
+
+    let (module_0, lib_0) =
+        TestingMod_Ref::load_instance_from(where_).unwrap_or_else(|e| panic!("{}", e));
+    let (module_1, lib_1) =
+        TestingMod_Ref::load_instance_from(where_).unwrap_or_else(|e| panic!("{}", e));
+
+    assert!(std::ptr::eq(lib_0, lib_1));
+    assert_eq!(module_0.0.to_raw_ptr(), module_1.0.to_raw_ptr());
+    assert_eq!(lib_0.path(), lib_1.path());
+}
+
+/// Tests that loading the same named root module multiple times with
+/// `load_named_instance_from` reuses the library and root module from the first time,
+/// only initializing the root module once.
+pub fn run_load_named_instance_tests(library_path: &std::path::Path) {
+    let where_ = LibraryPath::Directory(library_path);
+
+    let initializations = INITIALIZATION_COUNT.load(Ordering::SeqCst);
+
+    let (module_0, lib_0) = TestingMod_Ref::load_named_instance_from(where_, TestingMod_Ref::NAME)
+        .unwrap_or_else(|e| panic!("{}", e));
+    let (module_1, lib_1) = TestingMod_Ref::load_named_instance_from(where_, TestingMod_Ref::NAME)
+        .unwrap_or_else(|e| panic!("{}", e));
+
+    assert!(std::ptr::eq(lib_0, lib_1));
+    assert_eq!(module_0.0.to_raw_ptr(), module_1.0.to_raw_ptr());
+    assert_eq!(
+        INITIALIZATION_COUNT.load(Ordering::SeqCst),
+        initializations + 1
+    );
+}
+
+/// Tests that a `LoadedLibrary` can only be unloaded
+/// once the values tracked from it are dropped.
+pub fn run_loaded_library_tests(library_path: &Path) {
+    // Using a copy of the library that this process hasn't loaded before,
+    // since the original library was leaked by `load_from_directory`.
+    let dir = std::env::temp_dir().join(format!(
+        "abi_stable_testing_0_unload_{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    let original = TestingMod_Ref::get_library_path(library_path);
+    let copy = dir.join(original.file_name().unwrap());
+    fs::copy(&original, &copy).unwrap();
+    let copy = copy.canonicalize().unwrap();
+
+    let where_ = LibraryPath::FullPath(&copy);
+
+    assert_ne!(is_mapped(&copy), Some(true));
+
+    // safety: the testing library doesn't spawn threads,
+    // nor register thread-local destructors,
+    // and nothing from it outlives the `LoadedLibrary`.
+    let library = unsafe { LoadedLibrary::<TestingMod_Ref>::load_from(where_) }
+        .unwrap_or_else(|e| panic!("{}", e));
+    assert_eq!(library.outstanding_objects(), 0);
+    assert_ne!(is_mapped(&copy), Some(false));
+
+    let for_tests = library.module().map(|m| m.for_tests()());
+    assert_eq!(library.outstanding_objects(), 1);
+    assert_eq!(*for_tests.box_, 10);
+
+    let keep_alive = library.keep_alive();
+    assert_eq!(library.outstanding_objects(), 2);
+
+    let library = {
+        // safety: every value from the library is tracked.
+        let err = unsafe { library.unload() }.unwrap_err();
+        assert_eq!(err.outstanding(), 2);
+        err.into_library()
+    };
+
+    drop(for_tests);
+
+    // safety: it's the same library that was loaded above.
+    let library = match unsafe { library.reload(where_) } {
+        Err(ReloadError::InUse(err)) => {
+            assert_eq!(err.outstanding(), 1);
+            err.into_library()
+        }
+        Err(ReloadError::Load(e)) => panic!("{}", e),
+        Ok(_) => panic!("reloaded the library while it was in use"),
+    };
+
+    drop(keep_alive);
+    assert_eq!(library.outstanding_objects(), 0);
+
+    // safety: it's the same library that was loaded above.
+    let library = unsafe { library.reload(where_) }.unwrap_or_else(|e| panic!("{}", e));
+
+    library.module().greeter()("LoadedLibrary".into());
+
+    // safety: nothing from the library is alive.
+    unsafe { library.unload() }.unwrap();
+
+    assert_ne!(is_mapped(&copy), Some(true));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Whether the library at `path` is mapped into this process,
+/// returning None on platforms where this can't be checked.
+fn is_mapped(path: &Path) -> Option<bool> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let maps = fs::read_to_string("/proc/self/maps").ok()?;
+    let path = path.to_str()?;
+    Some(maps.lines().any(|line| line.ends_with(path)))
+}
+
 /// This tests that a type coming from a dynamic library
 /// cannot be converted back to its std-library equivalent
 /// while reusing the heap allocation.
@@ -70,6 +70,34 @@ impl RootModule for NonAbiStableLib_Ref {
 
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Contains a root module with the same layout as the `TestingMod` in the parent module,
+/// but with an incompatible version number.
+pub mod incompatible_version {
+    use abi_stable::{library::RootModule, sabi_types::VersionStrings, StableAbi};
+
+    impl RootModule for TestingMod_Ref {
+        abi_stable::declare_root_module_statics! {TestingMod_Ref}
+
+        const BASE_NAME: &'static str = "testing_1_loading_errors";
+        const NAME: &'static str = "testing_1_loading_errors";
+        const VERSION_STRINGS: VersionStrings = VersionStrings::new("1.0.0");
+    }
+
+    /// This type is used to test that libraries with an incompatible version are detected.
+    #[repr(C)]
+    #[derive(StableAbi)]
+    #[sabi(kind(Prefix(prefix_ref = TestingMod_Ref)))]
+    #[sabi(missing_field(panic))]
+    pub struct TestingMod {
+        #[sabi(last_prefix_field)]
+        pub a: u32,
+        pub b: u32,
+        pub c: u32,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 /// Parameters for the program passed through environment variables.
 ///
 /// The reason that env vars are used instead of command line arguments is because
@@ -0,0 +1,19 @@
+//! This library only exports root modules with the `export_root_modules` macro,
+//! to test that `discover` finds them.
+
+use testing_interface_1::{TestingMod, TestingMod_Ref};
+
+use abi_stable::prefix_type::PrefixTypeTrait;
+
+abi_stable::export_root_modules! {
+    get_library,
+}
+
+pub fn get_library() -> TestingMod_Ref {
+    TestingMod {
+        a: 21,
+        b: 34,
+        c: 55,
+    }
+    .leak_into_prefix()
+}
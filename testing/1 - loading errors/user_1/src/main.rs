@@ -1,14 +1,17 @@
 #![allow(clippy::print_literal)]
 
 use abi_stable::library::{
-    development_utils::compute_library_path, LibraryError, RootModule, RootModuleError,
+    development_utils::compute_library_path,
+    discover::{discover, LibraryStatusKind},
+    LibraryError, LibraryPath, LibrarySuffix, RawLibrary, RootModule, RootModuleError,
 };
 
 use testing_interface_1::{
-    get_env_vars, NonAbiStableLib_Ref, ReturnWhat, TestingMod_Ref, WithIncompatibleLayout_Ref,
+    get_env_vars, incompatible_version, NonAbiStableLib_Ref, ReturnWhat, TestingMod_Ref,
+    WithIncompatibleLayout_Ref,
 };
 
-use std::fmt;
+use std::{fmt, fs, path::Path};
 
 fn main() {
     let target: &std::path::Path = "../../../target/".as_ref();
@@ -91,6 +94,15 @@ fn main() {
                 print_error_sum(line!(), err);
             }
         }
+    }
+
+    {
+        let library_path = compute_library_path::<TestingMod_Ref>(target).unwrap();
+        discover_tests(&library_path, &envars.return_what);
+        discover_named_tests(&library_path);
+    }
+
+    {
         println!(
             "\n{S}{S}\n\nFinished successfully\n\n{S}{S}\n",
             S = "----------------------------------------",
@@ -98,6 +110,96 @@ fn main() {
     }
 }
 
+/// Tests `discover` with a copy of the testing library in a directory of its own.
+fn discover_tests(library_path: &Path, return_what: &ReturnWhat) {
+    let file_name = TestingMod_Ref::get_library_path(library_path);
+    let file_name = file_name.file_name().unwrap();
+
+    let dir = std::env::temp_dir().join(format!(
+        "abi_stable_testing_1_discover_{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    fs::copy(library_path.join(file_name), dir.join(file_name)).unwrap();
+
+    for _ in 0..2 {
+        let report = discover::<TestingMod_Ref>(&dir, LibrarySuffix::NoSuffix).unwrap();
+        let libraries = report.libraries();
+        assert_eq!(libraries.len(), 1);
+
+        let status = libraries[0].status();
+        match return_what {
+            ReturnWhat::Ok => {
+                let module = status.module().expect("Expected a compatible library");
+                assert_eq!(module.a(), 5);
+                assert_eq!(status.kind(), LibraryStatusKind::Compatible);
+
+                // Discovered root modules are the same ones that `load_instance_from` returns.
+                let (loaded, _) =
+                    TestingMod_Ref::load_instance_from(LibraryPath::FullPath(libraries[0].path()))
+                        .unwrap();
+                assert_eq!(loaded.0.to_raw_ptr(), module.0.to_raw_ptr());
+            }
+            ReturnWhat::Error | ReturnWhat::Panic => {
+                assert_eq!(status.kind(), LibraryStatusKind::RootModuleError);
+            }
+        }
+    }
+    {
+        let report =
+            discover::<incompatible_version::TestingMod_Ref>(&dir, LibrarySuffix::NoSuffix)
+                .unwrap();
+        let libraries = report.libraries();
+        assert_eq!(libraries.len(), 1);
+
+        let status = libraries[0].status();
+        assert_eq!(status.kind(), LibraryStatusKind::IncompatibleVersion);
+        assert!(
+            matches!(
+                status.error(),
+                Some(LibraryError::IncompatibleVersionNumber { .. })
+            ),
+            "{:?}",
+            status.error(),
+        );
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Tests `discover` with a library that only exports named root modules.
+fn discover_named_tests(library_path: &Path) {
+    let base_name = "testing_1_named_root_modules";
+    let library_file =
+        RawLibrary::path_in_directory(library_path, base_name, LibrarySuffix::NoSuffix);
+    let file_name = library_file.file_name().unwrap();
+
+    let dir = std::env::temp_dir().join(format!(
+        "abi_stable_testing_1_discover_named_{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    fs::copy(&library_file, dir.join(file_name)).unwrap();
+
+    let report = discover::<TestingMod_Ref>(&dir, LibrarySuffix::NoSuffix).unwrap();
+    let libraries = report.libraries();
+    assert_eq!(libraries.len(), 1);
+
+    let status = libraries[0].status();
+    assert_eq!(status.kind(), LibraryStatusKind::Compatible, "{:?}", status.error());
+    let module = status.module().unwrap();
+    assert_eq!(module.a(), 21);
+
+    let (loaded, _) = TestingMod_Ref::load_named_instance_from(
+        LibraryPath::FullPath(libraries[0].path()),
+        TestingMod_Ref::NAME,
+    )
+    .unwrap();
+    assert_eq!(loaded.0.to_raw_ptr(), module.0.to_raw_ptr());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 fn print_error_sum<E: fmt::Debug + fmt::Display>(line: u32, e: E) {
     let formatted = format!("{0} {0:?}", e);
     let sum = formatted.bytes().map(|x| x as u64).sum::<u64>();
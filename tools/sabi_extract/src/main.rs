@@ -1,9 +1,17 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
 
 use abi_stable::{
-    library::lib_header_from_path,
-    //abi_stability::check_layout_compatibility,
+    abi_stability::{check_layout_compatibility, AbiInstabilityReport, LayoutDiff},
+    library::{
+        lib_header_from_path, root_module_index_from_path, LibHeader, LibMetadata_Ref, LibraryError,
+    },
     reflection::export_module::MRItem,
+    std_types::RBoxError,
+    type_layout::TypeLayout,
 };
 
 use core_extensions::SelfOps;
@@ -36,6 +44,57 @@ enum Command {
         #[structopt(long = "--compact")]
         compact_json: bool,
     },
+
+    /// Prints the metadata of an abi_stable library,
+    /// without loading its root module.
+    #[structopt(name = "info")]
+    #[structopt(author = "_")]
+    Info {
+        /// The path to the library.
+        library_path: PathBuf,
+
+        /// Whether to output the metadata as json.
+        #[structopt(long = "--json")]
+        json: bool,
+    },
+
+    /// Checks that a new version of an abi_stable library is compatible with an old one,
+    /// in the same way that loading the new library in place of the old one would.
+    ///
+    /// Exits with a 1 exit code if the libraries are incompatible.
+    #[structopt(name = "check")]
+    #[structopt(author = "_")]
+    Check {
+        /// The path to the old version of the library.
+        old_library: PathBuf,
+
+        /// The path to the new version of the library.
+        new_library: PathBuf,
+
+        /// Whether to output layout errors as json.
+        #[structopt(long = "--json")]
+        json: bool,
+    },
+
+    /// Explains what changed between the root modules of two versions of an abi_stable library,
+    /// classifying every change as either compatible or breaking.
+    ///
+    /// Exits with a 1 exit code if there are breaking changes,
+    /// or if the versions of the libraries are incompatible.
+    #[structopt(name = "diff")]
+    #[structopt(author = "_")]
+    Diff {
+        /// The path to the old version of the library.
+        old_library: PathBuf,
+
+        /// The path to the new version of the library.
+        new_library: PathBuf,
+
+        /// Whether to output the changes as json,
+        /// with the version incompatibility (if any) in the `version_error` field.
+        #[structopt(long = "--json")]
+        json: bool,
+    },
 }
 
 fn main() {
@@ -83,7 +142,7 @@ version of abi_stable to be loaded successfully.
                     "The dynamic library does not support reflection:\n    {}",
                     library_path.display(),
                 );
-                std::process::exit(1);
+                process::exit(1);
             });
 
             let root_mod = MRItem::from_type_layout(layout);
@@ -107,5 +166,180 @@ version of abi_stable to be loaded successfully.
                 println!("{}", json);
             }
         }
+        Command::Info { library_path, json } => {
+            let lib_header = load_lib_header(&library_path);
+
+            // The named root modules exported with `export_root_modules`, if any.
+            let named_modules = root_module_index_from_path(&library_path)
+                .map(|index| index.modules())
+                .unwrap_or(&[])
+                .iter()
+                .filter_map(|module| Some((module.key(), module.lib_header().ok()?)))
+                .collect::<Vec<_>>();
+
+            if json {
+                let mut info = header_info_json(lib_header);
+                info["named_modules"] = named_modules
+                    .iter()
+                    .map(|(key, header)| {
+                        let mut info = header_info_json(header);
+                        info["key"] = (*key).into();
+                        info
+                    })
+                    .collect::<Vec<_>>()
+                    .into();
+                println!("{}", serde_json::to_string_pretty(&info).unwrap());
+            } else {
+                print_header_info(lib_header, "");
+                for (key, header) in named_modules {
+                    println!("\nnamed root module '{}':", key);
+                    print_header_info(header, "    ");
+                }
+            }
+        }
+        Command::Check {
+            old_library,
+            new_library,
+            json,
+        } => {
+            let old_header = load_lib_header(&old_library);
+            let new_header = load_lib_header(&new_library);
+
+            if let Err(e) = new_header.ensure_version_compatible(old_header.version_strings()) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+
+            let old_layout = get_layout(old_header, &old_library);
+            let new_layout = get_layout(new_header, &new_library);
+
+            let res = check_layout_compatibility(old_layout, new_layout).into_result();
+            if let Err(e) = res {
+                let report = AbiInstabilityReport::from_check_error(old_layout, new_layout, &e);
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&report).unwrap());
+                } else {
+                    eprintln!("{}", LibraryError::AbiInstability(RBoxError::new(report)));
+                }
+                process::exit(1);
+            }
+
+            println!(
+                "The library at:\n    {}\nis compatible with the one at:\n    {}",
+                new_library.display(),
+                old_library.display(),
+            );
+        }
+        Command::Diff {
+            old_library,
+            new_library,
+            json,
+        } => {
+            let old_header = load_lib_header(&old_library);
+            let new_header = load_lib_header(&new_library);
+
+            let version_res = new_header.ensure_version_compatible(old_header.version_strings());
+
+            let old_layout = get_layout(old_header, &old_library);
+            let new_layout = get_layout(new_header, &new_library);
+
+            let diff = LayoutDiff::new(old_layout, new_layout);
+            if json {
+                let mut output = serde_json::to_value(&diff).unwrap();
+                let version_error = version_res.as_ref().err().map(|e| e.to_string());
+                output["version_error"] = serde_json::to_value(version_error).unwrap();
+                println!("{}", serde_json::to_string_pretty(&output).unwrap());
+            } else {
+                if let Err(e) = &version_res {
+                    println!("{}\n", e.to_string().trim());
+                }
+                println!("{}", diff);
+            }
+
+            if version_res.is_err() || !diff.is_compatible() {
+                process::exit(1);
+            }
+        }
     }
 }
+
+fn load_lib_header(path: &Path) -> &'static LibHeader {
+    lib_header_from_path(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(2);
+    })
+}
+
+fn print_header_info(lib_header: &LibHeader, indent: &str) {
+    let consts = lib_header.root_mod_consts();
+    let metadata = lib_header.metadata();
+    let target_info = lib_header.target_info();
+
+    let timestamp = metadata
+        .build_timestamp()
+        .map_or_else(|| "unknown".to_string(), |x| format!("{} (UNIX time)", x));
+
+    println!("{}name: {}", indent, consts.name());
+    println!("{}base name: {}", indent, consts.base_name());
+    println!("{}version: {}", indent, consts.version_strings());
+    println!("{}description: {}", indent, metadata.description());
+    println!("{}authors: {}", indent, metadata.authors());
+    println!("{}license: {}", indent, metadata.license());
+    println!("{}built at: {}", indent, timestamp);
+    println!("{}rustc version: {}", indent, metadata.rustc_version());
+    println!("{}target: {}", indent, metadata.target());
+    println!(
+        "{}pointer width: {} bits",
+        indent,
+        target_info.pointer_width()
+    );
+    println!("{}endianness: {}", indent, target_info.endianness());
+    println!("{}panic strategy: {}", indent, target_info.panic_strategy());
+    println!("{}features: {:?}", indent, metadata.features());
+    for extra in metadata.extra() {
+        println!("{}{}: {}", indent, extra.0, extra.1);
+    }
+    println!(
+        "{}includes root module layout: {}",
+        indent,
+        lib_header.layout().is_some()
+    );
+}
+
+fn header_info_json(lib_header: &LibHeader) -> serde_json::Value {
+    let consts = lib_header.root_mod_consts();
+    let metadata: LibMetadata_Ref = lib_header.metadata();
+    let target_info = lib_header.target_info();
+
+    serde_json::json!({
+        "name": consts.name(),
+        "base_name": consts.base_name(),
+        "version": consts.version_strings().to_string(),
+        "description": metadata.description(),
+        "authors": metadata.authors(),
+        "license": metadata.license(),
+        "build_timestamp": metadata.build_timestamp().into_option(),
+        "rustc_version": metadata.rustc_version(),
+        "target": metadata.target(),
+        "pointer_width": target_info.pointer_width(),
+        "endianness": target_info.endianness().to_string(),
+        "panic_strategy": target_info.panic_strategy().to_string(),
+        "features": metadata.features().as_slice(),
+        "extra": metadata
+            .extra()
+            .iter()
+            .map(|x| (x.0.to_string(), serde_json::Value::from(x.1.as_str())))
+            .collect::<serde_json::Map<_, _>>(),
+        "includes_layout": lib_header.layout().is_some(),
+    })
+}
+
+fn get_layout(lib_header: &LibHeader, path: &Path) -> &'static TypeLayout {
+    lib_header.layout().unwrap_or_else(|| {
+        eprintln!(
+            "The dynamic library does not include the layout of its root module:\n    {}",
+            path.display(),
+        );
+        process::exit(2);
+    })
+}